@@ -15,6 +15,70 @@ fn main() {
         Service::new("ecs", "2014-11-13"),
         Service::new("elastictranscoder", "2012-09-25"),
         Service::new("sqs", "2012-11-05"),
+        Service::new("codecommit", "2015-04-13"),
+        Service::new("elbv2", "2015-12-01"),
+        Service::new("autoscaling", "2011-01-01"),
+        Service::new("mediaconvert", "2017-08-29"),
+        Service::new("opensearch", "2021-01-01"),
+        Service::new("timestream-write", "2018-11-01"),
+        Service::new("glacier", "2012-06-01"),
+        Service::new("appconfig", "2019-10-09"),
+        Service::new("logs", "2014-03-28"),
+        Service::new("sns", "2010-03-31"),
+        Service::new("dynamodbstreams", "2012-08-10"),
+        Service::new("iot", "2015-05-28"),
+        Service::new("batch", "2016-08-10"),
+        Service::new("transfer", "2018-11-05"),
+        Service::new("datasync", "2018-11-09"),
+        Service::new("ce", "2017-10-25"),
+        Service::new("config", "2014-11-12"),
+        Service::new("guardduty", "2017-11-28"),
+        Service::new("sagemaker", "2017-07-24"),
+        Service::new("sagemaker-runtime", "2017-05-13"),
+        Service::new("codepipeline", "2015-07-09"),
+        Service::new("codebuild", "2016-10-06"),
+        Service::new("secretsmanager", "2017-10-17"),
+        Service::new("firehose", "2015-08-04"),
+        Service::new("redshift", "2012-12-01"),
+        Service::new("network-firewall", "2020-11-12"),
+        Service::new("qldb", "2019-01-02"),
+        Service::new("qldb-session", "2019-07-11"),
+        Service::new("xray", "2016-04-12"),
+        Service::new("appmesh", "2019-01-25"),
+        Service::new("resourcegroupstaggingapi", "2017-01-26"),
+        Service::new("health", "2016-08-04"),
+        Service::new("organizations", "2016-11-28"),
+        Service::new("service-quotas", "2019-06-24"),
+        Service::new("ram", "2018-01-04"),
+        Service::new("mq", "2017-11-27"),
+        Service::new("kafka", "2018-11-14"),
+        Service::new("lexv2-runtime", "2020-08-07"),
+        Service::new("translate", "2017-07-01"),
+        Service::new("comprehend", "2017-11-27"),
+        Service::new("rekognition", "2016-06-27"),
+        Service::new("polly", "2016-06-10"),
+        Service::new("textract", "2018-06-27"),
+        Service::new("frauddetector", "2019-11-15"),
+        Service::new("pinpoint", "2016-12-01"),
+        Service::new("macie2", "2020-01-01"),
+        Service::new("inspector2", "2020-06-08"),
+        Service::new("detective", "2018-10-26"),
+        Service::new("cloudtrail", "2013-11-01"),
+        Service::new("securityhub", "2018-10-26"),
+        Service::new("lakeformation", "2017-03-31"),
+        Service::new("dms", "2016-01-01"),
+        Service::new("globalaccelerator", "2018-08-08"),
+        Service::new("apigatewayv2", "2018-11-29"),
+        Service::new("waf", "2015-08-24"),
+        Service::new("elasticache", "2015-02-02"),
+        Service::new("kinesisvideo", "2017-09-30"),
+        Service::new("ec2", "2016-11-15"),
+        Service::new("monitoring", "2010-08-01"),
+        Service::new("chime-sdk-messaging", "2021-05-15"),
+        Service::new("amplify", "2017-07-25"),
+        Service::new("appstream", "2016-12-01"),
+        Service::new("workspaces", "2015-04-08"),
+        Service::new("connect", "2017-08-08"),
     ];
 
     for service in services {
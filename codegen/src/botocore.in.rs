@@ -16,32 +16,60 @@ impl Service {
     }
 
     pub fn service_type_name(&self) -> &str {
+        // Both versions of the Elastic Load Balancing API share a full name.
+        if self.metadata.endpoint_prefix == "elasticloadbalancing" &&
+            self.metadata.api_version == "2015-12-01" {
+            return "ElbV2";
+        }
+
         match &self.metadata.service_full_name[..] {
+            "AWS Amplify" => "Amplify",
+            "AWS App Mesh" => "AppMesh",
+            "AWS Batch" => "Batch",
             "AWS Certificate Manager" => "Acm",
             "AWS CloudFormation" => "CloudFormation",
             "AWS CloudTrail" => "CloudTrail",
+            "AWS CodeBuild" => "CodeBuild",
             "AWS CodeCommit" => "CodeCommit",
             "AWS CodeDeploy" => "CodeDeploy",
             "AWS CodePipeline" => "CodePipeline",
             "AWS Config" => "ConfigService",
+            "AWS Cost Explorer Service" => "CostExplorer",
             "AWS Data Pipeline" => "DataPipeline",
+            "AWS DataSync" => "DataSync",
+            "AWS Database Migration Service" => "Dms",
             "AWS Device Farm" => "DeviceFarm",
             "AWS Direct Connect" => "DirectConnect",
             "AWS Directory Service" => "DirectoryService",
             "AWS Elastic Beanstalk" => "ElasticBeanstalk",
+            "AWS Elemental MediaConvert" => "MediaConvert",
+            "AWS Global Accelerator" => "GlobalAccelerator",
+            "AWS Health APIs and Notifications" => "Health",
             "AWS Identity and Access Management" => "Iam",
             "AWS Import/Export" => "ImportExport",
             "AWS IoT Data Plane" => "IotDataPlane",
             "AWS IoT" => "Iot",
             "AWS Key Management Service" => "Kms",
+            "AWS Lake Formation" => "LakeFormation",
             "AWS Lambda" => "Lambda",
             "AWS Marketplace Commerce Analytics" => "MarketplaceCommerceAnalytics",
+            "AWS Network Firewall" => "NetworkFirewall",
             "AWS OpsWorks" => "OpsWorks",
+            "AWS Organizations" => "Organizations",
+            "AWS Resource Access Manager" => "Ram",
+            "AWS Resource Groups Tagging API" => "Tagging",
+            "AWS Secrets Manager" => "SecretsManager",
             "AWS Security Token Service" => "Sts",
+            "AWS SecurityHub" => "SecurityHub",
             "AWS Storage Gateway" => "StorageGateway",
             "AWS Support" => "Support",
+            "AWS Transfer Family" => "Transfer",
             "AWS WAF" => "Waf",
+            "AWS X-Ray" => "XRay",
             "Amazon API Gateway" => "ApiGateway",
+            "Amazon AppConfig" => "AppConfig",
+            "Amazon AppStream" => "AppStream",
+            "Amazon Chime SDK Messaging" => "ChimeSdkMessaging",
             "Amazon CloudFront" => "CloudFront",
             "Amazon CloudHSM" => "CloudHsm",
             "Amazon CloudSearch Domain" => "CloudSearchDomain",
@@ -51,6 +79,9 @@ impl Service {
             "Amazon CloudWatch" => "CloudWatch",
             "Amazon Cognito Identity" => "CognitoIdentity",
             "Amazon Cognito Sync" => "CognitoSync",
+            "Amazon Comprehend" => "Comprehend",
+            "Amazon Connect Service" => "Connect",
+            "Amazon Detective" => "Detective",
             "Amazon DynamoDB Streams" => "DynamoDbStreams",
             "Amazon DynamoDB" => "DynamoDb",
             "Amazon EC2 Container Registry" => "Ecr",
@@ -61,16 +92,29 @@ impl Service {
             "Amazon Elastic MapReduce" => "Emr",
             "Amazon Elastic Transcoder" => "Ets",
             "Amazon Elasticsearch Service" => "ElasticsearchService",
+            "Amazon Fraud Detector" => "FraudDetector",
             "Amazon GameLift" => "GameLift",
             "Amazon Glacier" => "Glacier",
+            "Amazon GuardDuty" => "GuardDuty",
             "Amazon Inspector" => "Inspector",
             "Amazon Kinesis Firehose" => "KinesisFirehose",
             "Amazon Kinesis" => "Kinesis",
+            "Amazon Kinesis Video Streams" => "KinesisVideo",
+            "Amazon Lex Runtime V2" => "LexV2Runtime",
             "Amazon Machine Learning" => "MachineLearning",
+            "Amazon Macie 2" => "Macie2",
+            "Amazon OpenSearch Service" => "OpenSearch",
+            "Amazon Pinpoint" => "Pinpoint",
+            "Amazon Polly" => "Polly",
+            "Amazon QLDB Session" => "QldbSession",
+            "Amazon QLDB" => "Qldb",
             "Amazon Redshift" => "Redshift",
+            "Amazon Rekognition" => "Rekognition",
             "Amazon Relational Database Service" => "Rds",
             "Amazon Route 53 Domains" => "Route53Domains",
             "Amazon Route 53" => "Route53",
+            "Amazon SageMaker Runtime" => "SageMakerRuntime",
+            "Amazon SageMaker Service" => "SageMaker",
             "Amazon Simple Email Service" => "Ses",
             "Amazon Simple Notification Service" => "Sns",
             "Amazon Simple Queue Service" => "Sqs",
@@ -78,13 +122,35 @@ impl Service {
             "Amazon Simple Systems Management Service" => "Ssm",
             "Amazon Simple Workflow Service" => "Swf",
             "Amazon SimpleDB" => "SimpleDb",
+            "Amazon Textract" => "Textract",
+            "Amazon Timestream Write" => "TimestreamWrite",
+            "Amazon Translate" => "Translate",
             "Amazon WorkSpaces" => "Workspaces",
-            "Auto Scaling" => "Autoscaling",
+            "AmazonApiGatewayV2" => "ApiGatewayV2",
+            "AmazonMQ" => "Mq",
+            "Auto Scaling" => "AutoScaling",
             "Elastic Load Balancing" => "Elb",
+            "Inspector2" => "InspectorV2",
+            "Managed Streaming for Kafka" => "Msk",
+            "Service Quotas" => "ServiceQuotas",
             name => panic!("Unknown service full name: {}", name),
         }
     }
 
+    /// The name requests are signed with, which only some services set apart from the
+    /// endpoint prefix.
+    pub fn signing_name(&self) -> &str {
+        match self.metadata.signing_name {
+            Some(ref signing_name) => signing_name,
+            None => &self.metadata.endpoint_prefix,
+        }
+    }
+
+    /// The operation used to discover the endpoint for operations with endpoint discovery.
+    pub fn endpoint_operation(&self) -> Option<&Operation> {
+        self.operations.values().find(|operation| operation.endpoint_operation == Some(true))
+    }
+
     pub fn shape_for_member<'a>(&'a self, member: &Member) -> Option<&'a Shape> {
         self.shapes.get(&member.shape).map(|shape| shape)
     }
@@ -143,6 +209,8 @@ pub struct Member {
     pub location: Option<String>,
     #[serde(rename="locationName")]
     pub location_name: Option<String>,
+    #[serde(rename="queryName")]
+    pub query_name: Option<String>,
     pub shape: String,
     pub streaming: Option<bool>,
     #[serde(rename="xmlAttribute")]
@@ -248,6 +316,10 @@ pub struct Operation {
     pub documentation: Option<String>,
     #[serde(rename="documentationUrl")]
     pub documentation_url: Option<String>,
+    #[serde(rename="endpointdiscovery")]
+    pub endpoint_discovery: Option<BTreeMap<String, bool>>,
+    #[serde(rename="endpointoperation")]
+    pub endpoint_operation: Option<bool>,
     pub errors: Option<Vec<Error>>,
     pub http: HttpRequest,
     pub input: Option<Input>,
@@ -287,6 +359,8 @@ pub struct Metadata {
     pub service_full_name: String,
     #[serde(rename="signatureVersion")]
     pub signature_version: String,
+    #[serde(rename="signingName")]
+    pub signing_name: Option<String>,
     #[serde(rename="targetPrefix")]
     pub target_prefix: Option<String>,
     #[serde(rename="timestampFormat")]
@@ -1,7 +1,7 @@
 use inflector::Inflector;
 
 use botocore::{Operation, Service};
-use super::GenerateProtocol;
+use super::{GenerateProtocol, generate_endpoint_selection};
 
 pub struct JsonGenerator;
 
@@ -14,7 +14,8 @@ impl GenerateProtocol for JsonGenerator {
                 {documentation}
                 pub fn {method_name}(&mut self, input: &{input_type}) -> AwsResult<{output_type}> {{
                     let encoded = serde_json::to_string(input).unwrap();
-                    let mut request = SignedRequest::new(\"{http_method}\", \"{endpoint_prefix}\", self.region, \"{request_uri}\");
+                    let mut request = SignedRequest::new(\"{http_method}\", \"{signing_name}\", self.region, \"{request_uri}\");
+                    {endpoint_selection}
                     request.set_content_type(\"application/x-amz-json-{json_version}\".to_owned());
                     request.add_header(\"x-amz-target\", \"{target_prefix}.{name}\");
                     request.set_payload(Some(encoded.as_bytes()));
@@ -31,7 +32,8 @@ impl GenerateProtocol for JsonGenerator {
                 }}
                 ",
                 documentation = generate_documentation(operation).unwrap_or("".to_owned()),
-                endpoint_prefix = service.metadata.endpoint_prefix,
+                endpoint_selection = generate_endpoint_selection(service, operation),
+                signing_name = service.signing_name(),
                 http_method = operation.http.method,
                 input_type = operation.input_shape(),
                 method_name = operation.name.to_snake_case(),
@@ -58,7 +60,7 @@ impl GenerateProtocol for JsonGenerator {
     }
 
     fn generate_struct_attributes(&self) -> String {
-        "#[derive(Debug, Default, Deserialize, Serialize)]".to_owned()
+        "#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]".to_owned()
     }
 
 }
@@ -1,6 +1,6 @@
 use inflector::Inflector;
 
-use botocore::{Service, Shape};
+use botocore::{Operation, Service, Shape};
 
 use self::json::JsonGenerator;
 use self::query::QueryGenerator;
@@ -17,6 +17,10 @@ pub trait GenerateProtocol {
 
     fn generate_struct_attributes(&self) -> String;
 
+    fn timestamp_type(&self) -> &'static str {
+        "f64"
+    }
+
     fn generate_support_types(&self, _name: &str, _shape: &Shape, _service: &Service)
         -> Option<String> {
         None
@@ -25,8 +29,9 @@ pub trait GenerateProtocol {
 
 pub fn generate_source(service: &Service) -> String {
     match &service.metadata.protocol[..] {
+        "ec2" => generate(service, QueryGenerator::ec2()),
         "json" => generate(service, JsonGenerator),
-        "query" => generate(service, QueryGenerator),
+        "query" => generate(service, QueryGenerator::query()),
         "rest-json" => generate(service, RestJsonGenerator),
         protocol => panic!("Unknown protocol {}", protocol),
     }
@@ -52,6 +57,9 @@ where P: GenerateProtocol {
         pub struct {type_name}<P> where P: ProvideAwsCredentials {{
             credentials_provider: P,
             region: Region,
+            hostname: Option<String>,
+            interceptors: Vec<::std::sync::Arc<::interceptor::Interceptor + Send + Sync>>,
+            {endpoint_cache_field}
         }}
 
         impl<P> {type_name}<P> where P: ProvideAwsCredentials {{
@@ -59,19 +67,46 @@ where P: GenerateProtocol {
                 {type_name} {{
                     credentials_provider: credentials_provider,
                     region: region,
+                    hostname: None,
+                    interceptors: Vec::new(),
+                    {endpoint_cache_init}
                 }}
             }}
 
+            /// Send requests to `hostname` instead of the default endpoint for the region.
+            ///
+            /// The hostname may be given as a URL like `http://localhost:8000` to choose the scheme,
+            /// otherwise HTTPS is used.
+            pub fn set_hostname(&mut self, hostname: Option<String>) {{
+                self.hostname = hostname;
+            }}
+
+            /// Calls `interceptors`, in order, around every request the client sends.
+            pub fn set_interceptors(&mut self, interceptors: Vec<::std::sync::Arc<::interceptor::Interceptor + Send + Sync>>) {{
+                self.interceptors = interceptors;
+            }}
+
+            {discovered_hostname}
+
             {methods}
         }}
         ",
+        discovered_hostname = generate_discovered_hostname(service).unwrap_or("".to_owned()),
+        endpoint_cache_field = match service.endpoint_operation() {
+            Some(_) => "endpoint_cache: ::endpoint_cache::EndpointCache,",
+            None => "",
+        },
+        endpoint_cache_init = match service.endpoint_operation() {
+            Some(_) => "endpoint_cache: ::endpoint_cache::EndpointCache::new(),",
+            None => "",
+        },
         methods = protocol_generator.generate_methods(service),
         service_name = match &service.metadata.service_abbreviation {
             &Some(ref service_abbreviation) => service_abbreviation.as_str(),
             &None => {
                 match service.metadata.endpoint_prefix {
                     ref x if x == "elastictranscoder" => "Amazon Elastic Transcoder",
-                    _ => panic!("Unable to determine service abbreviation"),
+                    _ => service.metadata.service_full_name.as_str(),
                 }
             },
         },
@@ -79,6 +114,92 @@ where P: GenerateProtocol {
     )
 }
 
+/// Generates the method which looks up the endpoint for operations with endpoint discovery,
+/// calling the service's endpoint operation whenever the cached endpoint has expired.
+fn generate_discovered_hostname(service: &Service) -> Option<String> {
+    service.endpoint_operation().map(|operation| {
+        format!(
+            "fn discovered_hostname(&mut self) -> AwsResult<Option<String>> {{
+                if self.hostname.is_some() {{
+                    return Ok(self.hostname.clone());
+                }}
+
+                let now = ::time::now_utc().to_timespec();
+
+                if let Some(address) = self.endpoint_cache.address(now) {{
+                    return Ok(Some(address.to_owned()));
+                }}
+
+                let response = try!(self.{method_name}(&{input_type}::default()));
+
+                match response.endpoints.first() {{
+                    Some(endpoint) => {{
+                        self.endpoint_cache.update(
+                            endpoint.address.clone(),
+                            endpoint.cache_period_in_minutes,
+                            now,
+                        );
+
+                        Ok(Some(endpoint.address.clone()))
+                    }}
+                    None => Err(::error::AwsError::new(\"{name} returned no endpoints\")),
+                }}
+            }}",
+            input_type = operation.input_shape(),
+            method_name = operation.name.to_snake_case(),
+            name = operation.name,
+        )
+    })
+}
+
+/// Generates the code which points a request at the right endpoint, and hands it the
+/// client's interceptors.
+///
+/// Operations with endpoint discovery use the discovered endpoint, unless the hostname has
+/// been overridden. Services whose endpoint prefix differs from the name requests are signed
+/// with need the prefix set explicitly.
+pub fn generate_endpoint_selection(service: &Service, operation: &Operation) -> String {
+    let hostname = match (service.endpoint_operation(), &operation.endpoint_discovery) {
+        (Some(_), &Some(_)) => "request.set_hostname(try!(self.discovered_hostname()));",
+        _ => "request.set_hostname(self.hostname.clone());",
+    };
+
+    let endpoint_prefix = if service.signing_name() == service.metadata.endpoint_prefix {
+        "".to_owned()
+    } else {
+        format!(
+            "request.set_endpoint_prefix(Some(\"{}\".to_owned()));",
+            service.metadata.endpoint_prefix,
+        )
+    };
+
+    format!(
+        "{hostname}
+        {endpoint_prefix}
+        request.set_interceptors(self.interceptors.clone());",
+        endpoint_prefix = endpoint_prefix,
+        hostname = hostname,
+    )
+}
+
+/// Converts a botocore member name into a Rust field name, prefixing it with `aws_` if the
+/// snake cased name would collide with a Rust keyword.
+pub fn generate_field_name(member_name: &str) -> String {
+    let name = member_name.to_snake_case();
+
+    match &name[..] {
+        "abstract" | "alignof" | "as" | "become" | "box" | "break" | "const" | "continue" |
+        "crate" | "do" | "else" | "enum" | "extern" | "false" | "final" | "fn" | "for" | "if" |
+        "impl" | "in" | "let" | "loop" | "macro" | "match" | "mod" | "move" | "mut" |
+        "offsetof" | "override" | "priv" | "proc" | "pub" | "pure" | "ref" | "return" |
+        "self" | "sizeof" | "static" | "struct" | "super" | "trait" | "true" | "type" |
+        "typeof" | "unsafe" | "unsized" | "use" | "virtual" | "where" | "while" | "yield" => {
+            format!("aws_{}", name)
+        },
+        _ => name,
+    }
+}
+
 fn generate_list(name: &str, shape: &Shape) -> String {
     format!("pub type {} = Vec<{}>;", name, shape.member())
 }
@@ -92,11 +213,12 @@ fn generate_map(name: &str, shape: &Shape) -> String {
     )
 }
 
-fn generate_primitive_type(name: &str, shape_type: &str) -> String {
+fn generate_primitive_type(name: &str, shape_type: &str, timestamp_type: &str) -> String {
     let primitive_type = match shape_type {
         "blob" => "Vec<u8>",
         "boolean" => "bool",
-        "double" | "timestamp" => "f64",
+        "double" => "f64",
+        "timestamp" => timestamp_type,
         "float" => "f32",
         "integer" => "i32",
         "long" => "i64",
@@ -120,11 +242,17 @@ where P: GenerateProtocol {
             parts.push(format!("#[doc=\"{}\"]", docs.replace("\"", "\\\"")));
         }
 
+        // Timestamps formatted as ISO 8601 are kept as the strings they're sent as.
+        let timestamp_type = match shape.timestamp_format.as_ref().map(|format| &format[..]) {
+            Some("iso8601") => "String",
+            _ => protocol_generator.timestamp_type(),
+        };
+
         match &shape.shape_type[..] {
             "structure" => parts.push(generate_struct(service, name, shape, protocol_generator)),
             "map" => parts.push(generate_map(name, shape)),
             "list" => parts.push(generate_list(name, shape)),
-            shape_type => parts.push(generate_primitive_type(name, shape_type)),
+            shape_type => parts.push(generate_primitive_type(name, shape_type, timestamp_type)),
         }
 
         if let Some(support_types) = protocol_generator.generate_support_types(name, shape, &service) {
@@ -142,9 +270,10 @@ fn generate_struct<P>(
     protocol_generator: &P,
 ) -> String where P: GenerateProtocol {
     if shape.members.is_none() || shape.members.as_ref().unwrap().is_empty() {
+        // Braces rather than a unit struct, so JSON protocols send and accept `{}`, not `null`.
         format!(
             "{attributes}
-            pub struct {name};
+            pub struct {name} {{}}
             ",
             attributes = protocol_generator.generate_struct_attributes(),
             name = name,
@@ -158,23 +287,31 @@ fn generate_struct<P>(
             ",
             attributes = protocol_generator.generate_struct_attributes(),
             name = name,
-            struct_fields = generate_struct_fields(service, shape),
+            struct_fields = generate_struct_fields(service, name, shape),
         )
     }
 
 }
 
-fn generate_struct_fields(service: &Service, shape: &Shape) -> String {
+fn generate_struct_fields(service: &Service, struct_name: &str, shape: &Shape) -> String {
     shape.members.as_ref().unwrap().iter().map(|(member_name, member)| {
         let mut lines = Vec::with_capacity(4);
-        let name = member_name.to_snake_case();
+        let name = generate_field_name(member_name);
 
         if let Some(ref docs) = member.documentation {
             lines.push(format!("#[doc=\"{}\"]", docs.replace("\"", "\\\"")));
         }
 
         lines.push("#[allow(unused_attributes)]".to_owned());
-        lines.push(format!("#[serde(rename=\"{}\")]", member_name));
+        lines.push(format!(
+            "#[serde(rename=\"{}\")]",
+            member.location_name.as_ref().map(|name| &name[..]).unwrap_or(member_name),
+        ));
+
+        // Members bound to the URI, headers or status code travel outside of the body.
+        if member.location.is_some() {
+            lines.push("#[serde(default, skip_serializing)]".to_owned());
+        }
 
         if let Some(shape_type) = service.shape_type_for_member(member) {
             if shape_type == "blob" {
@@ -187,12 +324,17 @@ fn generate_struct_fields(service: &Service, shape: &Shape) -> String {
             }
         }
 
+        // A struct can only contain itself through a pointer.
+        let member_type = if member.shape == struct_name {
+            format!("Box<{}>", member.shape)
+        } else {
+            member.shape.clone()
+        };
+
         if shape.required(member_name) {
-            lines.push(format!("pub {}: {},",  name, member.shape));
-        } else if name == "type" {
-            lines.push(format!("pub aws_{}: Option<{}>,",  name, member.shape));
+            lines.push(format!("pub {}: {},",  name, member_type));
         } else {
-            lines.push(format!("pub {}: Option<{}>,",  name, member.shape));
+            lines.push(format!("pub {}: Option<{}>,",  name, member_type));
         }
 
         lines.join("\n")
@@ -1,9 +1,24 @@
 use inflector::Inflector;
 
 use botocore::{Member, Operation, Service, Shape};
-use super::GenerateProtocol;
+use super::{GenerateProtocol, generate_endpoint_selection, generate_field_name};
 
-pub struct QueryGenerator;
+/// Generates clients for the query protocol, and for the EC2 protocol, which is a variant of it.
+pub struct QueryGenerator {
+    ec2: bool,
+}
+
+impl QueryGenerator {
+    pub fn query() -> QueryGenerator {
+        QueryGenerator { ec2: false }
+    }
+
+    /// The EC2 protocol numbers list entries without a `member` segment, names parameters after
+    /// the `queryName` of each member, and doesn't wrap responses in a result element.
+    pub fn ec2() -> QueryGenerator {
+        QueryGenerator { ec2: true }
+    }
+}
 
 impl GenerateProtocol for QueryGenerator {
     fn generate_methods(&self, service: &Service) -> String {
@@ -13,23 +28,22 @@ impl GenerateProtocol for QueryGenerator {
 {method_signature} {{
     let mut request = SignedRequest::new(
         \"{http_method}\",
-        \"{endpoint_prefix}\",
+        \"{signing_name}\",
         self.region,
         \"{request_uri}\",
     );
+    {endpoint_selection}
     let mut params = Params::new();
 
     params.put(\"Action\", \"{operation_name}\");
+    params.put(\"Version\", \"{api_version}\");
     {serialize_input}
 
     request.set_params(params);
 
     let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
     let status = result.status.to_u16();
-    let mut reader = EventReader::new(result);
-    let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-    stack.next();
-    stack.next();
+    {response_reader}
 
     match status {{
         200 => {{
@@ -41,38 +55,56 @@ impl GenerateProtocol for QueryGenerator {
     }}
 }}
                 ",
+                api_version = &service.metadata.api_version,
                 documentation = generate_documentation(operation),
                 http_method = &operation.http.method,
-                endpoint_prefix = &service.metadata.endpoint_prefix,
-                method_return_value = generate_method_return_value(operation),
+                endpoint_selection = generate_endpoint_selection(service, operation),
+                signing_name = service.signing_name(),
+                method_return_value = generate_method_return_value(operation, self.ec2),
                 method_signature = generate_method_signature(operation),
                 operation_name = &operation.name,
                 request_uri = &operation.http.request_uri,
+                response_reader = generate_response_reader(self.ec2),
                 serialize_input = generate_method_input_serialization(operation),
             )
         }).collect::<Vec<String>>().join("\n")
     }
 
     fn generate_prelude(&self) -> String {
-        "use std::collections::HashMap;
-        use std::str::{FromStr, from_utf8};
-
-        use xml::EventReader;
-
-        use credential::ProvideAwsCredentials;
-        use error::AwsError;
-        use param::{Params, ServiceParams};
-        use region::Region;
-        use signature::SignedRequest;
-        use xmlutil::{Next, Peek, XmlParseError, XmlResponseFromAws};
-        use xmlutil::{characters, end_element, peek_at_name, start_element};
-        ".to_owned()
+        let ec2_imports = if self.ec2 {
+            "use xml::reader::ParserConfig;
+            use xmlutil::skip_tree;"
+        } else {
+            ""
+        };
+
+        format!(
+            "use std::collections::HashMap;
+            use std::str::{{FromStr, from_utf8}};
+
+            use xml::EventReader;
+
+            use credential::ProvideAwsCredentials;
+            use error::AwsError;
+            use param::{{Params, ServiceParams}};
+            use region::Region;
+            use signature::SignedRequest;
+            use xmlutil::{{Next, Peek, XmlParseError, XmlResponseFromAws}};
+            use xmlutil::{{characters, end_element, peek_at_name, start_element}};
+            {ec2_imports}
+            ",
+            ec2_imports = ec2_imports,
+        )
     }
 
     fn generate_struct_attributes(&self) -> String {
         "#[derive(Debug, Default)]".to_owned()
     }
 
+    fn timestamp_type(&self) -> &'static str {
+        "String"
+    }
+
     fn generate_support_types(&self, name: &str, shape: &Shape, service: &Service) -> Option<String> {
         Some(format!(
             "/// Deserializes `{name}` from XML.
@@ -92,9 +124,9 @@ impl GenerateProtocol for QueryGenerator {
                 }}
             }}
             ",
-            deserializer_body = generate_deserializer_body(name, shape, service),
+            deserializer_body = generate_deserializer_body(name, shape, service, self.ec2),
             name = name,
-            serializer_body = generate_serializer_body(shape),
+            serializer_body = generate_serializer_body(shape, self.ec2),
             serializer_signature = generate_serializer_signature(name, shape),
         ))
     }
@@ -118,11 +150,35 @@ fn generate_method_input_serialization(operation: &Operation) -> String {
     }
 }
 
-fn generate_method_return_value(operation: &Operation) -> String {
-    if operation.output.is_some() {
+fn generate_response_reader(ec2: bool) -> String {
+    if ec2 {
+        // EC2 responses are indented, and their outermost element is the output itself.
+        "let mut reader = EventReader::with_config(result, ParserConfig::new().trim_whitespace(true));
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next();".to_owned()
+    } else {
+        "let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next();
+        stack.next();".to_owned()
+    }
+}
+
+fn generate_method_return_value(operation: &Operation, ec2: bool) -> String {
+    if let Some(ref output) = operation.output {
+        // Query responses wrap the output shape in an element named by `resultWrapper`, which
+        // only sometimes matches the name of the shape itself. EC2 responses are named after
+        // the operation.
+        let tag_name = if ec2 {
+            format!("{}Response", operation.name)
+        } else {
+            output.result_wrapper.as_ref().unwrap_or(&output.shape).to_owned()
+        };
+
         format!(
-            "Ok(try!({output_type}Deserializer::deserialize(\"{output_type}\", &mut stack)))",
-            output_type = &operation.output.as_ref().unwrap().shape,
+            "Ok(try!({output_type}Deserializer::deserialize(\"{tag_name}\", &mut stack)))",
+            output_type = &output.shape,
+            tag_name = tag_name,
         )
     } else {
         "Ok(())".to_owned()
@@ -146,30 +202,57 @@ fn generate_method_signature(operation: &Operation) -> String {
     }
 }
 
-fn generate_deserializer_body(name: &str, shape: &Shape, service: &Service) -> String {
+fn generate_deserializer_body(name: &str, shape: &Shape, service: &Service, ec2: bool) -> String {
     match &shape.shape_type[..] {
         "list" => generate_list_deserializer(shape),
         "map" => generate_map_deserializer(shape),
-        "structure" => generate_struct_deserializer(name, shape, service),
+        "structure" => generate_struct_deserializer(name, shape, service, ec2),
         _ => generate_primitive_deserializer(shape),
     }
 }
 
 fn generate_list_deserializer(shape: &Shape) -> String {
+    if shape.flattened.is_some() {
+        return format!(
+            "
+            let mut obj = vec![];
+
+            while try!(peek_at_name(stack)) == tag_name {{
+                obj.push(try!({member_name}Deserializer::deserialize(tag_name, stack)));
+            }}
+
+            Ok(obj)
+            ",
+            member_name = shape.member()
+        );
+    }
+
     format!(
         "
         let mut obj = vec![];
 
-        while try!(peek_at_name(stack)) == tag_name {{
-            obj.push(try!({member_name}Deserializer::deserialize(tag_name, stack)));
+        try!(start_element(tag_name, stack));
+
+        while try!(peek_at_name(stack)) == \"{member_tag_name}\" {{
+            obj.push(try!({member_name}Deserializer::deserialize(\"{member_tag_name}\", stack)));
         }}
 
+        try!(end_element(tag_name, stack));
+
         Ok(obj)
         ",
-        member_name = shape.member()
+        member_name = shape.member(),
+        member_tag_name = list_member_tag_name(shape),
     )
 }
 
+/// The element name used for each entry of a list that is not flattened.
+fn list_member_tag_name(shape: &Shape) -> String {
+    shape.member.as_ref()
+        .and_then(|member| member.location_name.clone())
+        .unwrap_or("member".to_owned())
+}
+
 fn generate_map_deserializer(shape: &Shape) -> String {
     let key = shape.key.as_ref().unwrap();
     let value = shape.value.as_ref().unwrap();
@@ -199,7 +282,9 @@ fn generate_primitive_deserializer(shape: &Shape) -> String {
     let statement =  match &shape.shape_type[..] {
         "string" | "timestamp" => "try!(characters(stack))",
         "integer" => "i32::from_str(try!(characters(stack)).as_ref()).unwrap()",
-        "double" => "f32::from_str(try!(characters(stack)).as_ref()).unwrap()",
+        "long" => "i64::from_str(try!(characters(stack)).as_ref()).unwrap()",
+        "double" => "f64::from_str(try!(characters(stack)).as_ref()).unwrap()",
+        "float" => "f32::from_str(try!(characters(stack)).as_ref()).unwrap()",
         "blob" => "try!(characters(stack)).into_bytes()",
         "boolean" => "bool::from_str(try!(characters(stack)).as_ref()).unwrap()",
         shape_type => panic!("Unknown primitive shape type: {}", shape_type),
@@ -216,7 +301,24 @@ fn generate_primitive_deserializer(shape: &Shape) -> String {
     )
 }
 
-fn generate_struct_deserializer(name: &str, shape: &Shape, service: &Service) -> String {
+fn generate_struct_deserializer(name: &str, shape: &Shape, service: &Service, ec2: bool) -> String {
+    // EC2 responses include elements, such as `requestId`, which aren't part of the output.
+    if ec2 && shape.members.as_ref().unwrap().is_empty() {
+        return format!(
+            "try!(start_element(tag_name, stack));
+
+            while try!(peek_at_name(stack)) != \"\" {{
+                try!(skip_tree(stack));
+            }}
+
+            try!(end_element(tag_name, stack));
+
+            Ok({name}::default())
+            ",
+            name = name,
+        );
+    }
+
     if shape.members.as_ref().unwrap().is_empty() {
         return format!(
             "try!(start_element(tag_name, stack));
@@ -239,7 +341,7 @@ fn generate_struct_deserializer(name: &str, shape: &Shape, service: &Service) ->
         loop {{
             match &try!(peek_at_name(stack))[..] {{
                 {struct_field_deserializers}
-                _ => break,
+                {unknown_element}
             }}
         }}
 
@@ -248,16 +350,28 @@ fn generate_struct_deserializer(name: &str, shape: &Shape, service: &Service) ->
         Ok(obj)
         ",
         name = name,
-        struct_field_deserializers = generate_struct_field_deserializers(shape, service),
+        struct_field_deserializers = generate_struct_field_deserializers(name, shape, service, ec2),
+        unknown_element = if ec2 {
+            "\"\" => break,
+            _ => try!(skip_tree(stack)),"
+        } else {
+            "_ => break,"
+        },
     )
 }
 
-fn generate_struct_field_deserializers(shape: &Shape, service: &Service) -> String {
+fn generate_struct_field_deserializers(name: &str, shape: &Shape, service: &Service, ec2: bool) -> String {
     shape.members.as_ref().unwrap().iter().map(|(member_name, member)| {
         // look up member.shape in all_shapes.  use that shape.member.location_name
-        let mut location_name = member_name.to_string();
+        let mut location_name = if ec2 {
+            member.location_name.clone().unwrap_or(member_name.to_string())
+        } else {
+            member_name.to_string()
+        };
 
-        let parse_expression_location_name = if let Some(ref child_shape) = service.shape_for_member(member) {
+        let parse_expression_location_name = if ec2 {
+            Some(&location_name)
+        } else if let Some(ref child_shape) = service.shape_for_member(member) {
             if child_shape.flattened.is_some() {
                 if let Some(ref child_member) = child_shape.member {
                     if let Some(ref loc_name) = child_member.location_name {
@@ -275,13 +389,19 @@ fn generate_struct_field_deserializers(shape: &Shape, service: &Service) -> Stri
         } else {
             None
         };
-        let parse_expression = generate_struct_field_parse_expression(shape, member_name, member, parse_expression_location_name);
+        let parse_expression = generate_struct_field_parse_expression(
+            name,
+            shape,
+            member_name,
+            member,
+            parse_expression_location_name,
+        );
         format!(
             "\"{location_name}\" => {{
                 obj.{field_name} = {parse_expression};
                 continue;
             }}",
-            field_name = member_name.to_snake_case(),
+            field_name = generate_field_name(member_name),
             parse_expression = parse_expression,
             location_name = location_name,
         )
@@ -290,6 +410,7 @@ fn generate_struct_field_deserializers(shape: &Shape, service: &Service) -> Stri
 }
 
 fn generate_struct_field_parse_expression(
+    struct_name: &str,
     shape: &Shape,
     member_name: &str,
     member: &Member,
@@ -300,12 +421,17 @@ fn generate_struct_field_parse_expression(
         Some(loc) => loc.to_string(),
         None => member_name.to_string(),
     };
-    let expression = format!(
+    let mut expression = format!(
         "try!({name}Deserializer::deserialize(\"{location}\", stack))",
         name = member.shape,
         location = location_to_use,
     );
 
+    // Structs that contain themselves do so through a `Box`.
+    if member.shape == struct_name {
+        expression = format!("Box::new({})", expression);
+    }
+
     if shape.required(member_name) {
         expression
     } else {
@@ -313,11 +439,11 @@ fn generate_struct_field_parse_expression(
     }
 }
 
-fn generate_serializer_body(shape: &Shape) -> String {
+fn generate_serializer_body(shape: &Shape, ec2: bool) -> String {
     match &shape.shape_type[..] {
-        "list" => generate_list_serializer(shape),
+        "list" => generate_list_serializer(shape, ec2),
         "map" => generate_map_serializer(shape),
-        "structure" => generate_struct_serializer(shape),
+        "structure" => generate_struct_serializer(shape, ec2),
         _ => generate_primitive_serializer(shape),
     }
 }
@@ -330,13 +456,25 @@ fn generate_serializer_signature(name: &str, shape: &Shape) -> String {
     }
 }
 
-fn generate_list_serializer(shape: &Shape) -> String {
+fn generate_list_serializer(shape: &Shape, ec2: bool) -> String {
+    // Query list parameters are numbered from 1, and lists that aren't flattened nest their
+    // entries under an extra `member`-style segment: `Name.member.1`. EC2 lists never do.
+    let key_format = if ec2 || shape.flattened.is_some() {
+        "format!(\"{}.{}\", name, index + 1)".to_owned()
+    } else {
+        format!(
+            "format!(\"{{}}.{member_tag_name}.{{}}\", name, index + 1)",
+            member_tag_name = list_member_tag_name(shape),
+        )
+    };
+
     format!(
         "for (index, element) in obj.iter().enumerate() {{
-    let key = format!(\"{{}}.{{}}\", name, index);
+    let key = {key_format};
     {name}Serializer::serialize(params, &key, element);
 }}
         ",
+        key_format = key_format,
         name = shape.member(),
     )
 }
@@ -362,7 +500,7 @@ fn generate_map_serializer(shape: &Shape) -> String {
     )
 }
 
-fn generate_struct_serializer(shape: &Shape) -> String {
+fn generate_struct_serializer(shape: &Shape, ec2: bool) -> String {
     format!(
         "let mut prefix = name.to_string();
 if prefix != \"\" {{
@@ -371,12 +509,18 @@ if prefix != \"\" {{
 
 {struct_field_serializers}
         ",
-        struct_field_serializers = generate_struct_field_serializers(shape),
+        struct_field_serializers = generate_struct_field_serializers(shape, ec2),
     )
 }
 
-fn generate_struct_field_serializers(shape: &Shape) -> String {
+fn generate_struct_field_serializers(shape: &Shape, ec2: bool) -> String {
     shape.members.as_ref().unwrap().iter().map(|(member_name, member)| {
+        let tag_name = if ec2 {
+            ec2_parameter_name(member_name, member)
+        } else {
+            member.location_name.as_ref().map(|name| &name[..]).unwrap_or(member_name).to_owned()
+        };
+
         if shape.required(member_name) {
             format!(
                 "{member_shape_name}Serializer::serialize(
@@ -385,9 +529,9 @@ fn generate_struct_field_serializers(shape: &Shape) -> String {
     &obj.{field_name},
 );
                 ",
-                field_name = member_name.to_snake_case(),
+                field_name = generate_field_name(member_name),
                 member_shape_name = member.shape,
-                tag_name = member_name,
+                tag_name = tag_name,
             )
         } else {
             format!(
@@ -399,18 +543,38 @@ fn generate_struct_field_serializers(shape: &Shape) -> String {
     );
 }}
                 ",
-                field_name = member_name.to_snake_case(),
+                field_name = generate_field_name(member_name),
                 member_shape_name = member.shape,
-                tag_name = member.tag_name(),
+                tag_name = tag_name,
             )
         }
     }).collect::<Vec<String>>().join("\n")
 }
 
+/// The name EC2 expects a member to be sent as: its `queryName`, or its `locationName` with
+/// the first letter capitalized.
+fn ec2_parameter_name(member_name: &str, member: &Member) -> String {
+    if let Some(ref query_name) = member.query_name {
+        return query_name.clone();
+    }
+
+    match member.location_name {
+        Some(ref location_name) => {
+            let mut characters = location_name.chars();
+
+            match characters.next() {
+                Some(first) => first.to_uppercase().chain(characters).collect(),
+                None => String::new(),
+            }
+        }
+        None => member_name.to_owned(),
+    }
+}
+
 fn generate_primitive_serializer(shape: &Shape) -> String {
     let expression = match &shape.shape_type[..] {
         "string" | "timestamp" => "obj",
-        "integer" | "double" | "boolean" => "&obj.to_string()",
+        "integer" | "long" | "float" | "double" | "boolean" => "&obj.to_string()",
         "blob" => "from_utf8(obj).unwrap()",
         shape_type => panic!("Unknown primitive shape type: {}", shape_type),
     };
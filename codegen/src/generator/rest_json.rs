@@ -2,7 +2,7 @@ use inflector::Inflector;
 use regex::{Captures, Regex};
 
 use botocore::{Member, Operation, Service, Shape};
-use super::GenerateProtocol;
+use super::{GenerateProtocol, generate_endpoint_selection, generate_field_name};
 
 pub struct RestJsonGenerator;
 
@@ -17,7 +17,7 @@ impl GenerateProtocol for RestJsonGenerator {
 
             // Construct a list of format strings which will be used to format
             // the request URI, mapping the input struct to the URI arguments.
-            let member_uri_strings = generate_shape_member_uri_strings(input_shape);
+            let member_uri_strings = generate_shape_member_uri_strings(service, input_shape, &operation.http.request_uri);
 
             // A boolean controlling whether or not the payload should be loaded
             // into the request.
@@ -30,10 +30,14 @@ impl GenerateProtocol for RestJsonGenerator {
                 .iter()
                 .any(|(_, member)| member.location.is_none());
 
+            // Construct a list of strings which will be used to add the members
+            // bound to HTTP headers to the request.
+            let member_header_strings = generate_shape_member_header_strings(service, input_shape);
+
             // Construct a list of strings which will be used to load request
             // parameters from the input struct into a `Params` vec, which will
             // then be added to the request.
-            let member_param_strings = generate_shape_member_param_strings(input_shape);
+            let member_param_strings = generate_shape_member_param_strings(service, input_shape);
 
             format!("
                 {documentation}
@@ -42,49 +46,47 @@ impl GenerateProtocol for RestJsonGenerator {
 
                     {request_uri_formatter}
 
-                    let mut request = SignedRequest::new(\"{http_method}\", \"{endpoint_prefix}\", self.region, &request_uri);
+                    let mut request = SignedRequest::new(\"{http_method}\", \"{signing_name}\", self.region, &request_uri);
+                    {endpoint_selection}
                     request.set_content_type(\"application/x-amz-json-1.1\".to_owned());
                     {load_payload}
+                    {load_headers}
+                    {load_service_headers}
                     {load_params}
 
                     let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
                     let status = result.status.to_u16();
-                    let mut body = String::new();
-                    result.read_to_string(&mut body).unwrap();
-
-                    // `serde-json` serializes field-less structs as \"null\", but AWS returns
-                    // \"{{}}\" for a field-less response, so we must check for this result
-                    // and convert it if necessary.
-                    if body == \"{{}}\" {{
-                        body = \"null\".to_owned();
-                    }}
+                    let mut body = Vec::new();
+                    result.read_to_end(&mut body).unwrap();
 
-                    debug!(\"Response body: {{}}\", body);
+                    debug!(\"Response body: {{}}\", String::from_utf8_lossy(&body));
                     debug!(\"Response status: {{}}\", status);
 
                     match status {{
-                        {status_code} => {{
+                        200...299 => {{
                             {ok_response}
                         }}
-                        _ => Err(parse_json_protocol_error(&body)),
+                        _ => Err(parse_json_protocol_error(&String::from_utf8_lossy(&body))),
                     }}
                 }}
                 ",
                 documentation = generate_documentation(operation).unwrap_or("".to_owned()),
-                endpoint_prefix = service.metadata.endpoint_prefix,
+                endpoint_selection = generate_endpoint_selection(service, operation),
+                signing_name = service.signing_name(),
                 http_method = operation.http.method,
                 input_type = input_type,
                 method_name = operation.name.to_snake_case(),
-                status_code = operation.http.response_code.unwrap_or(200),
-                ok_response = generate_ok_response(operation, output_type),
+                ok_response = generate_ok_response(service, operation, output_type),
                 output_type = output_type,
                 request_uri_formatter = generate_uri_formatter(
                     &generate_snake_case_uri(&operation.http.request_uri),
                     &member_uri_strings
                 ),
-                load_payload = generate_payload_loading_string(load_payload),
+                load_payload = generate_payload_loading_string(service, input_shape, load_payload),
+                load_headers = member_header_strings.join("\n"),
+                load_service_headers = generate_service_headers(service),
                 load_params = generate_params_loading_string(&member_param_strings),
-                encode_input = generate_encoding_string(load_payload),
+                encode_input = generate_encoding_string(service, input_shape, load_payload),
             )
         }).collect::<Vec<String>>().join("\n")
     }
@@ -103,33 +105,178 @@ impl GenerateProtocol for RestJsonGenerator {
     }
 
     fn generate_struct_attributes(&self) -> String {
-        "#[derive(Debug, Default, Deserialize, Serialize)]".to_owned()
+        "#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]".to_owned()
+    }
+
+    fn generate_support_types(&self, name: &str, shape: &Shape, service: &Service) -> Option<String> {
+        let is_output = service.operations.values().any(|operation| {
+            operation.output.as_ref().map(|output| &output.shape[..]) == Some(name)
+        });
+
+        if is_output && shape.shape_type == "structure" && !is_json_body_shape(shape) {
+            Some(generate_from_response(service, name, shape))
+        } else {
+            None
+        }
     }
 }
 
-fn generate_encoding_string(load_payload: bool) -> String {
-    if load_payload {
-       "let encoded = serde_json::to_string(input).unwrap();".to_owned()
-    } else {
-        "".to_owned()
+/// Returns the member the shape binds to the HTTP body, if it has one, along with its name.
+fn payload_member<'a>(shape: &'a Shape) -> Option<(&'a str, &'a Member)> {
+    shape.payload.as_ref().and_then(|payload| {
+        shape.members.as_ref().unwrap().get(payload).map(|member| (&payload[..], member))
+    })
+}
+
+fn generate_encoding_string(service: &Service, shape: &Shape, load_payload: bool) -> String {
+    match payload_member(shape) {
+        // Blob payloads are sent as they are.
+        Some((_, member)) if service.shape_type_for_member(member) == Some("blob") => {
+            "".to_owned()
+        }
+        Some((member_name, _)) if shape.required(member_name) => {
+            format!(
+                "let encoded = serde_json::to_string(&input.{field_name}).unwrap();",
+                field_name = generate_field_name(member_name),
+            )
+        }
+        Some((member_name, _)) => {
+            format!(
+                "let encoded = input.{field_name}.as_ref().map(|payload| serde_json::to_string(payload).unwrap());",
+                field_name = generate_field_name(member_name),
+            )
+        }
+        None if load_payload => "let encoded = serde_json::to_string(input).unwrap();".to_owned(),
+        None => "".to_owned(),
     }
  }
 
-fn generate_payload_loading_string(load_payload: bool) -> String {
-    if load_payload {
-        "request.set_payload(Some(encoded.as_bytes()));".to_owned()
-    } else {
-        "".to_owned()
+fn generate_payload_loading_string(service: &Service, shape: &Shape, load_payload: bool) -> String {
+    match payload_member(shape) {
+        Some((member_name, member)) if service.shape_type_for_member(member) == Some("blob") => {
+            if shape.required(member_name) {
+                format!(
+                    "request.set_payload(Some(&input.{field_name}));",
+                    field_name = generate_field_name(member_name),
+                )
+            } else {
+                format!(
+                    "request.set_payload(input.{field_name}.as_ref().map(|payload| &payload[..]));",
+                    field_name = generate_field_name(member_name),
+                )
+            }
+        }
+        Some((member_name, _)) if shape.required(member_name) => {
+            "request.set_payload(Some(encoded.as_bytes()));".to_owned()
+        }
+        Some(_) => "request.set_payload(encoded.as_ref().map(|payload| payload.as_bytes()));".to_owned(),
+        None if load_payload => "request.set_payload(Some(encoded.as_bytes()));".to_owned(),
+        None => "".to_owned(),
+    }
+}
+
+/// Generates the headers a service expects on every request.
+fn generate_service_headers(service: &Service) -> String {
+    match &service.metadata.endpoint_prefix[..] {
+        "glacier" => {
+            format!(
+                "request.add_header(\"x-amz-glacier-version\", \"{}\");",
+                service.metadata.api_version,
+            )
+        }
+        _ => "".to_owned(),
+    }
+}
+
+fn generate_shape_member_header_strings(service: &Service, shape: &Shape) -> Vec<String> {
+    shape.members.as_ref().unwrap().iter()
+        .filter_map(|(member_name, member)| {
+            generate_header_load_string(service, shape, &member_name, member)
+        })
+        .collect::<Vec<String>>()
+}
+
+fn generate_header_load_string(service: &Service, shape: &Shape, member_name: &str, member: &Member)
+    -> Option<String> {
+    match member.location {
+        Some(ref x) if x == "header" => {
+            let header_name = member.location_name.as_ref().map(|name| &name[..]).unwrap_or(member_name);
+
+            let to_str = match service.shape_type_for_member(member) {
+                Some("string") => "",
+                Some("map") => panic!(
+                    "{}: the header member {} is a map, which must be bound to `headers` instead",
+                    service.metadata.service_full_name,
+                    member_name,
+                ),
+                _ => ".to_string()",
+            };
+
+            // `SignedRequest` sets the content type header itself when it's signed.
+            let load_header = |value: &str| {
+                if header_name.to_lowercase() == "content-type" {
+                    format!("request.set_content_type({value}{to_str}.to_owned())", value = value, to_str = to_str)
+                } else {
+                    format!(
+                        "request.add_header(\"{header_name}\", &{value}{to_str})",
+                        header_name = header_name,
+                        value = value,
+                        to_str = to_str,
+                    )
+                }
+            };
+
+            if shape.required(member_name) {
+                Some(format!(
+                    "{load_header};",
+                    load_header = load_header(&format!("input.{}", generate_field_name(member_name))),
+                ))
+            } else {
+                Some(format!(
+                    "match input.{field_name} {{
+                        Some(ref x) => {load_header},
+                        None => {{}},
+                    }}",
+                    field_name = generate_field_name(member_name),
+                    load_header = load_header("x"),
+                ))
+            }
+        },
+        // Maps of prefixed headers, such as S3's metadata, are sent as a header for each entry.
+        Some(ref x) if x == "headers" => {
+            let load_headers = |value: &str| {
+                format!(
+                    "for (key, value) in {value} {{ request.add_header(&format!(\"{prefix}{{}}\", key), value); }}",
+                    value = value,
+                    prefix = member.location_name.as_ref().map(|name| &name[..]).unwrap_or(""),
+                )
+            };
+
+            if shape.required(member_name) {
+                Some(load_headers(&format!("&input.{}", generate_field_name(member_name))))
+            } else {
+                Some(format!(
+                    "match input.{field_name} {{
+                        Some(ref x) => {{ {load_headers} }},
+                        None => {{}},
+                    }}",
+                    field_name = generate_field_name(member_name),
+                    load_headers = load_headers("x"),
+                ))
+            }
+        },
+        Some(_) => None,
+        None => None,
     }
 }
 
 fn generate_snake_case_uri(request_uri: &str) -> String {
     lazy_static! {
-        static ref URI_ARGS_REGEX: Regex = Regex::new(r"\{([\w\d]+)\}").unwrap();
+        static ref URI_ARGS_REGEX: Regex = Regex::new(r"\{([\w\d-]+)\+?\}").unwrap();
     }
 
     URI_ARGS_REGEX.replace_all(request_uri, |caps: &Captures| {
-        format!("{{{}}}", caps.at(1).map(Inflector::to_snake_case).unwrap())
+        format!("{{{}}}", caps.at(1).map(generate_field_name).unwrap())
     })
 }
 
@@ -147,29 +294,100 @@ fn generate_params_loading_string(param_strings: &[String]) -> String {
     }
 }
 
-fn generate_shape_member_param_strings(shape: &Shape) -> Vec<String> {
+fn generate_shape_member_param_strings(service: &Service, shape: &Shape) -> Vec<String> {
     shape.members.as_ref().unwrap().iter()
-        .filter_map(|(member_name, member)| generate_param_load_string(&member_name, member))
+        .filter_map(|(member_name, member)| {
+            generate_param_load_string(service, shape, &member_name, member)
+        })
         .collect::<Vec<String>>()
 }
 
-fn generate_param_load_string(member_name: &str, member: &Member) -> Option<String> {
+fn generate_param_load_string(service: &Service, shape: &Shape, member_name: &str, member: &Member)
+    -> Option<String> {
     match member.location {
         Some(ref x) if x == "querystring" => {
-            Some(format!(
-                "match input.{field_name} {{
-                    Some(ref x) => params.put(\"{member_name}\", x),
-                    None => {{}},
-                }}",
-                member_name = member_name,
-                field_name = member_name.to_snake_case(),
-            ))
+            let param_name = member.location_name.as_ref().map(|name| &name[..]).unwrap_or(member_name);
+            let member_shape = service.shape_for_member(member).expect("Member shape undefined");
+
+            // `value` is a reference to the member's value.
+            let load_param = |value: &str| {
+                match &member_shape.shape_type[..] {
+                    // Lists are sent as the same parameter repeated for each item.
+                    "list" => format!(
+                        "for item in {value} {{ request.add_repeated_param(\"{param_name}\", {item}); }}",
+                        value = value,
+                        param_name = param_name,
+                        item = generate_querystring_value(service, member_shape.member(), "item", member_name),
+                    ),
+                    // Maps bound to the querystring as a whole hold the parameters themselves,
+                    // with a list of values in the case of repeated parameters.
+                    "map" => {
+                        let value_shape = service.shapes.get(member_shape.value()).expect("Value shape undefined");
+
+                        if value_shape.shape_type == "list" {
+                            format!(
+                                "for (key, values) in {value} {{
+                                    for item in values {{ request.add_repeated_param(key, {item}); }}
+                                }}",
+                                value = value,
+                                item = generate_querystring_value(service, value_shape.member(), "item", member_name),
+                            )
+                        } else {
+                            format!(
+                                "for (key, item) in {value} {{ params.put(key, {item}); }}",
+                                value = value,
+                                item = generate_querystring_value(service, member_shape.value(), "item", member_name),
+                            )
+                        }
+                    }
+                    _ => format!(
+                        "params.put(\"{param_name}\", {param_value});",
+                        param_name = param_name,
+                        param_value = generate_querystring_value(service, &member.shape, value, member_name),
+                    ),
+                }
+            };
+
+            if shape.required(member_name) {
+                Some(load_param(&format!("&input.{}", generate_field_name(member_name))))
+            } else {
+                Some(format!(
+                    "match input.{field_name} {{
+                        Some(ref x) => {{ {load_param} }},
+                        None => {{}},
+                    }}",
+                    field_name = generate_field_name(member_name),
+                    load_param = load_param("x"),
+                ))
+            }
         },
         Some(_) => None,
         None => None,
     }
 }
 
+/// Generates a querystring value of the shape `shape_name` from `value`, a reference to it.
+fn generate_querystring_value(service: &Service, shape_name: &str, value: &str, member_name: &str) -> String {
+    let shape = service.shapes.get(shape_name).expect("Member shape undefined");
+
+    match &shape.shape_type[..] {
+        "string" => value.to_owned(),
+        // Timestamps are sent as ISO 8601, unless the shape says otherwise.
+        "timestamp" => match shape.timestamp_format.as_ref().map(|format| &format[..]) {
+            Some("iso8601") => value.to_owned(),
+            Some("unixTimestamp") => format!("&{}.to_string()", value),
+            _ => format!("&::param::iso8601_timestamp(*{})", value),
+        },
+        "boolean" | "double" | "float" | "integer" | "long" => format!("&{}.to_string()", value),
+        shape_type => panic!(
+            "{}: the querystring member {} holds a {}, which can't be sent in the querystring",
+            service.metadata.service_full_name,
+            member_name,
+            shape_type,
+        ),
+    }
+}
+
 fn generate_uri_formatter(request_uri: &str, uri_strings: &[String]) -> String {
     match uri_strings.len() {
         0 => {
@@ -187,19 +405,37 @@ fn generate_uri_formatter(request_uri: &str, uri_strings: &[String]) -> String {
     }
 }
 
-fn generate_shape_member_uri_strings(shape: &Shape) -> Vec<String> {
+fn generate_shape_member_uri_strings(service: &Service, shape: &Shape, request_uri: &str) -> Vec<String> {
     shape.members.as_ref().unwrap().iter()
-        .filter_map(|(member_name, member)| generate_member_format_string(&member_name.to_snake_case(), member))
+        .filter_map(|(member_name, member)| {
+            generate_member_format_string(service, request_uri, &member_name, member)
+        })
         .collect::<Vec<String>>()
 }
 
-fn generate_member_format_string(member_name: &str, member: &Member) -> Option<String> {
+fn generate_member_format_string(service: &Service, request_uri: &str, member_name: &str, member: &Member)
+    -> Option<String> {
     match member.location {
         Some(ref x) if x == "uri" => {
+            // The placeholders in the request URI are named after the member's location name,
+            // which doesn't always match the member name.
+            let location_name = member.location_name.as_ref().map(|name| &name[..]).unwrap_or(member_name);
+            let field_name = generate_field_name(member_name);
+
+            // Values such as ARNs are percent-encoded so their slashes stay in one segment,
+            // unless the placeholder is greedy (`{Key+}`) and spans several segments.
+            let value = if request_uri.contains(&format!("{{{}+}}", location_name)) {
+                format!("input.{}", field_name)
+            } else if service.shape_type_for_member(member) == Some("string") {
+                format!("::signature::encode_uri(&input.{})", field_name)
+            } else {
+                format!("input.{}", field_name)
+            };
+
             Some(format!(
-                "{member_name} = input.{field_name}",
-                field_name = member_name,
-                member_name = member_name,
+                "{uri_arg_name} = {value}",
+                uri_arg_name = generate_field_name(location_name),
+                value = value,
             ))
         },
         Some(_) => None,
@@ -213,10 +449,156 @@ fn generate_documentation(operation: &Operation) -> Option<String> {
     })
 }
 
-fn generate_ok_response(operation: &Operation, output_type: &str) -> String {
-    if operation.output.is_some() {
-        format!("Ok(serde_json::from_str::<{}>(&body).unwrap())", output_type)
+fn generate_ok_response(service: &Service, operation: &Operation, output_type: &str) -> String {
+    if operation.output.is_none() {
+        return "Ok(())".to_owned();
+    }
+
+    if is_json_body_shape(service.shapes.get(output_type).unwrap()) {
+        format!(
+            "// `serde-json` serializes field-less structs as \"null\", but AWS returns
+            // \"{{}}\" for a field-less response, so we must check for this result
+            // and convert it if necessary.
+            let body = if body == b\"{{}}\" {{ b\"null\".to_vec() }} else {{ body }};
+
+            Ok(serde_json::from_slice::<{output_type}>(&body).unwrap())",
+            output_type = output_type,
+        )
+    } else {
+        format!("Ok({}::from_response(status, &result.headers, body))", output_type)
+    }
+}
+
+/// Whether the shape is made up entirely of members in the JSON body, and so can be
+/// deserialized straight from it.
+fn is_json_body_shape(shape: &Shape) -> bool {
+    payload_member(shape).is_none() && shape.members.as_ref().map_or(true, |members| {
+        members.values().all(|member| member.location.is_none())
+    })
+}
+
+/// Generates the constructor for an output whose members are spread across the response's
+/// status code, headers and body.
+fn generate_from_response(service: &Service, name: &str, shape: &Shape) -> String {
+    let members = shape.members.as_ref().unwrap();
+    let payload = payload_member(shape);
+
+    let init = match payload {
+        Some((member_name, member)) if service.shape_type_for_member(member) != Some("blob") => {
+            format!(
+                "let mut output = {name}::default();
+                output.{field_name} = {value};",
+                field_name = generate_field_name(member_name),
+                name = name,
+                value = if shape.required(member_name) {
+                    "serde_json::from_slice(&body).unwrap()"
+                } else {
+                    "serde_json::from_slice(&body).ok()"
+                },
+            )
+        }
+        Some(_) => format!("let mut output = {}::default();", name),
+        None if members.values().any(|member| member.location.is_none()) => {
+            format!("let mut output = serde_json::from_slice::<{}>(&body).unwrap();", name)
+        }
+        None => format!("let mut output = {}::default();", name),
+    };
+
+    let assignments = members.iter()
+        .filter_map(|(member_name, member)| {
+            generate_output_member_assignment(service, shape, member_name, member)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    // A blob payload takes ownership of the body, so it has to be assigned last. An empty
+    // body, such as that of a 204 response, means there's no payload.
+    let blob_payload = match payload {
+        Some((member_name, member)) if service.shape_type_for_member(member) == Some("blob") => {
+            format!(
+                "output.{field_name} = {value};",
+                field_name = generate_field_name(member_name),
+                value = if shape.required(member_name) {
+                    "body"
+                } else {
+                    "if body.is_empty() { None } else { Some(body) }"
+                },
+            )
+        }
+        _ => "".to_owned(),
+    };
+
+    format!(
+        "impl {name} {{
+            #[allow(unused_variables)]
+            fn from_response(status: u16, headers: &::hyper::header::Headers, body: Vec<u8>) -> {name} {{
+                {init}
+                {assignments}
+                {blob_payload}
+
+                output
+            }}
+        }}",
+        assignments = assignments,
+        blob_payload = blob_payload,
+        init = init,
+        name = name,
+    )
+}
+
+/// Generates the assignment of an output member bound to a response header or the status code.
+fn generate_output_member_assignment(service: &Service, shape: &Shape, member_name: &str, member: &Member)
+    -> Option<String> {
+    let value = match member.location {
+        Some(ref x) if x == "header" => {
+            let header_name = member.location_name.as_ref().map(|name| &name[..]).unwrap_or(member_name);
+            let header_value = format!(
+                "headers.get_raw(\"{}\").map(|values| String::from_utf8_lossy(&values[0]).into_owned())",
+                header_name,
+            );
+
+            match service.shape_type_for_member(member) {
+                Some("string") => header_value,
+                Some("map") => panic!(
+                    "{}: the header member {} is a map, which must be bound to `headers` instead",
+                    service.metadata.service_full_name,
+                    member_name,
+                ),
+                _ => format!("{}.and_then(|value| value.parse().ok())", header_value),
+            }
+        }
+        // Maps of prefixed headers hold every header starting with the prefix, without it.
+        Some(ref x) if x == "headers" => {
+            let prefix = member.location_name.as_ref().map(|name| name.to_lowercase()).unwrap_or_else(String::new);
+
+            format!(
+                "{{
+                    let values = headers.iter()
+                        .filter(|header| header.name().to_lowercase().starts_with(\"{prefix}\"))
+                        .map(|header| (header.name()[{prefix_len}..].to_owned(), header.value_string()))
+                        .collect::<::std::collections::HashMap<String, String>>();
+
+                    if values.is_empty() {{ None }} else {{ Some(values) }}
+                }}",
+                prefix = prefix,
+                prefix_len = prefix.len(),
+            )
+        }
+        Some(ref x) if x == "statusCode" => "Some(status as i32)".to_owned(),
+        _ => return None,
+    };
+
+    if shape.required(member_name) {
+        Some(format!(
+            "output.{field_name} = {value}.unwrap_or_else(Default::default);",
+            field_name = generate_field_name(member_name),
+            value = value,
+        ))
     } else {
-        "Ok(())".to_owned()
+        Some(format!(
+            "output.{field_name} = {value};",
+            field_name = generate_field_name(member_name),
+            value = value,
+        ))
     }
 }
@@ -0,0 +1,160 @@
+//! AWS Amplify
+
+use std::collections::HashMap;
+
+include!(concat!(env!("OUT_DIR"), "/amplify.rs"));
+
+use pagination::read_all_pages;
+
+/// The platform of apps made of static files only.
+pub const WEB: &'static str = "WEB";
+
+/// The platform of apps with server-side rendering, such as Next.js apps.
+pub const WEB_COMPUTE: &'static str = "WEB_COMPUTE";
+
+/// The stage of production branches.
+pub const PRODUCTION: &'static str = "PRODUCTION";
+
+/// The stage of beta branches.
+pub const BETA: &'static str = "BETA";
+
+/// The stage of development branches.
+pub const DEVELOPMENT: &'static str = "DEVELOPMENT";
+
+/// The stage of experimental branches.
+pub const EXPERIMENTAL: &'static str = "EXPERIMENTAL";
+
+impl<P> AmplifyClient<P> where P: ProvideAwsCredentials {
+    /// Creates an app on the platform `WEB` or `WEB_COMPUTE`, connected to the Git
+    /// `repository` if given, which Amplify accesses with `oauth_token` unless it's hosted on
+    /// GitHub. Without a `build_spec`, the one in the repository is used.
+    pub fn add_app(
+        &mut self,
+        name: &str,
+        repository: Option<String>,
+        platform: &str,
+        oauth_token: Option<String>,
+        environment_variables: HashMap<String, String>,
+        build_spec: Option<String>,
+    ) -> AwsResult<App> {
+        let input = CreateAppRequest {
+            build_spec: build_spec,
+            environment_variables: if environment_variables.is_empty() { None } else { Some(environment_variables) },
+            name: name.to_owned(),
+            oauth_token: oauth_token,
+            platform: Some(platform.to_owned()),
+            repository: repository,
+            ..CreateAppRequest::default()
+        };
+
+        Ok(try!(self.create_app(&input)).app)
+    }
+
+    /// Connects the branch `branch_name` of an app's repository, in a stage such as
+    /// `PRODUCTION`. Pushes to it are only built and deployed if `enable_auto_build` is set.
+    /// Its `environment_variables` override those of the app.
+    pub fn add_branch(
+        &mut self,
+        app_id: &str,
+        branch_name: &str,
+        stage: &str,
+        enable_auto_build: bool,
+        environment_variables: HashMap<String, String>,
+    ) -> AwsResult<Branch> {
+        let input = CreateBranchRequest {
+            app_id: app_id.to_owned(),
+            branch_name: branch_name.to_owned(),
+            enable_auto_build: Some(enable_auto_build),
+            environment_variables: if environment_variables.is_empty() { None } else { Some(environment_variables) },
+            stage: Some(stage.to_owned()),
+            ..CreateBranchRequest::default()
+        };
+
+        Ok(try!(self.create_branch(&input)).branch)
+    }
+
+    /// Deploys a branch which isn't connected to a repository, from the files uploaded for
+    /// the job `job_id` by `CreateDeployment`, or from the zip file at `source_url`.
+    pub fn deploy_branch(&mut self, app_id: &str, branch_name: &str, job_id: Option<String>,
+        source_url: Option<String>) -> AwsResult<JobSummary> {
+        let input = StartDeploymentRequest {
+            app_id: app_id.to_owned(),
+            branch_name: branch_name.to_owned(),
+            job_id: job_id,
+            source_url: source_url,
+            ..StartDeploymentRequest::default()
+        };
+
+        Ok(try!(self.start_deployment(&input)).job_summary)
+    }
+
+    /// Calls `ListApps` until every page has been read, with up to `max_results` apps on each,
+    /// and returns all of them.
+    pub fn list_all_apps(&mut self, max_results: Option<i32>) -> AwsResult<Vec<App>> {
+        let mut input = ListAppsRequest {
+            max_results: max_results,
+            ..ListAppsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_apps(&input));
+
+            Ok((output.apps, output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{GetAppResult, ListDomainAssociationsResult, WEB};
+
+    fn read_sample(name: &str) -> String {
+        let mut body = String::new();
+        File::open(format!("tests/sample-data/{}", name)).unwrap().read_to_string(&mut body).unwrap();
+        body
+    }
+
+    #[test]
+    fn parse_app_with_production_branch() {
+        let app = serde_json::from_str::<GetAppResult>(&read_sample("amplify_get_app.json")).unwrap().app;
+
+        assert_eq!(app.app_id, "d2lf8qkn6rvp9h");
+        assert_eq!(app.name, "storefront");
+        assert_eq!(app.platform, WEB);
+        assert_eq!(app.default_domain, "d2lf8qkn6rvp9h.amplifyapp.com");
+        assert_eq!(app.environment_variables.get("API_URL"), Some(&"https://api.example.com".to_owned()));
+
+        let custom_rules = app.custom_rules.unwrap();
+        assert_eq!(custom_rules[0].status, Some("404-200".to_owned()));
+
+        let production_branch = app.production_branch.unwrap();
+        assert_eq!(production_branch.branch_name, Some("main".to_owned()));
+        assert_eq!(production_branch.status, Some("SUCCEED".to_owned()));
+    }
+
+    #[test]
+    fn parse_custom_domain_associations() {
+        let body = read_sample("amplify_list_domain_associations.json");
+        let domain_associations = serde_json::from_str::<ListDomainAssociationsResult>(&body).unwrap()
+            .domain_associations;
+
+        assert_eq!(domain_associations.len(), 1);
+
+        let example = &domain_associations[0];
+        assert_eq!(example.domain_name, "example.com");
+        assert_eq!(example.domain_status, "AVAILABLE");
+
+        // The apex domain is the sub domain without a prefix.
+        let prefixes = example.sub_domains.iter()
+            .map(|sub_domain| &sub_domain.sub_domain_setting.prefix[..])
+            .collect::<Vec<&str>>();
+        assert_eq!(prefixes, vec!["", "www"]);
+        assert!(example.sub_domains.iter().all(|sub_domain| sub_domain.verified));
+    }
+}
@@ -0,0 +1,136 @@
+//! Amazon API Gateway V2
+//!
+//! Creates HTTP and WebSocket APIs, along with the routes, integrations and stages that make
+//! them up.
+
+include!(concat!(env!("OUT_DIR"), "/apigatewayv2.rs"));
+
+/// The protocol type of HTTP APIs, and the integration type of HTTP endpoints.
+pub const HTTP: &'static str = "HTTP";
+
+/// The protocol type of WebSocket APIs.
+pub const WEBSOCKET: &'static str = "WEBSOCKET";
+
+/// The integration type of Lambda functions and other AWS services.
+pub const AWS_PROXY: &'static str = "AWS_PROXY";
+
+/// The integration type of HTTP endpoints the request is passed through to unchanged.
+pub const HTTP_PROXY: &'static str = "HTTP_PROXY";
+
+/// The integration type of responses made up by API Gateway itself.
+pub const MOCK: &'static str = "MOCK";
+
+/// The authorization type of routes anyone can call.
+pub const NONE: &'static str = "NONE";
+
+/// The authorization type of routes called with SigV4 signed requests.
+pub const AWS_IAM: &'static str = "AWS_IAM";
+
+/// The authorization type of routes authorized with JSON web tokens.
+pub const JWT: &'static str = "JWT";
+
+/// The version of the payload sent to Lambda integrations of HTTP APIs.
+pub const PAYLOAD_FORMAT_VERSION_2_0: &'static str = "2.0";
+
+/// The URL the API `api_id` in `region` is invoked at, which is its `api_endpoint`.
+pub fn invoke_url(api_id: &str, region: Region) -> String {
+    format!("https://{}.execute-api.{}.amazonaws.com", api_id, region)
+}
+
+impl<P> ApiGatewayV2Client<P> where P: ProvideAwsCredentials {
+    /// Creates an API with the protocol type `HTTP` or `WEBSOCKET`. For HTTP APIs,
+    /// `route_key` and `target` quick create a route to an integration and a default stage.
+    pub fn add_api(&mut self, name: &str, protocol_type: &str, route_key: Option<String>, target: Option<String>)
+        -> AwsResult<CreateApiResponse> {
+        let input = CreateApiRequest {
+            name: name.to_owned(),
+            protocol_type: protocol_type.to_owned(),
+            route_key: route_key,
+            target: target,
+            ..CreateApiRequest::default()
+        };
+
+        self.create_api(&input)
+    }
+
+    /// Creates the route `route_key`, such as `GET /pets` or `$default`, of the API `api_id`,
+    /// sending requests to `target`, which is `integrations/` followed by an integration ID.
+    pub fn add_route(&mut self, api_id: &str, route_key: &str, target: Option<String>,
+        authorization_type: Option<String>) -> AwsResult<CreateRouteResult> {
+        let input = CreateRouteRequest {
+            api_id: api_id.to_owned(),
+            authorization_type: authorization_type,
+            route_key: route_key.to_owned(),
+            target: target,
+            ..CreateRouteRequest::default()
+        };
+
+        self.create_route(&input)
+    }
+
+    /// Creates an integration of the API `api_id` with `integration_uri`, such as the ARN of a
+    /// Lambda function for the integration type `AWS_PROXY`.
+    pub fn add_integration(&mut self, api_id: &str, integration_type: &str, integration_uri: Option<String>,
+        payload_format_version: Option<String>) -> AwsResult<CreateIntegrationResult> {
+        let input = CreateIntegrationRequest {
+            api_id: api_id.to_owned(),
+            integration_type: integration_type.to_owned(),
+            integration_uri: integration_uri,
+            payload_format_version: payload_format_version,
+            ..CreateIntegrationRequest::default()
+        };
+
+        self.create_integration(&input)
+    }
+
+    /// Creates the stage `stage_name` of the API `api_id`, which deploys every change to the API
+    /// if `auto_deploy` is set.
+    pub fn add_stage(&mut self, api_id: &str, stage_name: &str, auto_deploy: bool)
+        -> AwsResult<CreateStageResponse> {
+        let input = CreateStageRequest {
+            api_id: api_id.to_owned(),
+            auto_deploy: Some(auto_deploy),
+            stage_name: stage_name.to_owned(),
+            ..CreateStageRequest::default()
+        };
+
+        self.create_stage(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use region::Region;
+    use super::{CreateApiResponse, HTTP, invoke_url};
+
+    #[test]
+    fn parse_created_http_api() {
+        let body = r#"{
+            "apiEndpoint": "https://a1b2c3d4e5.execute-api.eu-west-1.amazonaws.com",
+            "apiId": "a1b2c3d4e5",
+            "apiKeySelectionExpression": "$request.header.x-api-key",
+            "createdDate": "2020-04-08T19:05:45Z",
+            "disableExecuteApiEndpoint": false,
+            "name": "pets",
+            "protocolType": "HTTP",
+            "routeSelectionExpression": "$request.method $request.path",
+            "tags": {}
+        }"#;
+        let api = serde_json::from_str::<CreateApiResponse>(body).unwrap();
+
+        assert_eq!(api.api_id, Some("a1b2c3d4e5".to_owned()));
+        assert_eq!(api.protocol_type, Some(HTTP.to_owned()));
+        assert_eq!(api.created_date, Some("2020-04-08T19:05:45Z".to_owned()));
+        assert_eq!(api.api_endpoint, Some(invoke_url("a1b2c3d4e5", Region::EuWest1)));
+    }
+
+    #[test]
+    fn invoke_url_includes_api_id_and_region() {
+        assert_eq!(
+            invoke_url("xyz123", Region::UsEast1),
+            "https://xyz123.execute-api.us-east-1.amazonaws.com"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Amazon AppConfig
+
+include!(concat!(env!("OUT_DIR"), "/appconfig.rs"));
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+
+    use super::Configuration;
+
+    fn headers(configuration_version: &str) -> Headers {
+        let mut headers = Headers::new();
+        headers.set_raw("Configuration-Version", vec![configuration_version.as_bytes().to_vec()]);
+        headers.set_raw("Content-Type", vec![b"application/json".to_vec()]);
+
+        headers
+    }
+
+    #[test]
+    fn get_configuration_with_new_content() {
+        let configuration = Configuration::from_response(
+            200,
+            &headers("3"),
+            b"{\"featureFlag\":true}".to_vec(),
+        );
+
+        assert_eq!(configuration.configuration_version, Some("3".to_owned()));
+        assert_eq!(configuration.content_type, Some("application/json".to_owned()));
+        assert_eq!(configuration.content, Some(b"{\"featureFlag\":true}".to_vec()));
+    }
+
+    #[test]
+    fn get_configuration_with_unchanged_content() {
+        // AppConfig responds with 204 No Content when the client already has the latest
+        // version of the configuration.
+        let configuration = Configuration::from_response(204, &headers("3"), Vec::new());
+
+        assert_eq!(configuration.configuration_version, Some("3".to_owned()));
+        assert_eq!(configuration.content, None);
+    }
+}
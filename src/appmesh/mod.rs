@@ -0,0 +1,210 @@
+//! AWS App Mesh
+
+include!(concat!(env!("OUT_DIR"), "/appmesh.rs"));
+
+impl Duration {
+    pub fn milliseconds(value: i64) -> Duration {
+        Duration {
+            unit: Some("ms".to_owned()),
+            value: Some(value),
+        }
+    }
+
+    pub fn seconds(value: i64) -> Duration {
+        Duration {
+            unit: Some("s".to_owned()),
+            value: Some(value),
+        }
+    }
+}
+
+impl WeightedTarget {
+    /// A target sent `weight` out of the route's total weight of requests.
+    pub fn new(virtual_node: &str, weight: i32) -> WeightedTarget {
+        WeightedTarget {
+            virtual_node: virtual_node.to_owned(),
+            weight: weight,
+            ..WeightedTarget::default()
+        }
+    }
+}
+
+impl HttpRetryPolicy {
+    /// A policy retrying requests that fail with any of `http_retry_events`, such as
+    /// `server-error` or `gateway-error`, or with a TCP connection error.
+    pub fn new(max_retries: i64, per_retry_timeout: Duration, http_retry_events: Vec<String>)
+        -> HttpRetryPolicy {
+        HttpRetryPolicy {
+            http_retry_events: Some(http_retry_events),
+            max_retries: max_retries,
+            per_retry_timeout: per_retry_timeout,
+            tcp_retry_events: Some(vec!["connection-error".to_owned()]),
+        }
+    }
+}
+
+impl GrpcRetryPolicy {
+    /// A policy retrying requests that fail with any of `grpc_retry_events`, such as
+    /// `unavailable` or `deadline-exceeded`, or with a TCP connection error.
+    pub fn new(max_retries: i64, per_retry_timeout: Duration, grpc_retry_events: Vec<String>)
+        -> GrpcRetryPolicy {
+        GrpcRetryPolicy {
+            grpc_retry_events: Some(grpc_retry_events),
+            max_retries: max_retries,
+            per_retry_timeout: per_retry_timeout,
+            tcp_retry_events: Some(vec!["connection-error".to_owned()]),
+            ..GrpcRetryPolicy::default()
+        }
+    }
+}
+
+impl RouteSpec {
+    /// An HTTP route sending requests whose path starts with `prefix` to `weighted_targets`.
+    pub fn http(prefix: &str, weighted_targets: Vec<WeightedTarget>, retry_policy: Option<HttpRetryPolicy>)
+        -> RouteSpec {
+        RouteSpec {
+            http_route: Some(HttpRoute {
+                action: HttpRouteAction { weighted_targets: weighted_targets },
+                aws_match: HttpRouteMatch {
+                    prefix: Some(prefix.to_owned()),
+                    ..HttpRouteMatch::default()
+                },
+                retry_policy: retry_policy,
+                ..HttpRoute::default()
+            }),
+            ..RouteSpec::default()
+        }
+    }
+
+    /// A gRPC route sending calls to the service `service_name`, or to `method_name` of it
+    /// only, to `weighted_targets`.
+    pub fn grpc(service_name: &str, method_name: Option<String>, weighted_targets: Vec<WeightedTarget>,
+        retry_policy: Option<GrpcRetryPolicy>) -> RouteSpec {
+        RouteSpec {
+            grpc_route: Some(GrpcRoute {
+                action: GrpcRouteAction { weighted_targets: weighted_targets },
+                aws_match: GrpcRouteMatch {
+                    method_name: method_name,
+                    service_name: Some(service_name.to_owned()),
+                    ..GrpcRouteMatch::default()
+                },
+                retry_policy: retry_policy,
+                ..GrpcRoute::default()
+            }),
+            ..RouteSpec::default()
+        }
+    }
+}
+
+impl<P> AppMeshClient<P> where P: ProvideAwsCredentials {
+    /// Creates a mesh. Without an egress filter, its services can only reach each other.
+    pub fn create_mesh_data(&mut self, mesh_name: &str, egress_filter: Option<EgressFilter>,
+        service_discovery: Option<MeshServiceDiscovery>) -> AwsResult<MeshData> {
+        let input = CreateMeshInput {
+            mesh_name: mesh_name.to_owned(),
+            spec: Some(MeshSpec {
+                egress_filter: egress_filter,
+                service_discovery: service_discovery,
+            }),
+            ..CreateMeshInput::default()
+        };
+
+        Ok(try!(self.create_mesh(&input)).mesh)
+    }
+
+    /// Creates a virtual service in a mesh.
+    pub fn create_virtual_service_data(&mut self, mesh_name: &str, virtual_service_name: &str,
+        spec: VirtualServiceSpec) -> AwsResult<VirtualServiceData> {
+        let input = CreateVirtualServiceInput {
+            mesh_name: mesh_name.to_owned(),
+            spec: spec,
+            virtual_service_name: virtual_service_name.to_owned(),
+            ..CreateVirtualServiceInput::default()
+        };
+
+        Ok(try!(self.create_virtual_service(&input)).virtual_service)
+    }
+
+    /// Creates a route of a virtual router.
+    pub fn create_route_data(&mut self, mesh_name: &str, virtual_router_name: &str, route_name: &str,
+        spec: RouteSpec) -> AwsResult<RouteData> {
+        let input = CreateRouteInput {
+            mesh_name: mesh_name.to_owned(),
+            route_name: route_name.to_owned(),
+            spec: spec,
+            virtual_router_name: virtual_router_name.to_owned(),
+            ..CreateRouteInput::default()
+        };
+
+        Ok(try!(self.create_route(&input)).route)
+    }
+
+    /// Describes a route of a virtual router.
+    pub fn route(&mut self, mesh_name: &str, virtual_router_name: &str, route_name: &str)
+        -> AwsResult<RouteData> {
+        let input = DescribeRouteInput {
+            mesh_name: mesh_name.to_owned(),
+            route_name: route_name.to_owned(),
+            virtual_router_name: virtual_router_name.to_owned(),
+            ..DescribeRouteInput::default()
+        };
+
+        Ok(try!(self.describe_route(&input)).route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DescribeRouteOutput, Duration, GrpcRetryPolicy, RouteSpec, WeightedTarget};
+
+    #[test]
+    fn parse_route_with_two_weighted_backends() {
+        let mut body = String::new();
+        File::open("tests/sample-data/appmesh_describe_route.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let route = serde_json::from_str::<DescribeRouteOutput>(&body).unwrap().route;
+
+        assert_eq!(route.route_name, "checkout-route");
+        assert_eq!(route.virtual_router_name, "checkout-router");
+        assert_eq!(route.status.status, "ACTIVE");
+
+        let http_route = route.spec.http_route.unwrap();
+        assert_eq!(http_route.aws_match.prefix, Some("/checkout".to_owned()));
+
+        let targets = http_route.action.weighted_targets;
+        assert_eq!(targets, vec![
+            WeightedTarget::new("checkout-v1", 90),
+            WeightedTarget::new("checkout-v2", 10),
+        ]);
+
+        let retry_policy = http_route.retry_policy.unwrap();
+        assert_eq!(retry_policy.max_retries, 3);
+        assert_eq!(retry_policy.per_retry_timeout, Duration::milliseconds(2000));
+        assert_eq!(
+            retry_policy.http_retry_events,
+            Some(vec!["server-error".to_owned(), "gateway-error".to_owned()])
+        );
+    }
+
+    #[test]
+    fn grpc_route_spec() {
+        let spec = RouteSpec::grpc(
+            "orders.OrderService",
+            Some("PlaceOrder".to_owned()),
+            vec![WeightedTarget::new("orders-v1", 1)],
+            Some(GrpcRetryPolicy::new(2, Duration::seconds(1), vec!["unavailable".to_owned()])),
+        );
+        let json = serde_json::to_string(&spec).unwrap();
+
+        assert!(spec.http_route.is_none());
+        assert!(json.contains("\"methodName\":\"PlaceOrder\""));
+        assert!(json.contains("\"serviceName\":\"orders.OrderService\""));
+        assert!(json.contains("\"grpcRetryEvents\":[\"unavailable\"]"));
+        assert!(json.contains("\"perRetryTimeout\":{\"unit\":\"s\",\"value\":1}"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Amazon AppStream 2.0
+
+include!(concat!(env!("OUT_DIR"), "/appstream.rs"));
+
+use error::expect;
+
+/// The type of fleets whose instances are always running, so sessions start immediately.
+pub const ALWAYS_ON: &'static str = "ALWAYS_ON";
+
+/// The type of fleets whose instances are only running while users are streaming, so
+/// sessions take a minute or two to start.
+pub const ON_DEMAND: &'static str = "ON_DEMAND";
+
+/// The type of fleets without instances to manage, which stream from a pool AppStream keeps.
+pub const ELASTIC: &'static str = "ELASTIC";
+
+/// The state of a fleet whose instances can be streamed from.
+pub const RUNNING: &'static str = "RUNNING";
+
+/// The state of a fleet which has no instances running.
+pub const STOPPED: &'static str = "STOPPED";
+
+impl<P> AppStreamClient<P> where P: ProvideAwsCredentials {
+    /// Creates a fleet of `ALWAYS_ON`, `ON_DEMAND` or `ELASTIC` instances of `instance_type`,
+    /// such as `stream.standard.medium`, running the image `image_name` or `image_arn`. The
+    /// fleet is `STOPPED` until it's started with `StartFleet`.
+    pub fn add_fleet(
+        &mut self,
+        name: &str,
+        instance_type: &str,
+        fleet_type: &str,
+        compute_capacity: ComputeCapacity,
+        image_name: Option<String>,
+        image_arn: Option<String>,
+        vpc_config: Option<VpcConfig>,
+    ) -> AwsResult<Fleet> {
+        let input = CreateFleetRequest {
+            compute_capacity: Some(compute_capacity),
+            fleet_type: Some(fleet_type.to_owned()),
+            image_arn: image_arn,
+            image_name: image_name,
+            instance_type: instance_type.to_owned(),
+            name: name.to_owned(),
+            vpc_config: vpc_config,
+            ..CreateFleetRequest::default()
+        };
+
+        expect(try!(self.create_fleet(&input)).fleet, "the fleet")
+    }
+
+    /// Creates a stack, which users stream through once a fleet is attached to it, with the
+    /// storage they can reach and what they're allowed to do, such as copying to the clipboard.
+    pub fn add_stack(&mut self, name: &str, storage_connectors: Vec<StorageConnector>,
+        user_settings: Vec<UserSetting>) -> AwsResult<Stack> {
+        let input = CreateStackRequest {
+            name: name.to_owned(),
+            storage_connectors: if storage_connectors.is_empty() { None } else { Some(storage_connectors) },
+            user_settings: if user_settings.is_empty() { None } else { Some(user_settings) },
+            ..CreateStackRequest::default()
+        };
+
+        expect(try!(self.create_stack(&input)).stack, "the stack")
+    }
+
+    /// Attaches the fleet `fleet_name` to the stack `stack_name`, so users of the stack stream
+    /// from its instances.
+    pub fn attach_fleet(&mut self, fleet_name: &str, stack_name: &str) -> AwsResult<()> {
+        let input = AssociateFleetRequest {
+            fleet_name: fleet_name.to_owned(),
+            stack_name: stack_name.to_owned(),
+        };
+
+        try!(self.associate_fleet(&input));
+
+        Ok(())
+    }
+
+    /// Creates a URL which starts a streaming session for `user_id`, who doesn't need to be
+    /// known to AppStream. The URL can be used for `validity` seconds, 60 if not given.
+    pub fn streaming_url(&mut self, stack_name: &str, fleet_name: &str, user_id: &str, validity: Option<i64>)
+        -> AwsResult<CreateStreamingURLResult> {
+        let input = CreateStreamingURLRequest {
+            fleet_name: fleet_name.to_owned(),
+            stack_name: stack_name.to_owned(),
+            user_id: user_id.to_owned(),
+            validity: validity,
+            ..CreateStreamingURLRequest::default()
+        };
+
+        self.create_streaming_url(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DescribeFleetsResult, ON_DEMAND, RUNNING};
+
+    #[test]
+    fn parse_running_fleet_capacity() {
+        let mut body = String::new();
+        File::open("tests/sample-data/appstream_describe_fleets.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let fleets = serde_json::from_str::<DescribeFleetsResult>(&body).unwrap().fleets.unwrap();
+
+        assert_eq!(fleets.len(), 1);
+
+        let fleet = &fleets[0];
+        assert_eq!(fleet.name, "design-tools");
+        assert_eq!(fleet.state, RUNNING);
+        assert_eq!(fleet.fleet_type, Some(ON_DEMAND.to_owned()));
+        assert_eq!(fleet.instance_type, "stream.graphics.g4dn.xlarge");
+
+        // Of the instances running, one is streaming and three are waiting for users.
+        let capacity = &fleet.compute_capacity_status;
+        assert_eq!(capacity.desired, 4);
+        assert_eq!(capacity.running, Some(4));
+        assert_eq!(capacity.in_use, Some(1));
+        assert_eq!(capacity.available, Some(3));
+
+        let vpc_config = fleet.vpc_config.as_ref().unwrap();
+        assert_eq!(vpc_config.subnet_ids.as_ref().unwrap().len(), 2);
+    }
+}
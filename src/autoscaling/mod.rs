@@ -0,0 +1,70 @@
+//! Auto Scaling
+
+include!(concat!(env!("OUT_DIR"), "/autoscaling.rs"));
+
+use pagination::read_all_pages;
+
+impl<P> AutoScalingClient<P> where P: ProvideAwsCredentials {
+    /// Calls `DescribeAutoScalingGroups` until every page has been read, and returns all of the
+    /// groups in the region, or only those in `auto_scaling_group_names` if given.
+    pub fn describe_all_auto_scaling_groups(&mut self, auto_scaling_group_names: Option<Vec<String>>)
+        -> Result<Vec<AutoScalingGroup>, AwsError> {
+        let mut input = AutoScalingGroupNamesType {
+            auto_scaling_group_names: auto_scaling_group_names,
+            ..AutoScalingGroupNamesType::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.describe_auto_scaling_groups(&input));
+
+            Ok((output.auto_scaling_groups, output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use xml::reader::*;
+
+    use xmlutil::*;
+
+    use super::AutoScalingGroupsTypeDeserializer;
+
+    #[test]
+    fn describe_auto_scaling_groups_with_unhealthy_instance() {
+        let file = File::open("tests/sample-data/autoscaling_describe_auto_scaling_groups.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        reader.next(); // DescribeAutoScalingGroupsResponse
+        let result = AutoScalingGroupsTypeDeserializer::deserialize(
+            "DescribeAutoScalingGroupsResult",
+            &mut reader,
+        ).unwrap();
+
+        assert_eq!(result.auto_scaling_groups.len(), 1);
+
+        let group = &result.auto_scaling_groups[0];
+        assert_eq!(group.auto_scaling_group_name, "my-asg");
+        assert_eq!(group.desired_capacity, 3);
+        assert_eq!(group.availability_zones, vec!["us-west-2a".to_owned(), "us-west-2b".to_owned()]);
+
+        let instances = group.instances.as_ref().unwrap();
+        assert_eq!(instances.len(), 3);
+
+        let unhealthy: Vec<&str> = instances.iter()
+            .filter(|instance| instance.health_status == "Unhealthy")
+            .map(|instance| &instance.instance_id[..])
+            .collect();
+        assert_eq!(unhealthy, vec!["i-7c2b3d61"]);
+
+        assert_eq!(instances[2].lifecycle_state, "Pending");
+        assert_eq!(instances[2].protected_from_scale_in, true);
+    }
+}
@@ -0,0 +1,179 @@
+//! AWS Batch
+
+include!(concat!(env!("OUT_DIR"), "/batch.rs"));
+
+use pagination::read_all_pages;
+
+/// The most jobs a single `DescribeJobs` call can describe.
+const MAX_DESCRIBED_JOBS: usize = 100;
+
+/// The status of a job definition, which is `Inactive` once it has been deregistered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JobDefinitionStatus {
+    Active,
+    Inactive,
+}
+
+impl JobDefinitionStatus {
+    /// The status as Batch writes it.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            JobDefinitionStatus::Active => "ACTIVE",
+            JobDefinitionStatus::Inactive => "INACTIVE",
+        }
+    }
+}
+
+/// The state of a job, parsed from `JobDetail::status`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JobState {
+    Submitted,
+    Pending,
+    Runnable,
+    Starting,
+    Running,
+    Succeeded,
+    Failed,
+    Unknown,
+}
+
+impl JobState {
+    pub fn from_status(status: &str) -> JobState {
+        match status {
+            "SUBMITTED" => JobState::Submitted,
+            "PENDING" => JobState::Pending,
+            "RUNNABLE" => JobState::Runnable,
+            "STARTING" => JobState::Starting,
+            "RUNNING" => JobState::Running,
+            "SUCCEEDED" => JobState::Succeeded,
+            "FAILED" => JobState::Failed,
+            _ => JobState::Unknown,
+        }
+    }
+
+    /// Whether the job has stopped running, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        match *self {
+            JobState::Succeeded | JobState::Failed => true,
+            _ => false,
+        }
+    }
+}
+
+impl JobDetail {
+    pub fn state(&self) -> JobState {
+        JobState::from_status(&self.status)
+    }
+}
+
+impl SubmitJobRequest {
+    /// Creates a request to run a job from a job definition, which may be a name, a
+    /// `name:revision` or an ARN. Overrides, array properties and retry strategies can be set
+    /// on the request afterwards.
+    pub fn new(job_name: &str, job_queue: &str, job_definition: &str) -> SubmitJobRequest {
+        SubmitJobRequest {
+            job_definition: job_definition.to_owned(),
+            job_name: job_name.to_owned(),
+            job_queue: job_queue.to_owned(),
+            ..SubmitJobRequest::default()
+        }
+    }
+}
+
+impl CancelJobRequest {
+    pub fn new(job_id: &str, reason: &str) -> CancelJobRequest {
+        CancelJobRequest {
+            job_id: job_id.to_owned(),
+            reason: reason.to_owned(),
+        }
+    }
+}
+
+impl<P> BatchClient<P> where P: ProvideAwsCredentials {
+    /// Describes any number of jobs, splitting them across as many `DescribeJobs` calls as
+    /// needed.
+    pub fn describe_all_jobs(&mut self, job_ids: &[String]) -> AwsResult<Vec<JobDetail>> {
+        let mut jobs = Vec::new();
+
+        for chunk in job_ids.chunks(MAX_DESCRIBED_JOBS) {
+            let request = DescribeJobsRequest { jobs: chunk.to_vec() };
+
+            jobs.extend(try!(self.describe_jobs(&request)).jobs.unwrap_or_else(Vec::new));
+        }
+
+        Ok(jobs)
+    }
+
+    /// Lists the job definitions named in `job_definitions`, or all of them if it's empty,
+    /// following `nextToken` until every page has been read, and only those with `status` if
+    /// given.
+    pub fn describe_all_job_definitions(&mut self, job_definitions: Vec<String>,
+        status: Option<JobDefinitionStatus>) -> AwsResult<Vec<JobDefinition>> {
+        let mut request = DescribeJobDefinitionsRequest {
+            job_definitions: if job_definitions.is_empty() { None } else { Some(job_definitions) },
+            status: status.map(|status| status.as_str().to_owned()),
+            ..DescribeJobDefinitionsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            request.next_token = next_token;
+            let response = try!(self.describe_job_definitions(&request));
+
+            Ok((response.job_definitions.unwrap_or_else(Vec::new), response.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DescribeJobsResponse, JobDefinitionStatus, JobState, SubmitJobRequest};
+
+    #[test]
+    fn parse_failed_job() {
+        let mut body = String::new();
+        File::open("tests/sample-data/batch_describe_jobs_failed.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<DescribeJobsResponse>(&body).unwrap();
+        let jobs = response.jobs.unwrap();
+
+        assert_eq!(jobs.len(), 1);
+
+        let job = &jobs[0];
+        assert_eq!(job.job_name, "nightly-report");
+        assert_eq!(job.status, "FAILED");
+        assert_eq!(job.state(), JobState::Failed);
+        assert!(job.state().is_finished());
+        assert_eq!(job.status_reason, Some("Essential container in task exited".to_owned()));
+
+        let container = job.container.as_ref().unwrap();
+        assert_eq!(container.exit_code, Some(137));
+        assert_eq!(container.reason, Some("OutOfMemoryError: Container killed due to memory usage".to_owned()));
+
+        let attempts = job.attempts.as_ref().unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[1].status_reason, Some("Essential container in task exited".to_owned()));
+    }
+
+    #[test]
+    fn submit_job_request() {
+        let request = SubmitJobRequest::new("nightly-report", "reports", "report-generator:3");
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"jobDefinition\":\"report-generator:3\""));
+        assert!(json.contains("\"jobQueue\":\"reports\""));
+        assert_eq!(request.retry_strategy, None);
+    }
+
+    #[test]
+    fn job_and_job_definition_statuses() {
+        assert_eq!(JobDefinitionStatus::Active.as_str(), "ACTIVE");
+        assert_eq!(JobDefinitionStatus::Inactive.as_str(), "INACTIVE");
+        assert_eq!(JobState::from_status("RUNNABLE"), JobState::Runnable);
+        assert!(!JobState::from_status("RUNNABLE").is_finished());
+    }
+}
@@ -0,0 +1,330 @@
+//! Amazon Bedrock Runtime
+//!
+//! Invokes foundation models hosted by Bedrock. Request and response bodies are passed through
+//! untouched, since their format is defined by each model provider rather than by Bedrock.
+
+use std::ascii::AsciiExt;
+use std::io::Read;
+use std::sync::Arc;
+
+use hyper::client::Response;
+use rustc_serialize::base64::FromBase64;
+use serde_json::{Value, from_slice};
+
+use credential::ProvideAwsCredentials;
+use error::{AwsError, AwsResult, parse_json_protocol_error};
+use event_stream::{Message, read_message};
+use interceptor::Interceptor;
+use region::Region;
+use signature::{SignedRequest, encode_uri};
+
+/// The output of a call to `InvokeModel`.
+#[derive(Debug, Default)]
+pub struct InvokeModelResponse {
+    /// The inference response from the model, in the format specified by `content_type`.
+    pub body: Vec<u8>,
+    /// The MIME type of `body`.
+    pub content_type: String,
+}
+
+impl InvokeModelResponse {
+    /// Parses `body` as JSON, which is what all of the text models return.
+    pub fn json(&self) -> AwsResult<Value> {
+        from_slice::<Value>(&self.body).map_err(|err| {
+            AwsError::new(format!("Failed to parse model response as JSON: {}", err))
+        })
+    }
+}
+
+/// A chunk of a model's response, as streamed by `InvokeModelWithResponseStream`.
+#[derive(Debug, Default, PartialEq)]
+pub struct ResponseStreamEvent {
+    /// Part of the response from the model, in the same format `InvokeModel` would return.
+    pub bytes: Vec<u8>,
+}
+
+/// An iterator over the events of a `InvokeModelWithResponseStream` response.
+///
+/// Iteration stops after the first error, as the rest of the stream can't be trusted.
+pub struct ResponseStream<R> where R: Read {
+    reader: R,
+    finished: bool,
+}
+
+impl<R> ResponseStream<R> where R: Read {
+    pub fn new(reader: R) -> ResponseStream<R> {
+        ResponseStream {
+            reader: reader,
+            finished: false,
+        }
+    }
+}
+
+impl<R> Iterator for ResponseStream<R> where R: Read {
+    type Item = AwsResult<ResponseStreamEvent>;
+
+    fn next(&mut self) -> Option<AwsResult<ResponseStreamEvent>> {
+        if self.finished {
+            return None;
+        }
+
+        let event = match read_message(&mut self.reader) {
+            Ok(Some(message)) => parse_event(&message),
+            Ok(None) => {
+                self.finished = true;
+                return None;
+            }
+            Err(err) => Err(err),
+        };
+
+        self.finished = event.is_err();
+
+        Some(event)
+    }
+}
+
+fn parse_event(message: &Message) -> AwsResult<ResponseStreamEvent> {
+    match message.header_str(":message-type") {
+        Some("event") => {
+            match message.header_str(":event-type") {
+                Some("chunk") => {
+                    let payload = try!(from_slice::<Value>(&message.payload).map_err(|err| {
+                        AwsError::new(format!("Failed to parse event as JSON: {}", err))
+                    }));
+
+                    match payload.find("bytes").and_then(|bytes| bytes.as_string()) {
+                        Some(bytes) => {
+                            Ok(ResponseStreamEvent {
+                                bytes: try!(bytes.from_base64().map_err(|err| {
+                                    AwsError::new(format!("{}", err))
+                                })),
+                            })
+                        }
+                        None => Err(AwsError::new("Chunk event has no bytes")),
+                    }
+                }
+                event_type => Err(AwsError::new(format!("Unknown event type: {:?}", event_type))),
+            }
+        }
+        Some("exception") => {
+            let exception_type = message.header_str(":exception-type").unwrap_or("Unknown error");
+            let error_message = match from_slice::<Value>(&message.payload) {
+                Ok(json) => {
+                    json.find("message")
+                        .and_then(|message| message.as_string())
+                        .map(|message| message.to_owned())
+                }
+                Err(_) => None,
+            };
+
+            Err(AwsError::new(format!(
+                "{}: {}",
+                exception_type,
+                error_message.unwrap_or_else(|| String::from_utf8_lossy(&message.payload).into_owned())
+            )))
+        }
+        message_type => Err(AwsError::new(format!("Unknown message type: {:?}", message_type))),
+    }
+}
+
+/// A client for the Amazon Bedrock Runtime API.
+pub struct BedrockRuntimeClient<P> where P: ProvideAwsCredentials {
+    credentials_provider: P,
+    region: Region,
+    hostname: Option<String>,
+    interceptors: Vec<Arc<Interceptor + Send + Sync>>,
+}
+
+impl<P> BedrockRuntimeClient<P> where P: ProvideAwsCredentials {
+    pub fn new(credentials_provider: P, region: Region) -> Self {
+        BedrockRuntimeClient {
+            credentials_provider: credentials_provider,
+            region: region,
+            hostname: None,
+            interceptors: Vec::new(),
+        }
+    }
+
+    /// Send requests to `hostname` instead of the default endpoint for the region.
+    ///
+    /// The hostname may be given as a URL like `http://localhost:8000` to choose the scheme,
+    /// otherwise HTTPS is used.
+    pub fn set_hostname(&mut self, hostname: Option<String>) {
+        self.hostname = hostname;
+    }
+
+    /// Calls `interceptors`, in order, around every request the client sends.
+    pub fn set_interceptors(&mut self, interceptors: Vec<Arc<Interceptor + Send + Sync>>) {
+        self.interceptors = interceptors;
+    }
+
+    /// Returns the hostname requests are sent to.
+    pub fn hostname(&self) -> String {
+        match self.hostname {
+            Some(ref hostname) => hostname.to_owned(),
+            None => build_hostname(self.region),
+        }
+    }
+
+    /// Invokes the model `model_id` with the given request body and returns its response.
+    ///
+    /// `content_type` is the MIME type of `body` and `accept` the desired MIME type of the
+    /// response; most models only support `application/json` for both.
+    pub fn invoke_model(&mut self, model_id: &str, body: &[u8], content_type: &str, accept: &str)
+        -> AwsResult<InvokeModelResponse> {
+        let mut result = try!(self.execute("invoke", model_id, body, content_type, "Accept", accept));
+        let mut response_body = Vec::new();
+        try!(result.read_to_end(&mut response_body));
+
+        match result.status.to_u16() {
+            200 => {
+                Ok(InvokeModelResponse {
+                    body: response_body,
+                    content_type: header_value(&result, "Content-Type"),
+                })
+            }
+            _ => Err(parse_json_protocol_error(&String::from_utf8_lossy(&response_body))),
+        }
+    }
+
+    /// Invokes the model `model_id` with a JSON request body and streams back its response.
+    pub fn invoke_model_with_response_stream(&mut self, model_id: &str, body: &[u8])
+        -> AwsResult<ResponseStream<Response>> {
+        let mut result = try!(self.execute(
+            "invoke-with-response-stream",
+            model_id,
+            body,
+            "application/json",
+            "X-Amzn-Bedrock-Accept",
+            "application/json",
+        ));
+
+        match result.status.to_u16() {
+            200 => Ok(ResponseStream::new(result)),
+            _ => {
+                let mut response_body = String::new();
+                try!(result.read_to_string(&mut response_body));
+
+                Err(parse_json_protocol_error(&response_body))
+            }
+        }
+    }
+
+    fn execute(&mut self, action: &str, model_id: &str, body: &[u8], content_type: &str,
+        accept_header: &str, accept: &str) -> AwsResult<Response> {
+        // Requests are signed as "bedrock", but sent to the "bedrock-runtime" endpoint.
+        let request_uri = format!("/model/{}/{}", encode_uri(model_id), action);
+        let mut request = SignedRequest::new("POST", "bedrock", self.region, &request_uri);
+        request.set_hostname(Some(self.hostname()));
+        request.set_interceptors(self.interceptors.clone());
+        request.set_content_type(content_type.to_owned());
+        request.add_header(accept_header, accept);
+        request.set_payload(Some(body));
+
+        Ok(request.sign_and_execute(try!(self.credentials_provider.credentials())))
+    }
+}
+
+fn build_hostname(region: Region) -> String {
+    match region {
+        Region::CnNorth1 => format!("bedrock-runtime.{}.amazonaws.com.cn", region),
+        _ => format!("bedrock-runtime.{}.amazonaws.com", region),
+    }
+}
+
+fn header_value(response: &Response, name: &str) -> String {
+    for header in response.headers.iter() {
+        if header.name().eq_ignore_ascii_case(name) {
+            return header.value_string();
+        }
+    }
+
+    String::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use credential::ProfileProvider;
+    use region::Region;
+
+    use super::{BedrockRuntimeClient, InvokeModelResponse, ResponseStream};
+
+    fn sample_response(path: &str) -> InvokeModelResponse {
+        let mut body = Vec::new();
+        File::open(path).unwrap().read_to_end(&mut body).unwrap();
+
+        InvokeModelResponse {
+            body: body,
+            content_type: "application/json".to_owned(),
+        }
+    }
+
+    fn client() -> BedrockRuntimeClient<ProfileProvider> {
+        let provider = ProfileProvider::with_configuration(
+            "tests/sample-data/multiple_profile_credentials",
+            "foo",
+        );
+
+        BedrockRuntimeClient::new(provider, Region::UsEast1)
+    }
+
+    #[test]
+    fn regional_endpoint() {
+        assert_eq!(client().hostname(), "bedrock-runtime.us-east-1.amazonaws.com");
+    }
+
+    #[test]
+    fn custom_endpoint() {
+        let mut client = client();
+        client.set_hostname(Some("vpce-0123.bedrock-runtime.us-east-1.vpce.amazonaws.com".to_owned()));
+
+        assert_eq!(client.hostname(), "vpce-0123.bedrock-runtime.us-east-1.vpce.amazonaws.com");
+    }
+
+    #[test]
+    fn claude_response() {
+        let response = sample_response("tests/sample-data/bedrock_runtime_invoke_claude.json");
+        let json = response.json().unwrap();
+
+        assert_eq!(json.find("stop_reason").unwrap().as_string(), Some("end_turn"));
+        assert_eq!(
+            json.lookup("content").unwrap().as_array().unwrap()[0].find("text").unwrap().as_string(),
+            Some("Hello! How can I help you today?")
+        );
+        assert_eq!(json.lookup("usage.output_tokens").unwrap().as_u64(), Some(10));
+    }
+
+    #[test]
+    fn titan_response() {
+        let response = sample_response("tests/sample-data/bedrock_runtime_invoke_titan.json");
+        let json = response.json().unwrap();
+        let result = &json.find("results").unwrap().as_array().unwrap()[0];
+
+        assert_eq!(json.find("inputTextTokenCount").unwrap().as_u64(), Some(5));
+        assert_eq!(result.find("outputText").unwrap().as_string(), Some("\nHello! How can I help you today?"));
+        assert_eq!(result.find("completionReason").unwrap().as_string(), Some("FINISH"));
+    }
+
+    #[test]
+    fn response_stream_chunks_and_exception() {
+        let file = File::open("tests/sample-data/bedrock_runtime_response_stream.bin").unwrap();
+        let events = ResponseStream::new(file).collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0].as_ref().unwrap().bytes,
+            b"{\"completion\":\"Hello\",\"stop_reason\":null}".to_vec()
+        );
+        assert_eq!(
+            events[1].as_ref().unwrap().bytes,
+            b"{\"completion\":\" world\",\"stop_reason\":\"stop_sequence\"}".to_vec()
+        );
+        assert_eq!(
+            format!("{}", events[2].as_ref().unwrap_err()),
+            "modelStreamErrorException: The model stopped responding"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! AWS Cost Explorer
+//!
+//! Cost Explorer has a single endpoint in us-east-1, which requests are sent to and signed for
+//! whatever region the client is created with.
+
+include!(concat!(env!("OUT_DIR"), "/ce.rs"));
+
+use pagination::read_all_pages;
+
+impl DateInterval {
+    /// Creates an interval from `start`, inclusive, to `end`, exclusive, both as `YYYY-MM-DD`.
+    pub fn new(start: &str, end: &str) -> DateInterval {
+        DateInterval {
+            end: end.to_owned(),
+            start: start.to_owned(),
+        }
+    }
+}
+
+impl Expression {
+    /// Matches costs whose dimension `key`, such as `SERVICE` or `LINKED_ACCOUNT`, has one of
+    /// `values`.
+    pub fn dimension(key: &str, values: Vec<String>) -> Expression {
+        Expression {
+            dimensions: Some(DimensionValues {
+                key: Some(key.to_owned()),
+                values: Some(values),
+                ..DimensionValues::default()
+            }),
+            ..Expression::default()
+        }
+    }
+
+    /// Matches costs tagged with `key` set to one of `values`.
+    pub fn tag(key: &str, values: Vec<String>) -> Expression {
+        Expression {
+            tags: Some(TagValues {
+                key: Some(key.to_owned()),
+                values: Some(values),
+                ..TagValues::default()
+            }),
+            ..Expression::default()
+        }
+    }
+
+    /// Matches costs assigned to one of `values` of the cost category `key`.
+    pub fn cost_category(key: &str, values: Vec<String>) -> Expression {
+        Expression {
+            cost_categories: Some(CostCategoryValues {
+                key: Some(key.to_owned()),
+                values: Some(values),
+                ..CostCategoryValues::default()
+            }),
+            ..Expression::default()
+        }
+    }
+
+    pub fn and(expressions: Vec<Expression>) -> Expression {
+        Expression {
+            and: Some(expressions),
+            ..Expression::default()
+        }
+    }
+
+    pub fn or(expressions: Vec<Expression>) -> Expression {
+        Expression {
+            or: Some(expressions),
+            ..Expression::default()
+        }
+    }
+
+    pub fn not(expression: Expression) -> Expression {
+        Expression {
+            not: Some(Box::new(expression)),
+            ..Expression::default()
+        }
+    }
+}
+
+impl GetCostAndUsageRequest {
+    /// Creates a request for `metrics`, such as `UnblendedCost`, over `time_period` at a
+    /// `DAILY`, `MONTHLY` or `HOURLY` granularity.
+    pub fn new(time_period: DateInterval, granularity: &str, metrics: Vec<String>) -> GetCostAndUsageRequest {
+        GetCostAndUsageRequest {
+            granularity: granularity.to_owned(),
+            metrics: metrics,
+            time_period: time_period,
+            ..GetCostAndUsageRequest::default()
+        }
+    }
+}
+
+impl GetCostForecastRequest {
+    /// Creates a request to forecast `metric`, such as `UNBLENDED_COST`, over a `time_period`
+    /// in the future.
+    pub fn new(time_period: DateInterval, metric: &str, granularity: &str) -> GetCostForecastRequest {
+        GetCostForecastRequest {
+            granularity: granularity.to_owned(),
+            metric: metric.to_owned(),
+            time_period: time_period,
+            ..GetCostForecastRequest::default()
+        }
+    }
+}
+
+impl<P> CostExplorerClient<P> where P: ProvideAwsCredentials {
+    /// Calls `GetCostAndUsage` until every page of results has been read, and returns them in
+    /// order.
+    pub fn get_all_cost_and_usage(&mut self, input: &GetCostAndUsageRequest) -> AwsResult<Vec<ResultByTime>> {
+        let mut input = input.clone();
+
+        read_all_pages(input.next_page_token.take(), |next_page_token| {
+            input.next_page_token = next_page_token;
+            let response = try!(self.get_cost_and_usage(&input));
+
+            Ok((response.results_by_time.unwrap_or_else(Vec::new), response.next_page_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DateInterval, Expression, GetCostAndUsageRequest, GetCostAndUsageResponse};
+
+    #[test]
+    fn parse_monthly_costs_by_service() {
+        let mut body = String::new();
+        File::open("tests/sample-data/ce_get_cost_and_usage.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<GetCostAndUsageResponse>(&body).unwrap();
+        let results = response.results_by_time.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].time_period.as_ref().unwrap().start, "2016-10-01");
+        assert_eq!(results[0].estimated, Some(false));
+        assert_eq!(results[1].estimated, Some(true));
+
+        let groups = results[0].groups.as_ref().unwrap();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].keys, Some(vec!["Amazon Elastic Compute Cloud - Compute".to_owned()]));
+
+        let cost = &groups[0].metrics.as_ref().unwrap()["UnblendedCost"];
+        assert_eq!(cost.amount, Some("1520.4215".to_owned()));
+        assert_eq!(cost.unit, Some("USD".to_owned()));
+        assert_eq!(
+            results[1].groups.as_ref().unwrap()[2].keys,
+            Some(vec!["Amazon Simple Storage Service".to_owned()])
+        );
+    }
+
+    #[test]
+    fn nested_filter_expression() {
+        let filter = Expression::and(vec![
+            Expression::dimension("SERVICE", vec!["Amazon Simple Storage Service".to_owned()]),
+            Expression::not(Expression::or(vec![
+                Expression::tag("environment", vec!["dev".to_owned(), "test".to_owned()]),
+                Expression::cost_category("team", vec!["sandbox".to_owned()]),
+            ])),
+        ]);
+        let mut request = GetCostAndUsageRequest::new(
+            DateInterval::new("2016-10-01", "2016-12-01"),
+            "MONTHLY",
+            vec!["UnblendedCost".to_owned()],
+        );
+        request.filter = Some(filter);
+
+        let json = serde_json::to_string(&request).unwrap();
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+        assert_eq!(
+            value.lookup("Filter.And").unwrap().as_array().unwrap()[0].lookup("Dimensions.Key").unwrap().as_string(),
+            Some("SERVICE")
+        );
+
+        let or = value.lookup("Filter.And").unwrap().as_array().unwrap()[1].lookup("Not.Or").unwrap();
+        assert_eq!(or.as_array().unwrap()[0].lookup("Tags.Key").unwrap().as_string(), Some("environment"));
+        assert_eq!(or.as_array().unwrap()[1].lookup("CostCategories.Key").unwrap().as_string(), Some("team"));
+        assert_eq!(value.lookup("Granularity").unwrap().as_string(), Some("MONTHLY"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Amazon Chime SDK Messaging
+//!
+//! Every request is made on behalf of an app instance user or bot, whose ARN is sent as the
+//! `x-amz-chime-bearer` header and is checked against the channel's memberships.
+
+use openssl::crypto::rand::rand_bytes;
+use rustc_serialize::hex::ToHex;
+
+include!(concat!(env!("OUT_DIR"), "/chime-sdk-messaging.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+/// The mode of channels whose members can add other members.
+pub const UNRESTRICTED: &'static str = "UNRESTRICTED";
+
+/// The mode of channels where only moderators can add members.
+pub const RESTRICTED: &'static str = "RESTRICTED";
+
+/// The privacy of channels which any user of the app instance can find.
+pub const PUBLIC: &'static str = "PUBLIC";
+
+/// The privacy of channels which only their members can find.
+pub const PRIVATE: &'static str = "PRIVATE";
+
+/// The type of messages sent by users.
+pub const STANDARD: &'static str = "STANDARD";
+
+/// The type of messages sent by applications, which are never persisted.
+pub const CONTROL: &'static str = "CONTROL";
+
+/// The persistence of messages which are kept and can be listed.
+pub const PERSISTENT: &'static str = "PERSISTENT";
+
+/// The persistence of messages which are only delivered to members connected at the time.
+pub const NON_PERSISTENT: &'static str = "NON_PERSISTENT";
+
+/// Lists messages oldest first.
+pub const ASCENDING: &'static str = "ASCENDING";
+
+/// Lists messages newest first, as Chime does by default.
+pub const DESCENDING: &'static str = "DESCENDING";
+
+impl<P> ChimeSdkMessagingClient<P> where P: ProvideAwsCredentials {
+    /// Creates a channel in the app instance `app_instance_arn` on behalf of `chime_bearer`,
+    /// and returns its ARN. The mode is `UNRESTRICTED` or `RESTRICTED`, and the privacy
+    /// `PUBLIC` or `PRIVATE`. A random client request token is used if none is given.
+    pub fn add_channel(
+        &mut self,
+        app_instance_arn: &str,
+        name: &str,
+        mode: &str,
+        privacy: &str,
+        client_request_token: Option<String>,
+        chime_bearer: &str,
+    ) -> AwsResult<String> {
+        let input = CreateChannelRequest {
+            app_instance_arn: app_instance_arn.to_owned(),
+            chime_bearer: chime_bearer.to_owned(),
+            client_request_token: client_request_token.unwrap_or_else(|| rand_bytes(16).to_hex()),
+            mode: Some(mode.to_owned()),
+            name: name.to_owned(),
+            privacy: Some(privacy.to_owned()),
+            ..CreateChannelRequest::default()
+        };
+
+        expect(try!(self.create_channel(&input)).channel_arn, "the channel ARN")
+    }
+
+    /// Sends a `STANDARD` or `CONTROL` message to the channel `channel_arn` on behalf of
+    /// `chime_bearer`, who must be a member of it. A random client request token is used if
+    /// none is given.
+    pub fn post_channel_message(
+        &mut self,
+        channel_arn: &str,
+        content: &str,
+        message_type: &str,
+        persistence: &str,
+        client_request_token: Option<String>,
+        chime_bearer: &str,
+        metadata: Option<String>,
+    ) -> AwsResult<SendChannelMessageResponse> {
+        let input = SendChannelMessageRequest {
+            aws_type: message_type.to_owned(),
+            channel_arn: channel_arn.to_owned(),
+            chime_bearer: chime_bearer.to_owned(),
+            client_request_token: client_request_token.unwrap_or_else(|| rand_bytes(16).to_hex()),
+            content: content.to_owned(),
+            metadata: metadata,
+            persistence: persistence.to_owned(),
+            ..SendChannelMessageRequest::default()
+        };
+
+        self.send_channel_message(&input)
+    }
+
+    /// Calls `ListChannelMessages` until every page has been read, and returns the persisted
+    /// messages of the channel `channel_arn` in the order `ASCENDING` or `DESCENDING`, only
+    /// including those sent between `not_before` and `not_after` if given.
+    pub fn list_all_channel_messages(
+        &mut self,
+        channel_arn: &str,
+        sort_order: &str,
+        not_before: Option<f64>,
+        not_after: Option<f64>,
+        chime_bearer: &str,
+    ) -> AwsResult<Vec<ChannelMessageSummary>> {
+        let mut input = ListChannelMessagesRequest {
+            channel_arn: channel_arn.to_owned(),
+            chime_bearer: chime_bearer.to_owned(),
+            not_after: not_after,
+            not_before: not_before,
+            sort_order: Some(sort_order.to_owned()),
+            ..ListChannelMessagesRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_channel_messages(&input));
+
+            Ok((output.channel_messages.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
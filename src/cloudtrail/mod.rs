@@ -0,0 +1,162 @@
+//! AWS CloudTrail
+//!
+//! Records the API calls made in an account. The last 90 days of management events can be
+//! looked up directly, while trails deliver every event to S3 for as long as they're kept.
+
+include!(concat!(env!("OUT_DIR"), "/cloudtrail.rs"));
+
+use pagination::read_all_pages;
+
+/// An attribute to look events up by. Only a single attribute is supported per lookup.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Lookup {
+    EventId(String),
+    EventName(String),
+    /// Whether the event only read resources rather than changing them.
+    ReadOnly(bool),
+    Username(String),
+    ResourceType(String),
+    ResourceName(String),
+    /// The service the request was made to, such as `s3.amazonaws.com`.
+    EventSource(String),
+    AccessKeyId(String),
+}
+
+impl From<Lookup> for LookupAttribute {
+    fn from(lookup: Lookup) -> LookupAttribute {
+        let (key, value) = match lookup {
+            Lookup::EventId(value) => ("EventId", value),
+            Lookup::EventName(value) => ("EventName", value),
+            Lookup::ReadOnly(value) => ("ReadOnly", value.to_string()),
+            Lookup::Username(value) => ("Username", value),
+            Lookup::ResourceType(value) => ("ResourceType", value),
+            Lookup::ResourceName(value) => ("ResourceName", value),
+            Lookup::EventSource(value) => ("EventSource", value),
+            Lookup::AccessKeyId(value) => ("AccessKeyId", value),
+        };
+
+        LookupAttribute {
+            attribute_key: key.to_owned(),
+            attribute_value: value,
+        }
+    }
+}
+
+/// The optional settings of a new trail. Anything left unset takes CloudTrail's default, which
+/// is a trail of the current region's events, without log file validation.
+#[derive(Debug, Default)]
+pub struct CreateTrailOptions {
+    pub s3_key_prefix: Option<String>,
+    pub sns_topic_name: Option<String>,
+    pub include_global_service_events: Option<bool>,
+    pub is_multi_region_trail: Option<bool>,
+    pub enable_log_file_validation: Option<bool>,
+    pub cloud_watch_logs_log_group_arn: Option<String>,
+    pub cloud_watch_logs_role_arn: Option<String>,
+    pub kms_key_id: Option<String>,
+    pub is_organization_trail: Option<bool>,
+}
+
+impl CreateTrailRequest {
+    /// Creates a request for a trail which delivers its log files to `s3_bucket_name`.
+    pub fn new(name: &str, s3_bucket_name: &str, options: CreateTrailOptions) -> CreateTrailRequest {
+        CreateTrailRequest {
+            cloud_watch_logs_log_group_arn: options.cloud_watch_logs_log_group_arn,
+            cloud_watch_logs_role_arn: options.cloud_watch_logs_role_arn,
+            enable_log_file_validation: options.enable_log_file_validation,
+            include_global_service_events: options.include_global_service_events,
+            is_multi_region_trail: options.is_multi_region_trail,
+            is_organization_trail: options.is_organization_trail,
+            kms_key_id: options.kms_key_id,
+            name: name.to_owned(),
+            s3_bucket_name: s3_bucket_name.to_owned(),
+            s3_key_prefix: options.s3_key_prefix,
+            sns_topic_name: options.sns_topic_name,
+            ..CreateTrailRequest::default()
+        }
+    }
+}
+
+impl<P> CloudTrailClient<P> where P: ProvideAwsCredentials {
+    /// Calls `LookupEvents` until every page has been read, with up to `max_results` events on
+    /// each, and returns the management events matching `lookup_attributes` between
+    /// `start_time` and `end_time`, in seconds since the epoch, most recent first.
+    pub fn lookup_all_events(&mut self, lookup_attributes: Vec<Lookup>, start_time: Option<f64>,
+        end_time: Option<f64>, max_results: Option<i32>) -> AwsResult<Vec<Event>> {
+        let mut input = LookupEventsRequest {
+            end_time: end_time,
+            lookup_attributes: if lookup_attributes.is_empty() {
+                None
+            } else {
+                Some(lookup_attributes.into_iter().map(LookupAttribute::from).collect())
+            },
+            max_results: max_results,
+            start_time: start_time,
+            ..LookupEventsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.lookup_events(&input));
+
+            Ok((output.events.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Starts recording events with the trail `name`, which is its name or ARN.
+    pub fn start_trail(&mut self, name: &str) -> AwsResult<()> {
+        try!(self.start_logging(&StartLoggingRequest { name: name.to_owned() }));
+
+        Ok(())
+    }
+
+    /// Returns whether the trail `name` is logging, and the latest errors delivering its logs.
+    pub fn trail_status(&mut self, name: &str) -> AwsResult<GetTrailStatusResponse> {
+        self.get_trail_status(&GetTrailStatusRequest { name: name.to_owned() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{Lookup, LookupAttribute, LookupEventsResponse};
+
+    #[test]
+    fn parse_several_events() {
+        let mut body = String::new();
+        File::open("tests/sample-data/cloudtrail_lookup_events.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<LookupEventsResponse>(&body).unwrap();
+        let events = response.events.unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(response.next_token, Some("kbOt5LlZe++mErCebpy2Tg==".to_owned()));
+
+        let put_object = &events[0];
+        assert_eq!(put_object.event_name, Some("PutObject".to_owned()));
+        assert_eq!(put_object.event_source, Some("s3.amazonaws.com".to_owned()));
+        assert_eq!(put_object.read_only, Some("false".to_owned()));
+        assert_eq!(put_object.event_time, Some(1686562887.0));
+
+        let resources = put_object.resources.as_ref().unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[1].resource_type, Some("AWS::S3::Object".to_owned()));
+
+        let record = serde_json::from_str::<serde_json::Value>(put_object.cloud_trail_event.as_ref().unwrap()).unwrap();
+        assert_eq!(record.lookup("requestParameters.key").unwrap().as_string(), Some("reports/2023-06.csv"));
+
+        assert_eq!(events[1].event_name, Some("ConsoleLogin".to_owned()));
+    }
+
+    #[test]
+    fn read_only_lookup() {
+        let attribute = LookupAttribute::from(Lookup::ReadOnly(false));
+
+        assert_eq!(attribute.attribute_key, "ReadOnly");
+        assert_eq!(attribute.attribute_value, "false");
+    }
+}
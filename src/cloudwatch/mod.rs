@@ -0,0 +1,115 @@
+//! Amazon CloudWatch
+
+include!(concat!(env!("OUT_DIR"), "/monitoring.rs"));
+
+/// The state of a Contributor Insights rule which is evaluating log events.
+pub const ENABLED: &'static str = "ENABLED";
+
+/// The state of a Contributor Insights rule which has been paused.
+pub const DISABLED: &'static str = "DISABLED";
+
+/// The statistic of rules which rank contributors by the sum of a value in their log events.
+pub const SUM: &'static str = "Sum";
+
+/// The statistic of rules which rank contributors by the largest value in their log events.
+pub const MAXIMUM: &'static str = "Maximum";
+
+impl<P> CloudWatchClient<P> where P: ProvideAwsCredentials {
+    /// Creates a Contributor Insights rule, or replaces the definition of an existing one, in
+    /// the state `ENABLED` or `DISABLED`. Tags are only applied when the rule is created.
+    pub fn add_insight_rule(&mut self, rule_name: &str, rule_state: &str, rule_definition: &str, tags: Vec<Tag>)
+        -> Result<(), AwsError> {
+        let input = PutInsightRuleInput {
+            rule_definition: rule_definition.to_owned(),
+            rule_name: rule_name.to_owned(),
+            rule_state: Some(rule_state.to_owned()),
+            tags: if tags.is_empty() { None } else { Some(tags) },
+            ..PutInsightRuleInput::default()
+        };
+
+        try!(self.put_insight_rule(&input));
+
+        Ok(())
+    }
+
+    /// Returns the top contributors to a rule between the ISO 8601 times `start_time` and
+    /// `end_time`, with a datapoint every `period` seconds, along with the rule-wide `metrics`
+    /// requested, such as `UniqueContributors`.
+    pub fn insight_rule_report(
+        &mut self,
+        rule_name: &str,
+        start_time: &str,
+        end_time: &str,
+        period: i32,
+        max_contributor_count: Option<i32>,
+        metrics: Vec<String>,
+    ) -> Result<GetInsightRuleReportOutput, AwsError> {
+        let input = GetInsightRuleReportInput {
+            end_time: end_time.to_owned(),
+            max_contributor_count: max_contributor_count,
+            metrics: if metrics.is_empty() { None } else { Some(metrics) },
+            period: period,
+            rule_name: rule_name.to_owned(),
+            start_time: start_time.to_owned(),
+            ..GetInsightRuleReportInput::default()
+        };
+
+        self.get_insight_rule_report(&input)
+    }
+
+    /// Resumes evaluation of the Contributor Insights rules `rule_names`. Rules which couldn't
+    /// be enabled are listed in the `failures` of the response.
+    pub fn resume_insight_rules(&mut self, rule_names: Vec<String>) -> Result<EnableInsightRulesOutput, AwsError> {
+        self.enable_insight_rules(&EnableInsightRulesInput { rule_names: rule_names })
+    }
+
+    /// Pauses evaluation of the Contributor Insights rules `rule_names`, which stops them being
+    /// billed. Rules which couldn't be disabled are listed in the `failures` of the response.
+    pub fn pause_insight_rules(&mut self, rule_names: Vec<String>) -> Result<DisableInsightRulesOutput, AwsError> {
+        self.disable_insight_rules(&DisableInsightRulesInput { rule_names: rule_names })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use xml::reader::*;
+
+    use xmlutil::*;
+
+    use super::{GetInsightRuleReportOutputDeserializer, SUM};
+
+    #[test]
+    fn get_insight_rule_report_with_three_contributors() {
+        let file = File::open("tests/sample-data/cloudwatch_get_insight_rule_report.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        reader.next(); // GetInsightRuleReportResponse
+        let result = GetInsightRuleReportOutputDeserializer::deserialize("GetInsightRuleReportResult", &mut reader)
+            .unwrap();
+
+        assert_eq!(result.aggregation_statistic, Some(SUM.to_owned()));
+        assert_eq!(result.aggregate_value, Some(2487.0));
+        assert_eq!(result.approximate_unique_count, Some(3));
+        assert_eq!(result.key_labels, Some(vec!["srcAddr".to_owned()]));
+
+        let contributors = result.contributors.unwrap();
+        assert_eq!(contributors.len(), 3);
+        assert_eq!(contributors[0].keys, vec!["10.0.1.17".to_owned()]);
+        assert_eq!(contributors[0].approximate_aggregate_value, 1524.0);
+        assert_eq!(contributors[0].datapoints.len(), 2);
+        assert_eq!(contributors[0].datapoints[1].timestamp, "2020-04-01T12:05:00Z");
+        assert_eq!(contributors[0].datapoints[1].approximate_value, 623.0);
+
+        // With the Sum statistic, each contributor's aggregate is the sum of its datapoints.
+        for contributor in &contributors {
+            let sum = contributor.datapoints.iter().fold(0.0, |sum, datapoint| sum + datapoint.approximate_value);
+            assert_eq!(contributor.approximate_aggregate_value, sum);
+        }
+    }
+}
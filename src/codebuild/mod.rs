@@ -0,0 +1,152 @@
+//! AWS CodeBuild
+
+include!(concat!(env!("OUT_DIR"), "/codebuild.rs"));
+
+use pagination::read_all_pages;
+
+/// The state of a build, parsed from `Build::build_status`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BuildState {
+    Succeeded,
+    Failed,
+    Fault,
+    TimedOut,
+    InProgress,
+    Stopped,
+    Unknown,
+}
+
+impl BuildState {
+    pub fn from_status(status: &str) -> BuildState {
+        match status {
+            "SUCCEEDED" => BuildState::Succeeded,
+            "FAILED" => BuildState::Failed,
+            "FAULT" => BuildState::Fault,
+            "TIMED_OUT" => BuildState::TimedOut,
+            "IN_PROGRESS" => BuildState::InProgress,
+            "STOPPED" => BuildState::Stopped,
+            _ => BuildState::Unknown,
+        }
+    }
+
+    /// Whether the build has stopped running, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        match *self {
+            BuildState::InProgress | BuildState::Unknown => false,
+            _ => true,
+        }
+    }
+}
+
+impl Build {
+    /// The state of the build, which is `Unknown` if the response didn't include one.
+    pub fn state(&self) -> BuildState {
+        self.build_status.as_ref().map_or(BuildState::Unknown, |status| BuildState::from_status(status))
+    }
+}
+
+impl EnvironmentVariable {
+    /// A variable whose value is passed to the build as it is, rather than read from Parameter
+    /// Store or Secrets Manager.
+    pub fn plaintext(name: &str, value: &str) -> EnvironmentVariable {
+        EnvironmentVariable {
+            name: name.to_owned(),
+            aws_type: Some("PLAINTEXT".to_owned()),
+            value: value.to_owned(),
+        }
+    }
+}
+
+impl StartBuildInput {
+    /// Creates a request to build `project_name`, overriding its source version, environment
+    /// variables and buildspec where given.
+    pub fn new(project_name: &str, source_version: Option<String>,
+        environment_variables_override: Vec<EnvironmentVariable>, buildspec_override: Option<String>)
+        -> StartBuildInput {
+        StartBuildInput {
+            buildspec_override: buildspec_override,
+            environment_variables_override: if environment_variables_override.is_empty() {
+                None
+            } else {
+                Some(environment_variables_override)
+            },
+            project_name: project_name.to_owned(),
+            source_version: source_version,
+            ..StartBuildInput::default()
+        }
+    }
+}
+
+impl<P> CodeBuildClient<P> where P: ProvideAwsCredentials {
+    /// Calls `ListBuildsForProject` until every page has been read, and returns the IDs
+    /// of all of the project's builds. `sort_order` is `ASCENDING` or `DESCENDING`, the default.
+    pub fn list_all_builds_for_project(&mut self, project_name: &str, sort_order: Option<&str>)
+        -> AwsResult<Vec<String>> {
+        let mut input = ListBuildsForProjectInput {
+            project_name: project_name.to_owned(),
+            sort_order: sort_order.map(|sort_order| sort_order.to_owned()),
+            ..ListBuildsForProjectInput::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_builds_for_project(&input));
+
+            Ok((output.ids.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{BatchGetBuildsOutput, BuildState, EnvironmentVariable, StartBuildInput};
+
+    #[test]
+    fn parse_succeeded_build_phases() {
+        let mut body = String::new();
+        File::open("tests/sample-data/codebuild_batch_get_builds.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let output = serde_json::from_str::<BatchGetBuildsOutput>(&body).unwrap();
+        let builds = output.builds.unwrap();
+
+        assert_eq!(builds.len(), 1);
+        assert_eq!(output.builds_not_found, Some(vec![]));
+
+        let build = &builds[0];
+        assert_eq!(build.id, Some("web-app:b1e6661e-e4f2-4156-9ab9-82a19EXAMPLE".to_owned()));
+        assert_eq!(build.project_name, Some("web-app".to_owned()));
+        assert_eq!(build.state(), BuildState::Succeeded);
+
+        let phases = build.phases.as_ref().unwrap();
+        assert_eq!(phases.len(), 4);
+        assert_eq!(phases[1].phase_type, Some("INSTALL".to_owned()));
+        assert_eq!(phases[1].phase_status, Some("SUCCEEDED".to_owned()));
+        assert_eq!(phases[2].duration_in_seconds, Some(67));
+        assert_eq!(phases[3].phase_type, Some("COMPLETED".to_owned()));
+        assert_eq!(phases[3].phase_status, None);
+
+        let logs = build.logs.as_ref().unwrap();
+        assert_eq!(logs.group_name, Some("/aws/codebuild/web-app".to_owned()));
+        assert_eq!(logs.stream_name, Some("b1e6661e-e4f2-4156-9ab9-82a19EXAMPLE".to_owned()));
+    }
+
+    #[test]
+    fn start_build_input() {
+        let input = StartBuildInput::new(
+            "web-app",
+            Some("refs/heads/release".to_owned()),
+            vec![EnvironmentVariable::plaintext("STAGE", "production")],
+            None,
+        );
+        let json = serde_json::to_string(&input).unwrap();
+
+        assert!(json.contains("\"environmentVariablesOverride\":[{\"name\":\"STAGE\",\"type\":\"PLAINTEXT\",\
+                               \"value\":\"production\"}]"));
+        assert_eq!(StartBuildInput::new("web-app", None, Vec::new(), None).environment_variables_override, None);
+    }
+}
@@ -0,0 +1,59 @@
+//! AWS CodeCommit
+
+include!(concat!(env!("OUT_DIR"), "/codecommit.rs"));
+
+use pagination::read_all_pages;
+
+impl<P> CodeCommitClient<P> where P: ProvideAwsCredentials {
+    /// Calls `ListRepositories` until every page has been read, and returns the name and ID of
+    /// every repository in the region.
+    pub fn list_all_repositories(&mut self) -> AwsResult<Vec<RepositoryNameIdPair>> {
+        let mut input = ListRepositoriesInput::default();
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_repositories(&input));
+
+            Ok((output.repositories.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{GetFileOutput, ListRepositoriesOutput};
+
+    #[test]
+    fn get_file_with_binary_content() {
+        let mut body = String::new();
+        File::open("tests/sample-data/codecommit_get_file.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+
+        let output = serde_json::from_str::<GetFileOutput>(&body).unwrap();
+
+        assert_eq!(output.commit_id, "317f8570EXAMPLE");
+        assert_eq!(output.file_mode, "NORMAL");
+        assert_eq!(output.file_size, 10);
+        assert_eq!(output.file_content, vec![0x00, 0x9f, 0x92, 0x96, 0xff, 0x0a, 0x89, 0x50, 0x4e, 0x47]);
+    }
+
+    #[test]
+    fn parse_repository_list() {
+        let mut body = String::new();
+        File::open("tests/sample-data/codecommit_list_repositories.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+
+        let output = serde_json::from_str::<ListRepositoriesOutput>(&body).unwrap();
+        let repositories = output.repositories.unwrap();
+
+        assert_eq!(repositories.len(), 2);
+        assert_eq!(repositories[0].repository_name, Some("MyTestRepository".to_owned()));
+        assert_eq!(repositories[1].repository_id, Some("cfc29ac4-b0cb-44dc-9990-f6f51EXAMPLE".to_owned()));
+        assert_eq!(output.next_token, Some("aBcDeFgHiJkLmNoPqRsTuVwXyZ".to_owned()));
+    }
+}
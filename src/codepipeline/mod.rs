@@ -0,0 +1,102 @@
+//! AWS CodePipeline
+
+include!(concat!(env!("OUT_DIR"), "/codepipeline.rs"));
+
+use pagination::read_all_pages;
+
+/// The state of a pipeline execution, parsed from `PipelineExecution::status`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineExecutionState {
+    InProgress,
+    Stopped,
+    Stopping,
+    Succeeded,
+    Superseded,
+    Failed,
+    Unknown,
+}
+
+impl PipelineExecutionState {
+    pub fn from_status(status: &str) -> PipelineExecutionState {
+        match status {
+            "InProgress" => PipelineExecutionState::InProgress,
+            "Stopped" => PipelineExecutionState::Stopped,
+            "Stopping" => PipelineExecutionState::Stopping,
+            "Succeeded" => PipelineExecutionState::Succeeded,
+            "Superseded" => PipelineExecutionState::Superseded,
+            "Failed" => PipelineExecutionState::Failed,
+            _ => PipelineExecutionState::Unknown,
+        }
+    }
+
+    /// Whether the execution has stopped running, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        match *self {
+            PipelineExecutionState::InProgress | PipelineExecutionState::Stopping => false,
+            _ => true,
+        }
+    }
+}
+
+impl PipelineExecution {
+    /// The state of the execution, which is `Unknown` if the response didn't include one.
+    pub fn state(&self) -> PipelineExecutionState {
+        self.status.as_ref().map_or(PipelineExecutionState::Unknown, |status| {
+            PipelineExecutionState::from_status(status)
+        })
+    }
+}
+
+impl<P> CodePipelineClient<P> where P: ProvideAwsCredentials {
+    /// Calls `ListPipelines` until every page has been read, and returns all of the pipelines.
+    pub fn list_all_pipelines(&mut self) -> AwsResult<Vec<PipelineSummary>> {
+        let mut input = ListPipelinesInput::default();
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_pipelines(&input));
+
+            Ok((output.pipelines.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{GetPipelineExecutionOutput, PipelineExecutionState};
+
+    #[test]
+    fn parse_succeeded_execution_with_two_sources() {
+        let mut body = String::new();
+        File::open("tests/sample-data/codepipeline_get_pipeline_execution.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let output = serde_json::from_str::<GetPipelineExecutionOutput>(&body).unwrap();
+        let execution = output.pipeline_execution.unwrap();
+
+        assert_eq!(execution.pipeline_name, Some("web-release".to_owned()));
+        assert_eq!(execution.state(), PipelineExecutionState::Succeeded);
+        assert!(execution.state().is_finished());
+
+        let revisions = execution.artifact_revisions.unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].name, Some("AppSource".to_owned()));
+        assert_eq!(revisions[0].revision_id, Some("4f3c9c8e1a2b7d6e5f40312a9b8c7d6e5f4a3b2c".to_owned()));
+        assert_eq!(revisions[1].name, Some("ConfigSource".to_owned()));
+        assert_eq!(
+            revisions[1].revision_url,
+            Some("https://github.com/example/web-config/commit/9a8b7c6d5e4f".to_owned())
+        );
+    }
+
+    #[test]
+    fn execution_states() {
+        assert_eq!(PipelineExecutionState::from_status("Superseded"), PipelineExecutionState::Superseded);
+        assert!(!PipelineExecutionState::from_status("InProgress").is_finished());
+        assert_eq!(PipelineExecutionState::from_status("Cancelled"), PipelineExecutionState::Unknown);
+    }
+}
@@ -0,0 +1,137 @@
+//! Amazon Comprehend
+
+include!(concat!(env!("OUT_DIR"), "/comprehend.rs"));
+
+use error::expect;
+
+/// The input format of a job where each file in the input location is one document.
+pub const ONE_DOC_PER_FILE: &'static str = "ONE_DOC_PER_FILE";
+
+/// The input format of a job where each line of each file is one document.
+pub const ONE_DOC_PER_LINE: &'static str = "ONE_DOC_PER_LINE";
+
+impl Entity {
+    /// The offsets of the first character of the entity and of the character after it, in
+    /// characters rather than bytes from the start of the text. Entities can overlap, such as
+    /// an organization within a longer organization name.
+    pub fn offsets(&self) -> Option<(usize, usize)> {
+        match (self.begin_offset, self.end_offset) {
+            (Some(begin), Some(end)) if begin >= 0 && end >= begin => Some((begin as usize, end as usize)),
+            _ => None,
+        }
+    }
+}
+
+impl InputDataConfig {
+    /// The documents under the S3 URI `s3_uri`, in the format `ONE_DOC_PER_FILE` or
+    /// `ONE_DOC_PER_LINE`.
+    pub fn new(s3_uri: &str, input_format: &str) -> InputDataConfig {
+        InputDataConfig {
+            input_format: Some(input_format.to_owned()),
+            s3_uri: s3_uri.to_owned(),
+            ..InputDataConfig::default()
+        }
+    }
+}
+
+impl OutputDataConfig {
+    /// Writes a job's results under the S3 URI `s3_uri`.
+    pub fn new(s3_uri: &str) -> OutputDataConfig {
+        OutputDataConfig {
+            s3_uri: s3_uri.to_owned(),
+            ..OutputDataConfig::default()
+        }
+    }
+}
+
+impl<P> ComprehendClient<P> where P: ProvideAwsCredentials {
+    /// Detects whether `text` is positive, negative, neutral or mixed.
+    pub fn sentiment(&mut self, text: &str, language_code: &str) -> AwsResult<DetectSentimentResponse> {
+        let input = DetectSentimentRequest {
+            language_code: language_code.to_owned(),
+            text: text.to_owned(),
+        };
+
+        self.detect_sentiment(&input)
+    }
+
+    /// Detects the entities, such as people and places, in `text`. With `endpoint_arn`, a
+    /// custom entity recognizer is used and the language code is taken from its model.
+    pub fn entities(&mut self, text: &str, language_code: &str, endpoint_arn: Option<String>)
+        -> AwsResult<Vec<Entity>> {
+        let input = DetectEntitiesRequest {
+            endpoint_arn: endpoint_arn,
+            language_code: Some(language_code.to_owned()),
+            text: text.to_owned(),
+            ..DetectEntitiesRequest::default()
+        };
+
+        Ok(try!(self.detect_entities(&input)).entities.unwrap_or_else(Vec::new))
+    }
+
+    /// Detects the key noun phrases of `text`.
+    pub fn key_phrases(&mut self, text: &str, language_code: &str) -> AwsResult<Vec<KeyPhrase>> {
+        let input = DetectKeyPhrasesRequest {
+            language_code: language_code.to_owned(),
+            text: text.to_owned(),
+        };
+
+        Ok(try!(self.detect_key_phrases(&input)).key_phrases.unwrap_or_else(Vec::new))
+    }
+
+    /// Starts an asynchronous job detecting `number_of_topics` topics, or 10 without it, in
+    /// the documents of `input_data_config`, and returns its ID.
+    pub fn start_topics_detection(&mut self, input_data_config: InputDataConfig,
+        output_data_config: OutputDataConfig, data_access_role_arn: &str, number_of_topics: Option<i32>)
+        -> AwsResult<String> {
+        let input = StartTopicsDetectionJobRequest {
+            data_access_role_arn: data_access_role_arn.to_owned(),
+            input_data_config: input_data_config,
+            number_of_topics: number_of_topics,
+            output_data_config: output_data_config,
+            ..StartTopicsDetectionJobRequest::default()
+        };
+
+        expect(try!(self.start_topics_detection_job(&input)).job_id, "the job ID")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DetectEntitiesResponse, Entity};
+
+    #[test]
+    fn parse_overlapping_entities() {
+        let text = "The first Amazon Web Services offices opened in Seattle in 2006.";
+        let mut body = String::new();
+        File::open("tests/sample-data/comprehend_detect_entities.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let entities = serde_json::from_str::<DetectEntitiesResponse>(&body).unwrap().entities.unwrap();
+
+        assert_eq!(entities.len(), 4);
+        assert_eq!(entities[0].offsets(), Some((10, 29)));
+        assert_eq!(entities[1].offsets(), Some((10, 16)));
+        assert_eq!(entities[1].aws_type, Some("ORGANIZATION".to_owned()));
+
+        for entity in &entities {
+            let (begin, end) = entity.offsets().unwrap();
+
+            assert_eq!(&text[begin..end], &entity.text.as_ref().unwrap()[..]);
+        }
+    }
+
+    #[test]
+    fn entity_without_offsets() {
+        let entity = Entity {
+            begin_offset: Some(12),
+            ..Entity::default()
+        };
+
+        assert_eq!(entity.offsets(), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! AWS Config
+
+include!(concat!(env!("OUT_DIR"), "/config.rs"));
+
+use error::AwsError;
+use pagination::read_all_pages;
+
+impl ConfigurationItem {
+    /// Parses the resource's configuration, which AWS Config returns as a JSON string.
+    pub fn configuration_json(&self) -> AwsResult<Option<serde_json::Value>> {
+        match self.configuration {
+            Some(ref configuration) => {
+                serde_json::from_str::<serde_json::Value>(configuration).map(Some).map_err(|err| {
+                    AwsError::new(format!("Failed to parse configuration as JSON: {}", err))
+                })
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl GetResourceConfigHistoryRequest {
+    /// Creates a request for the configuration history of a resource, such as an
+    /// `AWS::EC2::Instance`, from the newest item to the oldest.
+    pub fn new(resource_type: &str, resource_id: &str) -> GetResourceConfigHistoryRequest {
+        GetResourceConfigHistoryRequest {
+            resource_id: resource_id.to_owned(),
+            resource_type: resource_type.to_owned(),
+            ..GetResourceConfigHistoryRequest::default()
+        }
+    }
+}
+
+impl ListDiscoveredResourcesRequest {
+    pub fn new(resource_type: &str, include_deleted_resources: bool) -> ListDiscoveredResourcesRequest {
+        ListDiscoveredResourcesRequest {
+            include_deleted_resources: Some(include_deleted_resources),
+            resource_type: resource_type.to_owned(),
+            ..ListDiscoveredResourcesRequest::default()
+        }
+    }
+}
+
+impl Source {
+    /// A source for the AWS managed rule `source_identifier`, such as
+    /// `S3_BUCKET_VERSIONING_ENABLED`.
+    pub fn aws_managed(source_identifier: &str) -> Source {
+        Source {
+            owner: "AWS".to_owned(),
+            source_identifier: Some(source_identifier.to_owned()),
+            ..Source::default()
+        }
+    }
+
+    /// A source for a custom rule evaluated by the Lambda function `function_arn` whenever one
+    /// of `source_details` happens.
+    pub fn custom_lambda(function_arn: &str, source_details: Vec<SourceDetail>) -> Source {
+        Source {
+            owner: "CUSTOM_LAMBDA".to_owned(),
+            source_details: Some(source_details),
+            source_identifier: Some(function_arn.to_owned()),
+            ..Source::default()
+        }
+    }
+}
+
+impl<P> ConfigServiceClient<P> where P: ProvideAwsCredentials {
+    /// Calls `GetResourceConfigHistory` until every page has been read, and returns the
+    /// configuration items in order.
+    pub fn get_all_resource_config_history(&mut self, input: &GetResourceConfigHistoryRequest)
+        -> AwsResult<Vec<ConfigurationItem>> {
+        let mut input = input.clone();
+
+        read_all_pages(input.next_token.take(), |next_token| {
+            input.next_token = next_token;
+            let response = try!(self.get_resource_config_history(&input));
+
+            Ok((response.configuration_items.unwrap_or_else(Vec::new), response.next_token))
+        })
+    }
+
+    /// Calls `ListDiscoveredResources` until every page has been read, and returns the
+    /// resources in order.
+    pub fn list_all_discovered_resources(&mut self, input: &ListDiscoveredResourcesRequest)
+        -> AwsResult<Vec<ResourceIdentifier>> {
+        let mut input = input.clone();
+
+        read_all_pages(input.next_token.take(), |next_token| {
+            input.next_token = next_token;
+            let response = try!(self.list_discovered_resources(&input));
+
+            Ok((response.resource_identifiers.unwrap_or_else(Vec::new), response.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{ConfigRule, GetResourceConfigHistoryResponse, PutConfigRuleRequest, Scope, Source};
+
+    #[test]
+    fn parse_ec2_instance_configuration_item() {
+        let mut body = String::new();
+        File::open("tests/sample-data/config_get_resource_config_history.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<GetResourceConfigHistoryResponse>(&body).unwrap();
+        let items = response.configuration_items.unwrap();
+
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.resource_type, Some("AWS::EC2::Instance".to_owned()));
+        assert_eq!(item.resource_id, Some("i-0b9a4f5e8c3d2a1f0".to_owned()));
+        assert_eq!(item.configuration_item_status, Some("OK".to_owned()));
+        assert_eq!(item.tags.as_ref().unwrap()["Name"], "web-1");
+
+        let configuration = item.configuration_json().unwrap().unwrap();
+        assert_eq!(configuration.find("instanceType").unwrap().as_string(), Some("t2.micro"));
+        assert_eq!(configuration.lookup("state.name").unwrap().as_string(), Some("running"));
+        assert_eq!(
+            configuration.find("securityGroups").unwrap().as_array().unwrap()[0]
+                .find("groupId").unwrap().as_string(),
+            Some("sg-0f1e2d3c")
+        );
+    }
+
+    #[test]
+    fn put_managed_config_rule() {
+        let request = PutConfigRuleRequest {
+            config_rule: ConfigRule {
+                config_rule_name: Some("s3-bucket-versioning-enabled".to_owned()),
+                scope: Some(Scope {
+                    compliance_resource_types: Some(vec!["AWS::S3::Bucket".to_owned()]),
+                    ..Scope::default()
+                }),
+                source: Source::aws_managed("S3_BUCKET_VERSIONING_ENABLED"),
+                ..ConfigRule::default()
+            },
+            ..PutConfigRuleRequest::default()
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+        assert_eq!(value.lookup("ConfigRule.Source.Owner").unwrap().as_string(), Some("AWS"));
+        assert_eq!(
+            value.lookup("ConfigRule.Source.SourceIdentifier").unwrap().as_string(),
+            Some("S3_BUCKET_VERSIONING_ENABLED")
+        );
+        assert_eq!(
+            value.lookup("ConfigRule.Scope.ComplianceResourceTypes").unwrap().as_array().unwrap().len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Amazon Connect
+
+use std::collections::HashMap;
+
+include!(concat!(env!("OUT_DIR"), "/connect.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+/// The number of agents logged in to the Contact Control Panel, whatever their status.
+pub const AGENTS_ONLINE: &'static str = "AGENTS_ONLINE";
+
+/// The number of agents who are available to take contacts.
+pub const AGENTS_AVAILABLE: &'static str = "AGENTS_AVAILABLE";
+
+/// The number of contacts waiting in a queue.
+pub const CONTACTS_IN_QUEUE: &'static str = "CONTACTS_IN_QUEUE";
+
+/// How long, in seconds, the contact which has waited longest in a queue has been waiting.
+pub const OLDEST_CONTACT_AGE: &'static str = "OLDEST_CONTACT_AGE";
+
+/// The unit of metrics which count agents or contacts.
+pub const COUNT: &'static str = "COUNT";
+
+/// The unit of metrics which measure time.
+pub const SECONDS: &'static str = "SECONDS";
+
+/// Groups current metrics by queue.
+pub const QUEUE: &'static str = "QUEUE";
+
+/// Groups current metrics by channel.
+pub const CHANNEL: &'static str = "CHANNEL";
+
+/// The channel of phone calls.
+pub const VOICE: &'static str = "VOICE";
+
+/// The channel of chats.
+pub const CHAT: &'static str = "CHAT";
+
+impl CurrentMetricResult {
+    /// The value of the metric `metric_name`, such as `AGENTS_ONLINE`, if it was requested.
+    pub fn value(&self, metric_name: &str) -> Option<f64> {
+        self.collections.as_ref().and_then(|collections| {
+            collections.iter()
+                .find(|data| {
+                    let name = data.metric.as_ref().and_then(|metric| metric.name.as_ref());
+                    name.map(|name| &name[..]) == Some(metric_name)
+                })
+                .and_then(|data| data.value)
+        })
+    }
+}
+
+impl<P> ConnectClient<P> where P: ProvideAwsCredentials {
+    /// Calls `ListInstances` until every page has been read, with up to `max_results`
+    /// instances on each, and returns all of them.
+    pub fn list_all_instances(&mut self, max_results: Option<i32>) -> AwsResult<Vec<InstanceSummary>> {
+        let mut input = ListInstancesRequest {
+            max_results: max_results,
+            ..ListInstancesRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_instances(&input));
+
+            Ok((output.instance_summary_list.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Routes calls to the phone number `phone_number_id` of an instance to the contact flow
+    /// `contact_flow_id`.
+    pub fn set_phone_number_contact_flow(&mut self, phone_number_id: &str, instance_id: &str,
+        contact_flow_id: &str) -> AwsResult<()> {
+        let input = AssociatePhoneNumberContactFlowRequest {
+            contact_flow_id: contact_flow_id.to_owned(),
+            instance_id: instance_id.to_owned(),
+            phone_number_id: phone_number_id.to_owned(),
+        };
+
+        self.associate_phone_number_contact_flow(&input)
+    }
+
+    /// Calls `destination_phone_number`, in E.164 format, and runs the contact flow
+    /// `contact_flow_id` once the call is answered, with `attributes` available to it. Returns
+    /// the ID of the contact. The call is made from the queue's outbound caller ID number
+    /// unless `source_phone_number` is given.
+    pub fn start_outbound_call(
+        &mut self,
+        destination_phone_number: &str,
+        contact_flow_id: &str,
+        instance_id: &str,
+        source_phone_number: Option<String>,
+        attributes: HashMap<String, String>,
+    ) -> AwsResult<String> {
+        let input = StartOutboundVoiceContactRequest {
+            attributes: if attributes.is_empty() { None } else { Some(attributes) },
+            contact_flow_id: contact_flow_id.to_owned(),
+            destination_phone_number: destination_phone_number.to_owned(),
+            instance_id: instance_id.to_owned(),
+            source_phone_number: source_phone_number,
+            ..StartOutboundVoiceContactRequest::default()
+        };
+
+        expect(try!(self.start_outbound_voice_contact(&input)).contact_id, "the contact ID")
+    }
+
+    /// Returns the real-time `current_metrics` of the queues or channels matching `filters`,
+    /// with a result for each combination of the `groupings`, such as `QUEUE` and `CHANNEL`.
+    pub fn current_metric_data(&mut self, instance_id: &str, filters: Filters, groupings: Vec<String>,
+        current_metrics: Vec<CurrentMetric>) -> AwsResult<GetCurrentMetricDataResponse> {
+        let input = GetCurrentMetricDataRequest {
+            current_metrics: current_metrics,
+            filters: filters,
+            groupings: if groupings.is_empty() { None } else { Some(groupings) },
+            instance_id: instance_id.to_owned(),
+            ..GetCurrentMetricDataRequest::default()
+        };
+
+        self.get_current_metric_data(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{AGENTS_ONLINE, CONTACTS_IN_QUEUE, COUNT, GetCurrentMetricDataResponse, OLDEST_CONTACT_AGE, VOICE};
+
+    #[test]
+    fn parse_current_metrics_by_queue() {
+        let mut body = String::new();
+        File::open("tests/sample-data/connect_get_current_metric_data.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<GetCurrentMetricDataResponse>(&body).unwrap();
+
+        assert_eq!(response.next_token, None);
+        assert_eq!(response.data_snapshot_time, Some(1625135400.0));
+
+        let results = response.metric_results.unwrap();
+        assert_eq!(results.len(), 2);
+
+        let dimensions = results[0].dimensions.as_ref().unwrap();
+        assert_eq!(dimensions.channel, Some(VOICE.to_owned()));
+        assert_eq!(
+            dimensions.queue.as_ref().unwrap().id,
+            Some("4e5f8b3c-97d1-4a4f-b8c2-6d1f0a9e3b21".to_owned())
+        );
+
+        let unit = results[0].collections.as_ref().unwrap()[0].metric.as_ref().unwrap().unit.clone();
+        assert_eq!(unit, Some(COUNT.to_owned()));
+
+        assert_eq!(results[0].value(AGENTS_ONLINE), Some(12.0));
+        assert_eq!(results[0].value(CONTACTS_IN_QUEUE), Some(5.0));
+        assert_eq!(results[1].value(AGENTS_ONLINE), Some(3.0));
+        assert_eq!(results[1].value(CONTACTS_IN_QUEUE), Some(0.0));
+
+        // Metrics which weren't requested have no value.
+        assert_eq!(results[0].value(OLDEST_CONTACT_AGE), None);
+    }
+}
@@ -9,12 +9,18 @@ use std::io::prelude::*;
 use std::io::BufReader;
 use std::ascii::AsciiExt;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
 use hyper::Client;
-use hyper::header::Connection;
+use hyper::header::{Authorization, Connection, Headers};
+use openssl::crypto::hash::Type::SHA1;
+use openssl::crypto::hash::hash;
+use rustc_serialize::hex::ToHex;
 use error::*;
 use regex::Regex;
-use chrono::{Duration, UTC, DateTime};
+use chrono::{Duration, UTC, DateTime, TimeZone};
 use serde_json::{Value, from_str};
+use signature::encode_uri;
 use std::time::Duration as StdDuration;
 
 /// AWS API access credentials, including access key, secret key, token (for IAM profiles), and
@@ -24,7 +30,8 @@ pub struct AwsCredentials {
     key: String,
     secret: String,
     token: Option<String>,
-    expires_at: DateTime<UTC>
+    expires_at: DateTime<UTC>,
+    expires: bool,
 }
 
 impl AwsCredentials {
@@ -37,6 +44,16 @@ impl AwsCredentials {
             secret: secret.into(),
             token: token,
             expires_at: expires_at,
+            expires: true,
+        }
+    }
+
+    /// Create a new `AwsCredentials` from long-term access keys, which never expire. The
+    /// provider still re-reads them after ten minutes, in case they've been changed.
+    pub fn without_expiry<K, S>(key:K, secret:S) -> AwsCredentials where K:Into<String>, S:Into<String> {
+        AwsCredentials {
+            expires: false,
+            ..AwsCredentials::new(key, secret, None, in_ten_minutes())
         }
     }
 
@@ -55,6 +72,12 @@ impl AwsCredentials {
         &self.expires_at
     }
 
+    /// Whether the credentials really expire at `expires_at`, rather than only being re-read
+    /// then. Only temporary credentials, such as those of a role, do.
+    pub fn expires(&self) -> bool {
+        self.expires
+    }
+
     /// Get a reference to the access token.
     pub fn token(&self) -> &Option<String> {
         &self.token
@@ -119,7 +142,7 @@ fn credentials_from_environment() -> Result<AwsCredentials, AwsError> {
         return Err(AwsError::new("Couldn't find either AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY or both in environment."));
     }
 
-    Ok(AwsCredentials::new(env_key, env_secret, None, in_ten_minutes()))
+    Ok(AwsCredentials::without_expiry(env_key, env_secret))
 }
 
 /// Provides AWS credentials from a profile in a credentials file.
@@ -236,7 +259,7 @@ fn parse_credentials_file(file_path: &Path) -> Result<HashMap<String, AwsCredent
         if profile_regex.is_match(&unwrapped_line) {
 
             if profile_name.is_some() && access_key.is_some() && secret_key.is_some() {
-                let creds = AwsCredentials::new(access_key.unwrap(), secret_key.unwrap(), None, in_ten_minutes());
+                let creds = AwsCredentials::without_expiry(access_key.unwrap(), secret_key.unwrap());
                 profiles.insert(profile_name.unwrap(), creds);
             }
 
@@ -272,7 +295,7 @@ fn parse_credentials_file(file_path: &Path) -> Result<HashMap<String, AwsCredent
     }
 
     if profile_name.is_some() && access_key.is_some() && secret_key.is_some() {
-        let creds = AwsCredentials::new(access_key.unwrap(), secret_key.unwrap(), None, in_ten_minutes());
+        let creds = AwsCredentials::without_expiry(access_key.unwrap(), secret_key.unwrap());
         profiles.insert(profile_name.unwrap(), creds);
     }
 
@@ -335,52 +358,350 @@ impl ProvideAwsCredentials for IamProvider {
                 return err("Had issues with reading iam role response: {}");
             }
 
-            let json_object: Value;
-            match from_str(&body) {
-                Err(_) => return err("Couldn't parse metadata response body."),
-                Ok(val) => json_object = val
-            };
+            self.credentials = Some(try!(credentials_from_json(&body)));
+        }
+
+        Ok(&self.credentials.as_ref().unwrap())
+    }
+}
+
+/// Parses the credentials returned by the EC2 instance metadata service and the ECS
+/// credentials endpoint, which share a format.
+fn credentials_from_json(body: &str) -> Result<AwsCredentials, AwsError> {
+    let json_object: Value = match from_str(body) {
+        Err(_) => return Err(AwsError::new("Couldn't parse metadata response body.")),
+        Ok(val) => val
+    };
+
+    let field = |name: &str| -> Result<String, AwsError> {
+        match json_object.find(name) {
+            None => Err(AwsError::new(format!("Couldn't find {} in response.", name))),
+            Some(val) => match val.as_string() {
+                None => Err(AwsError::new(format!("{} in response was not a string.", name))),
+                Some(val) => Ok(val.replace("\"", "")),
+            }
+        }
+    };
+
+    let access_key = try!(field("AccessKeyId"));
+    let secret_key = try!(field("SecretAccessKey"));
+    let expiration_time = try!(try!(field("Expiration")).parse());
+    let token_from_response = try!(field("Token"));
+
+    Ok(AwsCredentials::new(access_key, secret_key, Some(token_from_response), expiration_time))
+}
+
+/// The host of the ECS credentials endpoint, which `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI`
+/// is relative to.
+const ECS_CREDENTIALS_HOST: &'static str = "http://169.254.170.2";
+
+/// Provides AWS credentials from the credentials endpoint of an ECS container, for tasks with
+/// an IAM role.
+///
+/// ECS gives the container the path of the endpoint in
+/// `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI`. Elsewhere, such as on Greengrass, a full URL may
+/// be given instead in `AWS_CONTAINER_CREDENTIALS_FULL_URI`, along with the `Authorization`
+/// header to send in `AWS_CONTAINER_AUTHORIZATION_TOKEN`.
+#[derive(Clone, Debug)]
+pub struct EcsProvider {
+    credentials: Option<AwsCredentials>
+}
+
+impl EcsProvider {
+    /// Create a new `EcsProvider`.
+    pub fn new() -> EcsProvider {
+        EcsProvider { credentials: None }
+    }
+}
 
-            let access_key;
-            match json_object.find("AccessKeyId") {
-                None => return err("Couldn't find AccessKeyId in response."),
-                Some(val) => access_key = val.as_string().expect("AccessKeyId value was not a string").to_owned().replace("\"", "")
+impl Default for EcsProvider {
+    fn default() -> EcsProvider {
+        EcsProvider::new()
+    }
+}
+
+impl ProvideAwsCredentials for EcsProvider {
+    fn credentials(&mut self) -> Result<&AwsCredentials, AwsError> {
+        if self.credentials.is_none() || self.credentials.as_ref().unwrap().credentials_are_expired() {
+            let (address, authorization_token) = try!(ecs_credentials_endpoint(
+                var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI").ok(),
+                var("AWS_CONTAINER_CREDENTIALS_FULL_URI").ok(),
+                var("AWS_CONTAINER_AUTHORIZATION_TOKEN").ok(),
+            ));
+
+            let mut client = Client::new();
+            client.set_read_timeout(Some(StdDuration::from_secs(15)));
+            let mut request = client.get(&address).header(Connection::close());
+            if let Some(token) = authorization_token {
+                request = request.header(Authorization(token));
+            }
+
+            let mut response = match request.send() {
+                Err(_) => return err("Couldn't connect to the ECS credentials endpoint"),
+                Ok(received_response) => received_response
             };
 
-            let secret_key;
-            match json_object.find("SecretAccessKey") {
-                None => return err("Couldn't find SecretAccessKey in response."),
-                Some(val) => secret_key = val.as_string().expect("SecretAccessKey value was not a string").to_owned().replace("\"", "")
+            if !response.status.is_success() {
+                return Err(AwsError::new(format!("ECS credentials endpoint responded with {}", response.status)));
+            }
+
+            let mut body = String::new();
+            if let Err(_) = response.read_to_string(&mut body) {
+                return err("Didn't get a parsable response body from the ECS credentials endpoint");
+            }
+
+            self.credentials = Some(try!(credentials_from_json(&body)));
+        }
+
+        Ok(self.credentials.as_ref().unwrap())
+    }
+}
+
+/// Returns the URL of the ECS credentials endpoint and the `Authorization` header to send it,
+/// from the values of the environment variables configuring it. A relative URI takes
+/// precedence over a full one.
+fn ecs_credentials_endpoint(relative_uri: Option<String>, full_uri: Option<String>,
+    authorization_token: Option<String>) -> AwsResult<(String, Option<String>)> {
+    match (relative_uri, full_uri) {
+        (Some(relative_uri), _) => Ok((format!("{}{}", ECS_CREDENTIALS_HOST, relative_uri), None)),
+        (None, Some(full_uri)) => Ok((full_uri, authorization_token)),
+        (None, None) => Err(AwsError::new(
+            "Neither AWS_CONTAINER_CREDENTIALS_RELATIVE_URI nor AWS_CONTAINER_CREDENTIALS_FULL_URI is set."
+        )),
+    }
+}
+
+/// Provides AWS credentials for the role of an AWS SSO (IAM Identity Center) profile in the
+/// AWS config file, using the access token `aws sso login` caches.
+///
+/// The profile names its SSO start URL and region, and the account and role to assume, with
+/// `sso_start_url`, `sso_region`, `sso_account_id` and `sso_role_name`. When the cached token
+/// has expired, `aws sso login` has to be run again.
+#[derive(Clone, Debug)]
+pub struct SsoProvider {
+    credentials: Option<AwsCredentials>,
+    config_file_path: PathBuf,
+    cache_dir: PathBuf,
+    profile: String,
+    portal_url: Option<String>,
+}
+
+impl SsoProvider {
+    /// Create a new `SsoProvider` for the default config file path, SSO cache directory and
+    /// profile name.
+    pub fn new() -> AwsResult<SsoProvider> {
+        // ~/.aws/config and ~/.aws/sso/cache (Linux/Mac)
+        // %USERPROFILE%\.aws\config and %USERPROFILE%\.aws\sso\cache (Windows)
+        let aws_dir = match env::home_dir() {
+            Some(home_path) => home_path.join(".aws"),
+            None => return Err(AwsError::new("The environment variable HOME must be set.")),
+        };
+
+        Ok(SsoProvider::with_configuration(
+            aws_dir.join("config"),
+            aws_dir.join("sso").join("cache"),
+            "default",
+        ))
+    }
+
+    /// Create a new `SsoProvider` for the config file and SSO cache directory at the given
+    /// paths, using the given profile.
+    pub fn with_configuration<F, C, P>(config_file_path: F, cache_dir: C, profile: P) -> SsoProvider
+    where F: Into<PathBuf>, C: Into<PathBuf>, P: Into<String> {
+        SsoProvider {
+            credentials: None,
+            config_file_path: config_file_path.into(),
+            cache_dir: cache_dir.into(),
+            profile: profile.into(),
+            portal_url: None,
+        }
+    }
+
+    /// Get a reference to the profile name.
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Set the profile name.
+    pub fn set_profile<P>(&mut self, profile: P) where P: Into<String> {
+        self.profile = profile.into();
+    }
+
+    /// Set the URL of the SSO portal, such as `http://localhost:8000`, rather than that of
+    /// the profile's SSO region.
+    pub fn set_portal_url(&mut self, portal_url: Option<String>) {
+        self.portal_url = portal_url;
+    }
+}
+
+impl ProvideAwsCredentials for SsoProvider {
+    fn credentials(&mut self) -> Result<&AwsCredentials, AwsError> {
+        if self.credentials.is_none() || self.credentials.as_ref().unwrap().credentials_are_expired() {
+            let mut settings = try!(parse_config_profile(&self.config_file_path, &self.profile));
+            let (start_url, region, account_id, role_name) = {
+                let profile = &self.profile;
+                let mut setting = |name: &str| {
+                    settings.remove(name).ok_or_else(|| {
+                        AwsError::new(format!("Profile {} has no {}", profile, name))
+                    })
+                };
+
+                (try!(setting("sso_start_url")), try!(setting("sso_region")),
+                    try!(setting("sso_account_id")), try!(setting("sso_role_name")))
             };
 
-            let expiration;
-            match json_object.find("Expiration") {
-                None => return err("Couldn't find Expiration in response."),
-                Some(val) => expiration = val.as_string().expect("Expiration value was not a string").to_owned().replace("\"", "")
+            let access_token = try!(sso_access_token(&sso_cache_file(&self.cache_dir, &start_url)));
+
+            let portal_url = match self.portal_url {
+                Some(ref portal_url) => portal_url.to_owned(),
+                None => format!("https://portal.sso.{}.amazonaws.com", region),
             };
+            let address = format!(
+                "{}/federation/credentials?account_id={}&role_name={}",
+                portal_url,
+                encode_uri(&account_id),
+                encode_uri(&role_name)
+            );
 
-            let expiration_time = try!(expiration.parse());
+            let mut headers = Headers::new();
+            headers.set(Connection::close());
+            headers.set_raw("x-amz-sso_bearer_token", vec![access_token.into_bytes()]);
 
-            let token_from_response;
-            match json_object.find("Token") {
-                None => return err("Couldn't find Token in response."),
-                Some(val) => token_from_response = val.as_string().expect("Token value was not a string").to_owned().replace("\"", "")
+            let mut client = Client::new();
+            client.set_read_timeout(Some(StdDuration::from_secs(15)));
+            let mut response = match client.get(&address).headers(headers).send() {
+                Err(_) => return err("Couldn't connect to the SSO portal"),
+                Ok(received_response) => received_response
             };
 
-            self.credentials = Some(AwsCredentials::new(access_key, secret_key, Some(token_from_response), expiration_time));
+            let mut body = String::new();
+            if let Err(_) = response.read_to_string(&mut body) {
+                return err("Didn't get a parsable response body from the SSO portal");
+            }
+
+            if !response.status.is_success() {
+                return Err(AwsError::new(format!("GetRoleCredentials failed with {}: {}", response.status, body)));
+            }
+
+            self.credentials = Some(try!(sso_role_credentials_from_json(&body)));
         }
 
-        Ok(&self.credentials.as_ref().unwrap())
+        Ok(self.credentials.as_ref().unwrap())
     }
 }
 
+/// Reads the settings of a profile in an AWS config file, where profiles other than the
+/// default one are named `[profile name]`.
+fn parse_config_profile(file_path: &Path, profile: &str) -> Result<HashMap<String, String>, AwsError> {
+    let file = match File::open(file_path) {
+        Err(_) => return Err(AwsError::new("Couldn't open config file.")),
+        Ok(file) => file
+    };
+
+    let section = if profile == "default" { "default".to_owned() } else { format!("profile {}", profile) };
+    let section_regex = Regex::new(r"^\[([^\]]+)\]$").unwrap();
+    let mut settings: Option<HashMap<String, String>> = None;
+    let mut in_section = false;
+
+    for line in BufReader::new(&file).lines() {
+        let line = try!(line);
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(caps) = section_regex.captures(line) {
+            in_section = caps.at(1).unwrap().trim() == section;
+            if in_section && settings.is_none() {
+                settings = Some(HashMap::new());
+            }
+            continue;
+        }
+
+        if in_section {
+            if let Some(index) = line.find('=') {
+                let key = line[..index].trim().to_owned();
+                let value = line[index + 1..].trim().to_owned();
+                settings.as_mut().unwrap().insert(key, value);
+            }
+        }
+    }
+
+    settings.ok_or_else(|| AwsError::new(format!("Couldn't find profile {} in config file.", profile)))
+}
+
+/// The file `aws sso login` caches the access token for `start_url` in, named after the
+/// SHA-1 of the URL.
+fn sso_cache_file(cache_dir: &Path, start_url: &str) -> PathBuf {
+    cache_dir.join(format!("{}.json", hash(SHA1, start_url.as_bytes()).to_hex()))
+}
+
+/// Reads the access token from an SSO cache file, as long as it hasn't expired.
+fn sso_access_token(cache_file: &Path) -> Result<String, AwsError> {
+    let mut body = String::new();
+    match File::open(cache_file) {
+        Err(_) => return Err(AwsError::new("Couldn't find a cached SSO access token; run `aws sso login`.")),
+        Ok(mut file) => { try!(file.read_to_string(&mut body)); }
+    };
+
+    let json_object: Value = match from_str(&body) {
+        Err(_) => return Err(AwsError::new("Couldn't parse SSO cache file.")),
+        Ok(val) => val
+    };
+
+    let access_token = try!(json_object.find("accessToken").and_then(Value::as_string).ok_or_else(|| {
+        AwsError::new("Couldn't find accessToken in SSO cache file.")
+    }));
+    let expires_at = try!(json_object.find("expiresAt").and_then(Value::as_string).ok_or_else(|| {
+        AwsError::new("Couldn't find expiresAt in SSO cache file.")
+    }));
+
+    // Older versions of the AWS CLI wrote `2019-11-14T04:05:45UTC`.
+    let expires_at: DateTime<UTC> = try!(expires_at.replace("UTC", "Z").parse());
+    if expires_at < UTC::now() {
+        return Err(AwsError::new("The cached SSO access token has expired; run `aws sso login`."));
+    }
+
+    Ok(access_token.to_owned())
+}
+
+/// Parses the response of `GetRoleCredentials`, whose expiration is in milliseconds since
+/// the epoch.
+fn sso_role_credentials_from_json(body: &str) -> Result<AwsCredentials, AwsError> {
+    let json_object: Value = match from_str(body) {
+        Err(_) => return Err(AwsError::new("Couldn't parse GetRoleCredentials response body.")),
+        Ok(val) => val
+    };
+
+    let role_credentials = try!(json_object.find("roleCredentials").ok_or_else(|| {
+        AwsError::new("Couldn't find roleCredentials in response.")
+    }));
+    let field = |name: &str| {
+        role_credentials.find(name).and_then(Value::as_string).map(|val| val.to_owned()).ok_or_else(|| {
+            AwsError::new(format!("Couldn't find {} in response.", name))
+        })
+    };
+
+    let access_key = try!(field("accessKeyId"));
+    let secret_key = try!(field("secretAccessKey"));
+    let session_token = try!(field("sessionToken"));
+    let expiration = try!(role_credentials.find("expiration").and_then(Value::as_i64).ok_or_else(|| {
+        AwsError::new("Couldn't find expiration in response.")
+    }));
+    let expires_at = UTC.timestamp(expiration / 1000, (expiration % 1000) as u32 * 1000000);
+
+    Ok(AwsCredentials::new(access_key, secret_key, Some(session_token), expires_at))
+}
+
 /// Provides AWS credentials from multiple possible sources using a priority order.
 ///
 /// The following sources are checked in order for credentials when calling `credentials`:
 ///
 /// 1. Environment variables: `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
 /// 2. AWS credentials file. Usually located at `~/.aws/credentials`.
-/// 3. IAM instance profile. Will only work if running on an EC2 instance with an instance profile/role.
+/// 3. ECS task role. Will only work if running in an ECS container whose task has a role.
+/// 4. IAM instance profile. Will only work if running on an EC2 instance with an instance profile/role.
 ///
 /// If the sources are exhausted without finding credentials, an error is returned.
 #[derive(Debug, Clone)]
@@ -404,13 +725,19 @@ impl ProvideAwsCredentials for ChainProvider {
                 return Ok(self.credentials.as_ref().unwrap());
             }
 
+            if let Ok(creds) = EcsProvider::new().credentials() {
+                self.credentials = Some(creds.clone());
+
+                return Ok(self.credentials.as_ref().unwrap());
+            }
+
             if let Ok(creds) = IamProvider::new().credentials() {
                 self.credentials = Some(creds.clone());
 
                 return Ok(self.credentials.as_ref().unwrap());
             }
 
-            return Err(AwsError::new("Couldn't find AWS credentials in environment, credentials file, or IAM role."));
+            return Err(AwsError::new("Couldn't find AWS credentials in environment, credentials file, ECS task role, or IAM role."));
         }
 
         Ok(self.credentials.as_ref().unwrap())
@@ -436,14 +763,145 @@ impl ChainProvider {
     }
 }
 
+/// Whether credentials are still usable, as judged by an `ExpiryWatchProvider`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExpiryStatus {
+    /// The credentials aren't due to expire soon.
+    Valid,
+    /// The credentials expire within the provider's warning period.
+    ExpiringSoon,
+    /// The credentials have expired.
+    Expired,
+}
+
+fn expiry_status(expires_at: &DateTime<UTC>, warn_before_expiry: Duration) -> ExpiryStatus {
+    let now = UTC::now();
+
+    if *expires_at <= now {
+        ExpiryStatus::Expired
+    } else if *expires_at <= now + warn_before_expiry {
+        ExpiryStatus::ExpiringSoon
+    } else {
+        ExpiryStatus::Valid
+    }
+}
+
+/// Logs a warning if `expires_at` is soon or has passed, and returns how soon it is.
+fn warn_of_expiry(expires_at: &DateTime<UTC>, warn_before_expiry: Duration) -> ExpiryStatus {
+    let status = expiry_status(expires_at, warn_before_expiry);
+
+    match status {
+        ExpiryStatus::ExpiringSoon => warn!("AWS credentials expire soon, at {}", expires_at),
+        ExpiryStatus::Expired => warn!("AWS credentials expired at {}", expires_at),
+        ExpiryStatus::Valid => (),
+    }
+
+    status
+}
+
+/// How often the thread started by `ExpiryWatchProvider::spawn_watcher` checks the expiry.
+const EXPIRY_POLL_INTERVAL_SECONDS: u64 = 60;
+
+/// Wraps another provider, logging a warning whenever the credentials it produces are about
+/// to expire, and refusing to hand out credentials that already have. Long-term access keys,
+/// such as those of the environment or a profile, never expire and are passed through.
+///
+/// Credentials that expire unnoticed, such as those of an ECS task or an assumed role, make
+/// every request fail at once; the warning gives some notice. With `spawn_watcher`, it's
+/// also logged between requests.
+#[derive(Debug)]
+pub struct ExpiryWatchProvider<P> where P: ProvideAwsCredentials {
+    provider: P,
+    warn_before_expiry: Duration,
+    expires_at: Arc<Mutex<Option<DateTime<UTC>>>>,
+}
+
+impl<P> ExpiryWatchProvider<P> where P: ProvideAwsCredentials {
+    /// Create a new `ExpiryWatchProvider` warning five minutes before credentials expire.
+    pub fn new(provider: P) -> ExpiryWatchProvider<P> {
+        ExpiryWatchProvider::with_warn_before_expiry(provider, Duration::minutes(5))
+    }
+
+    /// Create a new `ExpiryWatchProvider` warning `warn_before_expiry` before credentials
+    /// expire.
+    pub fn with_warn_before_expiry(provider: P, warn_before_expiry: Duration) -> ExpiryWatchProvider<P> {
+        ExpiryWatchProvider {
+            provider: provider,
+            warn_before_expiry: warn_before_expiry,
+            expires_at: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Get a reference to the wrapped provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Checks the expiry of the credentials last produced, logging a warning if need be.
+    /// Before any have been, or if they don't expire, they're considered valid.
+    pub fn poll(&self) -> ExpiryStatus {
+        poll_expiry(&self.expires_at, self.warn_before_expiry)
+    }
+
+    /// Starts a thread polling the expiry of the credentials last produced once a minute, so
+    /// the warning is logged even while no requests are made. The thread stops once this
+    /// provider is dropped.
+    pub fn spawn_watcher(&self) -> thread::JoinHandle<()> {
+        let expires_at = Arc::downgrade(&self.expires_at);
+        let warn_before_expiry = self.warn_before_expiry;
+
+        thread::spawn(move || {
+            loop {
+                thread::sleep(StdDuration::from_secs(EXPIRY_POLL_INTERVAL_SECONDS));
+
+                match expires_at.upgrade() {
+                    Some(expires_at) => { poll_expiry(&expires_at, warn_before_expiry); }
+                    None => return,
+                }
+            }
+        })
+    }
+}
+
+fn poll_expiry(expires_at: &Mutex<Option<DateTime<UTC>>>, warn_before_expiry: Duration) -> ExpiryStatus {
+    match *expires_at.lock().unwrap() {
+        Some(ref expires_at) => warn_of_expiry(expires_at, warn_before_expiry),
+        None => ExpiryStatus::Valid,
+    }
+}
+
+impl<P> ProvideAwsCredentials for ExpiryWatchProvider<P> where P: ProvideAwsCredentials {
+    fn credentials(&mut self) -> Result<&AwsCredentials, AwsError> {
+        let credentials = try!(self.provider.credentials());
+
+        if !credentials.expires() {
+            *self.expires_at.lock().unwrap() = None;
+            return Ok(credentials);
+        }
+
+        *self.expires_at.lock().unwrap() = Some(credentials.expires_at().clone());
+
+        match warn_of_expiry(credentials.expires_at(), self.warn_before_expiry) {
+            ExpiryStatus::Expired => Err(AwsError::new(format!(
+                "ExpiredCredentials: the credentials expired at {}",
+                credentials.expires_at()
+            ))),
+            _ => Ok(credentials),
+        }
+    }
+}
+
 fn in_ten_minutes() -> DateTime<UTC> {
     UTC::now() + Duration::seconds(600)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::error::Error;
     use std::path::Path;
 
+    use chrono::{Duration, UTC};
+
     use super::*;
     use error::*;
 
@@ -551,4 +1009,169 @@ mod tests {
         let result = super::parse_credentials_file(Path::new("tests/"));
         assert_eq!(result.err(), Some(AwsError::new("Couldn't open file.")));
     }
+
+    struct StaticProvider {
+        credentials: AwsCredentials,
+    }
+
+    impl ProvideAwsCredentials for StaticProvider {
+        fn credentials(&mut self) -> Result<&AwsCredentials, AwsError> {
+            Ok(&self.credentials)
+        }
+    }
+
+    fn expiring_in(duration: Duration) -> StaticProvider {
+        StaticProvider {
+            credentials: AwsCredentials::new("foo", "bar", None, UTC::now() + duration),
+        }
+    }
+
+    #[test]
+    fn expiry_watch_provider_warns_of_credentials_expiring_soon() {
+        let mut provider = ExpiryWatchProvider::new(expiring_in(Duration::minutes(3)));
+        assert_eq!(provider.poll(), ExpiryStatus::Valid);
+
+        assert!(provider.credentials().is_ok());
+        assert_eq!(provider.poll(), ExpiryStatus::ExpiringSoon);
+    }
+
+    #[test]
+    fn expiry_watch_provider_with_a_shorter_warning_period() {
+        let mut provider = ExpiryWatchProvider::with_warn_before_expiry(
+            expiring_in(Duration::minutes(3)),
+            Duration::minutes(1),
+        );
+
+        assert!(provider.credentials().is_ok());
+        assert_eq!(provider.poll(), ExpiryStatus::Valid);
+    }
+
+    #[test]
+    fn expiry_watch_provider_refuses_expired_credentials() {
+        let mut provider = ExpiryWatchProvider::new(expiring_in(Duration::minutes(-1)));
+        let error = provider.credentials().err().unwrap();
+
+        assert!(error.description().starts_with("ExpiredCredentials: "));
+        assert_eq!(provider.poll(), ExpiryStatus::Expired);
+    }
+
+    #[test]
+    fn expiry_watch_provider_passes_long_term_keys_through() {
+        let profile = ProfileProvider::with_configuration(
+            "tests/sample-data/multiple_profile_credentials",
+            "foo",
+        );
+        let mut provider = ExpiryWatchProvider::with_warn_before_expiry(profile, Duration::minutes(15));
+
+        {
+            let credentials = provider.credentials().unwrap();
+            assert!(!credentials.expires());
+            assert_eq!(credentials.aws_access_key_id(), "foo_access_key");
+        }
+        assert_eq!(provider.poll(), ExpiryStatus::Valid);
+    }
+
+    #[test]
+    fn ecs_credentials_endpoint_relative_to_the_ecs_host() {
+        let endpoint = super::ecs_credentials_endpoint(
+            Some("/v2/credentials/6b3d0a2c".to_owned()),
+            None,
+            Some("ignored".to_owned()),
+        );
+
+        assert_eq!(endpoint, Ok(("http://169.254.170.2/v2/credentials/6b3d0a2c".to_owned(), None)));
+    }
+
+    #[test]
+    fn ecs_credentials_endpoint_full_uri_with_authorization() {
+        let endpoint = super::ecs_credentials_endpoint(
+            None,
+            Some("http://127.0.0.1:51679/credentials".to_owned()),
+            Some("Basic dXNlcjpwYXNz".to_owned()),
+        );
+
+        assert_eq!(
+            endpoint,
+            Ok(("http://127.0.0.1:51679/credentials".to_owned(), Some("Basic dXNlcjpwYXNz".to_owned())))
+        );
+    }
+
+    #[test]
+    fn ecs_credentials_endpoint_not_configured() {
+        assert!(super::ecs_credentials_endpoint(None, None, None).is_err());
+    }
+
+    #[test]
+    fn parse_config_file_sso_profile() {
+        let settings = super::parse_config_profile(
+            Path::new("tests/sample-data/sso_config"),
+            "developer",
+        ).unwrap();
+
+        assert_eq!(settings.get("sso_start_url"), Some(&"https://rusoto.awsapps.com/start".to_owned()));
+        assert_eq!(settings.get("sso_region"), Some(&"us-east-1".to_owned()));
+        assert_eq!(settings.get("sso_account_id"), Some(&"123456789012".to_owned()));
+        assert_eq!(settings.get("sso_role_name"), Some(&"ReadOnly".to_owned()));
+        assert_eq!(settings.get("region"), Some(&"eu-west-1".to_owned()));
+    }
+
+    #[test]
+    fn parse_config_file_missing_profile() {
+        let result = super::parse_config_profile(Path::new("tests/sample-data/sso_config"), "nobody");
+        assert_eq!(result.err(), Some(AwsError::new("Couldn't find profile nobody in config file.")));
+    }
+
+    #[test]
+    fn sso_access_token_from_cache_file() {
+        let cache_file = super::sso_cache_file(
+            Path::new("tests/sample-data/sso_cache"),
+            "https://rusoto.awsapps.com/start",
+        );
+
+        assert_eq!(
+            cache_file,
+            Path::new("tests/sample-data/sso_cache/f84ecced85664a70e6c5c5ae5b708c55b157e5bc.json")
+        );
+        assert_eq!(super::sso_access_token(&cache_file), Ok("mock-sso-access-token".to_owned()));
+    }
+
+    #[test]
+    fn sso_role_credentials() {
+        let credentials = super::sso_role_credentials_from_json(
+            r#"{"roleCredentials":{"accessKeyId":"ASIASSOACCESSKEY","secretAccessKey":"sso-secret",
+                "sessionToken":"sso-session-token","expiration":1466092800500}}"#
+        ).unwrap();
+
+        assert_eq!(credentials.aws_access_key_id(), "ASIASSOACCESSKEY");
+        assert_eq!(credentials.aws_secret_access_key(), "sso-secret");
+        assert_eq!(credentials.token(), &Some("sso-session-token".to_owned()));
+        assert_eq!(credentials.expires_at().timestamp(), 1466092800);
+    }
+
+    #[test]
+    fn container_credentials() {
+        let credentials = super::credentials_from_json(
+            r#"{"AccessKeyId":"ASIAECSACCESSKEY","SecretAccessKey":"ecs-secret","Token":"ecs-token",
+                "Expiration":"2016-06-16T16:00:00Z"}"#
+        ).unwrap();
+
+        assert_eq!(credentials.aws_access_key_id(), "ASIAECSACCESSKEY");
+        assert_eq!(credentials.token(), &Some("ecs-token".to_owned()));
+        assert_eq!(credentials.expires_at().timestamp(), 1466092800);
+    }
+
+    #[test]
+    fn malformed_container_credentials() {
+        let not_a_string = super::credentials_from_json(
+            r#"{"AccessKeyId":42,"SecretAccessKey":"ecs-secret","Token":"ecs-token",
+                "Expiration":"2016-06-16T16:00:00Z"}"#
+        );
+        assert_eq!(not_a_string.err(), Some(AwsError::new("AccessKeyId in response was not a string.")));
+
+        let missing = super::credentials_from_json(r#"{"AccessKeyId":"ASIAECSACCESSKEY"}"#);
+        assert_eq!(missing.err(), Some(AwsError::new("Couldn't find SecretAccessKey in response.")));
+
+        let not_json = super::credentials_from_json("<html>Service Unavailable</html>");
+        assert_eq!(not_json.err(), Some(AwsError::new("Couldn't parse metadata response body.")));
+    }
 }
@@ -0,0 +1,127 @@
+//! AWS DataSync
+
+include!(concat!(env!("OUT_DIR"), "/datasync.rs"));
+
+/// The state of a task execution, parsed from `DescribeTaskExecutionResponse::status`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TaskExecutionState {
+    Queued,
+    Launching,
+    Preparing,
+    Transferring,
+    Verifying,
+    Success,
+    Error,
+    Unknown,
+}
+
+impl TaskExecutionState {
+    pub fn from_status(status: &str) -> TaskExecutionState {
+        match status {
+            "QUEUED" => TaskExecutionState::Queued,
+            "LAUNCHING" => TaskExecutionState::Launching,
+            "PREPARING" => TaskExecutionState::Preparing,
+            "TRANSFERRING" => TaskExecutionState::Transferring,
+            "VERIFYING" => TaskExecutionState::Verifying,
+            "SUCCESS" => TaskExecutionState::Success,
+            "ERROR" => TaskExecutionState::Error,
+            _ => TaskExecutionState::Unknown,
+        }
+    }
+
+    /// Whether the execution has stopped, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        match *self {
+            TaskExecutionState::Success | TaskExecutionState::Error => true,
+            _ => false,
+        }
+    }
+}
+
+impl DescribeTaskExecutionResponse {
+    /// The state of the execution, which is `Unknown` if the response didn't include one.
+    pub fn state(&self) -> TaskExecutionState {
+        self.status.as_ref().map_or(TaskExecutionState::Unknown, |status| {
+            TaskExecutionState::from_status(status)
+        })
+    }
+}
+
+impl FilterRule {
+    /// Creates a rule matching any of `patterns`, which are paths relative to the location
+    /// that may contain `*` wildcards.
+    pub fn simple_pattern(patterns: &[&str]) -> FilterRule {
+        FilterRule {
+            filter_type: Some("SIMPLE_PATTERN".to_owned()),
+            value: Some(patterns.join("|")),
+        }
+    }
+}
+
+impl CreateTaskRequest {
+    pub fn new(source_location_arn: &str, destination_location_arn: &str) -> CreateTaskRequest {
+        CreateTaskRequest {
+            destination_location_arn: destination_location_arn.to_owned(),
+            source_location_arn: source_location_arn.to_owned(),
+            ..CreateTaskRequest::default()
+        }
+    }
+}
+
+impl StartTaskExecutionRequest {
+    /// Creates a request to run a task, only transferring the files matching `includes` if
+    /// any are given.
+    pub fn new(task_arn: &str, override_options: Option<Options>, includes: Vec<FilterRule>)
+        -> StartTaskExecutionRequest {
+        StartTaskExecutionRequest {
+            includes: if includes.is_empty() { None } else { Some(includes) },
+            override_options: override_options,
+            task_arn: task_arn.to_owned(),
+            ..StartTaskExecutionRequest::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{DescribeTaskExecutionResponse, FilterRule, StartTaskExecutionRequest};
+    use super::TaskExecutionState;
+
+    const TASK_ARN: &'static str = "arn:aws:datasync:us-east-1:123456789012:task/task-08de6e6697796f026";
+
+    #[test]
+    fn filter_rule_serialization() {
+        let rule = FilterRule::simple_pattern(&["/reports/2016/*", "/exports"]);
+
+        assert_eq!(
+            serde_json::to_string(&rule).unwrap(),
+            "{\"FilterType\":\"SIMPLE_PATTERN\",\"Value\":\"/reports/2016/*|/exports\"}"
+        );
+    }
+
+    #[test]
+    fn start_task_execution_includes() {
+        let request = StartTaskExecutionRequest::new(TASK_ARN, None, vec![
+            FilterRule::simple_pattern(&["/reports"]),
+        ]);
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"Includes\":[{\"FilterType\":\"SIMPLE_PATTERN\",\"Value\":\"/reports\"}]"));
+        assert_eq!(StartTaskExecutionRequest::new(TASK_ARN, None, Vec::new()).includes, None);
+    }
+
+    #[test]
+    fn task_execution_states() {
+        let response = serde_json::from_str::<DescribeTaskExecutionResponse>(
+            "{\"Status\":\"TRANSFERRING\",\"TaskExecutionArn\":\"arn:aws:datasync:us-east-1:123456789012:\
+             task/task-08de6e6697796f026/execution/exec-04ce9d516d69bd52f\"}"
+        ).unwrap();
+
+        assert_eq!(response.state(), TaskExecutionState::Transferring);
+        assert!(!response.state().is_finished());
+        assert!(TaskExecutionState::from_status("ERROR").is_finished());
+        assert_eq!(TaskExecutionState::from_status("CANCELLING"), TaskExecutionState::Unknown);
+    }
+}
@@ -0,0 +1,82 @@
+//! Amazon Detective
+//!
+//! Builds a behavior graph from an account's CloudTrail logs, VPC flow logs and GuardDuty
+//! findings, which security investigations explore in the Detective console. The graph's
+//! data itself isn't queried through this API.
+
+use std::collections::HashMap;
+
+include!(concat!(env!("OUT_DIR"), "/detective.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+impl<P> DetectiveClient<P> where P: ProvideAwsCredentials {
+    /// Creates a behavior graph for the account, which becomes its administrator account, and
+    /// returns the graph's ARN.
+    pub fn create_graph_arn(&mut self, tags: HashMap<String, String>) -> AwsResult<String> {
+        let input = CreateGraphRequest {
+            tags: if tags.is_empty() { None } else { Some(tags) },
+        };
+
+        expect(try!(self.create_graph(&input)).graph_arn, "the graph ARN")
+    }
+
+    /// Makes `account_id` the Detective administrator account of the organization.
+    pub fn enable_admin_account(&mut self, account_id: &str) -> AwsResult<()> {
+        self.enable_organization_admin_account(&EnableOrganizationAdminAccountRequest {
+            account_id: account_id.to_owned(),
+        })
+    }
+
+    /// Calls `ListGraphs` until every page has been read, with up to `max_results` graphs on
+    /// each, and returns the graphs the account administers. An account without any gets an
+    /// empty list.
+    pub fn list_all_graphs(&mut self, max_results: Option<i32>) -> AwsResult<Vec<Graph>> {
+        let mut input = ListGraphsRequest {
+            max_results: max_results,
+            ..ListGraphsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_graphs(&input));
+
+            Ok((output.graph_list.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::ListGraphsResponse;
+
+    #[test]
+    fn parse_graph_list() {
+        let mut body = String::new();
+        File::open("tests/sample-data/detective_list_graphs.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<ListGraphsResponse>(&body).unwrap();
+        let graphs = response.graph_list.unwrap();
+
+        assert_eq!(graphs.len(), 2);
+        assert_eq!(graphs[0].arn, Some("arn:aws:detective:us-east-1:123456789012:graph:027c7c4610ea".to_owned()));
+        assert_eq!(graphs[1].created_time, Some(1605830400.0));
+        assert_eq!(response.next_token, None);
+    }
+
+    #[test]
+    fn parse_empty_graph_list() {
+        let empty = serde_json::from_str::<ListGraphsResponse>("{\"GraphList\":[]}").unwrap();
+        let missing = serde_json::from_str::<ListGraphsResponse>("{}").unwrap();
+
+        assert_eq!(empty.graph_list, Some(Vec::new()));
+        assert_eq!(missing.graph_list, None);
+        assert_eq!(missing.next_token, None);
+    }
+}
@@ -0,0 +1,142 @@
+//! AWS Database Migration Service
+//!
+//! Migrates data between databases with replication tasks, which run on a replication
+//! instance and copy the tables a table mapping selects from a source endpoint to a target.
+
+include!(concat!(env!("OUT_DIR"), "/dms.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+/// The migration type of a task that copies the existing data and stops.
+pub const FULL_LOAD: &'static str = "full-load";
+
+/// The migration type of a task that only replicates ongoing changes.
+pub const CDC: &'static str = "cdc";
+
+/// The migration type of a task that copies the existing data, then replicates ongoing changes.
+pub const FULL_LOAD_AND_CDC: &'static str = "full-load-and-cdc";
+
+/// The start type of a task's first run.
+pub const START_REPLICATION: &'static str = "start-replication";
+
+/// The start type that resumes a stopped task where it left off.
+pub const RESUME_PROCESSING: &'static str = "resume-processing";
+
+/// The start type that restarts a task from scratch, reloading the target's tables.
+pub const RELOAD_TARGET: &'static str = "reload-target";
+
+impl<P> DmsClient<P> where P: ProvideAwsCredentials {
+    /// Creates a replication task of the migration type `FULL_LOAD`, `CDC` or
+    /// `FULL_LOAD_AND_CDC`, selecting tables with the JSON `table_mappings` and configured by
+    /// the JSON `task_settings`.
+    pub fn create_task(&mut self, task_identifier: &str, source_endpoint_arn: &str, target_endpoint_arn: &str,
+        replication_instance_arn: &str, migration_type: &str, table_mappings: &str, task_settings: Option<&str>)
+        -> AwsResult<ReplicationTask> {
+        let input = CreateReplicationTaskMessage {
+            migration_type: migration_type.to_owned(),
+            replication_instance_arn: replication_instance_arn.to_owned(),
+            replication_task_identifier: task_identifier.to_owned(),
+            replication_task_settings: task_settings.map(str::to_owned),
+            source_endpoint_arn: source_endpoint_arn.to_owned(),
+            table_mappings: table_mappings.to_owned(),
+            target_endpoint_arn: target_endpoint_arn.to_owned(),
+            ..CreateReplicationTaskMessage::default()
+        };
+
+        expect(try!(self.create_replication_task(&input)).replication_task, "the replication task")
+    }
+
+    /// Starts the replication task `replication_task_arn` with the start type
+    /// `START_REPLICATION`, `RESUME_PROCESSING` or `RELOAD_TARGET`.
+    pub fn start_task(&mut self, replication_task_arn: &str, start_replication_task_type: &str)
+        -> AwsResult<ReplicationTask> {
+        let input = StartReplicationTaskMessage {
+            replication_task_arn: replication_task_arn.to_owned(),
+            start_replication_task_type: start_replication_task_type.to_owned(),
+            ..StartReplicationTaskMessage::default()
+        };
+
+        expect(try!(self.start_replication_task(&input)).replication_task, "the replication task")
+    }
+
+    /// Calls `DescribeReplicationTasks` until every page has been read, and returns the tasks
+    /// that match `filters`, such as `replication-instance-arn`.
+    pub fn describe_all_replication_tasks(&mut self, filters: Vec<Filter>) -> AwsResult<Vec<ReplicationTask>> {
+        let mut input = DescribeReplicationTasksMessage {
+            filters: if filters.is_empty() { None } else { Some(filters) },
+            ..DescribeReplicationTasksMessage::default()
+        };
+
+        read_all_pages(None, |marker| {
+            input.marker = marker;
+            let output = try!(self.describe_replication_tasks(&input));
+
+            Ok((output.replication_tasks.unwrap_or_else(Vec::new), output.marker))
+        })
+    }
+
+    /// Calls `DescribeTableStatistics` until every page has been read, and returns the row
+    /// counts of each table the task `replication_task_arn` replicates.
+    pub fn describe_all_table_statistics(&mut self, replication_task_arn: &str)
+        -> AwsResult<Vec<TableStatistics>> {
+        let mut input = DescribeTableStatisticsMessage {
+            replication_task_arn: replication_task_arn.to_owned(),
+            ..DescribeTableStatisticsMessage::default()
+        };
+
+        read_all_pages(None, |marker| {
+            input.marker = marker;
+            let output = try!(self.describe_table_statistics(&input));
+
+            Ok((output.table_statistics.unwrap_or_else(Vec::new), output.marker))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DescribeReplicationTasksResponse, DescribeTableStatisticsResponse, FULL_LOAD_AND_CDC};
+
+    fn sample(name: &str) -> String {
+        let mut body = String::new();
+        File::open(format!("tests/sample-data/{}", name)).unwrap().read_to_string(&mut body).unwrap();
+
+        body
+    }
+
+    #[test]
+    fn parse_running_task() {
+        let body = sample("dms_describe_replication_tasks.json");
+        let response = serde_json::from_str::<DescribeReplicationTasksResponse>(&body).unwrap();
+        let tasks = response.replication_tasks.unwrap();
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].status, Some("running".to_owned()));
+        assert_eq!(tasks[0].migration_type, Some(FULL_LOAD_AND_CDC.to_owned()));
+
+        let stats = tasks[0].replication_task_stats.as_ref().unwrap();
+        assert_eq!(stats.full_load_progress_percent, Some(100));
+        assert_eq!(stats.tables_loaded, Some(2));
+        assert_eq!(stats.tables_errored, Some(0));
+        assert_eq!(stats.elapsed_time_millis, Some(5400000));
+    }
+
+    #[test]
+    fn parse_table_row_counts() {
+        let body = sample("dms_describe_table_statistics.json");
+        let statistics = serde_json::from_str::<DescribeTableStatisticsResponse>(&body).unwrap()
+            .table_statistics.unwrap();
+
+        assert_eq!(statistics.len(), 2);
+        assert_eq!(statistics[0].table_name, Some("orders".to_owned()));
+        assert_eq!(statistics[0].full_load_rows, Some(1250000));
+        assert_eq!(statistics[0].inserts, Some(3120));
+        assert_eq!(statistics[1].table_state, Some("Table completed".to_owned()));
+    }
+}
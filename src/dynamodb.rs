@@ -1,3 +0,0 @@
-//! Amazon DynamoDB
-
-include!(concat!(env!("OUT_DIR"), "/dynamodb.rs"));
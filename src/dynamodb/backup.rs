@@ -0,0 +1,101 @@
+//! Managing on-demand backups.
+
+use credential::ProvideAwsCredentials;
+use error::{AwsResult, expect};
+use pagination::read_all_pages;
+
+use super::{BackupDescription, BackupDetails, BackupSummary, CreateBackupInput, DeleteBackupInput};
+use super::{DynamoDbClient, ListBackupsInput, ProvisionedThroughput, RestoreTableFromBackupInput};
+use super::{SSESpecification, TableDescription};
+
+impl<P> DynamoDbClient<P> where P: ProvideAwsCredentials {
+    /// Backs up a table, returning the details of the backup while it's being created.
+    pub fn create_table_backup(&mut self, table_name: &str, backup_name: &str)
+        -> AwsResult<BackupDetails> {
+        let input = CreateBackupInput {
+            backup_name: backup_name.to_owned(),
+            table_name: table_name.to_owned(),
+        };
+
+        expect(try!(self.create_backup(&input)).backup_details, "the backup details")
+    }
+
+    /// Restores a backup to the new table `target_table_name`, with the backed up table's
+    /// billing mode, provisioned throughput and encryption unless they're overridden.
+    pub fn restore_table(&mut self, target_table_name: &str, backup_arn: &str,
+        billing_mode_override: Option<String>,
+        provisioned_throughput_override: Option<ProvisionedThroughput>,
+        sse_specification_override: Option<SSESpecification>) -> AwsResult<TableDescription> {
+        let input = RestoreTableFromBackupInput {
+            backup_arn: backup_arn.to_owned(),
+            billing_mode_override: billing_mode_override,
+            provisioned_throughput_override: provisioned_throughput_override,
+            sse_specification_override: sse_specification_override,
+            target_table_name: target_table_name.to_owned(),
+            ..RestoreTableFromBackupInput::default()
+        };
+
+        expect(try!(self.restore_table_from_backup(&input)).table_description, "the table description")
+    }
+
+    /// Calls `ListBackups` until every page has been read, and returns the backups of
+    /// `table_name`, or of every table. `backup_type` is `USER`, `SYSTEM`, `AWS_BACKUP` or
+    /// `ALL`; without it, only `USER` backups are listed.
+    pub fn list_all_backups(&mut self, table_name: Option<String>, backup_type: Option<String>)
+        -> AwsResult<Vec<BackupSummary>> {
+        let mut input = ListBackupsInput {
+            backup_type: backup_type,
+            table_name: table_name,
+            ..ListBackupsInput::default()
+        };
+
+        read_all_pages(None, |exclusive_start_backup_arn| {
+            input.exclusive_start_backup_arn = exclusive_start_backup_arn;
+            let output = try!(self.list_backups(&input));
+
+            Ok((output.backup_summaries.unwrap_or_else(Vec::new), output.last_evaluated_backup_arn))
+        })
+    }
+
+    /// Deletes a backup, returning its description as it was before it was deleted.
+    pub fn delete_table_backup(&mut self, backup_arn: &str) -> AwsResult<BackupDescription> {
+        let input = DeleteBackupInput { backup_arn: backup_arn.to_owned() };
+
+        expect(try!(self.delete_backup(&input)).backup_description, "the backup description")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::super::ListBackupsOutput;
+
+    #[test]
+    fn parse_user_and_system_backups() {
+        let mut body = String::new();
+        File::open("tests/sample-data/dynamodb_list_backups.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let output = serde_json::from_str::<ListBackupsOutput>(&body).unwrap();
+
+        assert_eq!(output.last_evaluated_backup_arn, None);
+
+        let backups = output.backup_summaries.unwrap();
+        assert_eq!(backups.len(), 2);
+
+        let user = &backups[0];
+        assert_eq!(user.backup_name, Some("orders-before-migration".to_owned()));
+        assert_eq!(user.backup_type, Some("USER".to_owned()));
+        assert_eq!(user.backup_status, Some("AVAILABLE".to_owned()));
+        assert_eq!(user.backup_size_bytes, Some(1048576));
+        assert_eq!(user.backup_expiry_date_time, None);
+
+        let system = &backups[1];
+        assert_eq!(system.backup_type, Some("SYSTEM".to_owned()));
+        assert_eq!(system.table_name, Some("orders".to_owned()));
+        assert_eq!(system.backup_expiry_date_time, Some(1514592000.0));
+    }
+}
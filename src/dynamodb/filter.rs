@@ -0,0 +1,371 @@
+//! Building `FilterExpression`s for `Scan` and `Query`.
+
+use super::{AttributeValue, ExpressionAttributeNameMap, ExpressionAttributeValueMap};
+use super::query::{alias_name, alias_value};
+
+/// Starts a condition on the attribute `name`.
+pub fn attr(name: &str) -> FilterAttribute {
+    FilterAttribute { name: name.to_owned() }
+}
+
+/// An attribute that a condition can be placed on, created with `attr`.
+#[derive(Clone, Debug)]
+pub struct FilterAttribute {
+    name: String,
+}
+
+/// The value a comparison is made against: either an attribute or the size of one.
+#[derive(Clone, Debug, PartialEq)]
+enum Operand {
+    Attribute(String),
+    Size(String),
+}
+
+/// The size of an attribute, created with `FilterAttribute::size`.
+#[derive(Clone, Debug)]
+pub struct AttributeSize {
+    name: String,
+}
+
+/// A condition that items must satisfy to be returned by a `Scan` or `Query`.
+///
+/// Conditions are combined with `and`, `or` and `not`, for example
+/// `attr("status").eq("active").and(attr("age").gt(18))`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterExpression {
+    condition: Condition,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Comparison(Operand, &'static str, AttributeValue),
+    Between(String, AttributeValue, AttributeValue),
+    In(String, Vec<AttributeValue>),
+    Function(&'static str, String, Option<AttributeValue>),
+    And(Box<FilterExpression>, Box<FilterExpression>),
+    Or(Box<FilterExpression>, Box<FilterExpression>),
+    Not(Box<FilterExpression>),
+}
+
+fn filter(condition: Condition) -> FilterExpression {
+    FilterExpression { condition: condition }
+}
+
+macro_rules! comparisons {
+    ($operand:ident) => {
+        /// Matches items where this equals `value`.
+        pub fn eq<V>(self, value: V) -> FilterExpression where V: Into<AttributeValue> {
+            self.compare("=", value)
+        }
+
+        /// Matches items where this doesn't equal `value`.
+        pub fn ne<V>(self, value: V) -> FilterExpression where V: Into<AttributeValue> {
+            self.compare("<>", value)
+        }
+
+        /// Matches items where this is less than `value`.
+        pub fn lt<V>(self, value: V) -> FilterExpression where V: Into<AttributeValue> {
+            self.compare("<", value)
+        }
+
+        /// Matches items where this is less than or equal to `value`.
+        pub fn le<V>(self, value: V) -> FilterExpression where V: Into<AttributeValue> {
+            self.compare("<=", value)
+        }
+
+        /// Matches items where this is greater than `value`.
+        pub fn gt<V>(self, value: V) -> FilterExpression where V: Into<AttributeValue> {
+            self.compare(">", value)
+        }
+
+        /// Matches items where this is greater than or equal to `value`.
+        pub fn ge<V>(self, value: V) -> FilterExpression where V: Into<AttributeValue> {
+            self.compare(">=", value)
+        }
+
+        fn compare<V>(self, operator: &'static str, value: V) -> FilterExpression
+        where V: Into<AttributeValue> {
+            filter(Condition::Comparison(Operand::$operand(self.name), operator, value.into()))
+        }
+    }
+}
+
+impl FilterAttribute {
+    comparisons!(Attribute);
+
+    /// Matches items where this attribute is between `low` and `high`, inclusive.
+    pub fn between<V>(self, low: V, high: V) -> FilterExpression where V: Into<AttributeValue> {
+        filter(Condition::Between(self.name, low.into(), high.into()))
+    }
+
+    /// Matches items where this attribute equals any of `values`. With no values, no items
+    /// match.
+    pub fn in_list<V>(self, values: Vec<V>) -> FilterExpression where V: Into<AttributeValue> {
+        filter(Condition::In(self.name, values.into_iter().map(Into::into).collect()))
+    }
+
+    /// Matches items that have this attribute.
+    pub fn exists(self) -> FilterExpression {
+        filter(Condition::Function("attribute_exists", self.name, None))
+    }
+
+    /// Matches items that don't have this attribute.
+    pub fn not_exists(self) -> FilterExpression {
+        filter(Condition::Function("attribute_not_exists", self.name, None))
+    }
+
+    /// Matches items where this attribute is of the given type, such as `"S"` or `"N"`.
+    pub fn attribute_type(self, attribute_type: &str) -> FilterExpression {
+        filter(Condition::Function("attribute_type", self.name, Some(attribute_type.into())))
+    }
+
+    /// Matches items where this attribute is a string starting with `prefix`.
+    pub fn begins_with(self, prefix: &str) -> FilterExpression {
+        filter(Condition::Function("begins_with", self.name, Some(prefix.into())))
+    }
+
+    /// Matches items where this attribute is a string containing `value`, or a set or list
+    /// with `value` as an element.
+    pub fn contains<V>(self, value: V) -> FilterExpression where V: Into<AttributeValue> {
+        filter(Condition::Function("contains", self.name, Some(value.into())))
+    }
+
+    /// The size of this attribute, for comparisons such as `attr("tags").size().gt(2)`.
+    pub fn size(self) -> AttributeSize {
+        AttributeSize { name: self.name }
+    }
+}
+
+impl AttributeSize {
+    comparisons!(Size);
+}
+
+impl FilterExpression {
+    /// Matches items that satisfy both this and `other`.
+    pub fn and(self, other: FilterExpression) -> FilterExpression {
+        filter(Condition::And(Box::new(self), Box::new(other)))
+    }
+
+    /// Matches items that satisfy this, `other` or both.
+    pub fn or(self, other: FilterExpression) -> FilterExpression {
+        filter(Condition::Or(Box::new(self), Box::new(other)))
+    }
+
+    /// Matches items that don't satisfy this.
+    pub fn not(self) -> FilterExpression {
+        filter(Condition::Not(Box::new(self)))
+    }
+
+    /// Returns the expression, `ExpressionAttributeNames` and `ExpressionAttributeValues`.
+    pub fn build(&self) -> (String, ExpressionAttributeNameMap, ExpressionAttributeValueMap) {
+        FilterExpressionBuilder::new().build(self)
+    }
+}
+
+/// Turns `FilterExpression`s into the strings and maps DynamoDB expects.
+///
+/// Attribute names are always aliased, and each name is only added once however many
+/// conditions refer to it. Use `with_attributes` to add the filter to the names and values of
+/// a key condition, so that a `Query` can be given both.
+#[derive(Debug, Default)]
+pub struct FilterExpressionBuilder {
+    names: ExpressionAttributeNameMap,
+    values: ExpressionAttributeValueMap,
+}
+
+impl FilterExpressionBuilder {
+    pub fn new() -> FilterExpressionBuilder {
+        FilterExpressionBuilder::default()
+    }
+
+    /// Starts from existing `ExpressionAttributeNames` and `ExpressionAttributeValues`, which
+    /// the placeholders of the filter won't clash with.
+    pub fn with_attributes(names: ExpressionAttributeNameMap, values: ExpressionAttributeValueMap)
+        -> FilterExpressionBuilder {
+        FilterExpressionBuilder {
+            names: names,
+            values: values,
+        }
+    }
+
+    /// Returns the expression for `filter`, along with all of the names and values.
+    pub fn build(mut self, filter: &FilterExpression)
+        -> (String, ExpressionAttributeNameMap, ExpressionAttributeValueMap) {
+        let expression = self.expression(filter);
+
+        (expression, self.names, self.values)
+    }
+
+    fn expression(&mut self, filter: &FilterExpression) -> String {
+        match filter.condition {
+            Condition::Comparison(ref operand, operator, ref value) => {
+                let operand = match *operand {
+                    Operand::Attribute(ref name) => self.name(name),
+                    Operand::Size(ref name) => format!("size({})", self.name(name)),
+                };
+
+                format!("{} {} {}", operand, operator, self.value(value))
+            }
+            Condition::Between(ref name, ref low, ref high) => {
+                let name = self.name(name);
+                let low = self.value(low);
+                let high = self.value(high);
+
+                format!("{} BETWEEN {} AND {}", name, low, high)
+            }
+            Condition::In(ref name, ref values) => {
+                let name = self.name(name);
+
+                // DynamoDB rejects `IN ()`, and has no literal for false, so an empty list
+                // becomes a condition no item can satisfy.
+                if values.is_empty() {
+                    return format!("(attribute_exists({0}) AND attribute_not_exists({0}))", name);
+                }
+
+                let values = values.iter().map(|value| self.value(value)).collect::<Vec<String>>();
+
+                format!("{} IN ({})", name, values.join(", "))
+            }
+            Condition::Function(function, ref name, ref argument) => {
+                let name = self.name(name);
+
+                match *argument {
+                    Some(ref value) => format!("{}({}, {})", function, name, self.value(value)),
+                    None => format!("{}({})", function, name),
+                }
+            }
+            Condition::And(ref left, ref right) => {
+                format!("{} AND {}", self.operand(left, filter), self.operand(right, filter))
+            }
+            Condition::Or(ref left, ref right) => {
+                format!("{} OR {}", self.operand(left, filter), self.operand(right, filter))
+            }
+            Condition::Not(ref inner) => format!("NOT {}", self.operand(inner, filter)),
+        }
+    }
+
+    /// Formats `operand` of `parent`, parenthesising it unless it binds at least as tightly.
+    ///
+    /// Chains of the same combinator are left unparenthesised, so `a.and(b).and(c)` reads as
+    /// `a AND b AND c`, while mixing `AND` and `OR`, or negating either, always adds them.
+    fn operand(&mut self, operand: &FilterExpression, parent: &FilterExpression) -> String {
+        let expression = self.expression(operand);
+
+        let needs_parentheses = match (&operand.condition, &parent.condition) {
+            (&Condition::And(..), &Condition::And(..)) |
+            (&Condition::Or(..), &Condition::Or(..)) => false,
+            (&Condition::And(..), _) | (&Condition::Or(..), _) => true,
+            _ => false,
+        };
+
+        if needs_parentheses {
+            format!("({})", expression)
+        } else {
+            expression
+        }
+    }
+
+    fn name(&mut self, name: &str) -> String {
+        alias_name(&mut self.names, name.to_owned())
+    }
+
+    fn value(&mut self, value: &AttributeValue) -> String {
+        alias_value(&mut self.values, value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{AttributeValue, KeyConditionBuilder};
+    use super::{FilterExpressionBuilder, attr};
+
+    #[test]
+    fn comparison() {
+        let (expression, names, values) = attr("age").gt(18).build();
+
+        assert_eq!(expression, "#n0 > :v0");
+        assert_eq!(names.get("#n0"), Some(&"age".to_owned()));
+        assert_eq!(values.get(":v0"), Some(&AttributeValue::from(18)));
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(attr("email").exists().build().0, "attribute_exists(#n0)");
+        assert_eq!(attr("deleted_at").not_exists().build().0, "attribute_not_exists(#n0)");
+        assert_eq!(attr("score").attribute_type("N").build().0, "attribute_type(#n0, :v0)");
+        assert_eq!(attr("name").begins_with("J").build().0, "begins_with(#n0, :v0)");
+        assert_eq!(attr("tags").contains("rust").build().0, "contains(#n0, :v0)");
+        assert_eq!(attr("tags").size().ge(3).build().0, "size(#n0) >= :v0");
+        assert_eq!(attr("age").between(18, 65).build().0, "#n0 BETWEEN :v0 AND :v1");
+        assert_eq!(
+            attr("status").in_list(vec!["active", "pending"]).build().0,
+            "#n0 IN (:v0, :v1)"
+        );
+    }
+
+    #[test]
+    fn empty_in_list_matches_nothing() {
+        let (expression, names, values) = attr("status").in_list(Vec::<String>::new()).build();
+
+        assert_eq!(expression, "(attribute_exists(#n0) AND attribute_not_exists(#n0))");
+        assert_eq!(names.get("#n0"), Some(&"status".to_owned()));
+        assert!(values.is_empty());
+
+        assert_eq!(
+            attr("status").in_list(Vec::<String>::new()).not().or(attr("age").gt(18)).build().0,
+            "NOT (attribute_exists(#n0) AND attribute_not_exists(#n0)) OR #n1 > :v0"
+        );
+    }
+
+    #[test]
+    fn and_merges_names_and_values() {
+        let (expression, names, values) = attr("status").eq("active")
+            .and(attr("age").gt(18))
+            .and(attr("status").ne("banned"))
+            .build();
+
+        assert_eq!(expression, "#n0 = :v0 AND #n1 > :v1 AND #n0 <> :v2");
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.get("#n0"), Some(&"status".to_owned()));
+        assert_eq!(names.get("#n1"), Some(&"age".to_owned()));
+        assert_eq!(values.len(), 3);
+        assert_eq!(values.get(":v2"), Some(&AttributeValue::from("banned")));
+    }
+
+    #[test]
+    fn nested_combinators_are_parenthesised() {
+        let filter = attr("a").eq(1)
+            .or(attr("b").eq(2))
+            .and(attr("c").eq(3).or(attr("d").eq(4)).not());
+
+        assert_eq!(
+            filter.build().0,
+            "(#n0 = :v0 OR #n1 = :v1) AND NOT (#n2 = :v2 OR #n3 = :v3)"
+        );
+
+        let filter = attr("a").eq(1).and(attr("b").eq(2)).or(attr("c").exists().not());
+
+        assert_eq!(filter.build().0, "(#n0 = :v0 AND #n1 = :v1) OR NOT attribute_exists(#n2)");
+
+        let filter = attr("a").eq(1).and(attr("b").eq(2).and(attr("c").eq(3))).not();
+
+        assert_eq!(filter.build().0, "NOT (#n0 = :v0 AND #n1 = :v1 AND #n2 = :v2)");
+    }
+
+    #[test]
+    fn filter_alongside_key_condition() {
+        let (key_condition, names, values) = KeyConditionBuilder::new()
+            .partition_key("customer_id", "42")
+            .build()
+            .unwrap();
+
+        let (filter, names, values) = FilterExpressionBuilder::with_attributes(names, values)
+            .build(&attr("status").eq("shipped").and(attr("customer_id").exists()));
+
+        assert_eq!(key_condition, "#n0 = :v0");
+        assert_eq!(filter, "#n1 = :v1 AND attribute_exists(#n0)");
+        assert_eq!(names.len(), 2);
+        assert_eq!(values.get(":v0"), Some(&AttributeValue::from("42")));
+        assert_eq!(values.get(":v1"), Some(&AttributeValue::from("shipped")));
+    }
+}
@@ -0,0 +1,143 @@
+//! Managing global tables, which replicate a table to other regions.
+//!
+//! These are global tables of version 2017.11.29, whose replicas are created as empty tables of
+//! the same name in each region before being grouped together.
+
+use credential::ProvideAwsCredentials;
+use error::{AwsError, AwsResult};
+use region::Region;
+
+use super::{CreateGlobalTableInput, CreateReplicaAction, DeleteReplicaAction, DescribeGlobalTableInput};
+use super::{DynamoDbClient, GlobalTableDescription, Replica, ReplicaUpdate, UpdateGlobalTableInput};
+
+/// The status of a replica which is being created.
+pub const CREATING: &'static str = "CREATING";
+
+/// The status of a replica which couldn't be created.
+pub const CREATION_FAILED: &'static str = "CREATION_FAILED";
+
+/// The status of a replica whose settings are being changed.
+pub const UPDATING: &'static str = "UPDATING";
+
+/// The status of a replica which is being deleted.
+pub const DELETING: &'static str = "DELETING";
+
+/// The status of a replica which is ready to use.
+pub const ACTIVE: &'static str = "ACTIVE";
+
+impl Replica {
+    /// A replica in `region`.
+    pub fn new(region: Region) -> Replica {
+        Replica { region_name: Some(region.to_string()) }
+    }
+}
+
+/// Adds a replica to a global table, or removes one from it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GlobalTableReplicaUpdate {
+    Create(Replica),
+    Delete(Replica),
+}
+
+impl From<GlobalTableReplicaUpdate> for ReplicaUpdate {
+    fn from(update: GlobalTableReplicaUpdate) -> ReplicaUpdate {
+        match update {
+            GlobalTableReplicaUpdate::Create(replica) => ReplicaUpdate {
+                create: Some(CreateReplicaAction { region_name: replica.region_name.unwrap_or_else(String::new) }),
+                delete: None,
+            },
+            GlobalTableReplicaUpdate::Delete(replica) => ReplicaUpdate {
+                create: None,
+                delete: Some(DeleteReplicaAction { region_name: replica.region_name.unwrap_or_else(String::new) }),
+            },
+        }
+    }
+}
+
+/// Returns the description of a response that should always include one.
+fn expect_description(description: Option<GlobalTableDescription>) -> AwsResult<GlobalTableDescription> {
+    description.ok_or_else(|| AwsError::new("Response did not include the global table description"))
+}
+
+impl GlobalTableDescription {
+    /// The regions whose replicas have the status `replica_status`, such as `CREATING`.
+    pub fn regions_with_status(&self, replica_status: &str) -> Vec<&str> {
+        self.replication_group.as_ref().map_or(Vec::new(), |replicas| {
+            replicas.iter()
+                .filter(|replica| replica.replica_status.as_ref().map(|status| &status[..]) == Some(replica_status))
+                .filter_map(|replica| replica.region_name.as_ref().map(|region_name| &region_name[..]))
+                .collect()
+        })
+    }
+}
+
+impl<P> DynamoDbClient<P> where P: ProvideAwsCredentials {
+    /// Groups the tables named `global_table_name` in each region of `replication_group` into a
+    /// global table. The tables must already exist, be empty and have streams enabled.
+    pub fn add_global_table(&mut self, global_table_name: &str, replication_group: Vec<Replica>)
+        -> AwsResult<GlobalTableDescription> {
+        let input = CreateGlobalTableInput {
+            global_table_name: global_table_name.to_owned(),
+            replication_group: replication_group,
+        };
+
+        expect_description(try!(self.create_global_table(&input)).global_table_description)
+    }
+
+    /// Adds replicas to a global table and removes them from it.
+    pub fn change_global_table(&mut self, global_table_name: &str, replica_updates: Vec<GlobalTableReplicaUpdate>)
+        -> AwsResult<GlobalTableDescription> {
+        let input = UpdateGlobalTableInput {
+            global_table_name: global_table_name.to_owned(),
+            replica_updates: replica_updates.into_iter().map(ReplicaUpdate::from).collect(),
+        };
+
+        expect_description(try!(self.update_global_table(&input)).global_table_description)
+    }
+
+    /// Describes a global table, including the status and global secondary indexes of each of
+    /// its replicas.
+    pub fn global_table(&mut self, global_table_name: &str) -> AwsResult<GlobalTableDescription> {
+        let input = DescribeGlobalTableInput { global_table_name: global_table_name.to_owned() };
+
+        expect_description(try!(self.describe_global_table(&input)).global_table_description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use region::Region;
+    use super::super::{DescribeGlobalTableOutput, Replica, ReplicaUpdate};
+    use super::{ACTIVE, CREATING, GlobalTableReplicaUpdate};
+
+    #[test]
+    fn parse_global_table_with_a_replica_being_created() {
+        let mut body = String::new();
+        File::open("tests/sample-data/dynamodb_describe_global_table.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let description = serde_json::from_str::<DescribeGlobalTableOutput>(&body).unwrap()
+            .global_table_description.unwrap();
+
+        assert_eq!(description.global_table_name, Some("orders".to_owned()));
+        assert_eq!(description.global_table_status, Some(ACTIVE.to_owned()));
+        assert_eq!(description.replication_group.as_ref().unwrap().len(), 3);
+        assert_eq!(description.regions_with_status(ACTIVE), vec!["us-east-1", "eu-west-1"]);
+        assert_eq!(description.regions_with_status(CREATING), vec!["ap-southeast-2"]);
+
+        let indexes = description.replication_group.as_ref().unwrap()[0].global_secondary_indexes.as_ref().unwrap();
+        assert_eq!(indexes[0].index_name, Some("by-customer".to_owned()));
+    }
+
+    #[test]
+    fn replica_updates_set_one_action() {
+        let update = ReplicaUpdate::from(GlobalTableReplicaUpdate::Delete(Replica::new(Region::EuWest1)));
+
+        assert_eq!(update.create, None);
+        assert_eq!(update.delete.unwrap().region_name, "eu-west-1");
+    }
+}
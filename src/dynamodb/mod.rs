@@ -0,0 +1,12 @@
+//! Amazon DynamoDB
+
+pub use self::filter::{AttributeSize, FilterAttribute, FilterExpression, FilterExpressionBuilder, attr};
+pub use self::global_table::{ACTIVE, CREATING, CREATION_FAILED, DELETING, GlobalTableReplicaUpdate, UPDATING};
+pub use self::query::KeyConditionBuilder;
+
+mod backup;
+mod filter;
+mod global_table;
+mod query;
+
+include!(concat!(env!("OUT_DIR"), "/dynamodb.rs"));
@@ -0,0 +1,293 @@
+//! Building `KeyConditionExpression`s for `Query`.
+
+use error::{AwsError, AwsResult};
+
+use super::{AttributeValue, ExpressionAttributeNameMap, ExpressionAttributeValueMap};
+
+impl<'a> From<&'a str> for AttributeValue {
+    fn from(value: &'a str) -> AttributeValue {
+        AttributeValue::from(value.to_owned())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> AttributeValue {
+        AttributeValue {
+            s: Some(value),
+            ..AttributeValue::default()
+        }
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> AttributeValue {
+        AttributeValue::from(value as i64)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> AttributeValue {
+        AttributeValue {
+            n: Some(value.to_string()),
+            ..AttributeValue::default()
+        }
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> AttributeValue {
+        AttributeValue {
+            n: Some(value.to_string()),
+            ..AttributeValue::default()
+        }
+    }
+}
+
+impl From<Vec<u8>> for AttributeValue {
+    fn from(value: Vec<u8>) -> AttributeValue {
+        AttributeValue {
+            b: Some(value),
+            ..AttributeValue::default()
+        }
+    }
+}
+
+/// A condition on the sort key of a `Query`.
+#[derive(Debug)]
+enum SortKeyCondition {
+    Equal(AttributeValue),
+    LessThan(AttributeValue),
+    LessThanOrEqual(AttributeValue),
+    GreaterThan(AttributeValue),
+    GreaterThanOrEqual(AttributeValue),
+    BeginsWith(AttributeValue),
+    Between(AttributeValue, AttributeValue),
+}
+
+/// Builds a `KeyConditionExpression` along with the attribute names and values it refers to.
+///
+/// Every attribute name is aliased through `ExpressionAttributeNames`, so keys whose names
+/// are reserved words, such as `status`, need no special treatment, and every value goes
+/// through `ExpressionAttributeValues`, so user input never ends up in the expression itself.
+///
+/// ```ignore
+/// let (expression, names, values) = KeyConditionBuilder::new()
+///     .partition_key("customer_id", "42")
+///     .sort_key_begins_with("order_date", "2016-05")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct KeyConditionBuilder {
+    partition_key: Option<(String, AttributeValue)>,
+    sort_key: Option<(String, SortKeyCondition)>,
+}
+
+impl KeyConditionBuilder {
+    pub fn new() -> KeyConditionBuilder {
+        KeyConditionBuilder::default()
+    }
+
+    /// Matches items whose partition key `name` equals `value`.
+    pub fn partition_key<V>(mut self, name: &str, value: V) -> Self
+    where V: Into<AttributeValue> {
+        self.partition_key = Some((name.to_owned(), value.into()));
+        self
+    }
+
+    /// Matches items whose sort key `name` equals `value`.
+    pub fn sort_key_equal<V>(self, name: &str, value: V) -> Self where V: Into<AttributeValue> {
+        self.sort_key(name, SortKeyCondition::Equal(value.into()))
+    }
+
+    /// Matches items whose sort key `name` is less than `value`.
+    pub fn sort_key_less_than<V>(self, name: &str, value: V) -> Self
+    where V: Into<AttributeValue> {
+        self.sort_key(name, SortKeyCondition::LessThan(value.into()))
+    }
+
+    /// Matches items whose sort key `name` is less than or equal to `value`.
+    pub fn sort_key_less_than_or_equal<V>(self, name: &str, value: V) -> Self
+    where V: Into<AttributeValue> {
+        self.sort_key(name, SortKeyCondition::LessThanOrEqual(value.into()))
+    }
+
+    /// Matches items whose sort key `name` is greater than `value`.
+    pub fn sort_key_greater_than<V>(self, name: &str, value: V) -> Self
+    where V: Into<AttributeValue> {
+        self.sort_key(name, SortKeyCondition::GreaterThan(value.into()))
+    }
+
+    /// Matches items whose sort key `name` is greater than or equal to `value`.
+    pub fn sort_key_greater_than_or_equal<V>(self, name: &str, value: V) -> Self
+    where V: Into<AttributeValue> {
+        self.sort_key(name, SortKeyCondition::GreaterThanOrEqual(value.into()))
+    }
+
+    /// Matches items whose sort key `name` starts with `prefix`.
+    pub fn sort_key_begins_with<V>(self, name: &str, prefix: V) -> Self
+    where V: Into<AttributeValue> {
+        self.sort_key(name, SortKeyCondition::BeginsWith(prefix.into()))
+    }
+
+    /// Matches items whose sort key `name` is between `low` and `high`, inclusive.
+    pub fn sort_key_between<V>(self, name: &str, low: V, high: V) -> Self
+    where V: Into<AttributeValue> {
+        self.sort_key(name, SortKeyCondition::Between(low.into(), high.into()))
+    }
+
+    fn sort_key(mut self, name: &str, condition: SortKeyCondition) -> Self {
+        self.sort_key = Some((name.to_owned(), condition));
+        self
+    }
+
+    /// Returns the expression, `ExpressionAttributeNames` and `ExpressionAttributeValues`.
+    pub fn build(self)
+        -> AwsResult<(String, ExpressionAttributeNameMap, ExpressionAttributeValueMap)> {
+        let mut names = ExpressionAttributeNameMap::new();
+        let mut values = ExpressionAttributeValueMap::new();
+
+        let (partition_key_name, partition_key_value) = match self.partition_key {
+            Some(partition_key) => partition_key,
+            None => return Err(AwsError::new("A key condition requires a partition key")),
+        };
+
+        let partition_key_name = alias_name(&mut names, partition_key_name);
+        let mut expression = comparison(&partition_key_name, "=", &mut values, partition_key_value);
+
+        if let Some((name, condition)) = self.sort_key {
+            let name = alias_name(&mut names, name);
+
+            let values = &mut values;
+            let condition = match condition {
+                SortKeyCondition::Equal(value) => comparison(&name, "=", values, value),
+                SortKeyCondition::LessThan(value) => comparison(&name, "<", values, value),
+                SortKeyCondition::LessThanOrEqual(value) => comparison(&name, "<=", values, value),
+                SortKeyCondition::GreaterThan(value) => comparison(&name, ">", values, value),
+                SortKeyCondition::GreaterThanOrEqual(value) => comparison(&name, ">=", values, value),
+                SortKeyCondition::BeginsWith(value) => {
+                    format!("begins_with({}, {})", name, alias_value(values, value))
+                }
+                SortKeyCondition::Between(low, high) => {
+                    let low = alias_value(values, low);
+                    let high = alias_value(values, high);
+
+                    format!("{} BETWEEN {} AND {}", name, low, high)
+                }
+            };
+
+            expression = format!("{} AND {}", expression, condition);
+        }
+
+        Ok((expression, names, values))
+    }
+}
+
+/// Formats `name <operator> value`, aliasing `value`.
+fn comparison(name: &str, operator: &str, values: &mut ExpressionAttributeValueMap,
+    value: AttributeValue) -> String {
+    format!("{} {} {}", name, operator, alias_value(values, value))
+}
+
+/// Returns the placeholder for `name`, adding it to `names` if it isn't there already.
+pub fn alias_name(names: &mut ExpressionAttributeNameMap, name: String) -> String {
+    if let Some((placeholder, _)) = names.iter().find(|&(_, existing)| *existing == name) {
+        return placeholder.clone();
+    }
+
+    let placeholder = unused_placeholder("#n", names.len(), |p| names.contains_key(p));
+    names.insert(placeholder.clone(), name);
+    placeholder
+}
+
+/// Adds `value` to `values` under a new placeholder, which is returned.
+pub fn alias_value(values: &mut ExpressionAttributeValueMap, value: AttributeValue) -> String {
+    let placeholder = unused_placeholder(":v", values.len(), |p| values.contains_key(p));
+    values.insert(placeholder.clone(), value);
+    placeholder
+}
+
+/// Numbers placeholders from `start` so that maps passed in by the caller are never clobbered.
+fn unused_placeholder<F>(prefix: &str, start: usize, is_used: F) -> String
+where F: Fn(&str) -> bool {
+    (start..).map(|i| format!("{}{}", prefix, i)).find(|p| !is_used(p)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::AttributeValue;
+    use super::KeyConditionBuilder;
+
+    fn build(builder: KeyConditionBuilder) -> String {
+        builder.build().unwrap().0
+    }
+
+    #[test]
+    fn partition_key_only() {
+        let (expression, names, values) = KeyConditionBuilder::new()
+            .partition_key("customer_id", "42")
+            .build()
+            .unwrap();
+
+        assert_eq!(expression, "#n0 = :v0");
+        assert_eq!(names.get("#n0"), Some(&"customer_id".to_owned()));
+        assert_eq!(values.get(":v0"), Some(&AttributeValue::from("42")));
+    }
+
+    #[test]
+    fn sort_key_conditions() {
+        let builder = || KeyConditionBuilder::new().partition_key("pk", "user#1");
+
+        assert_eq!(build(builder().sort_key_equal("sk", 1)), "#n0 = :v0 AND #n1 = :v1");
+        assert_eq!(build(builder().sort_key_less_than("sk", 1)), "#n0 = :v0 AND #n1 < :v1");
+        assert_eq!(
+            build(builder().sort_key_less_than_or_equal("sk", 1)),
+            "#n0 = :v0 AND #n1 <= :v1"
+        );
+        assert_eq!(build(builder().sort_key_greater_than("sk", 1)), "#n0 = :v0 AND #n1 > :v1");
+        assert_eq!(
+            build(builder().sort_key_greater_than_or_equal("sk", 1)),
+            "#n0 = :v0 AND #n1 >= :v1"
+        );
+        assert_eq!(
+            build(builder().sort_key_begins_with("sk", "order#")),
+            "#n0 = :v0 AND begins_with(#n1, :v1)"
+        );
+        assert_eq!(
+            build(builder().sort_key_between("sk", 10, 20)),
+            "#n0 = :v0 AND #n1 BETWEEN :v1 AND :v2"
+        );
+    }
+
+    #[test]
+    fn between_values_are_in_order() {
+        let (_, _, values) = KeyConditionBuilder::new()
+            .partition_key("pk", "user#1")
+            .sort_key_between("created_at", 10, 20)
+            .build()
+            .unwrap();
+
+        assert_eq!(values.get(":v1").unwrap().n, Some("10".to_owned()));
+        assert_eq!(values.get(":v2").unwrap().n, Some("20".to_owned()));
+    }
+
+    #[test]
+    fn reserved_words_are_aliased() {
+        let (expression, names, values) = KeyConditionBuilder::new()
+            .partition_key("status", "active")
+            .sort_key_begins_with("name", "a) OR (1 = 1")
+            .build()
+            .unwrap();
+
+        assert!(!expression.contains("status"));
+        assert!(!expression.contains("OR"));
+        assert_eq!(names.get("#n0"), Some(&"status".to_owned()));
+        assert_eq!(names.get("#n1"), Some(&"name".to_owned()));
+        assert_eq!(values.get(":v1").unwrap().s, Some("a) OR (1 = 1".to_owned()));
+    }
+
+    #[test]
+    fn partition_key_is_required() {
+        assert!(KeyConditionBuilder::new().sort_key_equal("sk", 1).build().is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! Amazon DynamoDB Streams
+//!
+//! Streams have their own endpoint, `streams.dynamodb.<region>.amazonaws.com`, so they're read
+//! with a separate client from the tables they belong to.
+
+include!(concat!(env!("OUT_DIR"), "/dynamodbstreams.rs"));
+
+use pagination::read_all_pages;
+
+/// Where in a shard a shard iterator starts reading.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShardIteratorPosition {
+    /// The oldest record still in the shard.
+    TrimHorizon,
+    /// Just after the newest record, so only records written from now on are read.
+    Latest,
+    /// The record with this sequence number.
+    AtSequenceNumber(String),
+    /// The record after the one with this sequence number.
+    AfterSequenceNumber(String),
+}
+
+impl GetShardIteratorInput {
+    pub fn new(stream_arn: &str, shard_id: &str, position: ShardIteratorPosition) -> GetShardIteratorInput {
+        let (shard_iterator_type, sequence_number) = match position {
+            ShardIteratorPosition::TrimHorizon => ("TRIM_HORIZON", None),
+            ShardIteratorPosition::Latest => ("LATEST", None),
+            ShardIteratorPosition::AtSequenceNumber(number) => ("AT_SEQUENCE_NUMBER", Some(number)),
+            ShardIteratorPosition::AfterSequenceNumber(number) => ("AFTER_SEQUENCE_NUMBER", Some(number)),
+        };
+
+        GetShardIteratorInput {
+            sequence_number: sequence_number,
+            shard_id: shard_id.to_owned(),
+            shard_iterator_type: shard_iterator_type.to_owned(),
+            stream_arn: stream_arn.to_owned(),
+        }
+    }
+}
+
+impl<P> DynamoDbStreamsClient<P> where P: ProvideAwsCredentials {
+    /// Lists every stream, or only those of `table_name`, following `LastEvaluatedStreamArn`
+    /// until all of them have been returned.
+    pub fn list_all_streams(&mut self, table_name: Option<String>) -> AwsResult<Vec<Stream>> {
+        let mut input = ListStreamsInput {
+            table_name: table_name,
+            ..ListStreamsInput::default()
+        };
+
+        read_all_pages(None, |exclusive_start_stream_arn| {
+            input.exclusive_start_stream_arn = exclusive_start_stream_arn;
+            let output = try!(self.list_streams(&input));
+
+            Ok((output.streams.unwrap_or_else(Vec::new), output.last_evaluated_stream_arn))
+        })
+    }
+
+    /// Returns an iterator for reading the shard `shard_id` from `position`, which is `None`
+    /// if the shard has been trimmed past it.
+    pub fn shard_iterator(&mut self, stream_arn: &str, shard_id: &str, position: ShardIteratorPosition)
+        -> AwsResult<Option<String>> {
+        let output = try!(self.get_shard_iterator(&GetShardIteratorInput::new(stream_arn, shard_id, position)));
+
+        Ok(output.shard_iterator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{GetRecordsOutput, GetShardIteratorInput, ShardIteratorPosition};
+
+    #[test]
+    fn parse_get_records_with_insert_and_remove() {
+        let mut body = String::new();
+        File::open("tests/sample-data/dynamodb_streams_get_records.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let output = serde_json::from_str::<GetRecordsOutput>(&body).unwrap();
+        let records = output.records.unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert!(output.next_shard_iterator.unwrap().starts_with("arn:aws:dynamodb:us-west-2"));
+
+        let insert = &records[0];
+        let stream_record = insert.dynamodb.as_ref().unwrap();
+        assert_eq!(insert.event_name, Some("INSERT".to_owned()));
+        assert_eq!(stream_record.sequence_number, Some("111".to_owned()));
+        assert_eq!(stream_record.size_bytes, Some(26));
+        assert_eq!(stream_record.stream_view_type, Some("NEW_AND_OLD_IMAGES".to_owned()));
+        assert_eq!(stream_record.keys.as_ref().unwrap()["Id"].n, Some("101".to_owned()));
+        assert_eq!(stream_record.new_image.as_ref().unwrap()["Message"].s, Some("New item!".to_owned()));
+        assert!(stream_record.old_image.is_none());
+
+        let remove = &records[1];
+        let stream_record = remove.dynamodb.as_ref().unwrap();
+        assert_eq!(remove.event_name, Some("REMOVE".to_owned()));
+        assert_eq!(stream_record.sequence_number, Some("222".to_owned()));
+        assert!(stream_record.new_image.is_none());
+        assert_eq!(
+            stream_record.old_image.as_ref().unwrap()["Message"].s,
+            Some("This item has changed".to_owned())
+        );
+    }
+
+    #[test]
+    fn shard_iterator_positions() {
+        let stream_arn = "arn:aws:dynamodb:us-west-2:111122223333:table/Forum/stream/2015-05-20T20:51:10.252";
+        let shard_id = "shardId-00000001414576573621-f55eea83";
+
+        let input = GetShardIteratorInput::new(stream_arn, shard_id, ShardIteratorPosition::TrimHorizon);
+        assert_eq!(input.shard_iterator_type, "TRIM_HORIZON");
+        assert_eq!(input.sequence_number, None);
+
+        let input = GetShardIteratorInput::new(
+            stream_arn,
+            shard_id,
+            ShardIteratorPosition::AfterSequenceNumber("222".to_owned()),
+        );
+        assert_eq!(input.shard_iterator_type, "AFTER_SEQUENCE_NUMBER");
+        assert_eq!(input.sequence_number, Some("222".to_owned()));
+        assert_eq!(input.shard_id, shard_id);
+    }
+}
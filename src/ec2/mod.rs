@@ -0,0 +1,150 @@
+//! Amazon Elastic Compute Cloud
+
+include!(concat!(env!("OUT_DIR"), "/ec2.rs"));
+
+use pagination::read_all_pages;
+
+/// The type of VPC endpoints which reach a service through network interfaces in the VPC's
+/// subnets, as PrivateLink does.
+pub const INTERFACE: &'static str = "Interface";
+
+/// The type of VPC endpoints which reach S3 or DynamoDB through routes in route tables.
+pub const GATEWAY: &'static str = "Gateway";
+
+/// The type of VPC endpoints which send traffic to a fleet of virtual appliances behind a
+/// Gateway Load Balancer.
+pub const GATEWAY_LOAD_BALANCER: &'static str = "GatewayLoadBalancer";
+
+/// The state of a VPC endpoint which is ready to use.
+pub const AVAILABLE: &'static str = "available";
+
+/// EC2 treats a missing list the same as an empty one, so empty lists are left out entirely.
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+impl<P> Ec2Client<P> where P: ProvideAwsCredentials {
+    /// Calls `DescribeVpcEndpoints` until every page has been read, and returns all of the VPC
+    /// endpoints, or only those in `vpc_endpoint_ids` if it isn't empty, which match `filters`.
+    pub fn describe_all_vpc_endpoints(&mut self, vpc_endpoint_ids: Vec<String>, filters: Vec<Filter>)
+        -> Result<Vec<VpcEndpoint>, AwsError> {
+        let mut input = DescribeVpcEndpointsRequest {
+            filters: non_empty(filters),
+            vpc_endpoint_ids: non_empty(vpc_endpoint_ids),
+            ..DescribeVpcEndpointsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.describe_vpc_endpoints(&input));
+
+            Ok((output.vpc_endpoints.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Creates a VPC endpoint of type `Interface`, `Gateway` or `GatewayLoadBalancer` for the
+    /// service `service_name`, such as `com.amazonaws.eu-west-1.s3`.
+    ///
+    /// Interface endpoints are placed in `subnet_ids` behind `security_group_ids`, and only
+    /// answer for the service's public DNS name if `private_dns_enabled` is set. Without a
+    /// `policy_document`, the endpoint allows full access to the service.
+    pub fn create_new_vpc_endpoint(
+        &mut self,
+        vpc_id: &str,
+        service_name: &str,
+        vpc_endpoint_type: &str,
+        subnet_ids: Vec<String>,
+        security_group_ids: Vec<String>,
+        policy_document: Option<String>,
+        private_dns_enabled: bool,
+    ) -> Result<CreateVpcEndpointResult, AwsError> {
+        let input = CreateVpcEndpointRequest {
+            policy_document: policy_document,
+            private_dns_enabled: Some(private_dns_enabled),
+            security_group_ids: non_empty(security_group_ids),
+            service_name: service_name.to_owned(),
+            subnet_ids: non_empty(subnet_ids),
+            vpc_endpoint_type: Some(vpc_endpoint_type.to_owned()),
+            vpc_id: vpc_id.to_owned(),
+            ..CreateVpcEndpointRequest::default()
+        };
+
+        self.create_vpc_endpoint(&input)
+    }
+
+    /// Moves an interface VPC endpoint into or out of subnets, and adds or removes the security
+    /// groups its network interfaces belong to. Returns whether EC2 accepted the change.
+    pub fn update_vpc_endpoint(
+        &mut self,
+        vpc_endpoint_id: &str,
+        add_subnet_ids: Vec<String>,
+        remove_subnet_ids: Vec<String>,
+        add_security_group_ids: Vec<String>,
+        remove_security_group_ids: Vec<String>,
+    ) -> Result<bool, AwsError> {
+        let input = ModifyVpcEndpointRequest {
+            add_security_group_ids: non_empty(add_security_group_ids),
+            add_subnet_ids: non_empty(add_subnet_ids),
+            remove_security_group_ids: non_empty(remove_security_group_ids),
+            remove_subnet_ids: non_empty(remove_subnet_ids),
+            vpc_endpoint_id: vpc_endpoint_id.to_owned(),
+            ..ModifyVpcEndpointRequest::default()
+        };
+
+        Ok(try!(self.modify_vpc_endpoint(&input)).aws_return.unwrap_or(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use xml::reader::*;
+
+    use xmlutil::*;
+
+    use super::{AVAILABLE, DescribeVpcEndpointsResultDeserializer, GATEWAY, INTERFACE};
+
+    #[test]
+    fn describe_interface_and_gateway_endpoints() {
+        let file = File::open("tests/sample-data/ec2_describe_vpc_endpoints.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser = EventReader::with_config(file, ParserConfig::new().trim_whitespace(true));
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = DescribeVpcEndpointsResultDeserializer::deserialize("DescribeVpcEndpointsResponse", &mut reader)
+            .unwrap();
+
+        assert_eq!(result.next_token, None);
+
+        let vpc_endpoints = result.vpc_endpoints.unwrap();
+        assert_eq!(vpc_endpoints.len(), 2);
+
+        let sqs = &vpc_endpoints[0];
+        assert_eq!(sqs.vpc_endpoint_type, Some(INTERFACE.to_owned()));
+        assert_eq!(sqs.state, Some(AVAILABLE.to_owned()));
+        assert_eq!(sqs.private_dns_enabled, Some(true));
+        assert_eq!(sqs.subnet_ids.as_ref().unwrap().len(), 2);
+        assert_eq!(sqs.network_interface_ids.as_ref().unwrap(), &vec![
+            "eni-2ec2b084".to_owned(),
+            "eni-1b4a65cf".to_owned(),
+        ]);
+        assert_eq!(sqs.groups.as_ref().unwrap()[0].group_id, Some("sg-54e8bf31".to_owned()));
+        assert_eq!(sqs.dns_entries.as_ref().unwrap().len(), 2);
+        assert!(sqs.route_table_ids.as_ref().unwrap().is_empty());
+
+        // Gateway endpoints are routed to rather than placed in subnets, and carry a policy.
+        let s3 = &vpc_endpoints[1];
+        assert_eq!(s3.vpc_endpoint_type, Some(GATEWAY.to_owned()));
+        assert_eq!(s3.service_name, Some("com.amazonaws.eu-west-1.s3".to_owned()));
+        assert_eq!(s3.route_table_ids.as_ref().unwrap(), &vec!["rtb-ea3c6b8f".to_owned()]);
+        assert!(s3.subnet_ids.as_ref().unwrap().is_empty());
+        assert!(s3.policy_document.as_ref().unwrap().contains("\"Effect\":\"Allow\""));
+    }
+}
@@ -0,0 +1,130 @@
+//! Amazon ElastiCache
+
+include!(concat!(env!("OUT_DIR"), "/elasticache.rs"));
+
+use pagination::read_all_pages;
+
+/// The engine of Redis cache clusters.
+pub const REDIS: &'static str = "redis";
+
+/// The engine of Memcached cache clusters.
+pub const MEMCACHED: &'static str = "memcached";
+
+/// The status of a cache cluster which is ready to use.
+pub const AVAILABLE: &'static str = "available";
+
+/// Returns the cache cluster of a response that should always include one.
+fn expect_cache_cluster(cache_cluster: Option<CacheCluster>, operation: &str) -> Result<CacheCluster, AwsError> {
+    cache_cluster.ok_or_else(|| AwsError::new(format!("{} response did not include a cache cluster", operation)))
+}
+
+impl<P> ElastiCacheClient<P> where P: ProvideAwsCredentials {
+    /// Calls `DescribeCacheClusters` until every page has been read, and returns all of the
+    /// cache clusters, or only `cache_cluster_id` if given. Their nodes and endpoints are only
+    /// included if `show_cache_node_info` is set.
+    pub fn describe_all_cache_clusters(&mut self, cache_cluster_id: Option<String>, show_cache_node_info: bool)
+        -> Result<Vec<CacheCluster>, AwsError> {
+        let mut input = DescribeCacheClustersMessage {
+            cache_cluster_id: cache_cluster_id,
+            show_cache_node_info: Some(show_cache_node_info),
+            ..DescribeCacheClustersMessage::default()
+        };
+
+        read_all_pages(None, |marker| {
+            input.marker = marker;
+            let output = try!(self.describe_cache_clusters(&input));
+
+            Ok((output.cache_clusters.unwrap_or_else(Vec::new), output.marker))
+        })
+    }
+
+    /// Creates a cache cluster of `num_cache_nodes` nodes running the engine `redis` or
+    /// `memcached`, and returns it as it was described on creation, which is usually in the
+    /// `creating` state.
+    pub fn create_new_cache_cluster(&mut self, cache_cluster_id: &str, cache_node_type: &str, engine: &str,
+        num_cache_nodes: i32, cache_parameter_group_name: Option<String>) -> Result<CacheCluster, AwsError> {
+        let input = CreateCacheClusterMessage {
+            cache_cluster_id: cache_cluster_id.to_owned(),
+            cache_node_type: Some(cache_node_type.to_owned()),
+            cache_parameter_group_name: cache_parameter_group_name,
+            engine: Some(engine.to_owned()),
+            num_cache_nodes: Some(num_cache_nodes),
+            ..CreateCacheClusterMessage::default()
+        };
+
+        expect_cache_cluster(try!(self.create_cache_cluster(&input)).cache_cluster, "CreateCacheCluster")
+    }
+
+    /// Modifies a cache cluster, turning automatic minor engine upgrades on or off if
+    /// `auto_minor_version_upgrade` is given. Unless `apply_immediately` is set, changes which
+    /// interrupt the cluster wait for its next maintenance window.
+    pub fn update_cache_cluster(&mut self, cache_cluster_id: &str, apply_immediately: bool,
+        auto_minor_version_upgrade: Option<bool>) -> Result<CacheCluster, AwsError> {
+        let input = ModifyCacheClusterMessage {
+            apply_immediately: Some(apply_immediately),
+            auto_minor_version_upgrade: auto_minor_version_upgrade,
+            cache_cluster_id: cache_cluster_id.to_owned(),
+            ..ModifyCacheClusterMessage::default()
+        };
+
+        expect_cache_cluster(try!(self.modify_cache_cluster(&input)).cache_cluster, "ModifyCacheCluster")
+    }
+
+    /// Deletes a cache cluster, which is `deleting` until it's gone. Redis clusters are
+    /// snapshotted as `final_snapshot_identifier` first if given.
+    pub fn remove_cache_cluster(&mut self, cache_cluster_id: &str, final_snapshot_identifier: Option<String>)
+        -> Result<CacheCluster, AwsError> {
+        let input = DeleteCacheClusterMessage {
+            cache_cluster_id: cache_cluster_id.to_owned(),
+            final_snapshot_identifier: final_snapshot_identifier,
+        };
+
+        expect_cache_cluster(try!(self.delete_cache_cluster(&input)).cache_cluster, "DeleteCacheCluster")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use xml::reader::*;
+
+    use xmlutil::*;
+
+    use super::{AVAILABLE, CacheClusterMessageDeserializer, REDIS};
+
+    #[test]
+    fn describe_available_redis_cluster() {
+        let file = File::open("tests/sample-data/elasticache_describe_cache_clusters.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        reader.next(); // DescribeCacheClustersResponse
+        let result = CacheClusterMessageDeserializer::deserialize("DescribeCacheClustersResult", &mut reader)
+            .unwrap();
+
+        assert_eq!(result.marker, None);
+
+        let cache_clusters = result.cache_clusters.unwrap();
+        assert_eq!(cache_clusters.len(), 1);
+
+        let sessions = &cache_clusters[0];
+        assert_eq!(sessions.cache_cluster_id, Some("sessions".to_owned()));
+        assert_eq!(sessions.cache_cluster_status, Some(AVAILABLE.to_owned()));
+        assert_eq!(sessions.engine, Some(REDIS.to_owned()));
+        assert_eq!(sessions.num_cache_nodes, Some(1));
+
+        // A standalone Redis cluster, without cluster mode, has no configuration endpoint or
+        // replication group, and is reached through its single node.
+        assert!(sessions.configuration_endpoint.is_none());
+        assert!(sessions.replication_group_id.is_none());
+
+        let cache_nodes = sessions.cache_nodes.as_ref().unwrap();
+        let endpoint = cache_nodes[0].endpoint.as_ref().unwrap();
+        assert_eq!(endpoint.address, Some("sessions.x7hfqe.0001.euw1.cache.amazonaws.com".to_owned()));
+        assert_eq!(endpoint.port, Some(6379));
+    }
+}
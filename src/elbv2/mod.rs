@@ -0,0 +1,82 @@
+//! Elastic Load Balancing v2
+
+include!(concat!(env!("OUT_DIR"), "/elbv2.rs"));
+
+use pagination::read_all_pages;
+
+impl<P> ElbV2Client<P> where P: ProvideAwsCredentials {
+    /// Calls `DescribeLoadBalancers` until every page has been read, and returns all of the
+    /// load balancers in the region.
+    pub fn describe_all_load_balancers(&mut self) -> Result<Vec<LoadBalancer>, AwsError> {
+        let mut input = DescribeLoadBalancersInput::default();
+
+        read_all_pages(None, |marker| {
+            input.marker = marker;
+            let output = try!(self.describe_load_balancers(&input));
+
+            Ok((output.load_balancers.unwrap_or_else(Vec::new), output.next_marker))
+        })
+    }
+
+    /// Calls `DescribeTargetGroups` until every page has been read, and returns all of the
+    /// target groups in the region, or only those of `load_balancer_arn` if given.
+    pub fn describe_all_target_groups(&mut self, load_balancer_arn: Option<String>)
+        -> Result<Vec<TargetGroup>, AwsError> {
+        let mut input = DescribeTargetGroupsInput {
+            load_balancer_arn: load_balancer_arn,
+            ..DescribeTargetGroupsInput::default()
+        };
+
+        read_all_pages(None, |marker| {
+            input.marker = marker;
+            let output = try!(self.describe_target_groups(&input));
+
+            Ok((output.target_groups.unwrap_or_else(Vec::new), output.next_marker))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use xml::reader::*;
+
+    use xmlutil::*;
+
+    use super::DescribeLoadBalancersOutputDeserializer;
+
+    #[test]
+    fn describe_load_balancers_with_two_albs() {
+        let file = File::open("tests/sample-data/elbv2_describe_load_balancers.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        reader.next(); // DescribeLoadBalancersResponse
+        let result = DescribeLoadBalancersOutputDeserializer::deserialize(
+            "DescribeLoadBalancersResult",
+            &mut reader,
+        );
+
+        let load_balancers = result.unwrap().load_balancers.unwrap();
+        assert_eq!(load_balancers.len(), 2);
+
+        let external = &load_balancers[0];
+        assert_eq!(external.load_balancer_name, Some("my-load-balancer".to_owned()));
+        assert_eq!(external.scheme, Some("internet-facing".to_owned()));
+        assert_eq!(external.state.as_ref().unwrap().code, Some("active".to_owned()));
+        assert_eq!(external.aws_type, Some("application".to_owned()));
+
+        let zones = external.availability_zones.as_ref().unwrap();
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[1].zone_name, Some("us-west-2b".to_owned()));
+
+        let internal = &load_balancers[1];
+        assert_eq!(internal.scheme, Some("internal".to_owned()));
+        assert_eq!(internal.state.as_ref().unwrap().code, Some("provisioning".to_owned()));
+        assert_eq!(internal.availability_zones.as_ref().unwrap().len(), 1);
+    }
+}
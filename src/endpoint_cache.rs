@@ -0,0 +1,77 @@
+//! Caching of endpoints found through endpoint discovery.
+//!
+//! Services such as Timestream only accept requests on endpoints returned by their
+//! `DescribeEndpoints` operation, each of which is valid for a limited time.
+
+use time::{Duration, Timespec};
+
+/// How long before its expiry an endpoint is refreshed, so that requests aren't sent to an
+/// endpoint which expires while they're in flight.
+const REFRESH_MARGIN_SECONDS: i64 = 60;
+
+/// The most recently discovered endpoint for a client, along with when it expires.
+#[derive(Debug, Default)]
+pub struct EndpointCache {
+    endpoint: Option<(String, Timespec)>,
+}
+
+impl EndpointCache {
+    pub fn new() -> EndpointCache {
+        EndpointCache::default()
+    }
+
+    /// Returns the cached address, unless there is none or it's due to be refreshed at `now`.
+    pub fn address(&self, now: Timespec) -> Option<&str> {
+        match self.endpoint {
+            Some((ref address, expires_at)) => {
+                if now + Duration::seconds(REFRESH_MARGIN_SECONDS) < expires_at {
+                    Some(address)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        }
+    }
+
+    /// Caches `address`, discovered at `now`, for `cache_period_in_minutes`.
+    pub fn update(&mut self, address: String, cache_period_in_minutes: i64, now: Timespec) {
+        self.endpoint = Some((address, now + Duration::minutes(cache_period_in_minutes)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Duration, Timespec};
+
+    use super::EndpointCache;
+
+    #[test]
+    fn empty_cache_has_no_address() {
+        assert_eq!(EndpointCache::new().address(Timespec::new(0, 0)), None);
+    }
+
+    #[test]
+    fn address_is_cached_until_shortly_before_expiry() {
+        let discovered_at = Timespec::new(1_500_000_000, 0);
+        let mut cache = EndpointCache::new();
+        cache.update("ingest-cell2.timestream.us-east-1.amazonaws.com".to_owned(), 1440, discovered_at);
+
+        assert_eq!(
+            cache.address(discovered_at + Duration::hours(23)),
+            Some("ingest-cell2.timestream.us-east-1.amazonaws.com")
+        );
+        assert_eq!(cache.address(discovered_at + Duration::minutes(1439)), None);
+        assert_eq!(cache.address(discovered_at + Duration::hours(25)), None);
+    }
+
+    #[test]
+    fn update_replaces_the_cached_address() {
+        let now = Timespec::new(1_500_000_000, 0);
+        let mut cache = EndpointCache::new();
+        cache.update("ingest-cell1.timestream.us-east-1.amazonaws.com".to_owned(), 10, now);
+        cache.update("ingest-cell2.timestream.us-east-1.amazonaws.com".to_owned(), 10, now);
+
+        assert_eq!(cache.address(now), Some("ingest-cell2.timestream.us-east-1.amazonaws.com"));
+    }
+}
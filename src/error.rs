@@ -36,6 +36,12 @@ pub fn parse_json_protocol_error(body: &str) -> AwsError {
     }
 }
 
+/// Returns the part of a response that should always be included, or an error naming it if
+/// the response left it out.
+pub fn expect<T>(value: Option<T>, description: &str) -> AwsResult<T> {
+    value.ok_or_else(|| AwsError::new(format!("Response did not include {}", description)))
+}
+
 impl AwsError {
     /// Create a new error with the given message.
 	pub fn new<S>(message: S) -> AwsError where S: Into<String> {
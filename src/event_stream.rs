@@ -0,0 +1,257 @@
+//! Decoding of the `application/vnd.amazon.eventstream` binary framing.
+//!
+//! Streaming APIs such as S3 Select and Bedrock's `InvokeModelWithResponseStream` send their
+//! responses as a sequence of messages, each made up of a prelude, a set of typed headers, a
+//! payload and a trailing CRC32 checksum.
+
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Read};
+use std::str;
+
+use error::{AwsError, AwsResult};
+
+const PRELUDE_LENGTH: usize = 12;
+const MESSAGE_CRC_LENGTH: usize = 4;
+
+/// The longest message the format allows, which is checked before the message is read so a
+/// corrupted length can't make the reader allocate gigabytes.
+const MAX_MESSAGE_LENGTH: usize = 16 * 1024 * 1024;
+
+/// A single message decoded from an event stream.
+#[derive(Debug, Default, PartialEq)]
+pub struct Message {
+    pub headers: BTreeMap<String, HeaderValue>,
+    pub payload: Vec<u8>,
+}
+
+impl Message {
+    /// Returns the value of the string header `name`, if the message has one.
+    pub fn header_str(&self, name: &str) -> Option<&str> {
+        match self.headers.get(name) {
+            Some(&HeaderValue::String(ref value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// The value of an event stream message header.
+#[derive(Debug, PartialEq)]
+pub enum HeaderValue {
+    Bool(bool),
+    Byte(i8),
+    Short(i16),
+    Integer(i32),
+    Long(i64),
+    Bytes(Vec<u8>),
+    String(String),
+    Timestamp(i64),
+    Uuid(Vec<u8>),
+}
+
+/// Reads the next message from `reader`, returning `None` once the stream has ended.
+pub fn read_message<R: Read>(reader: &mut R) -> AwsResult<Option<Message>> {
+    let mut prelude = [0u8; PRELUDE_LENGTH];
+    let mut read = 0;
+
+    // Only a stream which ends between messages has ended cleanly.
+    while read < PRELUDE_LENGTH {
+        match reader.read(&mut prelude[read..]) {
+            Ok(0) if read == 0 => return Ok(None),
+            Ok(0) => return Err(AwsError::new("Truncated event stream prelude")),
+            Ok(length) => read += length,
+            Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(AwsError::from(err)),
+        }
+    }
+
+    let total_length = read_u32(&prelude[0..4]) as usize;
+    let headers_length = read_u32(&prelude[4..8]) as usize;
+
+    if read_u32(&prelude[8..12]) != crc32(&prelude[0..8]) {
+        return Err(AwsError::new("Event stream prelude checksum mismatch"));
+    }
+
+    if total_length < PRELUDE_LENGTH + headers_length + MESSAGE_CRC_LENGTH || total_length > MAX_MESSAGE_LENGTH {
+        return Err(AwsError::new(format!("Invalid event stream message length: {}", total_length)));
+    }
+
+    let mut message = prelude.to_vec();
+    message.resize(total_length, 0);
+    try!(reader.read_exact(&mut message[PRELUDE_LENGTH..]));
+
+    let (checked, checksum) = message.split_at(total_length - MESSAGE_CRC_LENGTH);
+
+    if read_u32(checksum) != crc32(checked) {
+        return Err(AwsError::new("Event stream message checksum mismatch"));
+    }
+
+    let headers_end = PRELUDE_LENGTH + headers_length;
+
+    Ok(Some(Message {
+        headers: try!(parse_headers(&checked[PRELUDE_LENGTH..headers_end])),
+        payload: checked[headers_end..].to_vec(),
+    }))
+}
+
+fn parse_headers(mut bytes: &[u8]) -> AwsResult<BTreeMap<String, HeaderValue>> {
+    let mut headers = BTreeMap::new();
+
+    while !bytes.is_empty() {
+        let name_length = bytes[0] as usize;
+        let name = try!(utf8(try!(take(&mut bytes, 1, name_length)))).to_owned();
+        let value_type = try!(take(&mut bytes, 0, 1))[0];
+
+        let value = match value_type {
+            0 => HeaderValue::Bool(true),
+            1 => HeaderValue::Bool(false),
+            2 => HeaderValue::Byte(try!(take(&mut bytes, 0, 1))[0] as i8),
+            3 => HeaderValue::Short(read_u16(try!(take(&mut bytes, 0, 2))) as i16),
+            4 => HeaderValue::Integer(read_u32(try!(take(&mut bytes, 0, 4))) as i32),
+            5 => HeaderValue::Long(read_u64(try!(take(&mut bytes, 0, 8))) as i64),
+            6 | 7 => {
+                let length = read_u16(try!(take(&mut bytes, 0, 2))) as usize;
+                let value = try!(take(&mut bytes, 0, length));
+
+                if value_type == 6 {
+                    HeaderValue::Bytes(value.to_vec())
+                } else {
+                    HeaderValue::String(try!(utf8(value)).to_owned())
+                }
+            }
+            8 => HeaderValue::Timestamp(read_u64(try!(take(&mut bytes, 0, 8))) as i64),
+            9 => HeaderValue::Uuid(try!(take(&mut bytes, 0, 16)).to_vec()),
+            _ => return Err(AwsError::new(format!("Unknown event stream header type: {}", value_type))),
+        };
+
+        headers.insert(name, value);
+    }
+
+    Ok(headers)
+}
+
+/// Skips `skip` bytes, then splits the next `length` bytes off the front of `bytes`.
+fn take<'a>(bytes: &mut &'a [u8], skip: usize, length: usize) -> AwsResult<&'a [u8]> {
+    if bytes.len() < skip + length {
+        return Err(AwsError::new("Truncated event stream headers"));
+    }
+
+    let value = &bytes[skip..skip + length];
+    *bytes = &bytes[skip + length..];
+
+    Ok(value)
+}
+
+fn utf8(bytes: &[u8]) -> AwsResult<&str> {
+    str::from_utf8(bytes).map_err(|err| AwsError::new(format!("{}", err)))
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    (bytes[0] as u16) << 8 | bytes[1] as u16
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    bytes[..4].iter().fold(0, |value, &byte| value << 8 | byte as u32)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    bytes[..8].iter().fold(0, |value, &byte| value << 8 | byte as u64)
+}
+
+/// CRC32 as used by zlib and the event stream format (reflected polynomial `0xEDB88320`).
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFFu32;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::io::Cursor;
+
+    use super::{HeaderValue, crc32, read_message};
+
+    /// Frames `payload` as an event stream message with a single string header.
+    fn encode_message(name: &str, value: &str, payload: &[u8]) -> Vec<u8> {
+        let mut headers = vec![name.len() as u8];
+        headers.extend_from_slice(name.as_bytes());
+        headers.push(7);
+        headers.push((value.len() >> 8) as u8);
+        headers.push(value.len() as u8);
+        headers.extend_from_slice(value.as_bytes());
+
+        let total_length = 12 + headers.len() + payload.len() + 4;
+        let mut message = Vec::new();
+        message.extend_from_slice(&be_bytes(total_length as u32));
+        message.extend_from_slice(&be_bytes(headers.len() as u32));
+        let prelude_crc = crc32(&message);
+        message.extend_from_slice(&be_bytes(prelude_crc));
+        message.extend_from_slice(&headers);
+        message.extend_from_slice(payload);
+        let message_crc = crc32(&message);
+        message.extend_from_slice(&be_bytes(message_crc));
+
+        message
+    }
+
+    fn be_bytes(value: u32) -> [u8; 4] {
+        [(value >> 24) as u8, (value >> 16) as u8, (value >> 8) as u8, value as u8]
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn read_messages_until_end_of_stream() {
+        let mut bytes = encode_message(":event-type", "chunk", b"{\"a\":1}");
+        bytes.extend(encode_message(":event-type", "chunk", b"{\"b\":2}"));
+        let mut reader = Cursor::new(bytes);
+
+        let first = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(first.header_str(":event-type"), Some("chunk"));
+        assert_eq!(first.headers.get(":event-type"), Some(&HeaderValue::String("chunk".to_owned())));
+        assert_eq!(first.payload, b"{\"a\":1}".to_vec());
+
+        let second = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(second.payload, b"{\"b\":2}".to_vec());
+
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn truncated_prelude_is_an_error() {
+        let bytes = encode_message(":event-type", "chunk", b"{\"a\":1}");
+
+        assert!(read_message(&mut Cursor::new(&bytes[..5])).is_err());
+    }
+
+    #[test]
+    fn oversized_message_is_rejected() {
+        // A valid prelude claiming a 4 GiB message.
+        let mut bytes = vec![0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0];
+        let prelude_crc = crc32(&bytes);
+        bytes.extend_from_slice(&be_bytes(prelude_crc));
+
+        let error = read_message(&mut Cursor::new(bytes)).unwrap_err();
+        assert!(error.description().contains("Invalid event stream message length"));
+    }
+
+    #[test]
+    fn corrupted_message_is_rejected() {
+        let mut bytes = encode_message(":event-type", "chunk", b"{\"a\":1}");
+        let last = bytes.len() - 5;
+        bytes[last] ^= 0xFF;
+
+        assert!(read_message(&mut Cursor::new(bytes)).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+//! Amazon Kinesis Data Firehose
+
+include!(concat!(env!("OUT_DIR"), "/firehose.rs"));
+
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::thread;
+use std::time::Duration;
+
+use openssl::crypto::rand::rand_bytes;
+
+use error::AwsError;
+
+/// The most records a single `PutRecordBatch` call can deliver.
+const MAX_BATCH_RECORDS: usize = 500;
+
+/// The most data a single `PutRecordBatch` call can deliver, in bytes.
+const MAX_BATCH_BYTES: usize = 4 * 1024 * 1024;
+
+/// The longest `put_records_with_retry` waits before the first retry, in milliseconds. Each
+/// retry after it can wait twice as long as the last, up to `MAX_RETRY_DELAY_MS`.
+const BASE_RETRY_DELAY_MS: u64 = 100;
+
+const MAX_RETRY_DELAY_MS: u64 = 10_000;
+
+/// Where a delivery stream delivers its records.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeliveryStreamDestination {
+    S3(ExtendedS3DestinationConfiguration),
+    Redshift(RedshiftDestinationConfiguration),
+    Elasticsearch(ElasticsearchDestinationConfiguration),
+    Splunk(SplunkDestinationConfiguration),
+    HttpEndpoint(HttpEndpointDestinationConfiguration),
+}
+
+impl CreateDeliveryStreamInput {
+    /// Creates a request for a delivery stream of `delivery_stream_type`, which is `DirectPut`
+    /// for streams written to with `PutRecord` or `KinesisStreamAsSource`.
+    pub fn new(delivery_stream_name: &str, delivery_stream_type: &str, destination: DeliveryStreamDestination)
+        -> CreateDeliveryStreamInput {
+        let mut input = CreateDeliveryStreamInput {
+            delivery_stream_name: delivery_stream_name.to_owned(),
+            delivery_stream_type: Some(delivery_stream_type.to_owned()),
+            ..CreateDeliveryStreamInput::default()
+        };
+
+        match destination {
+            DeliveryStreamDestination::S3(configuration) => {
+                input.extended_s3_destination_configuration = Some(configuration)
+            }
+            DeliveryStreamDestination::Redshift(configuration) => {
+                input.redshift_destination_configuration = Some(configuration)
+            }
+            DeliveryStreamDestination::Elasticsearch(configuration) => {
+                input.elasticsearch_destination_configuration = Some(configuration)
+            }
+            DeliveryStreamDestination::Splunk(configuration) => {
+                input.splunk_destination_configuration = Some(configuration)
+            }
+            DeliveryStreamDestination::HttpEndpoint(configuration) => {
+                input.http_endpoint_destination_configuration = Some(configuration)
+            }
+        }
+
+        input
+    }
+}
+
+/// An error from `put_records_with_retry`, with the records that hadn't been accepted when the
+/// request failed, so they can be sent again.
+#[derive(Debug)]
+pub struct PutRecordsError {
+    /// The error of the `PutRecordBatch` call that failed.
+    pub error: AwsError,
+    /// The records that failed earlier in the attempt, those of the batch whose request
+    /// failed, and those that hadn't been sent yet.
+    pub records: Vec<Record>,
+}
+
+impl fmt::Display for PutRecordsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({} records weren't delivered)", self.error.description(), self.records.len())
+    }
+}
+
+impl Error for PutRecordsError {
+    fn description(&self) -> &str {
+        self.error.description()
+    }
+}
+
+/// Takes the records for the next `PutRecordBatch` call off the front of `pending`: as many as
+/// fit within both limits, but always at least one.
+fn next_batch(pending: &mut Vec<Record>) -> Vec<Record> {
+    let mut bytes = 0;
+    let mut count = 0;
+
+    for record in pending.iter().take(MAX_BATCH_RECORDS) {
+        bytes += record.data.len();
+
+        if bytes > MAX_BATCH_BYTES && count > 0 {
+            break;
+        }
+
+        count += 1;
+    }
+
+    let rest = pending.split_off(count);
+    mem::replace(pending, rest)
+}
+
+/// How long to wait before the `retry`th retry, counting from one: a random delay of up to
+/// twice the last one's limit, so clients that were throttled together don't retry together.
+fn retry_delay(retry: u32) -> Duration {
+    let limit = cmp::min(
+        BASE_RETRY_DELAY_MS.saturating_mul(1u64 << cmp::min(retry.saturating_sub(1), 16)),
+        MAX_RETRY_DELAY_MS,
+    );
+    let random = rand_bytes(8).iter().fold(0u64, |random, &byte| random << 8 | byte as u64);
+
+    Duration::from_millis(random % (limit + 1))
+}
+
+/// Returns the records of a batch that `PutRecordBatch` reported as failed, in their original
+/// order.
+fn failed_records(records: Vec<Record>, output: &PutRecordBatchOutput) -> Vec<Record> {
+    if output.failed_put_count == 0 {
+        return Vec::new();
+    }
+
+    records.into_iter()
+        .zip(output.request_responses.iter())
+        .filter(|&(_, ref response)| response.error_code.is_some())
+        .map(|(record, _)| record)
+        .collect()
+}
+
+impl<P> KinesisFirehoseClient<P> where P: ProvideAwsCredentials {
+    /// Writes a single record to a delivery stream and returns its ID.
+    pub fn put_record_data(&mut self, delivery_stream_name: &str, data: Vec<u8>) -> AwsResult<String> {
+        let input = PutRecordInput {
+            delivery_stream_name: delivery_stream_name.to_owned(),
+            record: Record { data: data },
+        };
+
+        Ok(try!(self.put_record(&input)).record_id)
+    }
+
+    /// Creates a delivery stream and returns its ARN.
+    pub fn create_delivery_stream_arn(&mut self, delivery_stream_name: &str, delivery_stream_type: &str,
+        destination: DeliveryStreamDestination) -> AwsResult<Option<String>> {
+        let input = CreateDeliveryStreamInput::new(delivery_stream_name, delivery_stream_type, destination);
+
+        Ok(try!(self.create_delivery_stream(&input)).delivery_stream_arn)
+    }
+
+    /// Describes the delivery stream `delivery_stream_name` and its destinations.
+    pub fn delivery_stream_description(&mut self, delivery_stream_name: &str)
+        -> AwsResult<DeliveryStreamDescription> {
+        let input = DescribeDeliveryStreamInput {
+            delivery_stream_name: delivery_stream_name.to_owned(),
+            ..DescribeDeliveryStreamInput::default()
+        };
+
+        Ok(try!(self.describe_delivery_stream(&input)).delivery_stream_description)
+    }
+
+    /// Writes any number of records to a delivery stream, in batches of up to 500 records and
+    /// 4 MiB, resending the records Firehose fails to accept up to `max_attempts` times in
+    /// total. Each retry waits a random, exponentially growing delay first.
+    ///
+    /// Returns the records that still hadn't been accepted after the last attempt, so an empty
+    /// result means every record was delivered. If a request fails, the records that hadn't
+    /// been accepted by then are returned with its error.
+    pub fn put_records_with_retry(&mut self, delivery_stream_name: &str, data: Vec<Vec<u8>>,
+        max_attempts: usize) -> Result<Vec<Record>, PutRecordsError> {
+        let mut pending = data.into_iter().map(|data| Record { data: data }).collect::<Vec<Record>>();
+
+        for attempt in 0..max_attempts {
+            if pending.is_empty() {
+                break;
+            }
+
+            if attempt > 0 {
+                thread::sleep(retry_delay(attempt as u32));
+            }
+
+            let mut failed = Vec::new();
+
+            while !pending.is_empty() {
+                let input = PutRecordBatchInput {
+                    delivery_stream_name: delivery_stream_name.to_owned(),
+                    records: next_batch(&mut pending),
+                };
+
+                match self.put_record_batch(&input) {
+                    Ok(output) => failed.extend(failed_records(input.records, &output)),
+                    Err(error) => {
+                        failed.extend(input.records);
+                        failed.extend(pending);
+
+                        return Err(PutRecordsError {
+                            error: error,
+                            records: failed,
+                        });
+                    }
+                }
+            }
+
+            pending = failed;
+        }
+
+        Ok(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+    use std::time::Duration;
+
+    use serde_json;
+
+    use super::{CreateDeliveryStreamInput, DeliveryStreamDestination, PutRecordBatchOutput, Record};
+    use super::{MAX_RETRY_DELAY_MS, SplunkDestinationConfiguration, failed_records, next_batch, retry_delay};
+
+    fn record(data: &str) -> Record {
+        Record { data: data.as_bytes().to_vec() }
+    }
+
+    #[test]
+    fn partial_batch_failure() {
+        let mut body = String::new();
+        File::open("tests/sample-data/firehose_put_record_batch_partial_failure.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let output = serde_json::from_str::<PutRecordBatchOutput>(&body).unwrap();
+
+        assert_eq!(output.failed_put_count, 2);
+        assert_eq!(output.request_responses.len(), 4);
+        assert_eq!(output.request_responses[1].error_code, Some("ServiceUnavailableException".to_owned()));
+        assert!(output.request_responses[0].record_id.is_some());
+
+        let failed = failed_records(vec![record("a"), record("b"), record("c"), record("d")], &output);
+        assert_eq!(failed, vec![record("b"), record("d")]);
+    }
+
+    #[test]
+    fn batches_are_limited_by_count() {
+        let mut pending = (0..1100).map(|i| record(&i.to_string())).collect::<Vec<Record>>();
+
+        assert_eq!(next_batch(&mut pending).len(), 500);
+        assert_eq!(next_batch(&mut pending).len(), 500);
+        assert_eq!(next_batch(&mut pending), (1000..1100).map(|i| record(&i.to_string())).collect::<Vec<_>>());
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn batches_are_limited_by_size() {
+        let megabyte = Record { data: vec![0; 1024 * 1024] };
+        let mut pending = vec![megabyte.clone(); 5];
+        pending.insert(3, record("small"));
+
+        assert_eq!(next_batch(&mut pending).len(), 4);
+        assert_eq!(next_batch(&mut pending).len(), 2);
+        assert!(pending.is_empty());
+
+        // A record over the limit on its own is still sent, for Firehose to reject.
+        let mut pending = vec![Record { data: vec![0; 5 * 1024 * 1024] }, megabyte];
+        assert_eq!(next_batch(&mut pending).len(), 1);
+        assert_eq!(next_batch(&mut pending).len(), 1);
+    }
+
+    #[test]
+    fn retry_delays_grow_up_to_a_limit() {
+        let millis = |delay: Duration| delay.as_secs() * 1000 + (delay.subsec_nanos() / 1_000_000) as u64;
+
+        for _ in 0..100 {
+            assert!(millis(retry_delay(1)) <= 100);
+            assert!(millis(retry_delay(3)) <= 400);
+            assert!(millis(retry_delay(40)) <= MAX_RETRY_DELAY_MS);
+        }
+    }
+
+    #[test]
+    fn records_are_base64_encoded() {
+        assert_eq!(serde_json::to_string(&record("hello")).unwrap(), "{\"Data\":\"aGVsbG8=\"}");
+    }
+
+    #[test]
+    fn destination_sets_matching_configuration() {
+        let input = CreateDeliveryStreamInput::new(
+            "web-logs",
+            "DirectPut",
+            DeliveryStreamDestination::Splunk(SplunkDestinationConfiguration {
+                hec_endpoint: "https://splunk.example.com:8088".to_owned(),
+                hec_endpoint_type: "Raw".to_owned(),
+                ..SplunkDestinationConfiguration::default()
+            }),
+        );
+
+        assert_eq!(input.delivery_stream_type, Some("DirectPut".to_owned()));
+        assert!(input.splunk_destination_configuration.is_some());
+        assert!(input.extended_s3_destination_configuration.is_none());
+    }
+}
@@ -0,0 +1,110 @@
+//! Amazon Fraud Detector
+//!
+//! Evaluates events, such as online payments or account registrations, against a detector's
+//! models and rules. Each matching rule returns its outcomes, such as `review` or `block`,
+//! which the caller acts on.
+
+use std::collections::HashMap;
+
+include!(concat!(env!("OUT_DIR"), "/frauddetector.rs"));
+
+/// The rule execution mode which only returns the outcomes of the first rule that matches.
+pub const FIRST_MATCHED: &'static str = "FIRST_MATCHED";
+
+/// The rule execution mode which returns the outcomes of every rule that matches.
+pub const ALL_MATCHED: &'static str = "ALL_MATCHED";
+
+impl GetEventPredictionResult {
+    /// The outcomes of every rule that matched, in the order the rules were evaluated,
+    /// without repeats.
+    pub fn outcomes(&self) -> Vec<&str> {
+        let mut outcomes: Vec<&str> = Vec::new();
+
+        for rule_result in self.rule_results.as_ref().map_or(&[][..], |rule_results| &rule_results[..]) {
+            for outcome in rule_result.outcomes.as_ref().map_or(&[][..], |outcomes| &outcomes[..]) {
+                if !outcomes.contains(&&outcome[..]) {
+                    outcomes.push(outcome);
+                }
+            }
+        }
+
+        outcomes
+    }
+}
+
+impl<P> FraudDetectorClient<P> where P: ProvideAwsCredentials {
+    /// Evaluates the event `event_id` of the type `event_type_name`, which happened at the
+    /// ISO 8601 `event_timestamp`, with the active version of the detector unless
+    /// `detector_version_id` is set.
+    pub fn predict(&mut self, detector_id: &str, detector_version_id: Option<String>, event_id: &str,
+        event_type_name: &str, entities: Vec<Entity>, event_timestamp: &str,
+        event_variables: HashMap<String, String>) -> AwsResult<GetEventPredictionResult> {
+        let input = GetEventPredictionRequest {
+            detector_id: detector_id.to_owned(),
+            detector_version_id: detector_version_id,
+            entities: entities,
+            event_id: event_id.to_owned(),
+            event_timestamp: event_timestamp.to_owned(),
+            event_type_name: event_type_name.to_owned(),
+            event_variables: event_variables,
+            ..GetEventPredictionRequest::default()
+        };
+
+        self.get_event_prediction(&input)
+    }
+
+    /// Creates a draft version of the detector `detector_id` with `rules` and
+    /// `model_versions`, evaluating the rules with the mode `FIRST_MATCHED` or `ALL_MATCHED`.
+    pub fn create_version(&mut self, detector_id: &str, rules: Vec<Rule>, rule_execution_mode: &str,
+        model_versions: Vec<ModelVersion>) -> AwsResult<CreateDetectorVersionResult> {
+        let input = CreateDetectorVersionRequest {
+            detector_id: detector_id.to_owned(),
+            model_versions: if model_versions.is_empty() { None } else { Some(model_versions) },
+            rule_execution_mode: Some(rule_execution_mode.to_owned()),
+            rules: rules,
+            ..CreateDetectorVersionRequest::default()
+        };
+
+        self.create_detector_version(&input)
+    }
+
+    /// Creates or updates the outcome `name`, which rules can return.
+    pub fn add_outcome(&mut self, name: &str, description: Option<String>, tags: Vec<Tag>) -> AwsResult<()> {
+        let input = PutOutcomeRequest {
+            description: description,
+            name: name.to_owned(),
+            tags: if tags.is_empty() { None } else { Some(tags) },
+        };
+
+        try!(self.put_outcome(&input));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::GetEventPredictionResult;
+
+    #[test]
+    fn parse_prediction_with_several_model_scores() {
+        let mut body = String::new();
+        File::open("tests/sample-data/frauddetector_get_event_prediction.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let result = serde_json::from_str::<GetEventPredictionResult>(&body).unwrap();
+
+        let model_scores = result.model_scores.as_ref().unwrap();
+        assert_eq!(model_scores.len(), 2);
+        assert_eq!(model_scores[0].model_version.as_ref().unwrap().model_type, "ONLINE_FRAUD_INSIGHTS");
+        assert_eq!(model_scores[0].scores.as_ref().unwrap()["payments_model_insightscore"], 962.0);
+        assert_eq!(model_scores[1].model_version.as_ref().unwrap().model_id, "account_takeover_model");
+
+        assert_eq!(result.rule_results.as_ref().unwrap().len(), 2);
+        assert_eq!(result.outcomes(), vec!["block", "investigate"]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Amazon Glacier
+
+include!(concat!(env!("OUT_DIR"), "/glacier.rs"));
+
+use openssl::crypto::hash::Type::SHA256;
+use openssl::crypto::hash::hash;
+use rustc_serialize::hex::ToHex;
+
+/// The size of the chunks the tree hash is built from.
+const TREE_HASH_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Computes the SHA256 tree hash Glacier uses to verify uploads, as a hex string.
+///
+/// The data is hashed in 1 MiB chunks, then adjacent pairs of hashes are concatenated and
+/// hashed until a single hash remains. A hash without a partner is carried up as it is.
+pub fn compute_tree_hash(data: &[u8]) -> String {
+    let mut hashes = data.chunks(TREE_HASH_CHUNK_SIZE)
+        .map(|chunk| hash(SHA256, chunk))
+        .collect::<Vec<Vec<u8>>>();
+
+    if hashes.is_empty() {
+        hashes.push(hash(SHA256, &[]));
+    }
+
+    while hashes.len() > 1 {
+        hashes = hashes.chunks(2)
+            .map(|pair| {
+                if pair.len() == 2 {
+                    let mut concatenated = pair[0].clone();
+                    concatenated.extend_from_slice(&pair[1]);
+
+                    hash(SHA256, &concatenated)
+                } else {
+                    pair[0].clone()
+                }
+            })
+            .collect();
+    }
+
+    hashes[0].to_hex()
+}
+
+impl UploadArchiveInput {
+    /// Creates a request to upload `body` to a vault owned by the requesting account, along
+    /// with the tree hash Glacier requires.
+    pub fn new(vault_name: &str, archive_description: Option<String>, body: Vec<u8>) -> UploadArchiveInput {
+        UploadArchiveInput {
+            account_id: "-".to_owned(),
+            archive_description: archive_description,
+            checksum: Some(compute_tree_hash(&body)),
+            body: Some(body),
+            vault_name: vault_name.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UploadArchiveInput, compute_tree_hash};
+
+    const MEGABYTE: usize = 1024 * 1024;
+
+    #[test]
+    fn tree_hash_of_small_data_is_its_sha256() {
+        assert_eq!(
+            compute_tree_hash(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            compute_tree_hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            compute_tree_hash(&vec![b'a'; MEGABYTE]),
+            "9bc1b2a288b26af7257a36277ae3816a7d4f16e89c1e7e77d0a5c48bad62b360"
+        );
+    }
+
+    #[test]
+    fn tree_hash_of_multiple_chunks() {
+        assert_eq!(
+            compute_tree_hash(&vec![b'a'; 2 * MEGABYTE]),
+            "560c2c9333c719cb00cfdffee3ba293db17f58743cdd1f7e4055373ae6300afa"
+        );
+
+        let data = (0..4 * MEGABYTE).map(|i| i as u8).collect::<Vec<u8>>();
+        assert_eq!(
+            compute_tree_hash(&data),
+            "4beec83d3a729a0dd6550672ebbd6fd2e925dd9f08d6df5bd333c0e61e8bdaa2"
+        );
+    }
+
+    #[test]
+    fn tree_hash_carries_up_unpaired_chunks() {
+        // Three chunks, the last of which is partial, so the third hash has no partner in the
+        // first round. This differs from the plain SHA256 of the data,
+        // f077ad66a499663ea49bdb0067bb7d51745c75a651e17c458435756986bbc523.
+        assert_eq!(
+            compute_tree_hash(&vec![b'a'; 2 * MEGABYTE + 5]),
+            "ffeffd23f81036e2844539c6d00bbab654e0d56ceaa22dfb7cd218c3d272d001"
+        );
+    }
+
+    #[test]
+    fn upload_archive_input_includes_tree_hash() {
+        let input = UploadArchiveInput::new("backups", Some("nightly".to_owned()), b"abc".to_vec());
+
+        assert_eq!(input.account_id, "-");
+        assert_eq!(
+            input.checksum,
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_owned())
+        );
+        assert_eq!(input.body, Some(b"abc".to_vec()));
+    }
+}
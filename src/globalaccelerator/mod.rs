@@ -0,0 +1,110 @@
+//! AWS Global Accelerator
+//!
+//! Routes traffic through static anycast IP addresses to endpoints in one or more regions.
+//! The API has a single endpoint, `globalaccelerator.amazonaws.com`, which requests are sent
+//! to and signed for us-west-2 whatever region the client is created with.
+
+use openssl::crypto::rand::rand_bytes;
+use rustc_serialize::hex::ToHex;
+
+include!(concat!(env!("OUT_DIR"), "/globalaccelerator.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+/// The IP address type of accelerators with IPv4 addresses only.
+pub const IPV4: &'static str = "IPV4";
+
+/// The IP address type of accelerators with both IPv4 and IPv6 addresses.
+pub const DUAL_STACK: &'static str = "DUAL_STACK";
+
+impl Accelerator {
+    /// The static IP addresses of the accelerator, across its IP sets.
+    pub fn ip_addresses(&self) -> Vec<&str> {
+        self.ip_sets.as_ref().map_or(Vec::new(), |ip_sets| {
+            ip_sets.iter()
+                .flat_map(|ip_set| ip_set.ip_addresses.as_ref().map_or(&[][..], |ip_addresses| &ip_addresses[..]))
+                .map(|ip_address| &ip_address[..])
+                .collect()
+        })
+    }
+}
+
+impl<P> GlobalAcceleratorClient<P> where P: ProvideAwsCredentials {
+    /// Creates an accelerator of the IP address type `IPV4` or `DUAL_STACK`, with the
+    /// addresses of a bring your own IP address range if `ip_addresses` isn't empty.
+    pub fn create(&mut self, name: &str, ip_address_type: &str, enabled: bool, ip_addresses: Vec<String>,
+        tags: Vec<Tag>) -> AwsResult<Accelerator> {
+        let input = CreateAcceleratorRequest {
+            enabled: Some(enabled),
+            idempotency_token: rand_bytes(16).to_hex(),
+            ip_address_type: Some(ip_address_type.to_owned()),
+            ip_addresses: if ip_addresses.is_empty() { None } else { Some(ip_addresses) },
+            name: name.to_owned(),
+            tags: if tags.is_empty() { None } else { Some(tags) },
+        };
+
+        expect(try!(self.create_accelerator(&input)).accelerator, "the accelerator")
+    }
+
+    /// Describes the accelerator `accelerator_arn`, including its IP sets and DNS name.
+    pub fn accelerator(&mut self, accelerator_arn: &str) -> AwsResult<DescribeAcceleratorResponse> {
+        self.describe_accelerator(&DescribeAcceleratorRequest { accelerator_arn: accelerator_arn.to_owned() })
+    }
+
+    /// Calls `ListAccelerators` until every page has been read, with up to `max_results`
+    /// accelerators on each, and returns all of them.
+    pub fn list_all_accelerators(&mut self, max_results: Option<i32>) -> AwsResult<Vec<Accelerator>> {
+        let mut input = ListAcceleratorsRequest {
+            max_results: max_results,
+            ..ListAcceleratorsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_accelerators(&input));
+
+            Ok((output.accelerators.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Turns the flow logs of the accelerator `accelerator_arn` on or off. They're written
+    /// under `flow_logs_s3_prefix` in `flow_logs_s3_bucket`, which flow logs need.
+    pub fn set_flow_logs(&mut self, accelerator_arn: &str, flow_logs_enabled: bool,
+        flow_logs_s3_bucket: Option<String>, flow_logs_s3_prefix: Option<String>)
+        -> AwsResult<AcceleratorAttributes> {
+        let input = UpdateAcceleratorAttributesRequest {
+            accelerator_arn: accelerator_arn.to_owned(),
+            flow_logs_enabled: Some(flow_logs_enabled),
+            flow_logs_s3_bucket: flow_logs_s3_bucket,
+            flow_logs_s3_prefix: flow_logs_s3_prefix,
+        };
+
+        expect(try!(self.update_accelerator_attributes(&input)).accelerator_attributes, "the attributes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DescribeAcceleratorResponse, IPV4};
+
+    #[test]
+    fn parse_accelerator_with_two_static_ips() {
+        let mut body = String::new();
+        File::open("tests/sample-data/globalaccelerator_describe_accelerator.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let accelerator = serde_json::from_str::<DescribeAcceleratorResponse>(&body).unwrap().accelerator.unwrap();
+
+        assert_eq!(accelerator.name, Some("checkout".to_owned()));
+        assert_eq!(accelerator.ip_address_type, Some(IPV4.to_owned()));
+        assert_eq!(accelerator.status, Some("DEPLOYED".to_owned()));
+        assert_eq!(accelerator.enabled, Some(true));
+        assert_eq!(accelerator.dns_name, Some("a5d53ff5ee6bca4ce.awsglobalaccelerator.com".to_owned()));
+        assert_eq!(accelerator.ip_addresses(), vec!["75.2.60.5", "99.83.190.51"]);
+    }
+}
@@ -0,0 +1,107 @@
+//! Amazon GuardDuty
+
+include!(concat!(env!("OUT_DIR"), "/guardduty.rs"));
+
+use pagination::read_all_pages;
+
+impl GetFindingsRequest {
+    pub fn new(detector_id: &str, finding_ids: Vec<String>) -> GetFindingsRequest {
+        GetFindingsRequest {
+            detector_id: detector_id.to_owned(),
+            finding_ids: finding_ids,
+            ..GetFindingsRequest::default()
+        }
+    }
+}
+
+impl UpdateFindingsFeedbackRequest {
+    /// Creates a request to mark findings as `USEFUL` or `NOT_USEFUL`.
+    pub fn new(detector_id: &str, finding_ids: Vec<String>, feedback: &str, comments: Option<String>)
+        -> UpdateFindingsFeedbackRequest {
+        UpdateFindingsFeedbackRequest {
+            comments: comments,
+            detector_id: detector_id.to_owned(),
+            feedback: feedback.to_owned(),
+            finding_ids: finding_ids,
+        }
+    }
+}
+
+impl<P> GuardDutyClient<P> where P: ProvideAwsCredentials {
+    /// Calls `ListFindings` until every page has been read, and returns the IDs of all of the
+    /// findings matching the request's criteria.
+    pub fn list_all_findings(&mut self, input: &ListFindingsRequest) -> AwsResult<Vec<String>> {
+        let mut input = input.clone();
+
+        read_all_pages(input.next_token.take(), |next_token| {
+            input.next_token = next_token;
+            let response = try!(self.list_findings(&input));
+
+            // The last page comes with an empty token rather than none at all.
+            let next_token = response.next_token.and_then(|token| {
+                if token.is_empty() { None } else { Some(token) }
+            });
+
+            Ok((response.finding_ids, next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{GetFindingsResponse, UpdateFindingsFeedbackRequest};
+
+    #[test]
+    fn parse_port_probe_finding() {
+        let mut body = String::new();
+        File::open("tests/sample-data/guardduty_get_findings_port_probe.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let findings = serde_json::from_str::<GetFindingsResponse>(&body).unwrap().findings;
+
+        assert_eq!(findings.len(), 1);
+
+        let finding = &findings[0];
+        assert_eq!(finding.aws_type, "Recon:EC2/PortProbeUnprotectedPort");
+        assert_eq!(finding.severity, 2.0);
+        assert_eq!(finding.account_id, "123456789012");
+        assert_eq!(finding.region, "us-east-1");
+        assert_eq!(finding.created_at, "2017-12-05T22:14:51.276Z");
+        assert_eq!(finding.updated_at, "2017-12-06T03:40:12.094Z");
+
+        let service = finding.service.as_ref().unwrap();
+        let action = service.action.as_ref().unwrap();
+        assert_eq!(action.action_type, Some("PORT_PROBE".to_owned()));
+
+        let details = action.port_probe_action.as_ref().unwrap().port_probe_details.as_ref().unwrap();
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].local_port_details.as_ref().unwrap().port, Some(22));
+        assert_eq!(details[1].local_port_details.as_ref().unwrap().port_name, Some("RDP".to_owned()));
+        assert_eq!(
+            details[1].remote_ip_details.as_ref().unwrap().ip_address_v4,
+            Some("198.51.100.23".to_owned())
+        );
+
+        let evidence = service.evidence.as_ref().unwrap();
+        let threat_intelligence = evidence.threat_intelligence_details.as_ref().unwrap();
+        assert_eq!(threat_intelligence[0].threat_list_name, Some("ProofPoint".to_owned()));
+    }
+
+    #[test]
+    fn update_findings_feedback_request() {
+        let request = UpdateFindingsFeedbackRequest::new(
+            "12abc34d567e8fa901bc2d34e56789f0",
+            vec!["3eb0d5c1c1e2e5a3f4b6a7c8d9e0f1a2".to_owned()],
+            "NOT_USEFUL",
+            Some("Expected traffic from the vulnerability scanner".to_owned()),
+        );
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"feedback\":\"NOT_USEFUL\""));
+        assert!(!json.contains("12abc34d567e8fa901bc2d34e56789f0"));
+    }
+}
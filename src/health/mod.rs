@@ -0,0 +1,178 @@
+//! AWS Health
+//!
+//! The Health API has a single endpoint in us-east-1, which requests are sent to and signed for
+//! whatever region the client is created with. It describes events in every region.
+
+include!(concat!(env!("OUT_DIR"), "/health.rs"));
+
+use pagination::read_all_pages;
+
+/// The most events a single `DescribeEventDetails` call can describe.
+const MAX_BATCH_EVENT_ARNS: usize = 10;
+
+impl DateTimeRange {
+    /// The times from `from` to `to`, both in seconds since the epoch.
+    pub fn new(from: f64, to: f64) -> DateTimeRange {
+        DateTimeRange {
+            from: Some(from),
+            to: Some(to),
+        }
+    }
+}
+
+impl EventFilter {
+    /// Matches the events of any of `services`, such as `EC2`, in any of `regions`, that
+    /// started within `start_time`. Empty lists match everything.
+    pub fn new(services: Vec<String>, regions: Vec<String>, start_time: Option<DateTimeRange>) -> EventFilter {
+        EventFilter {
+            regions: if regions.is_empty() { None } else { Some(regions) },
+            services: if services.is_empty() { None } else { Some(services) },
+            start_times: start_time.map(|start_time| vec![start_time]),
+            ..EventFilter::default()
+        }
+    }
+}
+
+impl EntityFilter {
+    /// Matches the entities affected by any of `event_arns`.
+    pub fn new(event_arns: Vec<String>) -> EntityFilter {
+        EntityFilter {
+            event_arns: event_arns,
+            ..EntityFilter::default()
+        }
+    }
+}
+
+impl<P> HealthClient<P> where P: ProvideAwsCredentials {
+    /// Calls `DescribeEvents` until every page has been read, and returns the events matching
+    /// `filter`. Their `status_code` is `open`, `closed` or `upcoming`.
+    pub fn describe_all_events(&mut self, filter: EventFilter) -> AwsResult<Vec<Event>> {
+        let mut input = DescribeEventsRequest {
+            filter: Some(filter),
+            ..DescribeEventsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.describe_events(&input));
+
+            Ok((output.events.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Calls `DescribeEventDetails` for any number of events, ten at a time, and returns the
+    /// details of those found along with the errors for those that weren't.
+    pub fn describe_all_event_details(&mut self, event_arns: Vec<String>)
+        -> AwsResult<DescribeEventDetailsResponse> {
+        let mut successful_set = Vec::new();
+        let mut failed_set = Vec::new();
+
+        for chunk in event_arns.chunks(MAX_BATCH_EVENT_ARNS) {
+            let input = DescribeEventDetailsRequest {
+                event_arns: chunk.to_vec(),
+                ..DescribeEventDetailsRequest::default()
+            };
+            let output = try!(self.describe_event_details(&input));
+
+            successful_set.extend(output.successful_set.unwrap_or_else(Vec::new));
+            failed_set.extend(output.failed_set.unwrap_or_else(Vec::new));
+        }
+
+        Ok(DescribeEventDetailsResponse {
+            failed_set: Some(failed_set),
+            successful_set: Some(successful_set),
+        })
+    }
+
+    /// Calls `DescribeAffectedEntities` until every page has been read, and returns the
+    /// entities, such as instances or volumes, matching `filter`.
+    pub fn describe_all_affected_entities(&mut self, filter: EntityFilter) -> AwsResult<Vec<AffectedEntity>> {
+        let mut input = DescribeAffectedEntitiesRequest {
+            filter: filter,
+            ..DescribeAffectedEntitiesRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.describe_affected_entities(&input));
+
+            Ok((output.entities.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Calls `DescribeAffectedAccountsForOrganization` until every page has been read, and
+    /// returns the IDs of the accounts of the organization affected by an event. Only the
+    /// organization's management account can call it.
+    pub fn describe_all_affected_accounts_for_organization(&mut self, event_arn: &str)
+        -> AwsResult<Vec<String>> {
+        let mut input = DescribeAffectedAccountsForOrganizationRequest {
+            event_arn: event_arn.to_owned(),
+            ..DescribeAffectedAccountsForOrganizationRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.describe_affected_accounts_for_organization(&input));
+
+            Ok((output.affected_accounts.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DateTimeRange, DescribeAffectedAccountsForOrganizationResponse};
+    use super::{DescribeEventsForOrganizationResponse, EventFilter};
+
+    fn read(path: &str) -> String {
+        let mut body = String::new();
+        File::open(path).unwrap().read_to_string(&mut body).unwrap();
+        body
+    }
+
+    #[test]
+    fn parse_organization_event_affecting_several_accounts() {
+        let body = read("tests/sample-data/health_describe_events_for_organization.json");
+        let events = serde_json::from_str::<DescribeEventsForOrganizationResponse>(&body).unwrap()
+            .events.unwrap();
+
+        assert_eq!(events.len(), 1);
+
+        let event = &events[0];
+        assert_eq!(event.event_type_code, Some("AWS_EC2_OPERATIONAL_ISSUE".to_owned()));
+        assert_eq!(event.service, Some("EC2".to_owned()));
+        assert_eq!(event.region, Some("us-east-1".to_owned()));
+        assert_eq!(event.status_code, Some("open".to_owned()));
+        assert_eq!(event.start_time, Some(1573646400.0));
+        assert_eq!(event.event_scope_code, Some("ACCOUNT_SPECIFIC".to_owned()));
+
+        let body = read("tests/sample-data/health_describe_affected_accounts_for_organization.json");
+        let affected = serde_json::from_str::<DescribeAffectedAccountsForOrganizationResponse>(&body).unwrap();
+
+        assert_eq!(affected.affected_accounts, Some(vec![
+            "123456789012".to_owned(),
+            "210987654321".to_owned(),
+            "555555555555".to_owned(),
+        ]));
+        assert_eq!(affected.event_scope_code, Some("ACCOUNT_SPECIFIC".to_owned()));
+    }
+
+    #[test]
+    fn event_filter() {
+        let filter = EventFilter::new(
+            vec!["EC2".to_owned()],
+            Vec::new(),
+            Some(DateTimeRange::new(1573603200.0, 1573689600.0)),
+        );
+        let json = serde_json::to_string(&filter).unwrap();
+
+        assert!(json.contains("\"services\":[\"EC2\"]"));
+        assert!(json.contains("\"regions\":null"));
+        assert!(json.contains("\"startTimes\":[{\"from\":1573603200"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Amazon Inspector
+//!
+//! Scans EC2 instances, container images and Lambda functions for software vulnerabilities
+//! and unintended network exposure, reporting each as a finding with a severity and a
+//! recommended remediation.
+
+use openssl::crypto::rand::rand_bytes;
+use rustc_serialize::hex::ToHex;
+
+include!(concat!(env!("OUT_DIR"), "/inspector2.rs"));
+
+use pagination::read_all_pages;
+
+/// The severity of the most urgent findings.
+pub const CRITICAL: &'static str = "CRITICAL";
+
+/// The type of findings for vulnerable operating system and programming language packages.
+pub const PACKAGE_VULNERABILITY: &'static str = "PACKAGE_VULNERABILITY";
+
+/// The type of findings for vulnerabilities in the code of Lambda functions.
+pub const CODE_VULNERABILITY: &'static str = "CODE_VULNERABILITY";
+
+/// The type of findings for network paths open to the internet.
+pub const NETWORK_REACHABILITY: &'static str = "NETWORK_REACHABILITY";
+
+impl Finding {
+    /// The highest CVSS version 3 base score of a package vulnerability finding, across the
+    /// sources that scored it.
+    pub fn cvss3_base_score(&self) -> Option<f64> {
+        self.package_vulnerability_details.as_ref()
+            .and_then(|details| details.cvss.as_ref())
+            .and_then(|cvss| {
+                cvss.iter()
+                    .filter(|score| score.version.starts_with('3'))
+                    .map(|score| score.base_score)
+                    .fold(None, |max, score| Some(max.map_or(score, |max: f64| max.max(score))))
+            })
+    }
+
+    /// The recommended way to remediate the finding, if Inspector has one.
+    pub fn recommendation(&self) -> Option<&str> {
+        self.remediation.recommendation.as_ref()
+            .and_then(|recommendation| recommendation.text.as_ref())
+            .map(|text| &text[..])
+    }
+}
+
+impl<P> InspectorV2Client<P> where P: ProvideAwsCredentials {
+    /// Calls `ListFindings` until every page has been read, with up to `max_results` findings
+    /// on each, and returns the findings that match `filter_criteria`.
+    pub fn list_all_findings(&mut self, filter_criteria: Option<FilterCriteria>, sort_criteria: Option<SortCriteria>,
+        max_results: Option<i32>) -> AwsResult<Vec<Finding>> {
+        let mut input = ListFindingsRequest {
+            filter_criteria: filter_criteria,
+            max_results: max_results,
+            sort_criteria: sort_criteria,
+            ..ListFindingsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_findings(&input));
+
+            Ok((output.findings.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Returns which scan types are enabled for each of `account_ids`, or the calling account
+    /// without any.
+    pub fn account_statuses(&mut self, account_ids: Vec<String>) -> AwsResult<BatchGetAccountStatusResponse> {
+        let input = BatchGetAccountStatusRequest {
+            account_ids: if account_ids.is_empty() { None } else { Some(account_ids) },
+        };
+
+        self.batch_get_account_status(&input)
+    }
+
+    /// Makes `delegated_admin_account_id` the delegated administrator of Inspector for the
+    /// organization.
+    pub fn enable_delegated_admin(&mut self, delegated_admin_account_id: &str)
+        -> AwsResult<EnableDelegatedAdminAccountResponse> {
+        let input = EnableDelegatedAdminAccountRequest {
+            client_token: Some(rand_bytes(16).to_hex()),
+            delegated_admin_account_id: delegated_admin_account_id.to_owned(),
+        };
+
+        self.enable_delegated_admin_account(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{CRITICAL, ListFindingsResponse, PACKAGE_VULNERABILITY};
+
+    #[test]
+    fn parse_critical_package_vulnerability() {
+        let mut body = String::new();
+        File::open("tests/sample-data/inspectorv2_list_findings.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<ListFindingsResponse>(&body).unwrap();
+        let findings = response.findings.unwrap();
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(response.next_token, None);
+
+        let finding = &findings[0];
+        assert_eq!(finding.severity, CRITICAL);
+        assert_eq!(finding.aws_type, PACKAGE_VULNERABILITY);
+        assert_eq!(finding.cvss3_base_score(), Some(9.8));
+        assert_eq!(finding.recommendation(), Some("Upgrade log4j-core to version 2.17.1 or later."));
+        assert_eq!(finding.resources[0].aws_type, "AWS_EC2_INSTANCE");
+
+        let details = finding.package_vulnerability_details.as_ref().unwrap();
+        assert_eq!(details.vulnerability_id, "CVE-2021-44228");
+        assert_eq!(details.vulnerable_packages.as_ref().unwrap()[0].fixed_in_version, Some("2.17.1".to_owned()));
+    }
+}
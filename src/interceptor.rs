@@ -0,0 +1,155 @@
+//! Hooks run around every request Rusoto sends.
+//!
+//! Each client calls its own interceptors, given to it with `set_interceptors`, or in
+//! `S3Config::interceptors` for S3, so every call it makes is instrumented without wrapping
+//! its methods:
+//!
+//! ```ignore
+//! let mut sqs = SqsClient::new(provider, Region::UsEast1);
+//! sqs.set_interceptors(vec![Arc::new(LogInterceptor)]);
+//! ```
+//!
+//! Every request calls `before_request` once, before it's first signed, and `after_response`
+//! for each response, including the redirects S3 answers some requests with. Interceptors are
+//! called in the order they were given.
+//!
+//! `LogInterceptor` logs every request, and with the `opentelemetry` feature,
+//! `OpenTelemetryInterceptor` records a span for each.
+
+use std::fmt;
+use std::str;
+use std::time::Duration;
+
+use hyper::client::Response;
+
+use signature::SignedRequest;
+
+/// Observes, and may change, the requests sent to AWS.
+pub trait Interceptor {
+    /// Called before a request is signed, so any headers or parameters it adds are signed too.
+    fn before_request(&self, _request: &mut SignedRequest) {}
+
+    /// Called once the response's status and headers have arrived, `duration` after the
+    /// request was signed. The body hasn't been read yet.
+    fn after_response(&self, _request: &SignedRequest, _response: &Response, _duration: Duration) {}
+}
+
+impl fmt::Debug for Interceptor + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Interceptor")
+    }
+}
+
+/// Logs the service, operation, region, status code, request ID and duration of every
+/// request at the `info` level.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogInterceptor;
+
+impl Interceptor for LogInterceptor {
+    fn after_response(&self, request: &SignedRequest, response: &Response, duration: Duration) {
+        info!(
+            "service={} operation={} region={} status={} request_id={} duration_ms={}",
+            request.service(),
+            operation(request).unwrap_or("-"),
+            request.region(),
+            response.status.to_u16(),
+            request_id(response).unwrap_or("-"),
+            duration.as_secs() * 1000 + (duration.subsec_nanos() / 1_000_000) as u64
+        );
+    }
+}
+
+/// Records an OpenTelemetry client span for every response, named after the service and
+/// operation, with them, the region, the status code and the request ID as attributes. Spans
+/// go to the tracer registered with `opentelemetry::global`.
+#[cfg(feature = "opentelemetry")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OpenTelemetryInterceptor;
+
+#[cfg(feature = "opentelemetry")]
+impl Interceptor for OpenTelemetryInterceptor {
+    fn after_response(&self, request: &SignedRequest, response: &Response, duration: Duration) {
+        use std::time::SystemTime;
+
+        use opentelemetry::{KeyValue, global};
+        use opentelemetry::trace::{Span, SpanKind, Tracer};
+
+        let operation = operation(request).unwrap_or("-");
+        let mut attributes = vec![
+            KeyValue::new("rpc.system", "aws-api"),
+            KeyValue::new("rpc.service", request.service().to_owned()),
+            KeyValue::new("rpc.method", operation.to_owned()),
+            KeyValue::new("aws.region", request.region().to_string()),
+            KeyValue::new("http.status_code", response.status.to_u16() as i64),
+        ];
+        if let Some(request_id) = request_id(response) {
+            attributes.push(KeyValue::new("aws.request_id", request_id.to_owned()));
+        }
+
+        // The span is recorded once the response has arrived, so it starts `duration` ago.
+        let tracer = global::tracer("rusoto");
+        let ended = SystemTime::now();
+        let mut span = tracer.span_builder(format!("{}.{}", request.service(), operation))
+            .with_kind(SpanKind::Client)
+            .with_start_time(ended - duration)
+            .with_attributes(attributes)
+            .start(&tracer);
+        span.end_with_timestamp(ended);
+    }
+}
+
+/// The name of the operation a request calls, from the `Action` parameter of query protocol
+/// requests or the `x-amz-target` header of JSON ones. REST requests don't name theirs.
+pub fn operation<'a>(request: &'a SignedRequest) -> Option<&'a str> {
+    if let Some(action) = request.params().get("Action") {
+        return Some(&action[..]);
+    }
+
+    request.headers().get("x-amz-target")
+        .and_then(|values| values.first())
+        .and_then(|value| str::from_utf8(value).ok())
+        .map(|target| target.rsplit('.').next().unwrap_or(target))
+}
+
+/// The ID AWS gave the request, which its support needs to look into a failure.
+pub fn request_id(response: &Response) -> Option<&str> {
+    ["x-amzn-RequestId", "x-amz-request-id"].iter()
+        .filter_map(|name| response.headers.get_raw(*name))
+        .filter_map(|values| values.first())
+        .filter_map(|value| str::from_utf8(value).ok())
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use param::{Params, ServiceParams};
+    use region::Region;
+    use signature::SignedRequest;
+
+    use super::operation;
+
+    #[test]
+    fn operation_of_query_request() {
+        let mut request = SignedRequest::new("POST", "sqs", Region::UsEast1, "/");
+        let mut params = Params::new();
+        params.put("Action", "SendMessage");
+        request.set_params(params);
+
+        assert_eq!(operation(&request), Some("SendMessage"));
+    }
+
+    #[test]
+    fn operation_of_json_request() {
+        let mut request = SignedRequest::new("POST", "dynamodb", Region::UsEast1, "/");
+        request.add_header("x-amz-target", "DynamoDB_20120810.PutItem");
+
+        assert_eq!(operation(&request), Some("PutItem"));
+    }
+
+    #[test]
+    fn rest_requests_have_no_operation() {
+        let request = SignedRequest::new("GET", "s3", Region::UsEast1, "/");
+
+        assert_eq!(operation(&request), None);
+    }
+}
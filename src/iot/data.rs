@@ -0,0 +1,119 @@
+//! The AWS IoT data plane, which publishes MQTT messages over HTTPS.
+
+use std::io::Read;
+use std::sync::Arc;
+
+use credential::ProvideAwsCredentials;
+use error::{AwsError, AwsResult, parse_json_protocol_error};
+use interceptor::Interceptor;
+use param::{Params, ServiceParams};
+use region::Region;
+use signature::{SignedRequest, encode_uri};
+
+/// A client for publishing messages to AWS IoT topics.
+///
+/// Each account has its own data endpoint, which `IotClient::describe_endpoint` returns for the
+/// `iot:Data-ATS` endpoint type. It should be passed to `set_hostname`; otherwise requests go
+/// to the legacy regional endpoint.
+pub struct IotDataClient<P> where P: ProvideAwsCredentials {
+    credentials_provider: P,
+    region: Region,
+    hostname: Option<String>,
+    interceptors: Vec<Arc<Interceptor + Send + Sync>>,
+}
+
+impl<P> IotDataClient<P> where P: ProvideAwsCredentials {
+    pub fn new(credentials_provider: P, region: Region) -> Self {
+        IotDataClient {
+            credentials_provider: credentials_provider,
+            region: region,
+            hostname: None,
+            interceptors: Vec::new(),
+        }
+    }
+
+    /// Send requests to `hostname` instead of the default endpoint for the region.
+    ///
+    /// The hostname may be given as a URL like `http://localhost:8000` to choose the scheme,
+    /// otherwise HTTPS is used.
+    pub fn set_hostname(&mut self, hostname: Option<String>) {
+        self.hostname = hostname;
+    }
+
+    /// Calls `interceptors`, in order, around every request the client sends.
+    pub fn set_interceptors(&mut self, interceptors: Vec<Arc<Interceptor + Send + Sync>>) {
+        self.interceptors = interceptors;
+    }
+
+    /// Publishes `payload` to `topic` with the given MQTT quality of service, which must be 0
+    /// or 1.
+    pub fn publish(&mut self, topic: &str, qos: u8, payload: &[u8]) -> AwsResult<()> {
+        if qos > 1 {
+            return Err(AwsError::new(format!("AWS IoT doesn't support QoS {}", qos)));
+        }
+
+        let mut request = publish_request(self.region, topic, qos, payload);
+        request.set_hostname(self.hostname.clone());
+        request.set_interceptors(self.interceptors.clone());
+
+        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let mut body = String::new();
+        try!(result.read_to_string(&mut body));
+
+        match result.status.to_u16() {
+            200 => Ok(()),
+            _ => Err(parse_json_protocol_error(&body)),
+        }
+    }
+}
+
+/// Builds a `Publish` request. The topic is a single path segment, so its slashes are encoded
+/// along with everything else.
+fn publish_request<'a>(region: Region, topic: &str, qos: u8, payload: &'a [u8]) -> SignedRequest<'a> {
+    let request_uri = format!("/topics/{}", encode_uri(topic));
+    let mut request = SignedRequest::new("POST", "iotdata", region, &request_uri);
+    request.set_endpoint_prefix(Some("data.iot".to_owned()));
+
+    let mut params = Params::new();
+    params.put("qos", &qos.to_string());
+    request.set_params(params);
+    request.set_payload(Some(payload));
+
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use credential::ProfileProvider;
+    use region::Region;
+
+    use super::{IotDataClient, publish_request};
+
+    #[test]
+    fn topic_is_encoded_into_a_single_path_segment() {
+        let request = publish_request(Region::UsEast1, "devices/thermostat 1/temp+°C", 1, b"21.5");
+
+        assert_eq!(request.path(), "/topics/devices%2Fthermostat%201%2Ftemp%2B%C2%B0C");
+        assert_eq!(request.hostname(), "data.iot.us-east-1.amazonaws.com");
+        assert_eq!(request.payload(), Some(&b"21.5"[..]));
+    }
+
+    #[test]
+    fn qos_is_a_query_parameter() {
+        let request = publish_request(Region::UsEast1, "alerts", 0, b"{}");
+
+        assert_eq!(request.params().get("qos"), Some(&"0".to_owned()));
+        assert_eq!(request.params().len(), 1);
+    }
+
+    #[test]
+    fn unsupported_qos_is_rejected() {
+        let provider = ProfileProvider::with_configuration(
+            "tests/sample-data/multiple_profile_credentials",
+            "foo",
+        );
+        let mut client = IotDataClient::new(provider, Region::UsEast1);
+
+        assert!(client.publish("alerts", 2, b"{}").is_err());
+    }
+}
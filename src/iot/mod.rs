@@ -0,0 +1,87 @@
+//! AWS IoT
+
+pub use self::data::IotDataClient;
+
+mod data;
+
+include!(concat!(env!("OUT_DIR"), "/iot.rs"));
+
+use std::collections::HashMap;
+
+fn attribute_payload(attributes: HashMap<String, String>) -> Option<AttributePayload> {
+    if attributes.is_empty() {
+        return None;
+    }
+
+    Some(AttributePayload {
+        attributes: Some(attributes),
+        ..AttributePayload::default()
+    })
+}
+
+impl CreateThingRequest {
+    pub fn new(thing_name: &str, thing_type_name: Option<String>, attributes: HashMap<String, String>)
+        -> CreateThingRequest {
+        CreateThingRequest {
+            attribute_payload: attribute_payload(attributes),
+            thing_name: thing_name.to_owned(),
+            thing_type_name: thing_type_name,
+            ..CreateThingRequest::default()
+        }
+    }
+}
+
+impl UpdateThingRequest {
+    /// Creates a request to set a thing's attributes, which only succeeds if the thing's
+    /// version is still `expected_version`, when one is given.
+    pub fn new(thing_name: &str, attributes: HashMap<String, String>, expected_version: Option<i64>)
+        -> UpdateThingRequest {
+        UpdateThingRequest {
+            attribute_payload: attribute_payload(attributes),
+            expected_version: expected_version,
+            thing_name: thing_name.to_owned(),
+            ..UpdateThingRequest::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json;
+
+    use super::{CreateThingRequest, DescribeThingResponse, UpdateThingRequest};
+
+    #[test]
+    fn create_thing_request() {
+        let mut attributes = HashMap::new();
+        attributes.insert("firmware".to_owned(), "1.4.2".to_owned());
+
+        let request = CreateThingRequest::new("thermostat-1", Some("Thermostat".to_owned()), attributes);
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"attributePayload\":{\"attributes\":{\"firmware\":\"1.4.2\"}"));
+        assert!(json.contains("\"thingTypeName\":\"Thermostat\""));
+        assert!(!json.contains("thermostat-1"));
+    }
+
+    #[test]
+    fn update_thing_without_attributes() {
+        let request = UpdateThingRequest::new("thermostat-1", HashMap::new(), Some(3));
+
+        assert_eq!(request.attribute_payload, None);
+        assert_eq!(request.expected_version, Some(3));
+    }
+
+    #[test]
+    fn parse_describe_thing() {
+        let body = b"{\"attributes\":{\"firmware\":\"1.4.2\"},\"thingName\":\"thermostat-1\",\
+                     \"thingTypeName\":\"Thermostat\",\"version\":3}";
+        let response = serde_json::from_slice::<DescribeThingResponse>(body).unwrap();
+
+        assert_eq!(response.thing_name, Some("thermostat-1".to_owned()));
+        assert_eq!(response.version, Some(3));
+        assert_eq!(response.attributes.unwrap()["firmware"], "1.4.2");
+    }
+}
@@ -0,0 +1,113 @@
+//! Amazon Managed Streaming for Apache Kafka
+//!
+//! Manages MSK clusters. Records are produced and consumed with a Kafka client connected to
+//! the cluster's bootstrap brokers.
+
+include!(concat!(env!("OUT_DIR"), "/kafka.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+impl BrokerNodeGroupInfo {
+    /// Brokers of `instance_type`, such as `kafka.m5.large`, spread across the availability
+    /// zones of `client_subnets`.
+    pub fn new(instance_type: &str, client_subnets: Vec<String>, security_groups: Vec<String>)
+        -> BrokerNodeGroupInfo {
+        BrokerNodeGroupInfo {
+            client_subnets: client_subnets,
+            instance_type: instance_type.to_owned(),
+            security_groups: if security_groups.is_empty() { None } else { Some(security_groups) },
+            ..BrokerNodeGroupInfo::default()
+        }
+    }
+}
+
+impl CreateClusterRequest {
+    /// Creates a request for a cluster of `number_of_broker_nodes` brokers, which must be a
+    /// multiple of the number of client subnets. Without `encryption_info`, data is encrypted
+    /// at rest with an AWS managed key and in transit with TLS.
+    pub fn new(cluster_name: &str, kafka_version: &str, number_of_broker_nodes: i32,
+        broker_node_group_info: BrokerNodeGroupInfo, encryption_info: Option<EncryptionInfo>) -> CreateClusterRequest {
+        CreateClusterRequest {
+            broker_node_group_info: broker_node_group_info,
+            cluster_name: cluster_name.to_owned(),
+            encryption_info: encryption_info,
+            kafka_version: kafka_version.to_owned(),
+            number_of_broker_nodes: number_of_broker_nodes,
+            ..CreateClusterRequest::default()
+        }
+    }
+}
+
+impl<P> MskClient<P> where P: ProvideAwsCredentials {
+    /// Starts creating a cluster and returns its ARN. The cluster can be used once
+    /// `cluster` reports its state as `ACTIVE`.
+    pub fn create_cluster_arn(&mut self, input: &CreateClusterRequest) -> AwsResult<String> {
+        expect(try!(self.create_cluster(input)).cluster_arn, "the cluster ARN")
+    }
+
+    /// Describes the cluster `cluster_arn`.
+    pub fn cluster(&mut self, cluster_arn: &str) -> AwsResult<ClusterInfo> {
+        let input = DescribeClusterRequest { cluster_arn: cluster_arn.to_owned() };
+
+        expect(try!(self.describe_cluster(&input)).cluster_info, "the cluster")
+    }
+
+    /// Returns the brokers a Kafka client connects to first, for plaintext, TLS and
+    /// SASL/SCRAM. Only the strings for the authentication the cluster allows are included.
+    pub fn bootstrap_brokers(&mut self, cluster_arn: &str) -> AwsResult<GetBootstrapBrokersResponse> {
+        self.get_bootstrap_brokers(&GetBootstrapBrokersRequest { cluster_arn: cluster_arn.to_owned() })
+    }
+
+    /// Calls `ListClusters` until every page has been read, and returns the clusters whose
+    /// names start with `cluster_name_filter`, or all of them without one.
+    pub fn list_all_clusters(&mut self, cluster_name_filter: Option<String>) -> AwsResult<Vec<ClusterInfo>> {
+        let mut input = ListClustersRequest {
+            cluster_name_filter: cluster_name_filter,
+            ..ListClustersRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_clusters(&input));
+
+            Ok((output.cluster_info_list.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{BrokerNodeGroupInfo, CreateClusterRequest, GetBootstrapBrokersResponse};
+
+    #[test]
+    fn parse_bootstrap_brokers() {
+        let mut body = String::new();
+        File::open("tests/sample-data/kafka_get_bootstrap_brokers.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let brokers = serde_json::from_str::<GetBootstrapBrokersResponse>(&body).unwrap();
+
+        assert!(brokers.bootstrap_broker_string.unwrap().ends_with(":9092"));
+        assert!(brokers.bootstrap_broker_string_tls.unwrap().ends_with(":9094"));
+
+        let sasl_scram = brokers.bootstrap_broker_string_sasl_scram.unwrap();
+        assert_eq!(sasl_scram.split(',').count(), 2);
+        assert!(sasl_scram.ends_with(":9096"));
+    }
+
+    #[test]
+    fn create_cluster_request() {
+        let brokers = BrokerNodeGroupInfo::new("kafka.m5.large",
+            vec!["subnet-0a1b2c3d".to_owned(), "subnet-4e5f6a7b".to_owned()], Vec::new());
+        let json = serde_json::to_string(&CreateClusterRequest::new("orders", "2.8.1", 2, brokers, None)).unwrap();
+
+        assert!(json.contains("\"numberOfBrokerNodes\":2"));
+        assert!(json.contains("\"instanceType\":\"kafka.m5.large\""));
+        assert!(json.contains("\"encryptionInfo\":null"));
+    }
+}
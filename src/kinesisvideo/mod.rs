@@ -0,0 +1,176 @@
+//! Amazon Kinesis Video Streams
+//!
+//! Creates and describes video streams. Media isn't sent to the Kinesis Video endpoint itself,
+//! but to a data endpoint for each stream and API, which `put_media` looks up first.
+
+include!(concat!(env!("OUT_DIR"), "/kinesisvideo.rs"));
+
+use error::{AwsError, expect};
+
+/// The API name of the data endpoint media is sent to.
+pub const PUT_MEDIA: &'static str = "PUT_MEDIA";
+
+/// The API name of the data endpoint media is read from.
+pub const GET_MEDIA: &'static str = "GET_MEDIA";
+
+/// The API name of the data endpoint which lists a stream's fragments.
+pub const LIST_FRAGMENTS: &'static str = "LIST_FRAGMENTS";
+
+/// The API name of the data endpoint which reads a list of fragments.
+pub const GET_MEDIA_FOR_FRAGMENT_LIST: &'static str = "GET_MEDIA_FOR_FRAGMENT_LIST";
+
+/// The timecode type of fragments whose timecodes are timestamps.
+pub const ABSOLUTE: &'static str = "ABSOLUTE";
+
+/// The timecode type of fragments whose timecodes are relative to the producer start
+/// timestamp.
+pub const RELATIVE: &'static str = "RELATIVE";
+
+/// The acknowledgement of a fragment, sent back for each stage of `PutMedia`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PutMediaAck {
+    /// `BUFFERING`, `RECEIVED`, `PERSISTED`, `ERROR` or `IDLE`.
+    pub event_type: String,
+    pub fragment_timecode: Option<i64>,
+    pub fragment_number: Option<String>,
+    pub error_id: Option<i64>,
+}
+
+/// Parses the acknowledgements in the body of a `PutMedia` response, which are JSON objects
+/// sent one after another as fragments are processed.
+fn parse_acks(body: &str) -> AwsResult<Vec<PutMediaAck>> {
+    let mut acks = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, character) in body.char_indices() {
+        if in_string {
+            match character {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match character {
+            '"' => in_string = true,
+            '{' => {
+                if depth == 0 {
+                    start = index;
+                }
+                depth += 1;
+            }
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    acks.push(try!(parse_ack(&body[start..index + 1])));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(acks)
+}
+
+fn parse_ack(json: &str) -> AwsResult<PutMediaAck> {
+    let value = try!(serde_json::from_str::<serde_json::Value>(json).map_err(|err| {
+        AwsError::new(format!("Failed to parse PutMedia acknowledgement as JSON: {}", err))
+    }));
+
+    Ok(PutMediaAck {
+        event_type: value.find("EventType").and_then(|value| value.as_string()).unwrap_or("").to_owned(),
+        fragment_timecode: value.find("FragmentTimecode").and_then(|value| value.as_i64()),
+        fragment_number: value.find("FragmentNumber").and_then(|value| value.as_string()).map(str::to_owned),
+        error_id: value.find("ErrorId").and_then(|value| value.as_i64()),
+    })
+}
+
+impl<P> KinesisVideoClient<P> where P: ProvideAwsCredentials {
+    /// Creates a stream, which is `CREATING` until it can take media, and returns its ARN.
+    /// Media is kept for `data_retention_in_hours`, or not at all without it.
+    pub fn add_stream(&mut self, stream_name: &str, device_name: Option<String>, media_type: Option<String>,
+        data_retention_in_hours: Option<i32>, kms_key_id: Option<String>) -> AwsResult<String> {
+        let input = CreateStreamInput {
+            data_retention_in_hours: data_retention_in_hours,
+            device_name: device_name,
+            kms_key_id: kms_key_id,
+            media_type: media_type,
+            stream_name: stream_name.to_owned(),
+            ..CreateStreamInput::default()
+        };
+
+        expect(try!(self.create_stream(&input)).stream_arn, "the stream ARN")
+    }
+
+    /// Describes the stream with the name `stream_name` or the ARN `stream_arn`.
+    pub fn stream(&mut self, stream_name: Option<String>, stream_arn: Option<String>) -> AwsResult<StreamInfo> {
+        let input = DescribeStreamInput { stream_arn: stream_arn, stream_name: stream_name };
+
+        expect(try!(self.describe_stream(&input)).stream_info, "the stream info")
+    }
+
+    /// Returns the URL of the endpoint to call the API `api_name`, such as `PUT_MEDIA`, on for
+    /// the stream with the name `stream_name` or the ARN `stream_arn`.
+    pub fn data_endpoint(&mut self, stream_name: Option<String>, stream_arn: Option<String>, api_name: &str)
+        -> AwsResult<String> {
+        let input = GetDataEndpointInput {
+            api_name: api_name.to_owned(),
+            stream_arn: stream_arn,
+            stream_name: stream_name,
+        };
+
+        expect(try!(self.get_data_endpoint(&input)).data_endpoint, "the data endpoint")
+    }
+
+    /// Sends `media`, which is Matroska (MKV) fragments, to the stream `stream_name` through
+    /// its `PUT_MEDIA` data endpoint, and returns the acknowledgements of the fragments.
+    /// `producer_start_timestamp` is in seconds since the epoch, and `fragment_timecode_type`
+    /// is `ABSOLUTE` or `RELATIVE` to it.
+    pub fn put_media(&mut self, stream_name: &str, media: &[u8], fragment_timecode_type: &str,
+        producer_start_timestamp: f64) -> AwsResult<Vec<PutMediaAck>> {
+        let data_endpoint = try!(self.data_endpoint(Some(stream_name.to_owned()), None, PUT_MEDIA));
+
+        let mut request = SignedRequest::new("POST", "kinesisvideo", self.region, "/putMedia");
+        request.set_hostname(Some(data_endpoint));
+        request.set_interceptors(self.interceptors.clone());
+        request.add_header("x-amzn-stream-name", stream_name);
+        request.add_header("x-amzn-fragment-timecode-type", fragment_timecode_type);
+        request.add_header("x-amzn-producer-start-timestamp", &format!("{:.3}", producer_start_timestamp));
+        request.set_payload(Some(media));
+
+        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let mut body = String::new();
+        try!(result.read_to_string(&mut body));
+
+        match result.status.to_u16() {
+            200 => parse_acks(&body),
+            _ => Err(parse_json_protocol_error(&body)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_acks;
+
+    #[test]
+    fn parse_acks_sent_back_to_back() {
+        let acks = parse_acks(
+            "{\"EventType\":\"RECEIVED\",\"FragmentTimecode\":1000,\"FragmentNumber\":\"9134385233318150666\"}\
+             {\"EventType\":\"PERSISTED\",\"FragmentTimecode\":1000,\"FragmentNumber\":\"9134385233318150666\"}\n\
+             {\"EventType\":\"ERROR\",\"FragmentTimecode\":3000,\"ErrorId\":4002}"
+        ).unwrap();
+
+        assert_eq!(acks.len(), 3);
+        assert_eq!(acks[0].event_type, "RECEIVED");
+        assert_eq!(acks[1].fragment_number, Some("9134385233318150666".to_owned()));
+        assert_eq!(acks[2].event_type, "ERROR");
+        assert_eq!(acks[2].error_id, Some(4002));
+        assert_eq!(acks[2].fragment_number, None);
+    }
+}
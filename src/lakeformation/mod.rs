@@ -0,0 +1,198 @@
+//! AWS Lake Formation
+//!
+//! Governs access to the databases and tables of the Glue Data Catalog, down to individual
+//! columns, and to the S3 locations registered as the data lake's storage.
+
+include!(concat!(env!("OUT_DIR"), "/lakeformation.rs"));
+
+use pagination::read_all_pages;
+
+/// The permission to read the data of a table or columns.
+pub const SELECT: &'static str = "SELECT";
+
+/// The permission to describe a database or table's metadata.
+pub const DESCRIBE: &'static str = "DESCRIBE";
+
+/// The permission to create tables in a database.
+pub const CREATE_TABLE: &'static str = "CREATE_TABLE";
+
+/// The permission to create tables that point at a registered data location.
+pub const DATA_LOCATION_ACCESS: &'static str = "DATA_LOCATION_ACCESS";
+
+/// Every permission on a resource.
+pub const ALL: &'static str = "ALL";
+
+/// A Data Catalog resource permissions are granted on, in the account's catalog.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataLakeResource {
+    Database {
+        name: String,
+    },
+    Table {
+        database_name: String,
+        name: String,
+    },
+    /// An S3 location registered with `RegisterResource`.
+    DataLocation {
+        resource_arn: String,
+    },
+    /// A subset of a table's columns.
+    Column {
+        database_name: String,
+        table_name: String,
+        column_names: Vec<String>,
+    },
+}
+
+impl From<DataLakeResource> for Resource {
+    fn from(resource: DataLakeResource) -> Resource {
+        match resource {
+            DataLakeResource::Database { name } => {
+                Resource {
+                    database: Some(DatabaseResource { name: name, ..DatabaseResource::default() }),
+                    ..Resource::default()
+                }
+            }
+            DataLakeResource::Table { database_name, name } => {
+                Resource {
+                    table: Some(TableResource {
+                        database_name: database_name,
+                        name: Some(name),
+                        ..TableResource::default()
+                    }),
+                    ..Resource::default()
+                }
+            }
+            DataLakeResource::DataLocation { resource_arn } => {
+                Resource {
+                    data_location: Some(DataLocationResource {
+                        resource_arn: resource_arn,
+                        ..DataLocationResource::default()
+                    }),
+                    ..Resource::default()
+                }
+            }
+            DataLakeResource::Column { database_name, table_name, column_names } => {
+                Resource {
+                    table_with_columns: Some(TableWithColumnsResource {
+                        column_names: Some(column_names),
+                        database_name: database_name,
+                        name: table_name,
+                        ..TableWithColumnsResource::default()
+                    }),
+                    ..Resource::default()
+                }
+            }
+        }
+    }
+}
+
+impl DataLakePrincipal {
+    /// The IAM user or role `arn`, or an external account ID.
+    pub fn new(identifier: &str) -> DataLakePrincipal {
+        DataLakePrincipal { data_lake_principal_identifier: Some(identifier.to_owned()) }
+    }
+}
+
+impl<P> LakeFormationClient<P> where P: ProvideAwsCredentials {
+    /// Grants `principal` the `permissions` on `resource`, along with the right to grant
+    /// `permissions_with_grant_option` to others.
+    pub fn grant(&mut self, principal: DataLakePrincipal, resource: DataLakeResource, permissions: Vec<String>,
+        permissions_with_grant_option: Vec<String>) -> AwsResult<()> {
+        let input = GrantPermissionsRequest {
+            permissions: permissions,
+            permissions_with_grant_option: if permissions_with_grant_option.is_empty() {
+                None
+            } else {
+                Some(permissions_with_grant_option)
+            },
+            principal: principal,
+            resource: resource.into(),
+            ..GrantPermissionsRequest::default()
+        };
+
+        try!(self.grant_permissions(&input));
+
+        Ok(())
+    }
+
+    /// Calls `ListPermissions` until every page has been read, and returns the permissions on
+    /// `resource` held by `principal`, either of which can be left out to list them all.
+    pub fn list_all_permissions(&mut self, resource: Option<DataLakeResource>,
+        principal: Option<DataLakePrincipal>) -> AwsResult<Vec<PrincipalResourcePermissions>> {
+        let mut input = ListPermissionsRequest {
+            principal: principal,
+            resource: resource.map(Resource::from),
+            ..ListPermissionsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_permissions(&input));
+
+            Ok((output.principal_resource_permissions.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Registers the S3 location `resource_arn` as storage of the data lake, accessed with the
+    /// service-linked role or `role_arn`.
+    pub fn register(&mut self, resource_arn: &str, use_service_linked_role: bool, role_arn: Option<String>)
+        -> AwsResult<()> {
+        let input = RegisterResourceRequest {
+            resource_arn: resource_arn.to_owned(),
+            role_arn: role_arn,
+            use_service_linked_role: Some(use_service_linked_role),
+            ..RegisterResourceRequest::default()
+        };
+
+        try!(self.register_resource(&input));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DataLakeResource, ListPermissionsResponse, Resource, SELECT};
+
+    #[test]
+    fn parse_column_permissions() {
+        let mut body = String::new();
+        File::open("tests/sample-data/lakeformation_list_permissions.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let permissions = serde_json::from_str::<ListPermissionsResponse>(&body).unwrap()
+            .principal_resource_permissions.unwrap();
+
+        assert_eq!(permissions.len(), 2);
+
+        let columns = permissions[0].resource.as_ref().unwrap().table_with_columns.as_ref().unwrap();
+        assert_eq!(columns.database_name, "sales");
+        assert_eq!(columns.name, "orders");
+        assert_eq!(columns.column_names, Some(vec!["order_id".to_owned(), "order_total".to_owned()]));
+        assert_eq!(permissions[0].permissions, Some(vec![SELECT.to_owned()]));
+        assert_eq!(
+            permissions[0].principal.as_ref().unwrap().data_lake_principal_identifier,
+            Some("arn:aws:iam::123456789012:role/analyst".to_owned())
+        );
+
+        let database = permissions[1].resource.as_ref().unwrap().database.as_ref().unwrap();
+        assert_eq!(database.name, "sales");
+    }
+
+    #[test]
+    fn column_resource() {
+        let resource = Resource::from(DataLakeResource::Column {
+            database_name: "sales".to_owned(),
+            table_name: "orders".to_owned(),
+            column_names: vec!["order_id".to_owned()],
+        });
+
+        assert!(resource.table.is_none());
+        assert_eq!(resource.table_with_columns.unwrap().name, "orders");
+    }
+}
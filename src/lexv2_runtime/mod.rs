@@ -0,0 +1,156 @@
+//! Amazon Lex Runtime V2
+//!
+//! Sends text and speech to a bot alias and returns its replies. `RecognizeUtterance` passes
+//! its session state, messages and interpretations in headers, as gzipped and base64 encoded
+//! JSON, which `encode_header` and `decode_header` convert to and from the types
+//! `RecognizeText` uses.
+//!
+//! `StartConversation` isn't supported, since it streams in both directions over HTTP/2.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use rustc_serialize::base64::{FromBase64, STANDARD, ToBase64};
+use serde::{Deserialize, Serialize};
+
+use error::AwsError;
+
+include!(concat!(env!("OUT_DIR"), "/lexv2-runtime.rs"));
+
+impl RecognizeTextRequest {
+    /// Creates a request to send `text` to the bot alias `bot_alias_id` for the locale
+    /// `locale_id`, such as `en_US`. Requests with the same `session_id` continue a
+    /// conversation.
+    pub fn new(bot_id: &str, bot_alias_id: &str, locale_id: &str, session_id: &str, text: &str,
+        session_state: Option<SessionState>, request_attributes: HashMap<String, String>) -> RecognizeTextRequest {
+        RecognizeTextRequest {
+            bot_alias_id: bot_alias_id.to_owned(),
+            bot_id: bot_id.to_owned(),
+            locale_id: locale_id.to_owned(),
+            request_attributes: if request_attributes.is_empty() { None } else { Some(request_attributes) },
+            session_id: session_id.to_owned(),
+            session_state: session_state,
+            text: text.to_owned(),
+        }
+    }
+}
+
+impl RecognizeUtteranceRequest {
+    /// Creates a request to send `input_stream`, which is either audio, such as
+    /// `audio/l16; rate=16000; channels=1`, or `text/plain; charset=utf-8`, to the bot alias
+    /// `bot_alias_id`. The reply is spoken unless `response_content_type` is
+    /// `text/plain;charset=utf-8`.
+    pub fn new(bot_id: &str, bot_alias_id: &str, locale_id: &str, session_id: &str, request_content_type: &str,
+        response_content_type: Option<String>, input_stream: Vec<u8>) -> RecognizeUtteranceRequest {
+        RecognizeUtteranceRequest {
+            bot_alias_id: bot_alias_id.to_owned(),
+            bot_id: bot_id.to_owned(),
+            input_stream: Some(input_stream),
+            locale_id: locale_id.to_owned(),
+            request_content_type: request_content_type.to_owned(),
+            response_content_type: response_content_type,
+            session_id: session_id.to_owned(),
+            ..RecognizeUtteranceRequest::default()
+        }
+    }
+}
+
+impl RecognizeUtteranceResponse {
+    /// The messages the bot replied with.
+    pub fn decode_messages(&self) -> AwsResult<Vec<Message>> {
+        self.messages.as_ref().map_or(Ok(Vec::new()), |messages| decode_header(messages))
+    }
+
+    /// The intents the utterance might have been meant as, most likely first.
+    pub fn decode_interpretations(&self) -> AwsResult<Vec<Interpretation>> {
+        self.interpretations.as_ref().map_or(Ok(Vec::new()), |interpretations| decode_header(interpretations))
+    }
+
+    /// The state of the conversation after the utterance.
+    pub fn decode_session_state(&self) -> AwsResult<Option<SessionState>> {
+        match self.session_state {
+            Some(ref session_state) => decode_header(session_state).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Gzips and base64 encodes the JSON of `value`, as `RecognizeUtterance` expects its
+/// `session_state` and `request_attributes` headers to be.
+pub fn encode_header<T>(value: &T) -> AwsResult<String> where T: Serialize {
+    let json = try!(serde_json::to_string(value).map_err(|err| AwsError::new(format!("{}", err))));
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::Default);
+
+    try!(encoder.write_all(json.as_bytes()).map_err(|err| AwsError::new(format!("{}", err))));
+
+    Ok(try!(encoder.finish().map_err(|err| AwsError::new(format!("{}", err)))).to_base64(STANDARD))
+}
+
+/// Decodes a gzipped and base64 encoded JSON header of a `RecognizeUtterance` response.
+pub fn decode_header<T>(value: &str) -> AwsResult<T> where T: Deserialize {
+    let compressed = try!(value.from_base64().map_err(|err| {
+        AwsError::new(format!("Failed to decode header as base64: {}", err))
+    }));
+    let mut json = Vec::new();
+
+    try!(GzDecoder::new(&compressed[..])
+        .and_then(|mut decoder| decoder.read_to_end(&mut json))
+        .map_err(|err| AwsError::new(format!("Failed to decompress header: {}", err))));
+
+    serde_json::from_slice(&json).map_err(|err| AwsError::new(format!("Failed to parse header as JSON: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{Message, RecognizeTextResponse, RecognizeUtteranceResponse, encode_header};
+
+    #[test]
+    fn parse_fulfilled_intent_with_several_slots() {
+        let mut body = String::new();
+        File::open("tests/sample-data/lexv2_runtime_recognize_text.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<RecognizeTextResponse>(&body).unwrap();
+
+        let intent = response.session_state.unwrap().intent.unwrap();
+        assert_eq!(intent.name, "OrderPizza");
+        assert_eq!(intent.state, Some("Fulfilled".to_owned()));
+
+        let slots = intent.slots.unwrap();
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots["Size"].value.as_ref().unwrap().interpreted_value, "Large");
+        assert_eq!(slots["Crust"].value.as_ref().unwrap().original_value, Some("thin".to_owned()));
+
+        let interpretations = response.interpretations.unwrap();
+        assert_eq!(interpretations.len(), 2);
+        assert_eq!(interpretations[0].nlu_confidence.as_ref().unwrap().score, Some(0.94));
+        assert_eq!(interpretations[1].nlu_confidence, None);
+
+        let messages = response.messages.unwrap();
+        assert_eq!(messages[0].content_type, "PlainText");
+        assert_eq!(response.request_attributes.unwrap()["channel"], "web");
+    }
+
+    #[test]
+    fn decode_encoded_messages() {
+        let messages = vec![Message {
+            content: Some("Which size would you like?".to_owned()),
+            content_type: "PlainText".to_owned(),
+            ..Message::default()
+        }];
+        let response = RecognizeUtteranceResponse {
+            messages: Some(encode_header(&messages).unwrap()),
+            ..RecognizeUtteranceResponse::default()
+        };
+
+        assert_eq!(response.decode_messages().unwrap(), messages);
+        assert_eq!(response.decode_session_state().unwrap(), None);
+    }
+}
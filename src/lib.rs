@@ -10,9 +10,11 @@
 //! A high level overview is available in `README.md` at https://github.com/rusoto/rusoto.
 
 extern crate chrono;
+#[cfg(feature = "lexv2_runtime")] extern crate flate2;
 extern crate hyper;
 #[macro_use] extern crate log;
 extern crate openssl;
+#[cfg(feature = "opentelemetry")] extern crate opentelemetry;
 extern crate regex;
 extern crate rustc_serialize;
 extern crate serde;
@@ -24,32 +26,174 @@ extern crate xml;
 pub use credential::{
     AwsCredentials,
     ChainProvider,
+    EcsProvider,
     EnvironmentProvider,
+    ExpiryStatus,
+    ExpiryWatchProvider,
     IamProvider,
     ProfileProvider,
     ProvideAwsCredentials,
+    SsoProvider,
 };
 pub use error::{AwsError, AwsResult};
+pub use interceptor::{Interceptor, LogInterceptor};
+#[cfg(feature = "opentelemetry")] pub use interceptor::OpenTelemetryInterceptor;
 pub use region::{ParseRegionError, Region};
+pub use signature::SignedRequest;
 
 mod credential;
+mod endpoint_cache;
 mod error;
+mod event_stream;
+mod interceptor;
+mod pagination;
 mod param;
 mod region;
 mod request;
 mod xmlutil;
 mod serialization;
 #[macro_use] mod signature;
+mod sigv4a;
 
+#[cfg(feature = "amplify")]
+pub mod amplify;
+#[cfg(feature = "apigatewayv2")]
+pub mod apigatewayv2;
+#[cfg(feature = "appconfig")]
+pub mod appconfig;
+#[cfg(feature = "appmesh")]
+pub mod appmesh;
+#[cfg(feature = "appstream")]
+pub mod appstream;
+#[cfg(feature = "autoscaling")]
+pub mod autoscaling;
+#[cfg(feature = "batch")]
+pub mod batch;
+#[cfg(feature = "bedrock_runtime")]
+pub mod bedrock_runtime;
+#[cfg(feature = "ce")]
+pub mod ce;
+#[cfg(feature = "chime_sdk_messaging")]
+pub mod chime_sdk_messaging;
+#[cfg(feature = "cloudtrail")]
+pub mod cloudtrail;
+#[cfg(feature = "cloudwatch")]
+pub mod cloudwatch;
+#[cfg(feature = "codebuild")]
+pub mod codebuild;
+#[cfg(feature = "codecommit")]
+pub mod codecommit;
+#[cfg(feature = "codepipeline")]
+pub mod codepipeline;
+#[cfg(feature = "comprehend")]
+pub mod comprehend;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "connect")]
+pub mod connect;
+#[cfg(feature = "datasync")]
+pub mod datasync;
+#[cfg(feature = "detective")]
+pub mod detective;
+#[cfg(feature = "dms")]
+pub mod dms;
 #[cfg(feature = "dynamodb")]
 pub mod dynamodb;
+#[cfg(feature = "dynamodb_streams")]
+pub mod dynamodb_streams;
+#[cfg(feature = "ec2")]
+pub mod ec2;
 #[cfg(feature = "ecs")]
 pub mod ecs;
+#[cfg(feature = "elasticache")]
+pub mod elasticache;
+#[cfg(feature = "elbv2")]
+pub mod elbv2;
 #[cfg(feature = "ets")]
 pub mod ets;
+#[cfg(feature = "firehose")]
+pub mod firehose;
+#[cfg(feature = "frauddetector")]
+pub mod frauddetector;
+#[cfg(feature = "glacier")]
+pub mod glacier;
+#[cfg(feature = "globalaccelerator")]
+pub mod globalaccelerator;
+#[cfg(feature = "guardduty")]
+pub mod guardduty;
+#[cfg(feature = "health")]
+pub mod health;
+#[cfg(feature = "inspectorv2")]
+pub mod inspectorv2;
+#[cfg(feature = "iot")]
+pub mod iot;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "kinesisvideo")]
+pub mod kinesisvideo;
 #[cfg(feature = "kms")]
 pub mod kms;
+#[cfg(feature = "lakeformation")]
+pub mod lakeformation;
+#[cfg(feature = "lexv2_runtime")]
+pub mod lexv2_runtime;
+#[cfg(feature = "logs")]
+pub mod logs;
+#[cfg(feature = "macie2")]
+pub mod macie2;
+#[cfg(feature = "mediaconvert")]
+pub mod mediaconvert;
+#[cfg(feature = "mq")]
+pub mod mq;
+#[cfg(feature = "network_firewall")]
+pub mod network_firewall;
+#[cfg(feature = "opensearch")]
+pub mod opensearch;
+#[cfg(feature = "organizations")]
+pub mod organizations;
+#[cfg(feature = "pinpoint")]
+pub mod pinpoint;
+#[cfg(feature = "polly")]
+pub mod polly;
+#[cfg(feature = "qldb")]
+pub mod qldb;
+#[cfg(feature = "qldb_session")]
+pub mod qldb_session;
+#[cfg(feature = "ram")]
+pub mod ram;
+#[cfg(feature = "redshift")]
+pub mod redshift;
+#[cfg(feature = "rekognition")]
+pub mod rekognition;
 #[cfg(feature = "s3")]
 pub mod s3;
+#[cfg(feature = "sagemaker")]
+pub mod sagemaker;
+#[cfg(feature = "sagemaker_runtime")]
+pub mod sagemaker_runtime;
+#[cfg(feature = "secretsmanager")]
+pub mod secretsmanager;
+#[cfg(feature = "securityhub")]
+pub mod securityhub;
+#[cfg(feature = "service_quotas")]
+pub mod service_quotas;
+#[cfg(feature = "sns")]
+pub mod sns;
 #[cfg(feature = "sqs")]
 pub mod sqs;
+#[cfg(feature = "tagging")]
+pub mod tagging;
+#[cfg(feature = "textract")]
+pub mod textract;
+#[cfg(feature = "timestream_write")]
+pub mod timestream_write;
+#[cfg(feature = "transfer")]
+pub mod transfer;
+#[cfg(feature = "translate")]
+pub mod translate;
+#[cfg(feature = "waf")]
+pub mod waf;
+#[cfg(feature = "workspaces")]
+pub mod workspaces;
+#[cfg(feature = "xray")]
+pub mod xray;
@@ -0,0 +1,164 @@
+//! Amazon CloudWatch Logs
+
+include!(concat!(env!("OUT_DIR"), "/logs.rs"));
+
+use std::thread;
+use std::time::Duration;
+
+use error::AwsError;
+
+/// The state of a Logs Insights query, parsed from `GetQueryResultsResponse::status`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueryState {
+    Scheduled,
+    Running,
+    Complete,
+    Failed,
+    Cancelled,
+    Timeout,
+    Unknown,
+}
+
+impl QueryState {
+    pub fn from_status(status: &str) -> QueryState {
+        match status {
+            "Scheduled" => QueryState::Scheduled,
+            "Running" => QueryState::Running,
+            "Complete" => QueryState::Complete,
+            "Failed" => QueryState::Failed,
+            "Cancelled" => QueryState::Cancelled,
+            "Timeout" => QueryState::Timeout,
+            _ => QueryState::Unknown,
+        }
+    }
+
+    /// Whether the query has stopped running, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        match *self {
+            QueryState::Scheduled | QueryState::Running => false,
+            _ => true,
+        }
+    }
+}
+
+impl StartQueryRequest {
+    /// Creates a request to run `query_string` over the given log groups, between two times
+    /// given in seconds since the epoch.
+    pub fn new(log_group_names: Vec<String>, start_time: i64, end_time: i64, query_string: &str,
+        limit: Option<i32>) -> StartQueryRequest {
+        StartQueryRequest {
+            end_time: end_time,
+            limit: limit,
+            log_group_names: Some(log_group_names),
+            query_string: query_string.to_owned(),
+            start_time: start_time,
+            ..StartQueryRequest::default()
+        }
+    }
+}
+
+impl GetQueryResultsResponse {
+    /// The state of the query, which is `Unknown` if the response didn't include one.
+    pub fn query_state(&self) -> QueryState {
+        self.status.as_ref().map_or(QueryState::Unknown, |status| QueryState::from_status(status))
+    }
+}
+
+impl<P> CloudWatchLogsClient<P> where P: ProvideAwsCredentials {
+    /// Calls `GetQueryResults` every `interval` until the query `query_id` has finished, then
+    /// returns its results.
+    ///
+    /// Queries that fail, are cancelled or time out are returned as errors.
+    pub fn poll_query(&mut self, query_id: &str, interval: Duration) -> AwsResult<GetQueryResultsResponse> {
+        let request = GetQueryResultsRequest { query_id: query_id.to_owned() };
+
+        poll_until_finished(|| self.get_query_results(&request), interval)
+    }
+}
+
+fn poll_until_finished<F>(mut get_query_results: F, interval: Duration) -> AwsResult<GetQueryResultsResponse>
+where F: FnMut() -> AwsResult<GetQueryResultsResponse> {
+    loop {
+        let response = try!(get_query_results());
+
+        match response.query_state() {
+            QueryState::Complete => return Ok(response),
+            state if state.is_finished() => {
+                return Err(AwsError::new(format!("Logs Insights query finished with status {:?}", state)));
+            }
+            _ => thread::sleep(interval),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+    use std::time::Duration;
+
+    use serde_json;
+
+    use error::AwsResult;
+    use super::{GetQueryResultsResponse, QueryState, StartQueryRequest, poll_until_finished};
+
+    fn response_with_status(status: &str) -> AwsResult<GetQueryResultsResponse> {
+        Ok(GetQueryResultsResponse {
+            status: Some(status.to_owned()),
+            ..GetQueryResultsResponse::default()
+        })
+    }
+
+    #[test]
+    fn parse_query_results() {
+        let mut body = String::new();
+        File::open("tests/sample-data/logs_get_query_results.json").unwrap().read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<GetQueryResultsResponse>(&body).unwrap();
+        let results = response.results.as_ref().unwrap();
+
+        assert_eq!(response.query_state(), QueryState::Complete);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0][0].field, Some("@timestamp".to_owned()));
+        assert_eq!(results[0][1].field, Some("@message".to_owned()));
+        assert_eq!(results[1][1].value, Some("ERROR Connection refused".to_owned()));
+        assert_eq!(response.statistics.unwrap().records_matched, Some(2.0));
+    }
+
+    #[test]
+    fn polling_stops_when_complete() {
+        let mut statuses = vec!["Complete", "Running", "Running", "Scheduled"];
+        let mut calls = 0;
+
+        let response = poll_until_finished(|| {
+            calls += 1;
+            response_with_status(statuses.pop().unwrap())
+        }, Duration::from_millis(0)).unwrap();
+
+        assert_eq!(response.query_state(), QueryState::Complete);
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn polling_fails_on_terminal_states() {
+        for status in &["Failed", "Cancelled", "Timeout", "Unknown"] {
+            let result = poll_until_finished(|| response_with_status(status), Duration::from_millis(0));
+
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn start_query_request() {
+        let request = StartQueryRequest::new(
+            vec!["/aws/lambda/resize".to_owned()],
+            1463097600,
+            1463184000,
+            "fields @timestamp, @message | filter @message like /ERROR/",
+            Some(100),
+        );
+
+        assert_eq!(request.log_group_names, Some(vec!["/aws/lambda/resize".to_owned()]));
+        assert_eq!(request.limit, Some(100));
+        assert!(serde_json::to_string(&request).unwrap().contains("\"queryString\""));
+    }
+}
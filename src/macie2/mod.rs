@@ -0,0 +1,149 @@
+//! Amazon Macie
+//!
+//! Discovers sensitive data, such as credentials and personal information, in S3 buckets
+//! with classification jobs, and reports what it finds, along with bucket policy issues, as
+//! findings.
+
+use openssl::crypto::rand::rand_bytes;
+use rustc_serialize::hex::ToHex;
+
+include!(concat!(env!("OUT_DIR"), "/macie2.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+/// The status of a Macie account that's enabled.
+pub const ENABLED: &'static str = "ENABLED";
+
+/// The status of a Macie account that's suspended, which stops its jobs and monitoring.
+pub const PAUSED: &'static str = "PAUSED";
+
+/// The job type of a classification job that runs once.
+pub const ONE_TIME: &'static str = "ONE_TIME";
+
+/// The job type of a classification job that runs on its `schedule_frequency`.
+pub const SCHEDULED: &'static str = "SCHEDULED";
+
+/// The category of sensitive data such as private keys and secret access keys.
+pub const CREDENTIALS: &'static str = "CREDENTIALS";
+
+impl S3JobDefinition {
+    /// Analyzes every object in `buckets` of the account `account_id`.
+    pub fn new(account_id: &str, buckets: Vec<String>) -> S3JobDefinition {
+        S3JobDefinition {
+            bucket_definitions: Some(vec![S3BucketDefinitionForJob {
+                account_id: account_id.to_owned(),
+                buckets: buckets,
+            }]),
+            ..S3JobDefinition::default()
+        }
+    }
+}
+
+impl Finding {
+    /// The categories of sensitive data the finding reports, such as `CREDENTIALS`.
+    pub fn sensitive_data_categories(&self) -> Vec<&str> {
+        self.classification_details.as_ref()
+            .and_then(|details| details.result.as_ref())
+            .and_then(|result| result.sensitive_data.as_ref())
+            .map_or(Vec::new(), |sensitive_data| {
+                sensitive_data.iter().filter_map(|item| item.category.as_ref().map(|category| &category[..])).collect()
+            })
+    }
+}
+
+impl<P> Macie2Client<P> where P: ProvideAwsCredentials {
+    /// Enables Macie for the account with the status `ENABLED`, or suspends it with `PAUSED`,
+    /// publishing policy findings every `finding_publishing_frequency`, such as `ONE_HOUR`.
+    pub fn enable(&mut self, finding_publishing_frequency: Option<String>, status: &str) -> AwsResult<()> {
+        let input = EnableMacieRequest {
+            client_token: Some(rand_bytes(16).to_hex()),
+            finding_publishing_frequency: finding_publishing_frequency,
+            status: Some(status.to_owned()),
+        };
+
+        try!(self.enable_macie(&input));
+
+        Ok(())
+    }
+
+    /// Creates a classification job of the type `ONE_TIME` or `SCHEDULED`, and returns its ID.
+    pub fn create_job(&mut self, name: &str, job_type: &str, s3_job_definition: S3JobDefinition,
+        schedule_frequency: Option<JobScheduleFrequency>) -> AwsResult<String> {
+        let input = CreateClassificationJobRequest {
+            client_token: rand_bytes(16).to_hex(),
+            job_type: job_type.to_owned(),
+            name: name.to_owned(),
+            s3_job_definition: s3_job_definition,
+            schedule_frequency: schedule_frequency,
+            ..CreateClassificationJobRequest::default()
+        };
+
+        expect(try!(self.create_classification_job(&input)).job_id, "the job ID")
+    }
+
+    /// Calls `ListFindings` until every page has been read, and returns the IDs of the findings
+    /// that match `finding_criteria`, in the order of `sort_criteria`.
+    pub fn list_all_findings(&mut self, finding_criteria: Option<FindingCriteria>,
+        sort_criteria: Option<SortCriteria>) -> AwsResult<Vec<String>> {
+        let mut input = ListFindingsRequest {
+            finding_criteria: finding_criteria,
+            sort_criteria: sort_criteria,
+            ..ListFindingsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_findings(&input));
+
+            Ok((output.finding_ids.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Describes the findings `finding_ids`, of which there can be up to 50.
+    pub fn findings(&mut self, finding_ids: Vec<String>) -> AwsResult<Vec<Finding>> {
+        let input = GetFindingsRequest {
+            finding_ids: finding_ids,
+            ..GetFindingsRequest::default()
+        };
+
+        Ok(try!(self.get_findings(&input)).findings.unwrap_or_else(Vec::new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{CREDENTIALS, GetFindingsResponse};
+
+    #[test]
+    fn parse_credentials_finding() {
+        let mut body = String::new();
+        File::open("tests/sample-data/macie2_get_findings.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let mut findings = serde_json::from_str::<GetFindingsResponse>(&body).unwrap().findings.unwrap();
+
+        assert_eq!(findings.len(), 1);
+
+        let finding = findings.remove(0);
+        assert_eq!(finding.aws_type, Some("SensitiveData:S3Object/Credentials".to_owned()));
+        assert_eq!(finding.created_at, Some("2023-06-12T09:41:27.012Z".to_owned()));
+        assert_eq!(finding.severity.as_ref().unwrap().description, Some("High".to_owned()));
+        assert_eq!(finding.sensitive_data_categories(), vec![CREDENTIALS]);
+
+        let result = finding.classification_details.as_ref().unwrap().result.as_ref().unwrap();
+        let detections = result.sensitive_data.as_ref().unwrap()[0].detections.as_ref().unwrap();
+        assert_eq!(detections[0].aws_type, Some("AWS_CREDENTIALS".to_owned()));
+        assert_eq!(detections[0].count, Some(2));
+
+        let resources = finding.resources_affected.unwrap();
+        let bucket = resources.s3_bucket.unwrap();
+        assert_eq!(bucket.name, Some("build-artifacts".to_owned()));
+        assert_eq!(bucket.default_server_side_encryption.unwrap().encryption_type, Some("aws:kms".to_owned()));
+        assert_eq!(resources.s3_object.unwrap().key, Some("deploy/config.env".to_owned()));
+    }
+}
@@ -0,0 +1,72 @@
+//! AWS Elemental MediaConvert
+
+include!(concat!(env!("OUT_DIR"), "/mediaconvert.rs"));
+
+use error::AwsError;
+
+impl<P> MediaConvertClient<P> where P: ProvideAwsCredentials {
+    /// Creates a client which sends requests to the account-specific endpoint for `region`.
+    ///
+    /// MediaConvert only accepts job requests on an endpoint unique to each account, so this
+    /// calls `DescribeEndpoints` once and caches the result for every subsequent request.
+    pub fn with_discovered_endpoint(credentials_provider: P, region: Region) -> AwsResult<Self> {
+        let mut client = MediaConvertClient::new(credentials_provider, region);
+        try!(client.discover_endpoint());
+
+        Ok(client)
+    }
+
+    /// Calls `DescribeEndpoints` on the current endpoint, then sends every subsequent request
+    /// to the account-specific endpoint it returns.
+    pub fn discover_endpoint(&mut self) -> AwsResult<()> {
+        let endpoints = try!(self.describe_endpoints(&DescribeEndpointsRequest::default()));
+
+        self.set_hostname(Some(try!(endpoint_url(&endpoints))));
+
+        Ok(())
+    }
+}
+
+/// Extracts the URL of the first endpoint returned by `DescribeEndpoints`, which
+/// `set_hostname` takes as is.
+fn endpoint_url(response: &DescribeEndpointsResponse) -> AwsResult<String> {
+    let url = response.endpoints.as_ref()
+        .and_then(|endpoints| endpoints.first())
+        .and_then(|endpoint| endpoint.url.as_ref());
+
+    match url {
+        Some(url) => Ok(url.to_owned()),
+        None => Err(AwsError::new("DescribeEndpoints returned no endpoints")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{DescribeEndpointsResponse, endpoint_url};
+
+    #[test]
+    fn endpoint_url_from_describe_endpoints() {
+        let mut body = String::new();
+        File::open("tests/sample-data/mediaconvert_describe_endpoints.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+
+        let response = serde_json::from_str::<DescribeEndpointsResponse>(&body).unwrap();
+
+        assert_eq!(
+            endpoint_url(&response).unwrap(),
+            "https://abcd1234.mediaconvert.us-west-2.amazonaws.com"
+        );
+    }
+
+    #[test]
+    fn endpoint_url_without_endpoints() {
+        let response = serde_json::from_str::<DescribeEndpointsResponse>("{\"endpoints\":[]}").unwrap();
+
+        assert!(endpoint_url(&response).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! Amazon MQ
+//!
+//! Manages ActiveMQ and RabbitMQ brokers. Messages are sent to a broker's endpoints with the
+//! engine's own protocols rather than through this client.
+
+include!(concat!(env!("OUT_DIR"), "/mq.rs"));
+
+use pagination::read_all_pages;
+
+/// The engine type of Apache ActiveMQ brokers.
+pub const ACTIVEMQ: &'static str = "ACTIVEMQ";
+
+/// The engine type of RabbitMQ brokers.
+pub const RABBITMQ: &'static str = "RABBITMQ";
+
+/// The deployment mode of a broker with a single instance in one availability zone.
+pub const SINGLE_INSTANCE: &'static str = "SINGLE_INSTANCE";
+
+/// The deployment mode of an ActiveMQ broker with a standby instance in a second availability zone.
+pub const ACTIVE_STANDBY_MULTI_AZ: &'static str = "ACTIVE_STANDBY_MULTI_AZ";
+
+/// The deployment mode of a RabbitMQ broker clustered across three availability zones.
+pub const CLUSTER_MULTI_AZ: &'static str = "CLUSTER_MULTI_AZ";
+
+impl User {
+    /// A broker user without access to the ActiveMQ web console.
+    pub fn new(username: &str, password: &str) -> User {
+        User {
+            password: password.to_owned(),
+            username: username.to_owned(),
+            ..User::default()
+        }
+    }
+}
+
+impl CreateBrokerRequest {
+    /// Creates a request for a broker that isn't publicly accessible, with the engine type
+    /// `ACTIVEMQ` or `RABBITMQ`, and a deployment mode such as `SINGLE_INSTANCE`.
+    pub fn new(broker_name: &str, engine_type: &str, engine_version: &str, host_instance_type: &str,
+        deployment_mode: &str, users: Vec<User>, auto_minor_version_upgrade: bool) -> CreateBrokerRequest {
+        CreateBrokerRequest {
+            auto_minor_version_upgrade: auto_minor_version_upgrade,
+            broker_name: broker_name.to_owned(),
+            deployment_mode: deployment_mode.to_owned(),
+            engine_type: engine_type.to_owned(),
+            engine_version: engine_version.to_owned(),
+            host_instance_type: host_instance_type.to_owned(),
+            publicly_accessible: false,
+            users: users,
+            ..CreateBrokerRequest::default()
+        }
+    }
+}
+
+impl<P> MqClient<P> where P: ProvideAwsCredentials {
+    /// Describes the broker `broker_id`, including its state and the endpoints of each of its
+    /// instances.
+    pub fn broker(&mut self, broker_id: &str) -> AwsResult<DescribeBrokerResponse> {
+        self.describe_broker(&DescribeBrokerRequest { broker_id: broker_id.to_owned() })
+    }
+
+    /// Calls `ListBrokers` until every page has been read, with up to `max_results` brokers on
+    /// each, and returns all of the brokers in the region.
+    pub fn list_all_brokers(&mut self, max_results: Option<i32>) -> AwsResult<Vec<BrokerSummary>> {
+        let mut input = ListBrokersRequest {
+            max_results: max_results,
+            ..ListBrokersRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_brokers(&input));
+
+            Ok((output.broker_summaries.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{ACTIVEMQ, CreateBrokerRequest, DescribeBrokerResponse, SINGLE_INSTANCE, User};
+
+    #[test]
+    fn parse_running_broker_with_several_endpoints() {
+        let mut body = String::new();
+        File::open("tests/sample-data/mq_describe_broker.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let broker = serde_json::from_str::<DescribeBrokerResponse>(&body).unwrap();
+
+        assert_eq!(broker.broker_name, Some("orders".to_owned()));
+        assert_eq!(broker.broker_state, Some("RUNNING".to_owned()));
+        assert_eq!(broker.engine_type, Some(ACTIVEMQ.to_owned()));
+
+        let instances = broker.broker_instances.unwrap();
+        assert_eq!(instances.len(), 1);
+
+        let endpoints = instances[0].endpoints.as_ref().unwrap();
+        assert_eq!(endpoints.len(), 3);
+        assert!(endpoints[1].starts_with("amqp+ssl://"));
+        assert!(endpoints[2].starts_with("stomp+ssl://"));
+    }
+
+    #[test]
+    fn create_broker_request() {
+        let request = CreateBrokerRequest::new("orders", ACTIVEMQ, "5.15.14", "mq.m5.large", SINGLE_INSTANCE,
+            vec![User::new("admin", "correct-horse-battery")], true);
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"engineType\":\"ACTIVEMQ\""));
+        assert!(json.contains("\"publiclyAccessible\":false"));
+        assert!(json.contains("\"username\":\"admin\""));
+    }
+}
@@ -0,0 +1,112 @@
+//! AWS Network Firewall
+
+use std::error::Error;
+
+use error::AwsError;
+
+include!(concat!(env!("OUT_DIR"), "/network-firewall.rs"));
+
+/// Whether an error is the `InvalidTokenException` Network Firewall returns when an update's
+/// token is stale, because the resource changed after it was described.
+pub fn is_invalid_token(error: &AwsError) -> bool {
+    error.description().starts_with("InvalidTokenException")
+}
+
+impl CreateFirewallRequest {
+    pub fn new(firewall_name: &str, firewall_policy_arn: &str, vpc_id: &str,
+        subnet_mappings: Vec<SubnetMapping>, tags: Vec<Tag>) -> CreateFirewallRequest {
+        CreateFirewallRequest {
+            firewall_name: firewall_name.to_owned(),
+            firewall_policy_arn: firewall_policy_arn.to_owned(),
+            subnet_mappings: subnet_mappings,
+            tags: if tags.is_empty() { None } else { Some(tags) },
+            vpc_id: vpc_id.to_owned(),
+            ..CreateFirewallRequest::default()
+        }
+    }
+}
+
+impl UpdateFirewallPolicyRequest {
+    /// Creates a request to replace the policy described by `description` with
+    /// `firewall_policy`, echoing the description's update token back.
+    pub fn from_description(description: &DescribeFirewallPolicyResponse, firewall_policy: FirewallPolicy)
+        -> UpdateFirewallPolicyRequest {
+        UpdateFirewallPolicyRequest {
+            firewall_policy: firewall_policy,
+            firewall_policy_arn: Some(description.firewall_policy_response.firewall_policy_arn.clone()),
+            update_token: description.update_token.clone(),
+            ..UpdateFirewallPolicyRequest::default()
+        }
+    }
+}
+
+impl<P> NetworkFirewallClient<P> where P: ProvideAwsCredentials {
+    /// Describes a firewall by name or ARN.
+    pub fn firewall(&mut self, firewall_name: Option<String>, firewall_arn: Option<String>)
+        -> AwsResult<DescribeFirewallResponse> {
+        self.describe_firewall(&DescribeFirewallRequest {
+            firewall_arn: firewall_arn,
+            firewall_name: firewall_name,
+        })
+    }
+
+    /// Describes the firewall policy `firewall_policy_arn`, passes its current rules to
+    /// `update`, then saves them with the update token of the description.
+    ///
+    /// Fails with an error `is_invalid_token` accepts if the policy was changed in between, in
+    /// which case calling this again reapplies `update` to the newer rules.
+    pub fn modify_firewall_policy<F>(&mut self, firewall_policy_arn: &str, update: F)
+        -> AwsResult<UpdateFirewallPolicyResponse> where F: FnOnce(&mut FirewallPolicy) {
+        let description = try!(self.describe_firewall_policy(&DescribeFirewallPolicyRequest {
+            firewall_policy_arn: Some(firewall_policy_arn.to_owned()),
+            ..DescribeFirewallPolicyRequest::default()
+        }));
+        let mut firewall_policy = try!(description.firewall_policy.clone().ok_or_else(|| {
+            AwsError::new("DescribeFirewallPolicy response did not include a firewall policy")
+        }));
+
+        update(&mut firewall_policy);
+
+        self.update_firewall_policy(&UpdateFirewallPolicyRequest::from_description(&description, firewall_policy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use error::AwsError;
+    use super::{DescribeFirewallPolicyResponse, UpdateFirewallPolicyRequest, is_invalid_token};
+
+    #[test]
+    fn update_token_round_trip() {
+        let mut body = String::new();
+        File::open("tests/sample-data/network_firewall_describe_firewall_policy.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let description = serde_json::from_str::<DescribeFirewallPolicyResponse>(&body).unwrap();
+
+        let mut firewall_policy = description.firewall_policy.clone().unwrap();
+        assert_eq!(firewall_policy.stateless_default_actions, vec!["aws:forward_to_sfe".to_owned()]);
+        firewall_policy.stateless_default_actions = vec!["aws:drop".to_owned()];
+
+        let request = UpdateFirewallPolicyRequest::from_description(&description, firewall_policy);
+        assert_eq!(request.update_token, "a3c7f3c2-2e4d-4b6a-9c1e-6f0d1f2a8b47");
+        assert_eq!(
+            request.firewall_policy_arn,
+            Some("arn:aws:network-firewall:us-west-2:123456789012:firewall-policy/egress".to_owned())
+        );
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"UpdateToken\":\"a3c7f3c2-2e4d-4b6a-9c1e-6f0d1f2a8b47\""));
+        assert!(json.contains("\"StatelessDefaultActions\":[\"aws:drop\"]"));
+    }
+
+    #[test]
+    fn stale_token_error() {
+        assert!(is_invalid_token(&AwsError::new("InvalidTokenException: Update token is stale")));
+        assert!(!is_invalid_token(&AwsError::new("ResourceNotFoundException: No such policy")));
+    }
+}
@@ -0,0 +1,53 @@
+//! Amazon OpenSearch Service
+
+include!(concat!(env!("OUT_DIR"), "/opensearch.rs"));
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::DescribeDomainResponse;
+
+    fn describe_domain_response(path: &str) -> DescribeDomainResponse {
+        let mut body = String::new();
+        File::open(path).unwrap().read_to_string(&mut body).unwrap();
+
+        serde_json::from_str::<DescribeDomainResponse>(&body).unwrap()
+    }
+
+    #[test]
+    fn describe_opensearch_domain() {
+        let status = describe_domain_response("tests/sample-data/opensearch_describe_domain.json")
+            .domain_status;
+
+        assert_eq!(status.domain_name, "search-logs");
+        assert_eq!(status.engine_version, Some("OpenSearch_2.11".to_owned()));
+        assert_eq!(status.processing, Some(false));
+        assert_eq!(
+            status.endpoint,
+            Some("search-search-logs-abcdefghijklmnop.us-east-1.es.amazonaws.com".to_owned())
+        );
+        assert_eq!(status.cluster_config.instance_count, Some(3));
+        assert_eq!(status.cluster_config.instance_type, Some("r6g.large.search".to_owned()));
+        assert_eq!(status.ebs_options.unwrap().volume_type, Some("gp3".to_owned()));
+    }
+
+    #[test]
+    fn describe_elasticsearch_domain() {
+        // The legacy Elasticsearch Service API reports the version as `ElasticsearchVersion`
+        // ("7.10"), while the OpenSearch Service API reports every domain's version in
+        // `EngineVersion`, prefixed with the engine name.
+        let status = describe_domain_response(
+            "tests/sample-data/opensearch_describe_domain_elasticsearch.json"
+        ).domain_status;
+
+        assert_eq!(status.domain_name, "legacy-search");
+        assert_eq!(status.engine_version, Some("Elasticsearch_7.10".to_owned()));
+        assert_eq!(status.processing, Some(true));
+        assert_eq!(status.cluster_config.instance_count, Some(2));
+        assert_eq!(status.cluster_config.zone_awareness_enabled, Some(false));
+    }
+}
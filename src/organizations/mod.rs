@@ -0,0 +1,226 @@
+//! AWS Organizations
+//!
+//! Organizations has a single endpoint in us-east-1, which requests are sent to and signed for
+//! whatever region the client is created with.
+
+include!(concat!(env!("OUT_DIR"), "/organizations.rs"));
+
+use std::thread;
+use std::time::Duration;
+
+use error::{AwsError, expect};
+use pagination::read_all_pages;
+
+/// The state of an account being created, parsed from `CreateAccountStatus::state`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountCreationState {
+    InProgress,
+    Succeeded,
+    Failed,
+    Unknown,
+}
+
+impl AccountCreationState {
+    pub fn from_state(state: &str) -> AccountCreationState {
+        match state {
+            "IN_PROGRESS" => AccountCreationState::InProgress,
+            "SUCCEEDED" => AccountCreationState::Succeeded,
+            "FAILED" => AccountCreationState::Failed,
+            _ => AccountCreationState::Unknown,
+        }
+    }
+}
+
+impl CreateAccountStatus {
+    /// The state of the request, which is `Unknown` if the status didn't include one.
+    pub fn creation_state(&self) -> AccountCreationState {
+        self.state.as_ref().map_or(AccountCreationState::Unknown, |state| AccountCreationState::from_state(state))
+    }
+}
+
+impl MoveAccountRequest {
+    /// Creates a request to move an account from the root or organizational unit
+    /// `source_parent_id` to `destination_parent_id`.
+    pub fn new(account_id: &str, source_parent_id: &str, destination_parent_id: &str) -> MoveAccountRequest {
+        MoveAccountRequest {
+            account_id: account_id.to_owned(),
+            destination_parent_id: destination_parent_id.to_owned(),
+            source_parent_id: source_parent_id.to_owned(),
+        }
+    }
+}
+
+impl<P> OrganizationsClient<P> where P: ProvideAwsCredentials {
+    /// Calls `ListAccounts` until every page has been read, and returns all of the accounts in
+    /// the organization.
+    pub fn list_all_accounts(&mut self) -> AwsResult<Vec<Account>> {
+        let mut input = ListAccountsRequest::default();
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_accounts(&input));
+
+            Ok((output.accounts.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Describes an account in the organization.
+    pub fn account(&mut self, account_id: &str) -> AwsResult<Account> {
+        let input = DescribeAccountRequest { account_id: account_id.to_owned() };
+
+        expect(try!(self.describe_account(&input)).account, "the account")
+    }
+
+    /// Starts creating a member account, returning the status to poll with
+    /// `poll_create_account_status`. Without `role_name`, the account can be administered
+    /// through `OrganizationAccountAccessRole`. `iam_user_access_to_billing` is `ALLOW` or
+    /// `DENY`.
+    pub fn create_member_account(&mut self, email: &str, account_name: &str, role_name: Option<String>,
+        iam_user_access_to_billing: Option<String>) -> AwsResult<CreateAccountStatus> {
+        let input = CreateAccountRequest {
+            account_name: account_name.to_owned(),
+            email: email.to_owned(),
+            iam_user_access_to_billing: iam_user_access_to_billing,
+            role_name: role_name,
+            ..CreateAccountRequest::default()
+        };
+
+        expect(try!(self.create_account(&input)).create_account_status, "the account status")
+    }
+
+    /// Calls `DescribeCreateAccountStatus` every `interval`, at most `max_attempts` times, until
+    /// the account has been created, then returns its status, which includes the new account's
+    /// ID.
+    ///
+    /// Accounts that couldn't be created are returned as errors with the failure reason, as are
+    /// statuses in a state this client doesn't know and accounts still being created after
+    /// `max_attempts` calls.
+    pub fn poll_create_account_status(&mut self, create_account_request_id: &str, interval: Duration,
+        max_attempts: usize) -> AwsResult<CreateAccountStatus> {
+        let request = DescribeCreateAccountStatusRequest {
+            create_account_request_id: create_account_request_id.to_owned(),
+        };
+
+        poll_until_created(|| {
+            expect(try!(self.describe_create_account_status(&request)).create_account_status, "the account status")
+        }, interval, max_attempts)
+    }
+}
+
+fn poll_until_created<F>(mut describe_create_account_status: F, interval: Duration, max_attempts: usize)
+    -> AwsResult<CreateAccountStatus>
+where F: FnMut() -> AwsResult<CreateAccountStatus> {
+    for attempt in 0..max_attempts {
+        if attempt > 0 {
+            thread::sleep(interval);
+        }
+
+        let status = try!(describe_create_account_status());
+
+        match status.creation_state() {
+            AccountCreationState::InProgress => {}
+            AccountCreationState::Succeeded => return Ok(status),
+            AccountCreationState::Failed => {
+                return Err(AwsError::new(format!(
+                    "Account creation failed: {}",
+                    status.failure_reason.as_ref().map_or("no reason given", |reason| &reason[..])
+                )));
+            }
+            AccountCreationState::Unknown => {
+                return Err(AwsError::new(format!(
+                    "Account creation has unknown state {}",
+                    status.state.as_ref().map_or("(none)", |state| &state[..])
+                )));
+            }
+        }
+    }
+
+    Err(AwsError::new(format!("Account creation still in progress after {} attempts", max_attempts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+    use std::time::Duration;
+
+    use serde_json;
+
+    use error::AwsResult;
+    use super::{AccountCreationState, CreateAccountStatus, ListAccountsResponse, poll_until_created};
+
+    fn status(state: &str, failure_reason: Option<&str>) -> AwsResult<CreateAccountStatus> {
+        Ok(CreateAccountStatus {
+            account_id: if state == "SUCCEEDED" { Some("333333333333".to_owned()) } else { None },
+            failure_reason: failure_reason.map(|reason| reason.to_owned()),
+            id: Some("car-exampleaccountcreationrequestid".to_owned()),
+            state: Some(state.to_owned()),
+            ..CreateAccountStatus::default()
+        })
+    }
+
+    #[test]
+    fn parse_accounts() {
+        let mut body = String::new();
+        File::open("tests/sample-data/organizations_list_accounts.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<ListAccountsResponse>(&body).unwrap();
+        let accounts = response.accounts.unwrap();
+
+        assert_eq!(response.next_token, Some("AAQB1c2VybmFtZ".to_owned()));
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].id, Some("111111111111".to_owned()));
+        assert_eq!(accounts[0].joined_method, Some("INVITED".to_owned()));
+        assert_eq!(accounts[1].name, Some("Sandbox".to_owned()));
+        assert_eq!(accounts[1].status, Some("SUSPENDED".to_owned()));
+    }
+
+    #[test]
+    fn polling_stops_when_succeeded() {
+        let mut states = vec!["SUCCEEDED", "IN_PROGRESS", "IN_PROGRESS"];
+        let mut calls = 0;
+
+        let created = poll_until_created(|| {
+            calls += 1;
+            status(states.pop().unwrap(), None)
+        }, Duration::from_millis(0), 5).unwrap();
+
+        assert_eq!(created.creation_state(), AccountCreationState::Succeeded);
+        assert_eq!(created.account_id, Some("333333333333".to_owned()));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn failed_creation_is_an_error() {
+        let mut states = vec!["FAILED", "IN_PROGRESS"];
+
+        let result = poll_until_created(|| {
+            let state = states.pop().unwrap();
+            status(state, if state == "FAILED" { Some("EMAIL_ALREADY_EXISTS") } else { None })
+        }, Duration::from_millis(0), 5);
+
+        assert_eq!(format!("{}", result.unwrap_err()), "Account creation failed: EMAIL_ALREADY_EXISTS");
+    }
+
+    #[test]
+    fn unknown_state_is_an_error() {
+        let mut states = vec!["SUSPENDED", "IN_PROGRESS"];
+
+        let result = poll_until_created(|| status(states.pop().unwrap(), None), Duration::from_millis(0), 5);
+
+        assert_eq!(format!("{}", result.unwrap_err()), "Account creation has unknown state SUSPENDED");
+    }
+
+    #[test]
+    fn polling_gives_up_after_max_attempts() {
+        let mut calls = 0;
+
+        let result = poll_until_created(|| {
+            calls += 1;
+            status("IN_PROGRESS", None)
+        }, Duration::from_millis(0), 3);
+
+        assert_eq!(format!("{}", result.unwrap_err()), "Account creation still in progress after 3 attempts");
+        assert_eq!(calls, 3);
+    }
+}
@@ -0,0 +1,69 @@
+//! Reading every page of paginated operations.
+
+use error::AwsResult;
+
+/// Calls `read_page` until every page has been read, and returns the items of all of them in
+/// order.
+///
+/// `read_page` is called with the token of the page to read, `first_token` the first time,
+/// and returns the page's items along with the token of the next page, if there is one.
+pub fn read_all_pages<K, T, F>(first_token: Option<K>, mut read_page: F) -> AwsResult<Vec<T>>
+where F: FnMut(Option<K>) -> AwsResult<(Vec<T>, Option<K>)> {
+    let mut items = Vec::new();
+    let mut token = first_token;
+
+    loop {
+        let (page, next_token) = try!(read_page(token));
+
+        items.extend(page);
+
+        match next_token {
+            Some(next_token) => token = Some(next_token),
+            None => return Ok(items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::AwsError;
+
+    use super::read_all_pages;
+
+    #[test]
+    fn pages_are_read_in_order_until_there_is_no_token() {
+        let mut tokens = Vec::new();
+
+        let items = read_all_pages(None, |token: Option<usize>| {
+            tokens.push(token);
+
+            let page = token.unwrap_or(0);
+            Ok((vec![page * 2, page * 2 + 1], if page < 2 { Some(page + 1) } else { None }))
+        }).unwrap();
+
+        assert_eq!(items, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(tokens, vec![None, Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn reading_starts_from_first_token() {
+        let items = read_all_pages(Some(2), |token: Option<usize>| {
+            let page = token.unwrap();
+            Ok((vec![page], if page < 3 { Some(page + 1) } else { None }))
+        }).unwrap();
+
+        assert_eq!(items, vec![2, 3]);
+    }
+
+    #[test]
+    fn a_failed_page_is_an_error() {
+        let result = read_all_pages(None, |token: Option<usize>| {
+            match token {
+                None => Ok((vec!["first"], Some(1))),
+                Some(_) => Err(AwsError::new("ThrottlingException: Rate exceeded")),
+            }
+        });
+
+        assert_eq!(result, Err(AwsError::new("ThrottlingException: Rate exceeded")));
+    }
+}
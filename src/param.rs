@@ -5,6 +5,9 @@
 //! Supports optional parameters for calling SQS and ETS.
 
 use std::collections::BTreeMap;
+
+use chrono::{TimeZone, UTC};
+
 pub type Params = BTreeMap<String, String>;
 
 /// Key:value pair for an service parameter.
@@ -17,3 +20,32 @@ impl ServiceParams for Params {
         self.insert(key.into(), val.into());
     }
 }
+
+/// Formats a timestamp given in seconds since the epoch as ISO 8601, the format REST services
+/// expect timestamps in the querystring in. Milliseconds are only included if there are any.
+pub fn iso8601_timestamp(seconds: f64) -> String {
+    let millis = (seconds * 1000.0).round() as i64;
+    let (seconds, millis) = match (millis / 1000, millis % 1000) {
+        (seconds, millis) if millis < 0 => (seconds - 1, millis + 1000),
+        whole_and_millis => whole_and_millis,
+    };
+    let time = UTC.timestamp(seconds, 0).format("%Y-%m-%dT%H:%M:%S");
+
+    if millis == 0 {
+        format!("{}Z", time)
+    } else {
+        format!("{}.{:03}Z", time, millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::iso8601_timestamp;
+
+    #[test]
+    fn timestamps_are_formatted_as_iso8601() {
+        assert_eq!(iso8601_timestamp(1621080000.0), "2021-05-15T12:00:00Z");
+        assert_eq!(iso8601_timestamp(1621080000.25), "2021-05-15T12:00:00.250Z");
+        assert_eq!(iso8601_timestamp(-0.5), "1969-12-31T23:59:59.500Z");
+    }
+}
@@ -0,0 +1,186 @@
+//! Amazon Pinpoint
+//!
+//! Sends messages to users over email, SMS, push notification and voice channels, records
+//! the events of their endpoints and runs campaigns targeting segments of them.
+
+use std::collections::HashMap;
+
+include!(concat!(env!("OUT_DIR"), "/pinpoint.rs"));
+
+/// The channel type of push notifications through Apple Push Notification service.
+pub const APNS: &'static str = "APNS";
+
+/// The channel type of push notifications through Firebase Cloud Messaging.
+pub const GCM: &'static str = "GCM";
+
+/// The channel type of email messages.
+pub const EMAIL: &'static str = "EMAIL";
+
+/// The channel type of SMS messages.
+pub const SMS: &'static str = "SMS";
+
+/// The channel type of voice messages.
+pub const VOICE: &'static str = "VOICE";
+
+/// The delivery status of a message that was delivered to its recipient.
+pub const SUCCESSFUL: &'static str = "SUCCESSFUL";
+
+/// A message for a single channel.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChannelMessage {
+    Apns(APNSMessage),
+    Gcm(GCMMessage),
+    Email(EmailMessage),
+    Sms(SMSMessage),
+    Voice(VoiceMessage),
+}
+
+impl ChannelMessage {
+    /// The channel type, such as `APNS`, of the addresses the message is sent to.
+    pub fn channel_type(&self) -> &'static str {
+        match *self {
+            ChannelMessage::Apns(_) => APNS,
+            ChannelMessage::Gcm(_) => GCM,
+            ChannelMessage::Email(_) => EMAIL,
+            ChannelMessage::Sms(_) => SMS,
+            ChannelMessage::Voice(_) => VOICE,
+        }
+    }
+}
+
+impl MessageRequest {
+    /// Sends `message` to each of `addresses`, which are email addresses, phone numbers or
+    /// device tokens depending on the message's channel.
+    pub fn new(addresses: Vec<String>, message: ChannelMessage) -> MessageRequest {
+        let channel_type = message.channel_type();
+        let mut configuration = DirectMessageConfiguration::default();
+
+        match message {
+            ChannelMessage::Apns(message) => configuration.apns_message = Some(message),
+            ChannelMessage::Gcm(message) => configuration.gcm_message = Some(message),
+            ChannelMessage::Email(message) => configuration.email_message = Some(message),
+            ChannelMessage::Sms(message) => configuration.sms_message = Some(message),
+            ChannelMessage::Voice(message) => configuration.voice_message = Some(message),
+        }
+
+        MessageRequest {
+            addresses: Some(addresses.into_iter().map(|address| {
+                (address, AddressConfiguration {
+                    channel_type: Some(channel_type.to_owned()),
+                    ..AddressConfiguration::default()
+                })
+            }).collect()),
+            message_configuration: configuration,
+            ..MessageRequest::default()
+        }
+    }
+}
+
+impl MessageResponse {
+    /// The addresses whose message wasn't delivered, along with the result explaining why.
+    pub fn failed_addresses(&self) -> Vec<(&str, &MessageResult)> {
+        let mut failed = self.result.as_ref().map_or(Vec::new(), |results| {
+            results.iter()
+                .filter(|&(_, result)| result.delivery_status != SUCCESSFUL)
+                .map(|(address, result)| (&address[..], result))
+                .collect()
+        });
+
+        failed.sort_by(|a, b| a.0.cmp(b.0));
+        failed
+    }
+}
+
+impl EventsBatch {
+    /// The `events` of `endpoint`, keyed by unique event IDs.
+    pub fn new(endpoint: PublicEndpoint, events: HashMap<String, Event>) -> EventsBatch {
+        EventsBatch {
+            endpoint: endpoint,
+            events: events,
+        }
+    }
+}
+
+impl<P> PinpointClient<P> where P: ProvideAwsCredentials {
+    /// Sends a message through the project `application_id` and returns the result for each
+    /// address.
+    pub fn send(&mut self, application_id: &str, message_request: MessageRequest) -> AwsResult<MessageResponse> {
+        let input = SendMessagesRequest {
+            application_id: application_id.to_owned(),
+            message_request: message_request,
+        };
+
+        Ok(try!(self.send_messages(&input)).message_response)
+    }
+
+    /// Records the events of each batch in `batch_item`, which is keyed by endpoint ID, and
+    /// returns the result for each endpoint and event.
+    pub fn record_events(&mut self, application_id: &str, batch_item: HashMap<String, EventsBatch>)
+        -> AwsResult<EventsResponse> {
+        let input = PutEventsRequest {
+            application_id: application_id.to_owned(),
+            events_request: EventsRequest { batch_item: batch_item },
+        };
+
+        Ok(try!(self.put_events(&input)).events_response)
+    }
+
+    /// Creates a campaign in the project `application_id`, which starts on its schedule.
+    pub fn add_campaign(&mut self, application_id: &str, write_campaign_request: WriteCampaignRequest)
+        -> AwsResult<CampaignResponse> {
+        let input = CreateCampaignRequest {
+            application_id: application_id.to_owned(),
+            write_campaign_request: write_campaign_request,
+        };
+
+        Ok(try!(self.create_campaign(&input)).campaign_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{APNSMessage, ChannelMessage, MessageRequest, MessageResponse};
+
+    #[test]
+    fn push_notification_request() {
+        let message = ChannelMessage::Apns(APNSMessage {
+            body: Some("Your order has shipped".to_owned()),
+            title: Some("Order update".to_owned()),
+            badge: Some(1),
+            ..APNSMessage::default()
+        });
+        let request = MessageRequest::new(vec!["a1b2c3d4".to_owned()], message);
+        let json = serde_json::to_string(&request).unwrap();
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+        assert_eq!(value.lookup("Addresses.a1b2c3d4.ChannelType").unwrap().as_string(), Some("APNS"));
+        assert_eq!(value.lookup("MessageConfiguration.APNSMessage.Body").unwrap().as_string(),
+            Some("Your order has shipped"));
+        assert_eq!(value.lookup("MessageConfiguration.APNSMessage.Title").unwrap().as_string(), Some("Order update"));
+        assert_eq!(value.lookup("MessageConfiguration.APNSMessage.Badge").unwrap().as_i64(), Some(1));
+        assert!(value.lookup("MessageConfiguration.GCMMessage").unwrap().is_null());
+    }
+
+    #[test]
+    fn partial_delivery_failure() {
+        let mut body = String::new();
+        File::open("tests/sample-data/pinpoint_send_messages.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<MessageResponse>(&body).unwrap();
+
+        assert_eq!(response.result.as_ref().unwrap().len(), 3);
+        assert_eq!(response.result.as_ref().unwrap()["+14255550100"].delivery_status, "SUCCESSFUL");
+
+        let failed = response.failed_addresses();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].0, "+14255550101");
+        assert_eq!(failed[0].1.delivery_status, "PERMANENT_FAILURE");
+        assert_eq!(failed[0].1.status_code, 400);
+        assert_eq!(failed[1].1.delivery_status, "OPT_OUT");
+    }
+}
@@ -0,0 +1,130 @@
+//! Amazon Polly
+//!
+//! Synthesizes speech from plain text or SSML. The audio of `SynthesizeSpeech` is read into
+//! memory in full, so longer texts are better split up or synthesized with an asynchronous
+//! speech synthesis task.
+
+include!(concat!(env!("OUT_DIR"), "/polly.rs"));
+
+use pagination::read_all_pages;
+
+/// The output format of MP3 audio.
+pub const MP3: &'static str = "mp3";
+
+/// The output format of Ogg Vorbis audio.
+pub const OGG_VORBIS: &'static str = "ogg_vorbis";
+
+/// The output format of signed 16-bit, 1 channel, little-endian PCM audio.
+pub const PCM: &'static str = "pcm";
+
+/// The output format of speech marks, with one JSON object per line.
+pub const JSON: &'static str = "json";
+
+/// The engine of standard text-to-speech voices.
+pub const STANDARD: &'static str = "standard";
+
+/// The engine of neural text-to-speech voices, which only some voices and regions support.
+pub const NEURAL: &'static str = "neural";
+
+/// Returns the content type of audio synthesized in `output_format`, such as `audio/mpeg` for
+/// `mp3`.
+pub fn content_type(output_format: &str) -> Option<&'static str> {
+    match output_format {
+        MP3 => Some("audio/mpeg"),
+        OGG_VORBIS => Some("audio/ogg"),
+        PCM => Some("audio/pcm"),
+        JSON => Some("application/x-json-stream"),
+        _ => None,
+    }
+}
+
+impl SynthesizeSpeechInput {
+    /// Creates a request to speak plain `text` with the voice `voice_id`, such as `Joanna`,
+    /// using the engine `STANDARD` or `NEURAL`. Speech marks are returned instead of audio when
+    /// `output_format` is `JSON` and `speech_mark_types` is set.
+    pub fn new(text: &str, voice_id: &str, output_format: &str, engine: &str, language_code: Option<String>,
+        lexicon_names: Vec<String>, speech_mark_types: Vec<String>, sample_rate: Option<String>)
+        -> SynthesizeSpeechInput {
+        SynthesizeSpeechInput {
+            engine: Some(engine.to_owned()),
+            language_code: language_code,
+            lexicon_names: if lexicon_names.is_empty() { None } else { Some(lexicon_names) },
+            output_format: output_format.to_owned(),
+            sample_rate: sample_rate,
+            speech_mark_types: if speech_mark_types.is_empty() { None } else { Some(speech_mark_types) },
+            text: text.to_owned(),
+            voice_id: voice_id.to_owned(),
+            ..SynthesizeSpeechInput::default()
+        }
+    }
+}
+
+impl<P> PollyClient<P> where P: ProvideAwsCredentials {
+    /// Speaks plain `text` with the voice `voice_id` and the standard engine, and returns the
+    /// audio in `output_format`.
+    pub fn speak(&mut self, text: &str, voice_id: &str, output_format: &str) -> AwsResult<Vec<u8>> {
+        let input = SynthesizeSpeechInput::new(text, voice_id, output_format, STANDARD, None, Vec::new(),
+            Vec::new(), None);
+
+        Ok(try!(self.synthesize_speech(&input)).audio_stream.unwrap_or_else(Vec::new))
+    }
+
+    /// Calls `DescribeVoices` until every page has been read, and returns the voices for
+    /// `language_code` and `engine`, or all of them without either. Bilingual voices are
+    /// included for their additional languages with `include_additional_language_codes`.
+    pub fn describe_all_voices(&mut self, language_code: Option<String>, engine: Option<String>,
+        include_additional_language_codes: bool) -> AwsResult<Vec<Voice>> {
+        let mut input = DescribeVoicesInput {
+            engine: engine,
+            include_additional_language_codes: Some(include_additional_language_codes),
+            language_code: language_code,
+            ..DescribeVoicesInput::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.describe_voices(&input));
+
+            Ok((output.voices.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Calls `ListLexicons` until every page has been read, and returns all of the lexicons in
+    /// the region.
+    pub fn list_all_lexicons(&mut self) -> AwsResult<Vec<LexiconDescription>> {
+        let mut input = ListLexiconsInput::default();
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_lexicons(&input));
+
+            Ok((output.lexicons.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+
+    use super::{MP3, SynthesizeSpeechOutput, content_type};
+
+    #[test]
+    fn mp3_content_type() {
+        assert_eq!(content_type(MP3), Some("audio/mpeg"));
+        assert_eq!(content_type("wav"), None);
+    }
+
+    #[test]
+    fn synthesized_speech_headers() {
+        let mut headers = Headers::new();
+        headers.set_raw("Content-Type", vec![content_type(MP3).unwrap().as_bytes().to_vec()]);
+        headers.set_raw("x-amzn-RequestCharacters", vec![b"12".to_vec()]);
+
+        let output = SynthesizeSpeechOutput::from_response(200, &headers, b"ID3\x04".to_vec());
+
+        assert_eq!(output.content_type, Some("audio/mpeg".to_owned()));
+        assert_eq!(output.request_characters, Some(12));
+        assert_eq!(output.audio_stream, Some(b"ID3\x04".to_vec()));
+    }
+}
@@ -0,0 +1,60 @@
+//! Amazon QLDB
+//!
+//! Manages ledgers. Statements are run against a ledger's data through the separate
+//! `qldb_session` client.
+
+use std::collections::HashMap;
+
+include!(concat!(env!("OUT_DIR"), "/qldb.rs"));
+
+/// The permissions mode that only checks access to the ledger as a whole, without table or
+/// PartiQL command level policies. AWS recommends `STANDARD` instead.
+pub const ALLOW_ALL: &'static str = "ALLOW_ALL";
+
+/// The permissions mode that checks table and PartiQL command level IAM policies.
+pub const STANDARD: &'static str = "STANDARD";
+
+impl CreateLedgerRequest {
+    /// Creates a request for a ledger with the permissions mode `ALLOW_ALL` or `STANDARD`.
+    /// Ledgers with deletion protection can't be deleted until it's turned off.
+    pub fn new(name: &str, permissions_mode: &str, tags: HashMap<String, String>, deletion_protection: bool)
+        -> CreateLedgerRequest {
+        CreateLedgerRequest {
+            deletion_protection: Some(deletion_protection),
+            name: name.to_owned(),
+            permissions_mode: permissions_mode.to_owned(),
+            tags: if tags.is_empty() { None } else { Some(tags) },
+            ..CreateLedgerRequest::default()
+        }
+    }
+}
+
+impl<P> QldbClient<P> where P: ProvideAwsCredentials {
+    /// Describes the ledger `name`, including its state and permissions mode.
+    pub fn ledger(&mut self, name: &str) -> AwsResult<DescribeLedgerResponse> {
+        self.describe_ledger(&DescribeLedgerRequest { name: name.to_owned() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json;
+
+    use super::{CreateLedgerRequest, STANDARD};
+
+    #[test]
+    fn create_ledger_request() {
+        let mut tags = HashMap::new();
+        tags.insert("team".to_owned(), "audit".to_owned());
+
+        let request = CreateLedgerRequest::new("vehicle-registration", STANDARD, tags, true);
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"DeletionProtection\":true"));
+        assert!(json.contains("\"Name\":\"vehicle-registration\",\"PermissionsMode\":\"STANDARD\""));
+        assert!(json.contains("\"Tags\":{\"team\":\"audit\"}"));
+        assert_eq!(CreateLedgerRequest::new("ledger", STANDARD, HashMap::new(), false).tags, None);
+    }
+}
@@ -0,0 +1,108 @@
+//! Amazon QLDB Session
+//!
+//! Runs PartiQL statements against a QLDB ledger. Every call is a `SendCommand` carrying one
+//! command: a session is started, then each transaction is started, has its statements
+//! executed, and is committed, all under the session's token.
+
+use error::AwsError;
+
+include!(concat!(env!("OUT_DIR"), "/qldb-session.rs"));
+
+impl SendCommandRequest {
+    /// A command starting a session on the ledger `ledger_name`.
+    pub fn start_session(ledger_name: &str) -> SendCommandRequest {
+        SendCommandRequest {
+            start_session: Some(StartSessionRequest { ledger_name: ledger_name.to_owned() }),
+            ..SendCommandRequest::default()
+        }
+    }
+
+    /// A command starting a transaction in the session `session_token`.
+    pub fn start_transaction(session_token: &str) -> SendCommandRequest {
+        SendCommandRequest {
+            session_token: Some(session_token.to_owned()),
+            start_transaction: Some(StartTransactionRequest::default()),
+            ..SendCommandRequest::default()
+        }
+    }
+
+    /// A command executing `statement` in a transaction, with its parameters in Ion.
+    pub fn execute_statement(session_token: &str, transaction_id: &str, statement: &str,
+        parameters: Vec<ValueHolder>) -> SendCommandRequest {
+        SendCommandRequest {
+            execute_statement: Some(ExecuteStatementRequest {
+                parameters: if parameters.is_empty() { None } else { Some(parameters) },
+                statement: statement.to_owned(),
+                transaction_id: transaction_id.to_owned(),
+            }),
+            session_token: Some(session_token.to_owned()),
+            ..SendCommandRequest::default()
+        }
+    }
+
+    /// A command committing a transaction. `commit_digest` is the Ion hash of the transaction's
+    /// ID and statements, which QLDB checks against its own before committing.
+    pub fn commit_transaction(session_token: &str, transaction_id: &str, commit_digest: Vec<u8>)
+        -> SendCommandRequest {
+        SendCommandRequest {
+            commit_transaction: Some(CommitTransactionRequest {
+                commit_digest: commit_digest,
+                transaction_id: transaction_id.to_owned(),
+            }),
+            session_token: Some(session_token.to_owned()),
+            ..SendCommandRequest::default()
+        }
+    }
+}
+
+/// Returns the result of the command a `SendCommand` response should always include one for.
+fn expect_result<T>(result: Option<T>, command: &str) -> AwsResult<T> {
+    result.ok_or_else(|| {
+        AwsError::new(format!("SendCommand response did not include a {} result", command))
+    })
+}
+
+impl<P> QldbSessionClient<P> where P: ProvideAwsCredentials {
+    /// Starts a session on a ledger and returns its token.
+    pub fn start_session(&mut self, ledger_name: &str) -> AwsResult<String> {
+        let output = try!(self.send_command(&SendCommandRequest::start_session(ledger_name)));
+        let result = try!(expect_result(output.start_session, "StartSession"));
+
+        result.session_token.ok_or_else(|| {
+            AwsError::new("StartSession result did not include a session token")
+        })
+    }
+
+    /// Starts a transaction and returns its ID.
+    pub fn start_transaction(&mut self, session_token: &str) -> AwsResult<String> {
+        let output = try!(self.send_command(&SendCommandRequest::start_transaction(session_token)));
+        let result = try!(expect_result(output.start_transaction, "StartTransaction"));
+
+        result.transaction_id.ok_or_else(|| {
+            AwsError::new("StartTransaction result did not include a transaction ID")
+        })
+    }
+
+    /// Executes a statement in a transaction and returns the first page of its results.
+    pub fn execute_statement(&mut self, session_token: &str, transaction_id: &str, statement: &str,
+        parameters: Vec<ValueHolder>) -> AwsResult<Page> {
+        let request = SendCommandRequest::execute_statement(
+            session_token,
+            transaction_id,
+            statement,
+            parameters,
+        );
+        let output = try!(self.send_command(&request));
+        let result = try!(expect_result(output.execute_statement, "ExecuteStatement"));
+
+        Ok(result.first_page.unwrap_or_else(Page::default))
+    }
+
+    /// Commits a transaction.
+    pub fn commit_transaction(&mut self, session_token: &str, transaction_id: &str, commit_digest: Vec<u8>)
+        -> AwsResult<CommitTransactionResult> {
+        let request = SendCommandRequest::commit_transaction(session_token, transaction_id, commit_digest);
+
+        expect_result(try!(self.send_command(&request)).commit_transaction, "CommitTransaction")
+    }
+}
@@ -0,0 +1,96 @@
+//! AWS Resource Access Manager
+//!
+//! Shares resources, such as subnets and transit gateways, with other accounts or with an
+//! organization.
+
+include!(concat!(env!("OUT_DIR"), "/ram.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+/// The resource owner for the shares the calling account created.
+pub const SELF: &'static str = "SELF";
+
+/// The resource owner for the shares other accounts created and shared with the calling one.
+pub const OTHER_ACCOUNTS: &'static str = "OTHER-ACCOUNTS";
+
+impl<P> RamClient<P> where P: ProvideAwsCredentials {
+    /// Creates a share of `resource_arns` with `principals`, which are account IDs,
+    /// organization or organizational unit ARNs. Unless `allow_external_principals` is set,
+    /// only accounts in the caller's organization can be added.
+    pub fn create_share(&mut self, name: &str, resource_arns: Vec<String>, principals: Vec<String>,
+        allow_external_principals: bool) -> AwsResult<ResourceShare> {
+        let input = CreateResourceShareRequest {
+            allow_external_principals: Some(allow_external_principals),
+            name: name.to_owned(),
+            principals: if principals.is_empty() { None } else { Some(principals) },
+            resource_arns: if resource_arns.is_empty() { None } else { Some(resource_arns) },
+            ..CreateResourceShareRequest::default()
+        };
+
+        expect(try!(self.create_resource_share(&input)).resource_share, "the resource share")
+    }
+
+    /// Adds resources and principals to an existing share, returning one association for each.
+    pub fn associate_with_share(&mut self, resource_share_arn: &str, resource_arns: Vec<String>,
+        principals: Vec<String>) -> AwsResult<Vec<ResourceShareAssociation>> {
+        let input = AssociateResourceShareRequest {
+            principals: if principals.is_empty() { None } else { Some(principals) },
+            resource_arns: if resource_arns.is_empty() { None } else { Some(resource_arns) },
+            resource_share_arn: resource_share_arn.to_owned(),
+            ..AssociateResourceShareRequest::default()
+        };
+
+        Ok(try!(self.associate_resource_share(&input)).resource_share_associations.unwrap_or_else(Vec::new))
+    }
+
+    /// Calls `GetResourceShares` until every page has been read, and returns the shares owned
+    /// by `SELF` or `OTHER_ACCOUNTS`, limited to `resource_share_arns` unless it's empty.
+    pub fn get_all_resource_shares(&mut self, resource_owner: &str, resource_share_arns: Vec<String>)
+        -> AwsResult<Vec<ResourceShare>> {
+        let mut input = GetResourceSharesRequest {
+            resource_owner: resource_owner.to_owned(),
+            resource_share_arns: if resource_share_arns.is_empty() { None } else { Some(resource_share_arns) },
+            ..GetResourceSharesRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.get_resource_shares(&input));
+
+            Ok((output.resource_shares.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::GetResourceSharesResponse;
+
+    #[test]
+    fn parse_active_and_pending_shares() {
+        let mut body = String::new();
+        File::open("tests/sample-data/ram_get_resource_shares.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<GetResourceSharesResponse>(&body).unwrap();
+        let shares = response.resource_shares.unwrap();
+
+        assert_eq!(response.next_token, None);
+        assert_eq!(shares.len(), 2);
+
+        assert_eq!(shares[0].name, Some("shared-subnets".to_owned()));
+        assert_eq!(shares[0].status, Some("ACTIVE".to_owned()));
+        assert_eq!(shares[0].allow_external_principals, Some(false));
+        assert_eq!(shares[0].tags.as_ref().unwrap()[0].value, Some("network".to_owned()));
+
+        assert_eq!(shares[1].name, Some("shared-transit-gateway".to_owned()));
+        assert_eq!(shares[1].status, Some("PENDING".to_owned()));
+        assert_eq!(shares[1].allow_external_principals, Some(true));
+        assert_eq!(shares[1].tags, None);
+    }
+}
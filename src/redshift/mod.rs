@@ -0,0 +1,168 @@
+//! Amazon Redshift
+
+include!(concat!(env!("OUT_DIR"), "/redshift.rs"));
+
+use pagination::read_all_pages;
+
+/// The optional settings of a new cluster. Anything left unset takes Redshift's default, which
+/// is a single-node cluster with a database named `dev`.
+#[derive(Debug, Default)]
+pub struct CreateClusterOptions {
+    pub db_name: Option<String>,
+    pub cluster_type: Option<String>,
+    pub number_of_nodes: Option<i32>,
+    pub port: Option<i32>,
+    pub cluster_subnet_group_name: Option<String>,
+    pub vpc_security_group_ids: Option<Vec<String>>,
+    pub availability_zone: Option<String>,
+    pub publicly_accessible: Option<bool>,
+    pub encrypted: Option<bool>,
+    pub kms_key_id: Option<String>,
+}
+
+impl CreateClusterMessage {
+    pub fn new(cluster_identifier: &str, node_type: &str, master_username: &str,
+        master_user_password: &str, options: CreateClusterOptions) -> CreateClusterMessage {
+        CreateClusterMessage {
+            availability_zone: options.availability_zone,
+            cluster_identifier: cluster_identifier.to_owned(),
+            cluster_subnet_group_name: options.cluster_subnet_group_name,
+            cluster_type: options.cluster_type,
+            db_name: options.db_name,
+            encrypted: options.encrypted,
+            kms_key_id: options.kms_key_id,
+            master_user_password: master_user_password.to_owned(),
+            master_username: master_username.to_owned(),
+            node_type: node_type.to_owned(),
+            number_of_nodes: options.number_of_nodes,
+            port: options.port,
+            publicly_accessible: options.publicly_accessible,
+            vpc_security_group_ids: options.vpc_security_group_ids,
+            ..CreateClusterMessage::default()
+        }
+    }
+}
+
+impl GetClusterCredentialsMessage {
+    /// Creates a request for a temporary password for `db_user`, which is created if it doesn't
+    /// exist when `auto_create` is set. Credentials last 900 seconds unless `duration_seconds`
+    /// says otherwise.
+    pub fn new(cluster_identifier: &str, db_user: &str, db_name: Option<String>,
+        duration_seconds: Option<i32>, auto_create: bool) -> GetClusterCredentialsMessage {
+        GetClusterCredentialsMessage {
+            auto_create: Some(auto_create),
+            cluster_identifier: cluster_identifier.to_owned(),
+            db_name: db_name,
+            db_user: db_user.to_owned(),
+            duration_seconds: duration_seconds,
+            ..GetClusterCredentialsMessage::default()
+        }
+    }
+}
+
+/// Returns the cluster of a response that should always include one.
+fn expect_cluster(cluster: Option<Cluster>, operation: &str) -> Result<Cluster, AwsError> {
+    cluster.ok_or_else(|| AwsError::new(format!("{} response did not include a cluster", operation)))
+}
+
+impl<P> RedshiftClient<P> where P: ProvideAwsCredentials {
+    /// Calls `DescribeClusters` until every page has been read, and returns all of the clusters,
+    /// or only `cluster_identifier` if given.
+    pub fn describe_all_clusters(&mut self, cluster_identifier: Option<String>)
+        -> Result<Vec<Cluster>, AwsError> {
+        let mut input = DescribeClustersMessage {
+            cluster_identifier: cluster_identifier,
+            ..DescribeClustersMessage::default()
+        };
+
+        read_all_pages(None, |marker| {
+            input.marker = marker;
+            let output = try!(self.describe_clusters(&input));
+
+            Ok((output.clusters.unwrap_or_else(Vec::new), output.marker))
+        })
+    }
+
+    /// Creates a cluster and returns it as it was described on creation, which is usually in
+    /// the `creating` state.
+    pub fn create_new_cluster(&mut self, cluster_identifier: &str, node_type: &str,
+        master_username: &str, master_user_password: &str, options: CreateClusterOptions)
+        -> Result<Cluster, AwsError> {
+        let input = CreateClusterMessage::new(
+            cluster_identifier,
+            node_type,
+            master_username,
+            master_user_password,
+            options,
+        );
+
+        expect_cluster(try!(self.create_cluster(&input)).cluster, "CreateCluster")
+    }
+
+    /// Reboots a cluster, which is `rebooting` until it becomes available again.
+    pub fn reboot(&mut self, cluster_identifier: &str) -> Result<Cluster, AwsError> {
+        let input = RebootClusterMessage { cluster_identifier: cluster_identifier.to_owned() };
+
+        expect_cluster(try!(self.reboot_cluster(&input)).cluster, "RebootCluster")
+    }
+
+    /// Returns a temporary database user name and password, and when they expire.
+    pub fn cluster_credentials(&mut self, cluster_identifier: &str, db_user: &str,
+        db_name: Option<String>, duration_seconds: Option<i32>, auto_create: bool)
+        -> Result<ClusterCredentials, AwsError> {
+        let input = GetClusterCredentialsMessage::new(
+            cluster_identifier,
+            db_user,
+            db_name,
+            duration_seconds,
+            auto_create,
+        );
+
+        self.get_cluster_credentials(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use xml::reader::*;
+
+    use xmlutil::*;
+
+    use super::ClustersMessageDeserializer;
+
+    #[test]
+    fn describe_clusters_in_two_availability_zones() {
+        let file = File::open("tests/sample-data/redshift_describe_clusters.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        reader.next(); // DescribeClustersResponse
+        let result = ClustersMessageDeserializer::deserialize("DescribeClustersResult", &mut reader).unwrap();
+
+        assert_eq!(result.marker, None);
+
+        let clusters = result.clusters.unwrap();
+        assert_eq!(clusters.len(), 2);
+
+        let analytics = &clusters[0];
+        assert_eq!(analytics.cluster_identifier, Some("analytics".to_owned()));
+        assert_eq!(analytics.cluster_status, Some("available".to_owned()));
+        assert_eq!(analytics.availability_zone, Some("us-west-2a".to_owned()));
+        assert_eq!(analytics.number_of_nodes, Some(4));
+
+        let endpoint = analytics.endpoint.as_ref().unwrap();
+        assert_eq!(endpoint.address, Some("analytics.c7xkfigw9q1x.us-west-2.redshift.amazonaws.com".to_owned()));
+        assert_eq!(endpoint.port, Some(5439));
+
+        let reporting = &clusters[1];
+        assert_eq!(reporting.cluster_identifier, Some("reporting".to_owned()));
+        assert_eq!(reporting.cluster_status, Some("creating".to_owned()));
+        assert_eq!(reporting.availability_zone, Some("us-west-2c".to_owned()));
+        assert!(reporting.endpoint.is_none());
+    }
+}
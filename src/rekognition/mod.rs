@@ -0,0 +1,145 @@
+//! Amazon Rekognition
+//!
+//! Detects labels and faces in images, compares faces between two images and indexes faces
+//! into collections to search them later. Images are either sent with the request or read
+//! from S3.
+
+include!(concat!(env!("OUT_DIR"), "/rekognition.rs"));
+
+/// The facial attributes returned by default: the bounding box, confidence, pose, quality
+/// and landmarks.
+pub const DEFAULT: &'static str = "DEFAULT";
+
+/// All facial attributes, including age range, emotions and whether the eyes are open.
+pub const ALL: &'static str = "ALL";
+
+/// The quality filter that indexes or compares every face detected.
+pub const NONE: &'static str = "NONE";
+
+/// The quality filter that lets Rekognition choose the bar faces have to meet.
+pub const AUTO: &'static str = "AUTO";
+
+/// An image for Rekognition to analyze.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RekognitionImage {
+    /// An image stored in S3, in the same region as the client.
+    S3Object {
+        bucket: String,
+        name: String,
+        version: Option<String>,
+    },
+    /// The bytes of a JPEG or PNG image, of up to 5 MB.
+    Bytes(Vec<u8>),
+}
+
+impl From<RekognitionImage> for Image {
+    fn from(image: RekognitionImage) -> Image {
+        match image {
+            RekognitionImage::S3Object { bucket, name, version } => {
+                Image {
+                    s3_object: Some(S3Object {
+                        bucket: Some(bucket),
+                        name: Some(name),
+                        version: version,
+                    }),
+                    ..Image::default()
+                }
+            }
+            RekognitionImage::Bytes(bytes) => {
+                Image {
+                    bytes: Some(bytes),
+                    ..Image::default()
+                }
+            }
+        }
+    }
+}
+
+impl<P> RekognitionClient<P> where P: ProvideAwsCredentials {
+    /// Detects up to `max_labels` labels, such as objects and scenes, in `image`, leaving out
+    /// those with less than `min_confidence` percent confidence.
+    pub fn labels(&mut self, image: RekognitionImage, max_labels: Option<i32>, min_confidence: Option<f32>)
+        -> AwsResult<DetectLabelsResponse> {
+        let input = DetectLabelsRequest {
+            image: image.into(),
+            max_labels: max_labels,
+            min_confidence: min_confidence,
+            ..DetectLabelsRequest::default()
+        };
+
+        self.detect_labels(&input)
+    }
+
+    /// Detects the faces in `image`, with the facial `attributes` `DEFAULT` or `ALL`.
+    pub fn faces(&mut self, image: RekognitionImage, attributes: Vec<String>) -> AwsResult<DetectFacesResponse> {
+        let input = DetectFacesRequest {
+            attributes: if attributes.is_empty() { None } else { Some(attributes) },
+            image: image.into(),
+        };
+
+        self.detect_faces(&input)
+    }
+
+    /// Compares the largest face in `source_image` with each face in `target_image`, matching
+    /// those at least `similarity_threshold` percent similar.
+    pub fn compare(&mut self, source_image: RekognitionImage, target_image: RekognitionImage,
+        similarity_threshold: Option<f32>) -> AwsResult<CompareFacesResponse> {
+        let input = CompareFacesRequest {
+            similarity_threshold: similarity_threshold,
+            source_image: source_image.into(),
+            target_image: target_image.into(),
+            ..CompareFacesRequest::default()
+        };
+
+        self.compare_faces(&input)
+    }
+
+    /// Indexes up to `max_faces` of the faces in `image` into the collection `collection_id`,
+    /// largest first, leaving out those that don't meet `quality_filter`, such as `AUTO`.
+    pub fn index(&mut self, collection_id: &str, image: RekognitionImage, external_image_id: Option<String>,
+        max_faces: Option<i32>, quality_filter: Option<String>) -> AwsResult<IndexFacesResponse> {
+        let input = IndexFacesRequest {
+            collection_id: collection_id.to_owned(),
+            external_image_id: external_image_id,
+            image: image.into(),
+            max_faces: max_faces,
+            quality_filter: quality_filter,
+            ..IndexFacesRequest::default()
+        };
+
+        self.index_faces(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{DetectLabelsRequest, Image, RekognitionImage};
+
+    #[test]
+    fn bytes_image_is_base64_encoded() {
+        let request = DetectLabelsRequest {
+            image: RekognitionImage::Bytes(b"\xff\xd8\xff\xe0".to_vec()).into(),
+            ..DetectLabelsRequest::default()
+        };
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"Bytes\":\"/9j/4A==\""));
+        assert!(json.contains("\"S3Object\":null"));
+    }
+
+    #[test]
+    fn s3_object_image() {
+        let image: Image = RekognitionImage::S3Object {
+            bucket: "photos".to_owned(),
+            name: "team.jpg".to_owned(),
+            version: None,
+        }.into();
+        let s3_object = image.s3_object.unwrap();
+
+        assert_eq!(image.bytes, None);
+        assert_eq!(s3_object.bucket, Some("photos".to_owned()));
+        assert_eq!(s3_object.name, Some("team.jpg".to_owned()));
+    }
+}
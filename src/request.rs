@@ -27,7 +27,9 @@ pub fn send_request(signed_request: &SignedRequest) -> Response {
         hyper_headers.set_raw(h.0.to_owned(), h.1.to_owned());
     }
 
-    let mut final_uri = format!("https://{}{}", signed_request.hostname(), signed_request.canonical_uri());
+    // The canonical URI is encoded a second time for signing, so the path is sent instead.
+    let hostname = signed_request.hostname();
+    let mut final_uri = format!("{}://{}{}", signed_request.scheme(), hostname, signed_request.path());
     if !signed_request.canonical_query_string().is_empty() {
         final_uri = final_uri + &format!("?{}", signed_request.canonical_query_string());
     }
@@ -4,11 +4,15 @@
 #![allow(unused_variables, unused_mut)]
 
 use std::ascii::AsciiExt;
+use std::cmp;
 use std::collections::HashMap;
+use std::error::Error;
 use std::io::BufReader;
 use std::io::Read;
 use std::str::FromStr;
 use std::str;
+use std::sync::Arc;
+use std::thread;
 
 use hyper::client::Response;
 use openssl::crypto::hash::Type::MD5;
@@ -16,11 +20,13 @@ use openssl::crypto::hash::hash;
 use rustc_serialize::base64::{ToBase64, STANDARD};
 use xml::*;
 
-use credential::ProvideAwsCredentials;
+use credential::{AwsCredentials, ProvideAwsCredentials};
 use error::AwsError;
+use interceptor::Interceptor;
+use pagination::read_all_pages;
 use param::{Params, ServiceParams};
 use region::Region;
-use signature::SignedRequest;
+use signature::{SignedRequest, split_endpoint};
 use xmlutil::*;
 
 #[derive(Debug, Default)]
@@ -116,26 +122,17 @@ impl PutBucketNotificationRequestWriter {
     }
 }
 
-pub type Errors = Vec<String>;
+pub type Errors = Vec<S3ClientError>;
 /// Parse `Errors` from XML
 struct ErrorsParser;
 impl ErrorsParser {
     fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Errors, XmlParseError> {
-        let mut obj : Vec<String> = Vec::new();
+        let mut obj = Vec::new();
         while try!(peek_at_name(stack)) == "Error" {
-            obj.push(try!(ErrorsParser::parse_single_error(stack)));
+            obj.push(try!(S3ClientErrorParser::parse_xml("Error", stack)));
         }
         Ok(obj)
     }
-    // hand crafted:
-    fn parse_single_error<T: Peek + Next>(stack: &mut T) -> Result<String, XmlParseError> {
-        // TODO: go back to try!
-
-        match characters(stack) {
-            Err(why) => Err(why),
-            Ok(val) => Ok(val),
-        }
-    }
 }
 /// Write `Errors` contents to a `SignedRequest`
 struct ErrorsWriter;
@@ -144,14 +141,10 @@ impl ErrorsWriter {
         let mut index = 1;
         for element in obj.iter() {
             let key = &format!("{}.{}", name, index);
-            ErrorsWriter::write_param(params, key, &element);
+            S3ClientErrorWriter::write_params(params, key, &element);
             index += 1;
         }
     }
-    // hand crafted:
-    fn write_param(params: &mut Params, key: &str, value: &str) {
-        params.put(key, value);
-    }
 }
 #[derive(Debug, Default)]
 pub struct PutBucketVersioningRequest {
@@ -282,6 +275,13 @@ pub struct Destination {
     /// Amazon resource name (ARN) of the bucket where you want Amazon S3 to store
     /// replicas of the object identified by the rule.
     pub bucket: BucketName,
+    /// The account owning the destination bucket, for cross-account replication.
+    pub account: Option<AccountId>,
+    /// The storage class of the replicas, rather than that of the source object.
+    pub storage_class: Option<StorageClass>,
+    pub access_control_translation: Option<AccessControlTranslation>,
+    pub replication_time: Option<ReplicationTime>,
+    pub metrics: Option<Metrics>,
 }
 
 /// Parse `Destination` from XML
@@ -296,6 +296,26 @@ impl DestinationParser {
                 obj.bucket = try!(BucketNameParser::parse_xml("Bucket", stack));
                 continue;
             }
+            if current_name == "Account" {
+                obj.account = Some(try!(AccountIdParser::parse_xml("Account", stack)));
+                continue;
+            }
+            if current_name == "StorageClass" {
+                obj.storage_class = Some(try!(StorageClassParser::parse_xml("StorageClass", stack)));
+                continue;
+            }
+            if current_name == "AccessControlTranslation" {
+                obj.access_control_translation = Some(try!(AccessControlTranslationParser::parse_xml("AccessControlTranslation", stack)));
+                continue;
+            }
+            if current_name == "ReplicationTime" {
+                obj.replication_time = Some(try!(ReplicationTimeParser::parse_xml("ReplicationTime", stack)));
+                continue;
+            }
+            if current_name == "Metrics" {
+                obj.metrics = Some(try!(MetricsParser::parse_xml("Metrics", stack)));
+                continue;
+            }
             break;
         }
         try!(end_element(tag_name, stack));
@@ -311,6 +331,254 @@ impl DestinationWriter {
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
     }
 }
+pub type AccountId = String;
+/// Parse `AccountId` from XML
+struct AccountIdParser;
+impl AccountIdParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AccountId, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `Enabled` or `Disabled`, for the optional parts of a replication rule.
+pub type ReplicationFeatureStatus = String;
+/// Parse `ReplicationFeatureStatus` from XML
+struct ReplicationFeatureStatusParser;
+impl ReplicationFeatureStatusParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ReplicationFeatureStatus, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+pub type Minutes = i32;
+/// Parse `Minutes` from XML
+struct MinutesParser;
+impl MinutesParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Minutes, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+pub type Priority = i32;
+/// Parse `Priority` from XML
+struct PriorityParser;
+impl PriorityParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Priority, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Makes the owner of the destination bucket the owner of the replicas, for cross-account
+/// replication. `owner` must be `Destination`.
+#[derive(Debug, Default)]
+pub struct AccessControlTranslation {
+    pub owner: String,
+}
+
+/// Parse `AccessControlTranslation` from XML
+struct AccessControlTranslationParser;
+impl AccessControlTranslationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AccessControlTranslation, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = AccessControlTranslation::default();
+        if try!(peek_at_name(stack)) == "Owner" {
+            try!(start_element("Owner", stack));
+            obj.owner = try!(characters(stack));
+            try!(end_element("Owner", stack));
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// A number of minutes, as used by S3 Replication Time Control.
+#[derive(Debug, Default)]
+pub struct ReplicationTimeValue {
+    pub minutes: Option<Minutes>,
+}
+
+/// Parse `ReplicationTimeValue` from XML
+struct ReplicationTimeValueParser;
+impl ReplicationTimeValueParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ReplicationTimeValue, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ReplicationTimeValue::default();
+        if try!(peek_at_name(stack)) == "Minutes" {
+            obj.minutes = Some(try!(MinutesParser::parse_xml("Minutes", stack)));
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// S3 Replication Time Control, which replicates most objects within seconds and all of
+/// them within `time`, which must be 15 minutes. It requires `metrics` to be enabled too.
+#[derive(Debug, Default)]
+pub struct ReplicationTime {
+    pub status: ReplicationFeatureStatus,
+    pub time: ReplicationTimeValue,
+}
+
+/// Parse `ReplicationTime` from XML
+struct ReplicationTimeParser;
+impl ReplicationTimeParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ReplicationTime, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ReplicationTime::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Status" {
+                obj.status = try!(ReplicationFeatureStatusParser::parse_xml("Status", stack));
+                continue;
+            }
+            if current_name == "Time" {
+                obj.time = try!(ReplicationTimeValueParser::parse_xml("Time", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Replication metrics and events, published when replication falls behind by more than
+/// `event_threshold`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub status: ReplicationFeatureStatus,
+    pub event_threshold: Option<ReplicationTimeValue>,
+}
+
+/// Parse `Metrics` from XML
+struct MetricsParser;
+impl MetricsParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Metrics, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = Metrics::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Status" {
+                obj.status = try!(ReplicationFeatureStatusParser::parse_xml("Status", stack));
+                continue;
+            }
+            if current_name == "EventThreshold" {
+                obj.event_threshold = Some(try!(ReplicationTimeValueParser::parse_xml("EventThreshold", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Objects matching both a prefix and all of a set of tags.
+#[derive(Debug, Default)]
+pub struct ReplicationRuleAndOperator {
+    pub prefix: Option<Prefix>,
+    pub tags: TagSet,
+}
+
+/// Parse `ReplicationRuleAndOperator` from XML
+struct ReplicationRuleAndOperatorParser;
+impl ReplicationRuleAndOperatorParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ReplicationRuleAndOperator, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ReplicationRuleAndOperator::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            if current_name == "Tag" {
+                obj.tags.push(try!(TagParser::parse_xml("Tag", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// The objects a rule replicates: those with a prefix, with a tag, or with both through
+/// `and`. An empty filter replicates the whole bucket.
+#[derive(Debug, Default)]
+pub struct ReplicationRuleFilter {
+    pub prefix: Option<Prefix>,
+    pub tag: Option<Tag>,
+    pub and: Option<ReplicationRuleAndOperator>,
+}
+
+/// Parse `ReplicationRuleFilter` from XML
+struct ReplicationRuleFilterParser;
+impl ReplicationRuleFilterParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ReplicationRuleFilter, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ReplicationRuleFilter::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            if current_name == "Tag" {
+                obj.tag = Some(try!(TagParser::parse_xml("Tag", stack)));
+                continue;
+            }
+            if current_name == "And" {
+                obj.and = Some(try!(ReplicationRuleAndOperatorParser::parse_xml("And", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Whether delete markers are replicated. Rules with a `filter` must say so.
+#[derive(Debug, Default)]
+pub struct DeleteMarkerReplication {
+    pub status: ReplicationFeatureStatus,
+}
+
+/// Parse `DeleteMarkerReplication` from XML
+struct DeleteMarkerReplicationParser;
+impl DeleteMarkerReplicationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<DeleteMarkerReplication, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = DeleteMarkerReplication::default();
+        if try!(peek_at_name(stack)) == "Status" {
+            obj.status = try!(ReplicationFeatureStatusParser::parse_xml("Status", stack));
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Whether objects that existed before the rule was added are replicated too.
+#[derive(Debug, Default)]
+pub struct ExistingObjectReplication {
+    pub status: ReplicationFeatureStatus,
+}
+
+/// Parse `ExistingObjectReplication` from XML
+struct ExistingObjectReplicationParser;
+impl ExistingObjectReplicationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ExistingObjectReplication, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ExistingObjectReplication::default();
+        if try!(peek_at_name(stack)) == "Status" {
+            obj.status = try!(ReplicationFeatureStatusParser::parse_xml("Status", stack));
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
 pub type IfNoneMatch = String;
 /// Parse `IfNoneMatch` from XML
 struct IfNoneMatchParser;
@@ -446,6 +714,8 @@ pub struct PutBucketReplicationRequest {
     pub replication_configuration: ReplicationConfiguration,
     pub content_md5: Option<ContentMD5>,
     pub bucket: BucketName,
+    /// An Object Lock token, needed when the destination bucket has Object Lock enabled.
+    pub token: Option<ObjectLockToken>,
 }
 
 /// Parse `PutBucketReplicationRequest` from XML
@@ -527,9 +797,6 @@ impl GetObjectTorrentRequestWriter {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
     }
 }
@@ -699,7 +966,7 @@ pub struct PutObjectOutput {
     /// requested, the response will include this header confirming the encryption
     /// algorithm used.
     pub sse_customer_algorithm: SSECustomerAlgorithm,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// Version of the object.
     pub version_id: ObjectVersionId,
     /// Entity tag for the uploaded object.
@@ -732,7 +999,7 @@ impl PutObjectOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "x-amz-version-id" {
@@ -772,7 +1039,9 @@ impl PutObjectOutputWriter {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
         SSECustomerAlgorithmWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-algorithm"), &obj.sse_customer_algorithm);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "x-amz-version-id"), &obj.version_id);
         ETagWriter::write_params(params, &(prefix.to_string() + "ETag"), &obj.e_tag);
         ExpirationWriter::write_params(params, &(prefix.to_string() + "x-amz-expiration"), &obj.expiration);
@@ -783,7 +1052,7 @@ impl PutObjectOutputWriter {
 }
 #[derive(Debug, Default)]
 pub struct PutObjectAclOutput {
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
 }
 
 /// Parse `PutObjectAclOutput` from XML
@@ -795,7 +1064,7 @@ impl PutObjectAclOutputParser {
         loop {
             let current_name = try!(peek_at_name(stack));
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             break;
@@ -810,7 +1079,9 @@ impl PutObjectAclOutputWriter {
     fn write_params(params: &mut Params, name: &str, obj: &PutObjectAclOutput) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
     }
 }
 /// Container for replication rules. You can add as many as 1,000 rules. Total
@@ -833,8 +1104,8 @@ impl ReplicationConfigurationParser {
         let mut obj = ReplicationConfiguration::default();
         loop {
             let current_name = try!(peek_at_name(stack));
-            if current_name == "ReplicationRule" {
-                obj.rules = try!(ReplicationRulesParser::parse_xml("ReplicationRule", stack));
+            if current_name == "Rule" {
+                obj.rules = try!(ReplicationRulesParser::parse_xml("Rule", stack));
                 continue;
             }
             if current_name == "Role" {
@@ -862,7 +1133,22 @@ impl ReplicationConfigurationWriter {
 /// Documentation on downloading objects from requester pays buckets can be found
 /// at http://docs.aws.amazon.com/AmazonS3/latest/dev/ObjectsinRequesterPaysBucket
 /// s.html
-pub type RequestPayer = String;
+///
+/// It's sent as the `x-amz-request-payer` header rather than as a parameter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RequestPayer {
+    Requester,
+}
+
+impl RequestPayer {
+    /// The value of the `x-amz-request-payer` header.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            RequestPayer::Requester => "requester",
+        }
+    }
+}
+
 /// Parse `RequestPayer` from XML
 struct RequestPayerParser;
 impl RequestPayerParser {
@@ -870,14 +1156,11 @@ impl RequestPayerParser {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
-        Ok(obj)
-    }
-}
-/// Write `RequestPayer` contents to a `SignedRequest`
-struct RequestPayerWriter;
-impl RequestPayerWriter {
-    fn write_params(params: &mut Params, name: &str, obj: &RequestPayer) {
-        params.put(name, obj);
+
+        match &obj[..] {
+            "requester" => Ok(RequestPayer::Requester),
+            _ => Err(XmlParseError::new(&format!("Unknown request payer {}", obj))),
+        }
     }
 }
 #[derive(Debug, Default)]
@@ -1667,9 +1950,6 @@ impl CreateMultipartUploadRequestWriter {
     fn write_params(params: &mut Params, name: &str, obj: &CreateMultipartUploadRequest) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         if let Some(ref obj) = obj.content_encoding {
             ContentEncodingWriter::write_params(params, &(prefix.to_string() + "Content-Encoding"), obj);
         }
@@ -1878,8 +2158,8 @@ struct DeletedObjectsParser;
 impl DeletedObjectsParser {
     fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<DeletedObjects, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "DeletedObject" {
-            obj.push(try!(DeletedObjectParser::parse_xml("DeletedObject", stack)));
+        while try!(peek_at_name(stack)) == "Deleted" {
+            obj.push(try!(DeletedObjectParser::parse_xml("Deleted", stack)));
         }
         Ok(obj)
     }
@@ -2019,7 +2299,7 @@ impl DeleteMarkersWriter {
 /// Output from `AbortMultipartUpload` call
 #[derive(Debug, Default)]
 pub struct AbortMultipartUploadOutput {
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
 }
 
 /// Write `AbortMultipartUploadOutput` contents to a `SignedRequest`
@@ -2028,7 +2308,9 @@ impl AbortMultipartUploadOutputWriter {
     fn write_params(params: &mut Params, name: &str, obj: &AbortMultipartUploadOutput) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
     }
 }
 
@@ -2165,9 +2447,6 @@ impl GetObjectAclRequestWriter {
             ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "versionId"), obj);
         }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
     }
 }
@@ -2432,9 +2711,6 @@ impl AbortMultipartUploadRequestWriter {
         if prefix != "" { prefix.push_str("."); }
         MultipartUploadIdWriter::write_params(params, &(prefix.to_string() + "uploadId"), &obj.upload_id);
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
     }
 }
@@ -2468,7 +2744,7 @@ pub struct UploadPartCopyOutput {
     /// The Server-side encryption algorithm used when storing this object in S3
     /// (e.g., AES256, aws:kms).
     pub server_side_encryption: ServerSideEncryption,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// If server-side encryption with a customer-provided encryption key was
     /// requested, the response will include this header to provide round trip message
     /// integrity verification of the customer-provided encryption key.
@@ -2500,7 +2776,7 @@ impl UploadPartCopyOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "x-amz-server-side-encryption-customer-key-MD5" {
@@ -2530,7 +2806,9 @@ impl UploadPartCopyOutputWriter {
         SSECustomerAlgorithmWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-algorithm"), &obj.sse_customer_algorithm);
         CopySourceVersionIdWriter::write_params(params, &(prefix.to_string() + "x-amz-copy-source-version-id"), &obj.copy_source_version_id);
         ServerSideEncryptionWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption"), &obj.server_side_encryption);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         SSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-key-MD5"), &obj.sse_customer_key_md5);
         CopyPartResultWriter::write_params(params, &(prefix.to_string() + "CopyPartResult"), &obj.copy_part_result);
         SSEKMSKeyIdWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-aws-kms-key-id"), &obj.ssekms_key_id);
@@ -2782,7 +3060,7 @@ pub struct DeleteObjectOutput {
     /// Returns the version ID of the delete marker created as a result of the DELETE
     /// operation.
     pub version_id: ObjectVersionId,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// Specifies whether the versioned object that was permanently deleted was (true)
     /// or was not (false) a delete marker.
     pub delete_marker: DeleteMarker,
@@ -2801,7 +3079,7 @@ impl DeleteObjectOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "x-amz-delete-marker" {
@@ -2821,7 +3099,9 @@ impl DeleteObjectOutputWriter {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
         ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "x-amz-version-id"), &obj.version_id);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         DeleteMarkerWriter::write_params(params, &(prefix.to_string() + "x-amz-delete-marker"), &obj.delete_marker);
     }
 }
@@ -3003,9 +3283,6 @@ impl HeadObjectRequestWriter {
         if let Some(ref obj) = obj.version_id {
             ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "versionId"), obj);
         }
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
         if let Some(ref obj) = obj.if_none_match {
             IfNoneMatchWriter::write_params(params, &(prefix.to_string() + "If-None-Match"), obj);
@@ -3412,7 +3689,7 @@ impl CloudFunctionInvocationRoleWriter {
 pub struct HeadObjectOutput {
     /// Last modified date of the object
     pub last_modified: LastModified,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// Specifies what content encodings have been applied to the object and thus what
     /// decoding mechanisms must be applied to obtain the media-type referenced by the
     /// Content-Type header field.
@@ -3488,7 +3765,7 @@ impl HeadObjectOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "Content-Encoding" {
@@ -3592,7 +3869,9 @@ impl HeadObjectOutputWriter {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
         LastModifiedWriter::write_params(params, &(prefix.to_string() + "Last-Modified"), &obj.last_modified);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         ContentEncodingWriter::write_params(params, &(prefix.to_string() + "Content-Encoding"), &obj.content_encoding);
         ReplicationStatusWriter::write_params(params, &(prefix.to_string() + "x-amz-replication-status"), &obj.replication_status);
         StorageClassWriter::write_params(params, &(prefix.to_string() + "x-amz-storage-class"), &obj.storage_class);
@@ -4127,7 +4406,7 @@ pub struct CreateMultipartUploadOutput {
     /// requested, the response will include this header confirming the encryption
     /// algorithm used.
     pub sse_customer_algorithm: SSECustomerAlgorithm,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// Name of the bucket to which the multipart upload was initiated.
     pub bucket: BucketName,
     /// ID for the initiated multipart upload.
@@ -4159,7 +4438,7 @@ impl CreateMultipartUploadOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "Bucket" {
@@ -4199,7 +4478,9 @@ impl CreateMultipartUploadOutputWriter {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
         SSECustomerAlgorithmWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-algorithm"), &obj.sse_customer_algorithm);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
         MultipartUploadIdWriter::write_params(params, &(prefix.to_string() + "UploadId"), &obj.upload_id);
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
@@ -4508,9 +4789,6 @@ impl PutObjectAclRequestWriter {
             GrantWriteACPWriter::write_params(params, &(prefix.to_string() + "x-amz-grant-write-acp"), obj);
         }
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         if let Some(ref obj) = obj.content_md5 {
             ContentMD5Writer::write_params(params, &(prefix.to_string() + "Content-MD5"), obj);
         }
@@ -4930,73 +5208,213 @@ impl ListObjectsOutputWriter {
         CommonPrefixListWriter::write_params(params, &(prefix.to_string() + "CommonPrefix"), &obj.common_prefixes);
     }
 }
-pub type GrantWriteACP = String;
-/// Parse `GrantWriteACP` from XML
-struct GrantWriteACPParser;
-impl GrantWriteACPParser {
-    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<GrantWriteACP, XmlParseError> {
+pub type ContinuationToken = String;
+/// Parse `ContinuationToken` from XML
+struct ContinuationTokenParser;
+impl ContinuationTokenParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ContinuationToken, XmlParseError> {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
         Ok(obj)
     }
 }
-/// Write `GrantWriteACP` contents to a `SignedRequest`
-struct GrantWriteACPWriter;
-impl GrantWriteACPWriter {
-    fn write_params(params: &mut Params, name: &str, obj: &GrantWriteACP) {
-        params.put(name, obj);
-    }
-}
-pub type CORSRules = Vec<CORSRule>;
-/// Parse `CORSRules` from XML
-struct CORSRulesParser;
-impl CORSRulesParser {
-    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<CORSRules, XmlParseError> {
-        let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "CORSRule" {
-            obj.push(try!(CORSRuleParser::parse_xml("CORSRule", stack)));
-        }
-        Ok(obj)
-    }
-}
-/// Write `CORSRules` contents to a `SignedRequest`
-struct CORSRulesWriter;
-impl CORSRulesWriter {
-    fn write_params(params: &mut Params, name: &str, obj: &CORSRules) {
-        let mut index = 1;
-        for element in obj.iter() {
-            let key = &format!("{}.{}", name, index);
-            CORSRuleWriter::write_params(params, key, &element);
-            index += 1;
-        }
-    }
-}
-pub type ContentLanguage = String;
-/// Parse `ContentLanguage` from XML
-struct ContentLanguageParser;
-impl ContentLanguageParser {
-    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ContentLanguage, XmlParseError> {
+pub type StartAfter = String;
+pub type KeyCount = i32;
+/// Parse `KeyCount` from XML
+struct KeyCountParser;
+impl KeyCountParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<KeyCount, XmlParseError> {
         try!(start_element(tag_name, stack));
-        let obj = try!(characters(stack));
+        let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
         try!(end_element(tag_name, stack));
         Ok(obj)
     }
 }
-/// Write `ContentLanguage` contents to a `SignedRequest`
-struct ContentLanguageWriter;
-impl ContentLanguageWriter {
-    fn write_params(params: &mut Params, name: &str, obj: &ContentLanguage) {
-        params.put(name, obj);
+#[derive(Debug, Default)]
+pub struct ListObjectsV2Request {
+    pub bucket: BucketName,
+    /// Limits the response to keys that begin with the specified prefix.
+    pub prefix: Option<Prefix>,
+    /// Sets the maximum number of keys returned in the response. The response might
+    /// contain fewer keys but will never contain more.
+    pub max_keys: Option<MaxKeys>,
+    /// A delimiter is a character you use to group keys.
+    pub delimiter: Option<Delimiter>,
+    /// The `NextContinuationToken` of the previous page, to continue listing from.
+    pub continuation_token: Option<ContinuationToken>,
+    /// Only keys after this one are listed.
+    pub start_after: Option<StartAfter>,
+    /// Set to `RequestPayer::Requester` to list a requester pays bucket.
+    pub request_payer: Option<RequestPayer>,
+}
+
+/// Write `ListObjectsV2Request` contents to a `SignedRequest`
+struct ListObjectsV2RequestWriter;
+impl ListObjectsV2RequestWriter {
+    fn write_params(params: &mut Params, obj: &ListObjectsV2Request) {
+        params.put("list-type", "2");
+        if let Some(ref prefix) = obj.prefix {
+            params.put("prefix", prefix);
+        }
+        if let Some(ref max_keys) = obj.max_keys {
+            params.put("max-keys", &max_keys.to_string());
+        }
+        if let Some(ref delimiter) = obj.delimiter {
+            params.put("delimiter", delimiter);
+        }
+        if let Some(ref continuation_token) = obj.continuation_token {
+            params.put("continuation-token", continuation_token);
+        }
+        if let Some(ref start_after) = obj.start_after {
+            params.put("start-after", start_after);
+        }
     }
 }
 #[derive(Debug, Default)]
-pub struct ListBucketsOutput {
-    pub owner: Owner,
-    pub buckets: Buckets,
+pub struct ListObjectsV2Output {
+    pub name: BucketName,
+    pub prefix: Prefix,
+    pub delimiter: Delimiter,
+    pub max_keys: MaxKeys,
+    /// The number of keys returned with this request.
+    pub key_count: KeyCount,
+    /// A flag that indicates whether or not Amazon S3 returned all of the results
+    /// that satisfied the search criteria.
+    pub is_truncated: IsTruncated,
+    pub contents: ObjectList,
+    pub common_prefixes: CommonPrefixList,
+    pub continuation_token: Option<ContinuationToken>,
+    /// Pass this as the `continuation_token` of the next request to get the next page.
+    pub next_continuation_token: Option<ContinuationToken>,
+    pub start_after: Option<StartAfter>,
 }
 
-/// Parse `ListBucketsOutput` from XML
+/// Parse `ListObjectsV2Output` from XML
+struct ListObjectsV2OutputParser;
+impl ListObjectsV2OutputParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ListObjectsV2Output, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ListObjectsV2Output::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Name" {
+                obj.name = try!(BucketNameParser::parse_xml("Name", stack));
+                continue;
+            }
+            if current_name == "Prefix" {
+                obj.prefix = try!(PrefixParser::parse_xml("Prefix", stack));
+                continue;
+            }
+            if current_name == "Delimiter" {
+                obj.delimiter = try!(DelimiterParser::parse_xml("Delimiter", stack));
+                continue;
+            }
+            if current_name == "MaxKeys" {
+                obj.max_keys = try!(MaxKeysParser::parse_xml("MaxKeys", stack));
+                continue;
+            }
+            if current_name == "KeyCount" {
+                obj.key_count = try!(KeyCountParser::parse_xml("KeyCount", stack));
+                continue;
+            }
+            if current_name == "IsTruncated" {
+                obj.is_truncated = try!(IsTruncatedParser::parse_xml("IsTruncated", stack));
+                continue;
+            }
+            if current_name == "Contents" {
+                obj.contents.push(try!(ObjectParser::parse_xml("Contents", stack)));
+                continue;
+            }
+            if current_name == "CommonPrefixes" {
+                obj.common_prefixes.push(try!(CommonPrefixParser::parse_xml("CommonPrefixes", stack)));
+                continue;
+            }
+            if current_name == "ContinuationToken" {
+                obj.continuation_token = Some(try!(ContinuationTokenParser::parse_xml("ContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "NextContinuationToken" {
+                obj.next_continuation_token = Some(try!(ContinuationTokenParser::parse_xml("NextContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "StartAfter" {
+                obj.start_after = Some(try!(ContinuationTokenParser::parse_xml("StartAfter", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+pub type GrantWriteACP = String;
+/// Parse `GrantWriteACP` from XML
+struct GrantWriteACPParser;
+impl GrantWriteACPParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<GrantWriteACP, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Write `GrantWriteACP` contents to a `SignedRequest`
+struct GrantWriteACPWriter;
+impl GrantWriteACPWriter {
+    fn write_params(params: &mut Params, name: &str, obj: &GrantWriteACP) {
+        params.put(name, obj);
+    }
+}
+pub type CORSRules = Vec<CORSRule>;
+/// Parse `CORSRules` from XML
+struct CORSRulesParser;
+impl CORSRulesParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<CORSRules, XmlParseError> {
+        let mut obj = Vec::new();
+        while try!(peek_at_name(stack)) == "CORSRule" {
+            obj.push(try!(CORSRuleParser::parse_xml("CORSRule", stack)));
+        }
+        Ok(obj)
+    }
+}
+/// Write `CORSRules` contents to a `SignedRequest`
+struct CORSRulesWriter;
+impl CORSRulesWriter {
+    fn write_params(params: &mut Params, name: &str, obj: &CORSRules) {
+        let mut index = 1;
+        for element in obj.iter() {
+            let key = &format!("{}.{}", name, index);
+            CORSRuleWriter::write_params(params, key, &element);
+            index += 1;
+        }
+    }
+}
+pub type ContentLanguage = String;
+/// Parse `ContentLanguage` from XML
+struct ContentLanguageParser;
+impl ContentLanguageParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ContentLanguage, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Write `ContentLanguage` contents to a `SignedRequest`
+struct ContentLanguageWriter;
+impl ContentLanguageWriter {
+    fn write_params(params: &mut Params, name: &str, obj: &ContentLanguage) {
+        params.put(name, obj);
+    }
+}
+#[derive(Debug, Default)]
+pub struct ListBucketsOutput {
+    pub owner: Owner,
+    pub buckets: Buckets,
+}
+
+/// Parse `ListBucketsOutput` from XML
 struct ListBucketsOutputParser;
 impl ListBucketsOutputParser {
     fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ListBucketsOutput, XmlParseError> {
@@ -5091,9 +5509,6 @@ impl DeleteObjectRequestWriter {
             ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "versionId"), obj);
         }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
     }
 }
@@ -5526,8 +5941,8 @@ struct ReplicationRulesParser;
 impl ReplicationRulesParser {
     fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ReplicationRules, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "ReplicationRule" {
-            obj.push(try!(ReplicationRuleParser::parse_xml("ReplicationRule", stack)));
+        while try!(peek_at_name(stack)) == "Rule" {
+            obj.push(try!(ReplicationRuleParser::parse_xml("Rule", stack)));
         }
         Ok(obj)
     }
@@ -5795,7 +6210,7 @@ impl ResponseContentTypeWriter {
 }
 #[derive(Debug, Default)]
 pub struct CompleteMultipartUploadOutput {
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     pub bucket: BucketName,
     /// Version of the object.
     pub version_id: ObjectVersionId,
@@ -5823,7 +6238,7 @@ impl CompleteMultipartUploadOutputParser {
         loop {
             let current_name = try!(peek_at_name(stack));
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "Bucket" {
@@ -5870,7 +6285,9 @@ impl CompleteMultipartUploadOutputWriter {
     fn write_params(params: &mut Params, name: &str, obj: &CompleteMultipartUploadOutput) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
         ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "x-amz-version-id"), &obj.version_id);
         ETagWriter::write_params(params, &(prefix.to_string() + "ETag"), &obj.e_tag);
@@ -6024,7 +6441,7 @@ pub struct CopyObjectOutput {
     /// The Server-side encryption algorithm used when storing this object in S3
     /// (e.g., AES256, aws:kms).
     pub server_side_encryption: ServerSideEncryption,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// If the object expiration is configured, the response includes this header.
     pub expiration: Expiration,
     /// If server-side encryption with a customer-provided encryption key was
@@ -6058,7 +6475,7 @@ impl CopyObjectOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "x-amz-expiration" {
@@ -6092,7 +6509,9 @@ impl CopyObjectOutputWriter {
         SSECustomerAlgorithmWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-algorithm"), &obj.sse_customer_algorithm);
         CopySourceVersionIdWriter::write_params(params, &(prefix.to_string() + "x-amz-copy-source-version-id"), &obj.copy_source_version_id);
         ServerSideEncryptionWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption"), &obj.server_side_encryption);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         ExpirationWriter::write_params(params, &(prefix.to_string() + "x-amz-expiration"), &obj.expiration);
         SSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-key-MD5"), &obj.sse_customer_key_md5);
         CopyObjectResultWriter::write_params(params, &(prefix.to_string() + "CopyObjectResult"), &obj.copy_object_result);
@@ -6220,7 +6639,7 @@ pub struct UploadPartOutput {
     pub server_side_encryption: ServerSideEncryption,
     /// Entity tag for the uploaded object.
     pub e_tag: ETag,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// If server-side encryption with a customer-provided encryption key was
     /// requested, the response will include this header to provide round trip message
     /// integrity verification of the customer-provided encryption key.
@@ -6251,7 +6670,7 @@ impl UploadPartOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "x-amz-server-side-encryption-customer-key-MD5" {
@@ -6277,7 +6696,9 @@ impl UploadPartOutputWriter {
         SSECustomerAlgorithmWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-algorithm"), &obj.sse_customer_algorithm);
         ServerSideEncryptionWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption"), &obj.server_side_encryption);
         ETagWriter::write_params(params, &(prefix.to_string() + "ETag"), &obj.e_tag);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         SSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-key-MD5"), &obj.sse_customer_key_md5);
         SSEKMSKeyIdWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-aws-kms-key-id"), &obj.ssekms_key_id);
     }
@@ -6496,12 +6917,18 @@ pub struct ReplicationRule {
     pub status: ReplicationRuleStatus,
     /// Object keyname prefix identifying one or more objects to which the rule
     /// applies. Maximum prefix length can be up to 1,024 characters. Overlapping
-    /// prefixes are not supported.
+    /// prefixes are not supported. Rules with a `filter` leave this empty.
     pub prefix: Prefix,
+    /// Which objects the rule applies to, in place of `prefix`.
+    pub filter: Option<ReplicationRuleFilter>,
+    /// Which rule wins when several apply to an object; the highest priority does.
+    pub priority: Option<Priority>,
     pub destination: Destination,
     /// Unique identifier for the rule. The value cannot be longer than 255
     /// characters.
     pub id: Option<ID>,
+    pub delete_marker_replication: Option<DeleteMarkerReplication>,
+    pub existing_object_replication: Option<ExistingObjectReplication>,
 }
 
 /// Parse `ReplicationRule` from XML
@@ -6520,6 +6947,14 @@ impl ReplicationRuleParser {
                 obj.prefix = try!(PrefixParser::parse_xml("Prefix", stack));
                 continue;
             }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(ReplicationRuleFilterParser::parse_xml("Filter", stack)));
+                continue;
+            }
+            if current_name == "Priority" {
+                obj.priority = Some(try!(PriorityParser::parse_xml("Priority", stack)));
+                continue;
+            }
             if current_name == "Destination" {
                 obj.destination = try!(DestinationParser::parse_xml("Destination", stack));
                 continue;
@@ -6528,6 +6963,14 @@ impl ReplicationRuleParser {
                 obj.id = Some(try!(IDParser::parse_xml("ID", stack)));
                 continue;
             }
+            if current_name == "DeleteMarkerReplication" {
+                obj.delete_marker_replication = Some(try!(DeleteMarkerReplicationParser::parse_xml("DeleteMarkerReplication", stack)));
+                continue;
+            }
+            if current_name == "ExistingObjectReplication" {
+                obj.existing_object_replication = Some(try!(ExistingObjectReplicationParser::parse_xml("ExistingObjectReplication", stack)));
+                continue;
+            }
             break;
         }
         try!(end_element(tag_name, stack));
@@ -6674,7 +7117,7 @@ impl ConditionWriter {
 pub struct DeleteObjectsOutput {
     pub deleted: DeletedObjects,
     pub errors: Errors,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
 }
 
 /// Parse `DeleteObjectsOutput` from XML
@@ -6685,8 +7128,8 @@ impl DeleteObjectsOutputParser {
         let mut obj = DeleteObjectsOutput::default();
         loop {
             let current_name = try!(peek_at_name(stack));
-            if current_name == "DeletedObject" {
-                obj.deleted = try!(DeletedObjectsParser::parse_xml("DeletedObject", stack));
+            if current_name == "Deleted" {
+                obj.deleted = try!(DeletedObjectsParser::parse_xml("Deleted", stack));
                 continue;
             }
             if current_name == "Error" {
@@ -6694,7 +7137,7 @@ impl DeleteObjectsOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             break;
@@ -6709,9 +7152,11 @@ impl DeleteObjectsOutputWriter {
     fn write_params(params: &mut Params, name: &str, obj: &DeleteObjectsOutput) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        DeletedObjectsWriter::write_params(params, &(prefix.to_string() + "DeletedObject"), &obj.deleted);
+        DeletedObjectsWriter::write_params(params, &(prefix.to_string() + "Deleted"), &obj.deleted);
         ErrorsWriter::write_params(params, &(prefix.to_string() + "Error"), &obj.errors);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
     }
 }
 #[derive(Debug, Default)]
@@ -6816,7 +7261,7 @@ impl PutBucketLifecycleRequestWriter {
 #[derive(Debug, Default)]
 pub struct GetObjectTorrentOutput {
     pub body: Body,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
 }
 
 /// Parse `GetObjectTorrentOutput` from XML
@@ -6832,7 +7277,7 @@ impl GetObjectTorrentOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             break;
@@ -6848,7 +7293,9 @@ impl GetObjectTorrentOutputWriter {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
         BodyWriter::write_params(params, &(prefix.to_string() + "Body"), &obj.body);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
     }
 }
 pub type ContentLength = i32;
@@ -6974,7 +7421,7 @@ pub struct GetObjectOutput {
     pub last_modified: LastModified,
     /// The portion of the object returned in the response.
     pub content_range: ContentRange,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     /// Specifies what content encodings have been applied to the object and thus what
     /// decoding mechanisms must be applied to obtain the media-type referenced by the
     /// Content-Type header field.
@@ -7056,7 +7503,7 @@ impl GetObjectOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "Content-Encoding" {
@@ -7165,7 +7612,9 @@ impl GetObjectOutputWriter {
         if prefix != "" { prefix.push_str("."); }
         LastModifiedWriter::write_params(params, &(prefix.to_string() + "Last-Modified"), &obj.last_modified);
         ContentRangeWriter::write_params(params, &(prefix.to_string() + "Content-Range"), &obj.content_range);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         ContentEncodingWriter::write_params(params, &(prefix.to_string() + "Content-Encoding"), &obj.content_encoding);
         ReplicationStatusWriter::write_params(params, &(prefix.to_string() + "x-amz-replication-status"), &obj.replication_status);
         StorageClassWriter::write_params(params, &(prefix.to_string() + "x-amz-storage-class"), &obj.storage_class);
@@ -8167,9 +8616,6 @@ impl CopyObjectRequestWriter {
     fn write_params(params: &mut Params, name: &str, obj: &CopyObjectRequest) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         if let Some(ref obj) = obj.copy_source_if_modified_since {
             CopySourceIfModifiedSinceWriter::write_params(params, &(prefix.to_string() + "x-amz-copy-source-if-modified-since"), obj);
         }
@@ -8303,9 +8749,6 @@ impl DeleteObjectsRequestWriter {
             MFAWriter::write_params(params, &(prefix.to_string() + "x-amz-mfa"), obj);
         }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         DeleteWriter::write_params(params, &(prefix.to_string() + "Delete"), &obj.delete);
     }
 }
@@ -8326,7 +8769,7 @@ pub struct ListPartsOutput {
     pub storage_class: StorageClass,
     /// Object key for which the multipart upload was initiated.
     pub key: ObjectKey,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
     pub owner: Owner,
     /// Maximum number of parts that were allowed in the response.
     pub max_parts: MaxParts,
@@ -8373,7 +8816,7 @@ impl ListPartsOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             if current_name == "Owner" {
@@ -8411,7 +8854,9 @@ impl ListPartsOutputWriter {
         MultipartUploadIdWriter::write_params(params, &(prefix.to_string() + "UploadId"), &obj.upload_id);
         StorageClassWriter::write_params(params, &(prefix.to_string() + "StorageClass"), &obj.storage_class);
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
         OwnerWriter::write_params(params, &(prefix.to_string() + "Owner"), &obj.owner);
         MaxPartsWriter::write_params(params, &(prefix.to_string() + "MaxParts"), &obj.max_parts);
         IsTruncatedWriter::write_params(params, &(prefix.to_string() + "IsTruncated"), &obj.is_truncated);
@@ -8777,9 +9222,6 @@ impl UploadPartCopyRequestWriter {
         if let Some(ref obj) = obj.copy_source_sse_customer_key_md5 {
             CopySourceSSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-copy-source-server-side-encryption-customer-key-MD5"), obj);
         }
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         if let Some(ref obj) = obj.copy_source_sse_customer_key {
             CopySourceSSECustomerKeyWriter::write_params(params, &(prefix.to_string() + "x-amz-copy-source-server-side-encryption-customer-key"), obj);
         }
@@ -9444,9 +9886,6 @@ impl GetObjectRequestWriter {
         if let Some(ref obj) = obj.version_id {
             ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "versionId"), obj);
         }
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         if let Some(ref obj) = obj.response_cache_control {
             ResponseCacheControlWriter::write_params(params, &(prefix.to_string() + "response-cache-control"), obj);
         }
@@ -9650,7 +10089,7 @@ impl ExpiresWriter {
 }
 #[derive(Debug, Default)]
 pub struct RestoreObjectOutput {
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
 }
 
 /// Parse `RestoreObjectOutput` from XML
@@ -9662,7 +10101,7 @@ impl RestoreObjectOutputParser {
         loop {
             let current_name = try!(peek_at_name(stack));
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             break;
@@ -9677,7 +10116,9 @@ impl RestoreObjectOutputWriter {
     fn write_params(params: &mut Params, name: &str, obj: &RestoreObjectOutput) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
     }
 }
 #[derive(Debug, Default)]
@@ -9736,9 +10177,6 @@ impl RestoreObjectRequestWriter {
             RestoreRequestWriter::write_params(params, &(prefix.to_string() + "RestoreRequest"), obj);
         }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
     }
 }
@@ -9779,7 +10217,7 @@ pub struct GetObjectAclOutput {
     pub owner: Owner,
     /// A list of grants.
     pub grants: Grants,
-    pub request_charged: RequestCharged,
+    pub request_charged: Option<RequestCharged>,
 }
 
 /// Parse `GetObjectAclOutput` from XML
@@ -9799,7 +10237,7 @@ impl GetObjectAclOutputParser {
                 continue;
             }
             if current_name == "x-amz-request-charged" {
-                obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
+                obj.request_charged = Some(try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack)));
                 continue;
             }
             break;
@@ -9816,7 +10254,9 @@ impl GetObjectAclOutputWriter {
         if prefix != "" { prefix.push_str("."); }
         OwnerWriter::write_params(params, &(prefix.to_string() + "Owner"), &obj.owner);
         GrantsWriter::write_params(params, &(prefix.to_string() + "Grant"), &obj.grants);
-        RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
+        if let Some(ref obj) = obj.request_charged {
+            RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), obj);
+        }
     }
 }
 pub type ReplaceKeyWith = String;
@@ -10281,7 +10721,28 @@ impl URIWriter {
 }
 /// If present, indicates that the requester was successfully charged for the
 /// request.
-pub type RequestCharged = String;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RequestCharged {
+    Requester,
+}
+
+impl RequestCharged {
+    /// Parses the value of the `x-amz-request-charged` header.
+    pub fn from_header(value: &str) -> Option<RequestCharged> {
+        match value {
+            "requester" => Some(RequestCharged::Requester),
+            _ => None,
+        }
+    }
+
+    /// The value of the `x-amz-request-charged` header.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            RequestCharged::Requester => "requester",
+        }
+    }
+}
+
 /// Parse `RequestCharged` from XML
 struct RequestChargedParser;
 impl RequestChargedParser {
@@ -10289,14 +10750,17 @@ impl RequestChargedParser {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
-        Ok(obj)
+
+        RequestCharged::from_header(&obj).ok_or_else(|| {
+            XmlParseError::new(&format!("Unknown request charged {}", obj))
+        })
     }
 }
 /// Write `RequestCharged` contents to a `SignedRequest`
 struct RequestChargedWriter;
 impl RequestChargedWriter {
     fn write_params(params: &mut Params, name: &str, obj: &RequestCharged) {
-        params.put(name, obj);
+        params.put(name, obj.as_str());
     }
 }
 #[derive(Debug, Default)]
@@ -10838,9 +11302,6 @@ impl ListPartsRequestWriter {
     fn write_params(params: &mut Params, name: &str, obj: &ListPartsRequest) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
         MultipartUploadIdWriter::write_params(params, &(prefix.to_string() + "uploadId"), &obj.upload_id);
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
@@ -11018,87 +11479,1327 @@ impl MaxPartsWriter {
         params.put(name, &obj.to_string());
     }
 }
-pub struct S3Client<P> where P: ProvideAwsCredentials {
-    credentials_provider: P,
-    region: Region,
+/// `GOVERNANCE`, which users with `s3:BypassGovernanceRetention` can override, or
+/// `COMPLIANCE`, which nobody can.
+pub type ObjectLockMode = String;
+/// Parse `ObjectLockMode` from XML
+struct ObjectLockModeParser;
+impl ObjectLockModeParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ObjectLockMode, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
 }
-
-impl<P> S3Client<P> where P: ProvideAwsCredentials {
-    pub fn new(credentials_provider: P, region: Region) -> S3Client<P> {
-        S3Client { credentials_provider: credentials_provider, region: region }
+pub type ObjectLockRetainUntilDate = String;
+/// Parse `ObjectLockRetainUntilDate` from XML
+struct ObjectLockRetainUntilDateParser;
+impl ObjectLockRetainUntilDateParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ObjectLockRetainUntilDate, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
     }
+}
+/// `Enabled` is the only value S3 accepts.
+pub type ObjectLockEnabled = String;
+/// Parse `ObjectLockEnabled` from XML
+struct ObjectLockEnabledParser;
+impl ObjectLockEnabledParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ObjectLockEnabled, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+pub type ObjectLockToken = String;
+pub type Years = i32;
+/// Parse `Years` from XML
+struct YearsParser;
+impl YearsParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Years, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// How long an object version is protected from being overwritten or deleted.
+#[derive(Debug, Default)]
+pub struct ObjectLockRetention {
+    pub mode: Option<ObjectLockMode>,
+    /// The date and time, in ISO 8601 format, the retention period expires.
+    pub retain_until_date: Option<ObjectLockRetainUntilDate>,
+}
 
-    /// Returns metadata about all of the versions of objects in a bucket.
-    pub fn list_object_versions(&mut self, input: &ListObjectVersionsRequest) -> Result<ListObjectVersionsOutput, AwsError> {
-        let mut request = SignedRequest::new("GET", "s3", self.region, "/{Bucket}?versions");
-        let mut params = Params::new();
-        params.put("Action", "ListObjectVersions");
-        ListObjectVersionsRequestWriter::write_params(&mut params, "", &input);
-        request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
-        let status = result.status.to_u16();
-        let mut reader = EventReader::new(result);
-        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-        stack.next(); // xml start tag
-        stack.next();
-        match status {
-            200 => {
-                Ok(try!(ListObjectVersionsOutputParser::parse_xml("ListObjectVersionsOutput", &mut stack)))
+/// Parse `ObjectLockRetention` from XML
+struct ObjectLockRetentionParser;
+impl ObjectLockRetentionParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ObjectLockRetention, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ObjectLockRetention::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Mode" {
+                obj.mode = Some(try!(ObjectLockModeParser::parse_xml("Mode", stack)));
+                continue;
             }
-            _ => { Err(AwsError::new("error")) }
-        }
-    }
-    /// Replaces a policy on a bucket. If the bucket already has a policy, the one in
-    /// this request completely replaces it.
-    pub fn put_bucket_policy(&mut self, input: &PutBucketPolicyRequest) -> Result<(), AwsError> {
-        let mut request = SignedRequest::new("PUT", "s3", self.region, "/{Bucket}?policy");
-        let mut params = Params::new();
-        params.put("Action", "PutBucketPolicy");
-        PutBucketPolicyRequestWriter::write_params(&mut params, "", &input);
-        request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
-        let status = result.status.to_u16();
-        let mut reader = EventReader::new(result);
-        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-        stack.next(); // xml start tag
-        stack.next();
-        match status {
-            200 => {
-                Ok(())
+            if current_name == "RetainUntilDate" {
+                obj.retain_until_date = Some(try!(ObjectLockRetainUntilDateParser::parse_xml("RetainUntilDate", stack)));
+                continue;
             }
-            _ => { Err(AwsError::new("error")) }
+            break;
         }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
     }
-    /// Returns some or all (up to 1000) of the objects in a bucket. You can use the
-    /// request parameters as selection criteria to return a subset of the objects in
-    /// a bucket.
-    pub fn list_objects(&mut self, input: &ListObjectsRequest) -> Result<ListObjectsOutput, AwsError> {
-        let mut request = SignedRequest::new("GET", "s3", self.region, "/{Bucket}");
-        let mut params = Params::new();
-        params.put("Action", "ListObjects");
-        ListObjectsRequestWriter::write_params(&mut params, "", &input);
-        request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
-        let status = result.status.to_u16();
-        let mut reader = EventReader::new(result);
-        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-        stack.next(); // xml start tag
-        stack.next();
-        match status {
-            200 => {
-                Ok(try!(ListObjectsOutputParser::parse_xml("ListObjectsOutput", &mut stack)))
+}
+/// The retention applied to new object versions that aren't given one of their own. Only
+/// one of `days` and `years` may be set.
+#[derive(Debug, Default)]
+pub struct DefaultRetention {
+    pub mode: Option<ObjectLockMode>,
+    pub days: Option<Days>,
+    pub years: Option<Years>,
+}
+
+/// Parse `DefaultRetention` from XML
+struct DefaultRetentionParser;
+impl DefaultRetentionParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<DefaultRetention, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = DefaultRetention::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Mode" {
+                obj.mode = Some(try!(ObjectLockModeParser::parse_xml("Mode", stack)));
+                continue;
             }
-            _ => { Err(AwsError::new("error")) }
+            if current_name == "Days" {
+                obj.days = Some(try!(DaysParser::parse_xml("Days", stack)));
+                continue;
+            }
+            if current_name == "Years" {
+                obj.years = Some(try!(YearsParser::parse_xml("Years", stack)));
+                continue;
+            }
+            break;
         }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
     }
-    /// Set the website configuration for a bucket.
-    pub fn put_bucket_website(&mut self, input: &PutBucketWebsiteRequest) -> Result<(), AwsError> {
-        let mut request = SignedRequest::new("PUT", "s3", self.region, "/{Bucket}?website");
-        let mut params = Params::new();
+}
+#[derive(Debug, Default)]
+pub struct ObjectLockRule {
+    pub default_retention: Option<DefaultRetention>,
+}
+
+/// Parse `ObjectLockRule` from XML
+struct ObjectLockRuleParser;
+impl ObjectLockRuleParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ObjectLockRule, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ObjectLockRule::default();
+        if try!(peek_at_name(stack)) == "DefaultRetention" {
+            obj.default_retention = Some(try!(DefaultRetentionParser::parse_xml("DefaultRetention", stack)));
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// The Object Lock configuration of a bucket. Object Lock can only be enabled on buckets
+/// with versioning enabled.
+#[derive(Debug, Default)]
+pub struct ObjectLockConfiguration {
+    pub object_lock_enabled: Option<ObjectLockEnabled>,
+    pub rule: Option<ObjectLockRule>,
+}
+
+/// Parse `ObjectLockConfiguration` from XML
+struct ObjectLockConfigurationParser;
+impl ObjectLockConfigurationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ObjectLockConfiguration, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ObjectLockConfiguration::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "ObjectLockEnabled" {
+                obj.object_lock_enabled = Some(try!(ObjectLockEnabledParser::parse_xml("ObjectLockEnabled", stack)));
+                continue;
+            }
+            if current_name == "Rule" {
+                obj.rule = Some(try!(ObjectLockRuleParser::parse_xml("Rule", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+#[derive(Debug, Default)]
+pub struct PutObjectLockConfigurationRequest {
+    pub bucket: BucketName,
+    pub object_lock_configuration: ObjectLockConfiguration,
+    /// Needed to enable Object Lock on an existing bucket; S3 support provides it.
+    pub token: Option<ObjectLockToken>,
+}
+
+#[derive(Debug, Default)]
+pub struct GetObjectLockConfigurationRequest {
+    pub bucket: BucketName,
+}
+
+#[derive(Debug, Default)]
+pub struct PutObjectRetentionRequest {
+    pub bucket: BucketName,
+    pub key: ObjectKey,
+    pub retention: ObjectLockRetention,
+    /// The version to protect, rather than the latest one.
+    pub version_id: Option<ObjectVersionId>,
+    /// Allows a `GOVERNANCE` retention to be shortened or removed, for callers with the
+    /// `s3:BypassGovernanceRetention` permission.
+    pub bypass_governance_retention: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct GetObjectRetentionRequest {
+    pub bucket: BucketName,
+    pub key: ObjectKey,
+    pub version_id: Option<ObjectVersionId>,
+}
+
+pub type IntelligentTieringId = String;
+/// Parse `IntelligentTieringId` from XML
+struct IntelligentTieringIdParser;
+impl IntelligentTieringIdParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<IntelligentTieringId, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `Enabled` or `Disabled`.
+pub type IntelligentTieringStatus = String;
+/// Parse `IntelligentTieringStatus` from XML
+struct IntelligentTieringStatusParser;
+impl IntelligentTieringStatusParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<IntelligentTieringStatus, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `ARCHIVE_ACCESS`, for objects not accessed in at least 90 days, or
+/// `DEEP_ARCHIVE_ACCESS`, for objects not accessed in at least 180 days.
+pub type IntelligentTieringAccessTier = String;
+/// Parse `IntelligentTieringAccessTier` from XML
+struct IntelligentTieringAccessTierParser;
+impl IntelligentTieringAccessTierParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<IntelligentTieringAccessTier, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Moves objects to `access_tier` once they haven't been accessed for `days` days.
+#[derive(Debug, Default)]
+pub struct Tiering {
+    pub days: Days,
+    pub access_tier: IntelligentTieringAccessTier,
+}
+
+/// Parse `Tiering` from XML
+struct TieringParser;
+impl TieringParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Tiering, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = Tiering::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Days" {
+                obj.days = try!(DaysParser::parse_xml("Days", stack));
+                continue;
+            }
+            if current_name == "AccessTier" {
+                obj.access_tier = try!(IntelligentTieringAccessTierParser::parse_xml("AccessTier", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Objects matching both a prefix and all of a set of tags.
+#[derive(Debug, Default)]
+pub struct IntelligentTieringAndOperator {
+    pub prefix: Option<Prefix>,
+    pub tags: TagSet,
+}
+
+/// Parse `IntelligentTieringAndOperator` from XML
+struct IntelligentTieringAndOperatorParser;
+impl IntelligentTieringAndOperatorParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<IntelligentTieringAndOperator, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = IntelligentTieringAndOperator::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            if current_name == "Tag" {
+                obj.tags.push(try!(TagParser::parse_xml("Tag", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// The objects a configuration applies to: those with a prefix, with a tag, or with both
+/// through `and`. A configuration without a filter applies to the whole bucket.
+#[derive(Debug, Default)]
+pub struct IntelligentTieringFilter {
+    pub prefix: Option<Prefix>,
+    pub tag: Option<Tag>,
+    pub and: Option<IntelligentTieringAndOperator>,
+}
+
+/// Parse `IntelligentTieringFilter` from XML
+struct IntelligentTieringFilterParser;
+impl IntelligentTieringFilterParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<IntelligentTieringFilter, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = IntelligentTieringFilter::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            if current_name == "Tag" {
+                obj.tag = Some(try!(TagParser::parse_xml("Tag", stack)));
+                continue;
+            }
+            if current_name == "And" {
+                obj.and = Some(try!(IntelligentTieringAndOperatorParser::parse_xml("And", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Archives the objects matching `filter` to the access tiers of `tierings`.
+#[derive(Debug, Default)]
+pub struct IntelligentTieringConfiguration {
+    pub id: IntelligentTieringId,
+    pub filter: Option<IntelligentTieringFilter>,
+    pub status: IntelligentTieringStatus,
+    pub tierings: Vec<Tiering>,
+}
+
+/// Parse `IntelligentTieringConfiguration` from XML
+struct IntelligentTieringConfigurationParser;
+impl IntelligentTieringConfigurationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<IntelligentTieringConfiguration, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = IntelligentTieringConfiguration::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Id" {
+                obj.id = try!(IntelligentTieringIdParser::parse_xml("Id", stack));
+                continue;
+            }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(IntelligentTieringFilterParser::parse_xml("Filter", stack)));
+                continue;
+            }
+            if current_name == "Status" {
+                obj.status = try!(IntelligentTieringStatusParser::parse_xml("Status", stack));
+                continue;
+            }
+            if current_name == "Tiering" {
+                obj.tierings.push(try!(TieringParser::parse_xml("Tiering", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+#[derive(Debug, Default)]
+pub struct PutBucketIntelligentTieringConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: IntelligentTieringId,
+    pub intelligent_tiering_configuration: IntelligentTieringConfiguration,
+}
+
+#[derive(Debug, Default)]
+pub struct GetBucketIntelligentTieringConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: IntelligentTieringId,
+}
+
+#[derive(Debug, Default)]
+pub struct DeleteBucketIntelligentTieringConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: IntelligentTieringId,
+}
+
+#[derive(Debug, Default)]
+pub struct ListBucketIntelligentTieringConfigurationsRequest {
+    pub bucket: BucketName,
+    /// The `NextContinuationToken` of the previous page, to continue listing from.
+    pub continuation_token: Option<ContinuationToken>,
+}
+
+#[derive(Debug, Default)]
+pub struct ListBucketIntelligentTieringConfigurationsOutput {
+    pub is_truncated: IsTruncated,
+    pub continuation_token: Option<ContinuationToken>,
+    /// Pass this as the `continuation_token` of the next request to get the next page.
+    pub next_continuation_token: Option<ContinuationToken>,
+    pub intelligent_tiering_configurations: Vec<IntelligentTieringConfiguration>,
+}
+
+/// Parse `ListBucketIntelligentTieringConfigurationsOutput` from XML
+struct ListBucketIntelligentTieringConfigurationsOutputParser;
+impl ListBucketIntelligentTieringConfigurationsOutputParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ListBucketIntelligentTieringConfigurationsOutput, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ListBucketIntelligentTieringConfigurationsOutput::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "IsTruncated" {
+                obj.is_truncated = try!(IsTruncatedParser::parse_xml("IsTruncated", stack));
+                continue;
+            }
+            if current_name == "ContinuationToken" {
+                obj.continuation_token = Some(try!(ContinuationTokenParser::parse_xml("ContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "NextContinuationToken" {
+                obj.next_continuation_token = Some(try!(ContinuationTokenParser::parse_xml("NextContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "IntelligentTieringConfiguration" {
+                obj.intelligent_tiering_configurations.push(
+                    try!(IntelligentTieringConfigurationParser::parse_xml("IntelligentTieringConfiguration", stack))
+                );
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Whether Transfer Acceleration is on for a bucket.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccelerateStatus {
+    Enabled,
+    Suspended,
+}
+
+impl AccelerateStatus {
+    /// The status as S3 writes it in XML.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            AccelerateStatus::Enabled => "Enabled",
+            AccelerateStatus::Suspended => "Suspended",
+        }
+    }
+}
+
+/// Parse `AccelerateStatus` from XML
+struct AccelerateStatusParser;
+impl AccelerateStatusParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AccelerateStatus, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+
+        match &obj[..] {
+            "Enabled" => Ok(AccelerateStatus::Enabled),
+            "Suspended" => Ok(AccelerateStatus::Suspended),
+            _ => Err(XmlParseError::new(&format!("Unknown Transfer Acceleration status {}", obj))),
+        }
+    }
+}
+/// The Transfer Acceleration configuration of a bucket.
+#[derive(Debug, Default)]
+struct AccelerateConfiguration {
+    /// Buckets that have never had acceleration enabled have no status.
+    status: Option<AccelerateStatus>,
+}
+
+/// Parse `AccelerateConfiguration` from XML
+struct AccelerateConfigurationParser;
+impl AccelerateConfigurationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AccelerateConfiguration, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = AccelerateConfiguration::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Status" {
+                obj.status = Some(try!(AccelerateStatusParser::parse_xml("Status", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+
+pub type InventoryId = String;
+/// Parse `InventoryId` from XML
+struct InventoryIdParser;
+impl InventoryIdParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryId, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `Daily` or `Weekly`.
+pub type InventoryFrequency = String;
+/// Parse `InventoryFrequency` from XML
+struct InventoryFrequencyParser;
+impl InventoryFrequencyParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryFrequency, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `CSV`, `ORC` or `Parquet`.
+pub type InventoryFormat = String;
+/// Parse `InventoryFormat` from XML
+struct InventoryFormatParser;
+impl InventoryFormatParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryFormat, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `All`, to list every version of each object, or `Current`.
+pub type InventoryIncludedObjectVersions = String;
+/// Parse `InventoryIncludedObjectVersions` from XML
+struct InventoryIncludedObjectVersionsParser;
+impl InventoryIncludedObjectVersionsParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryIncludedObjectVersions, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// A column added to the report, such as `Size`, `LastModifiedDate`, `StorageClass`,
+/// `ETag` or `EncryptionStatus`.
+pub type InventoryOptionalField = String;
+/// Parse `InventoryOptionalField` from XML
+struct InventoryOptionalFieldParser;
+impl InventoryOptionalFieldParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryOptionalField, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+pub type InventoryOptionalFields = Vec<InventoryOptionalField>;
+/// Parse `InventoryOptionalFields` from XML
+struct InventoryOptionalFieldsParser;
+impl InventoryOptionalFieldsParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryOptionalFields, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = Vec::new();
+        while try!(peek_at_name(stack)) == "Field" {
+            obj.push(try!(InventoryOptionalFieldParser::parse_xml("Field", stack)));
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+pub type IsEnabled = bool;
+/// Parse `IsEnabled` from XML
+struct IsEnabledParser;
+impl IsEnabledParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<IsEnabled, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack)) == "true";
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// How often a report is produced.
+#[derive(Debug, Default)]
+pub struct InventorySchedule {
+    pub frequency: InventoryFrequency,
+}
+
+/// Parse `InventorySchedule` from XML
+struct InventoryScheduleParser;
+impl InventoryScheduleParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventorySchedule, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = InventorySchedule::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Frequency" {
+                obj.frequency = try!(InventoryFrequencyParser::parse_xml("Frequency", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// How reports are encrypted: with S3 managed keys if `sse_s3` is set, or with the KMS key
+/// `sse_kms_key_id`.
+#[derive(Debug, Default)]
+pub struct InventoryEncryption {
+    pub sse_s3: bool,
+    pub sse_kms_key_id: Option<String>,
+}
+
+/// Parse `InventoryEncryption` from XML
+struct InventoryEncryptionParser;
+impl InventoryEncryptionParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryEncryption, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = InventoryEncryption::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "SSE-S3" {
+                try!(start_element("SSE-S3", stack));
+                try!(end_element("SSE-S3", stack));
+                obj.sse_s3 = true;
+                continue;
+            }
+            if current_name == "SSE-KMS" {
+                try!(start_element("SSE-KMS", stack));
+                if try!(peek_at_name(stack)) == "KeyId" {
+                    try!(start_element("KeyId", stack));
+                    obj.sse_kms_key_id = Some(try!(characters(stack)));
+                    try!(end_element("KeyId", stack));
+                }
+                try!(end_element("SSE-KMS", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Where reports are written: under `prefix` in `bucket`, which is the ARN of a bucket, owned
+/// by the account `account_id` if given.
+#[derive(Debug, Default)]
+pub struct InventoryS3BucketDestination {
+    pub account_id: Option<AccountId>,
+    pub bucket: BucketName,
+    pub format: InventoryFormat,
+    pub prefix: Option<Prefix>,
+    pub encryption: Option<InventoryEncryption>,
+}
+
+/// Parse `InventoryS3BucketDestination` from XML
+struct InventoryS3BucketDestinationParser;
+impl InventoryS3BucketDestinationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryS3BucketDestination, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = InventoryS3BucketDestination::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "AccountId" {
+                obj.account_id = Some(try!(AccountIdParser::parse_xml("AccountId", stack)));
+                continue;
+            }
+            if current_name == "Bucket" {
+                obj.bucket = try!(BucketNameParser::parse_xml("Bucket", stack));
+                continue;
+            }
+            if current_name == "Format" {
+                obj.format = try!(InventoryFormatParser::parse_xml("Format", stack));
+                continue;
+            }
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            if current_name == "Encryption" {
+                obj.encryption = Some(try!(InventoryEncryptionParser::parse_xml("Encryption", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+#[derive(Debug, Default)]
+pub struct InventoryDestination {
+    pub s3_bucket_destination: InventoryS3BucketDestination,
+}
+
+/// Parse `InventoryDestination` from XML
+struct InventoryDestinationParser;
+impl InventoryDestinationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryDestination, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = InventoryDestination::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "S3BucketDestination" {
+                obj.s3_bucket_destination = try!(
+                    InventoryS3BucketDestinationParser::parse_xml("S3BucketDestination", stack)
+                );
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Reports only on the objects whose keys start with `prefix`.
+#[derive(Debug, Default)]
+pub struct InventoryFilter {
+    pub prefix: Prefix,
+}
+
+/// Parse `InventoryFilter` from XML
+struct InventoryFilterParser;
+impl InventoryFilterParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryFilter, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = InventoryFilter::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = try!(PrefixParser::parse_xml("Prefix", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Lists the objects of a bucket, and their metadata, in reports written on `schedule` to
+/// `destination`.
+#[derive(Debug, Default)]
+pub struct InventoryConfiguration {
+    pub destination: InventoryDestination,
+    pub is_enabled: IsEnabled,
+    pub filter: Option<InventoryFilter>,
+    pub id: InventoryId,
+    pub included_object_versions: InventoryIncludedObjectVersions,
+    pub optional_fields: InventoryOptionalFields,
+    pub schedule: InventorySchedule,
+}
+
+/// Parse `InventoryConfiguration` from XML
+struct InventoryConfigurationParser;
+impl InventoryConfigurationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<InventoryConfiguration, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = InventoryConfiguration::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Destination" {
+                obj.destination = try!(InventoryDestinationParser::parse_xml("Destination", stack));
+                continue;
+            }
+            if current_name == "IsEnabled" {
+                obj.is_enabled = try!(IsEnabledParser::parse_xml("IsEnabled", stack));
+                continue;
+            }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(InventoryFilterParser::parse_xml("Filter", stack)));
+                continue;
+            }
+            if current_name == "Id" {
+                obj.id = try!(InventoryIdParser::parse_xml("Id", stack));
+                continue;
+            }
+            if current_name == "IncludedObjectVersions" {
+                obj.included_object_versions = try!(
+                    InventoryIncludedObjectVersionsParser::parse_xml("IncludedObjectVersions", stack)
+                );
+                continue;
+            }
+            if current_name == "OptionalFields" {
+                obj.optional_fields = try!(InventoryOptionalFieldsParser::parse_xml("OptionalFields", stack));
+                continue;
+            }
+            if current_name == "Schedule" {
+                obj.schedule = try!(InventoryScheduleParser::parse_xml("Schedule", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+#[derive(Debug, Default)]
+pub struct PutBucketInventoryConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: InventoryId,
+    pub inventory_configuration: InventoryConfiguration,
+}
+
+#[derive(Debug, Default)]
+pub struct GetBucketInventoryConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: InventoryId,
+}
+
+#[derive(Debug, Default)]
+pub struct DeleteBucketInventoryConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: InventoryId,
+}
+
+#[derive(Debug, Default)]
+pub struct ListBucketInventoryConfigurationsRequest {
+    pub bucket: BucketName,
+    /// The `NextContinuationToken` of the previous page, to continue listing from.
+    pub continuation_token: Option<ContinuationToken>,
+}
+
+#[derive(Debug, Default)]
+pub struct ListBucketInventoryConfigurationsOutput {
+    pub is_truncated: IsTruncated,
+    pub continuation_token: Option<ContinuationToken>,
+    /// Pass this as the `continuation_token` of the next request to get the next page.
+    pub next_continuation_token: Option<ContinuationToken>,
+    pub inventory_configurations: Vec<InventoryConfiguration>,
+}
+
+/// Parse `ListBucketInventoryConfigurationsOutput` from XML
+struct ListBucketInventoryConfigurationsOutputParser;
+impl ListBucketInventoryConfigurationsOutputParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ListBucketInventoryConfigurationsOutput, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ListBucketInventoryConfigurationsOutput::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "IsTruncated" {
+                obj.is_truncated = try!(IsTruncatedParser::parse_xml("IsTruncated", stack));
+                continue;
+            }
+            if current_name == "ContinuationToken" {
+                obj.continuation_token = Some(try!(ContinuationTokenParser::parse_xml("ContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "NextContinuationToken" {
+                obj.next_continuation_token = Some(try!(ContinuationTokenParser::parse_xml("NextContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "InventoryConfiguration" {
+                obj.inventory_configurations.push(
+                    try!(InventoryConfigurationParser::parse_xml("InventoryConfiguration", stack))
+                );
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+pub type AnalyticsId = String;
+/// Parse `AnalyticsId` from XML
+struct AnalyticsIdParser;
+impl AnalyticsIdParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AnalyticsId, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `V_1`, the only version of the exported data's columns.
+pub type StorageClassAnalysisSchemaVersion = String;
+/// Parse `StorageClassAnalysisSchemaVersion` from XML
+struct StorageClassAnalysisSchemaVersionParser;
+impl StorageClassAnalysisSchemaVersionParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<StorageClassAnalysisSchemaVersion, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// `CSV`, the only format analysis is exported in.
+pub type AnalyticsS3ExportFileFormat = String;
+/// Parse `AnalyticsS3ExportFileFormat` from XML
+struct AnalyticsS3ExportFileFormatParser;
+impl AnalyticsS3ExportFileFormatParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AnalyticsS3ExportFileFormat, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let obj = try!(characters(stack));
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Objects matching both a prefix and all of a set of tags.
+#[derive(Debug, Default)]
+pub struct AnalyticsAndOperator {
+    pub prefix: Option<Prefix>,
+    pub tags: TagSet,
+}
+
+/// Parse `AnalyticsAndOperator` from XML
+struct AnalyticsAndOperatorParser;
+impl AnalyticsAndOperatorParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AnalyticsAndOperator, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = AnalyticsAndOperator::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            if current_name == "Tag" {
+                obj.tags.push(try!(TagParser::parse_xml("Tag", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// The objects analysed: those with a prefix, with a tag, or with both through `and`. A
+/// configuration without a filter analyses the whole bucket.
+#[derive(Debug, Default)]
+pub struct AnalyticsFilter {
+    pub prefix: Option<Prefix>,
+    pub tag: Option<Tag>,
+    pub and: Option<AnalyticsAndOperator>,
+}
+
+/// Parse `AnalyticsFilter` from XML
+struct AnalyticsFilterParser;
+impl AnalyticsFilterParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AnalyticsFilter, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = AnalyticsFilter::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            if current_name == "Tag" {
+                obj.tag = Some(try!(TagParser::parse_xml("Tag", stack)));
+                continue;
+            }
+            if current_name == "And" {
+                obj.and = Some(try!(AnalyticsAndOperatorParser::parse_xml("And", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Where analysis is exported: under `prefix` in `bucket`, which is the ARN of a bucket,
+/// owned by the account `bucket_account_id` if given.
+#[derive(Debug, Default)]
+pub struct AnalyticsS3BucketDestination {
+    pub format: AnalyticsS3ExportFileFormat,
+    pub bucket_account_id: Option<AccountId>,
+    pub bucket: BucketName,
+    pub prefix: Option<Prefix>,
+}
+
+/// Parse `AnalyticsS3BucketDestination` from XML
+struct AnalyticsS3BucketDestinationParser;
+impl AnalyticsS3BucketDestinationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AnalyticsS3BucketDestination, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = AnalyticsS3BucketDestination::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Format" {
+                obj.format = try!(AnalyticsS3ExportFileFormatParser::parse_xml("Format", stack));
+                continue;
+            }
+            if current_name == "BucketAccountId" {
+                obj.bucket_account_id = Some(try!(AccountIdParser::parse_xml("BucketAccountId", stack)));
+                continue;
+            }
+            if current_name == "Bucket" {
+                obj.bucket = try!(BucketNameParser::parse_xml("Bucket", stack));
+                continue;
+            }
+            if current_name == "Prefix" {
+                obj.prefix = Some(try!(PrefixParser::parse_xml("Prefix", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+#[derive(Debug, Default)]
+pub struct AnalyticsExportDestination {
+    pub s3_bucket_destination: AnalyticsS3BucketDestination,
+}
+
+/// Parse `AnalyticsExportDestination` from XML
+struct AnalyticsExportDestinationParser;
+impl AnalyticsExportDestinationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AnalyticsExportDestination, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = AnalyticsExportDestination::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "S3BucketDestination" {
+                obj.s3_bucket_destination = try!(
+                    AnalyticsS3BucketDestinationParser::parse_xml("S3BucketDestination", stack)
+                );
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Exports the analysis daily, as columns of `output_schema_version`, to `destination`.
+#[derive(Debug, Default)]
+pub struct StorageClassAnalysisDataExport {
+    pub output_schema_version: StorageClassAnalysisSchemaVersion,
+    pub destination: AnalyticsExportDestination,
+}
+
+/// Parse `StorageClassAnalysisDataExport` from XML
+struct StorageClassAnalysisDataExportParser;
+impl StorageClassAnalysisDataExportParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<StorageClassAnalysisDataExport, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = StorageClassAnalysisDataExport::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "OutputSchemaVersion" {
+                obj.output_schema_version = try!(
+                    StorageClassAnalysisSchemaVersionParser::parse_xml("OutputSchemaVersion", stack)
+                );
+                continue;
+            }
+            if current_name == "Destination" {
+                obj.destination = try!(AnalyticsExportDestinationParser::parse_xml("Destination", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// The analysis of how often objects are accessed, which is only shown in the console
+/// unless it's exported through `data_export`.
+#[derive(Debug, Default)]
+pub struct StorageClassAnalysis {
+    pub data_export: Option<StorageClassAnalysisDataExport>,
+}
+
+/// Parse `StorageClassAnalysis` from XML
+struct StorageClassAnalysisParser;
+impl StorageClassAnalysisParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<StorageClassAnalysis, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = StorageClassAnalysis::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "DataExport" {
+                obj.data_export = Some(try!(StorageClassAnalysisDataExportParser::parse_xml("DataExport", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Analyses the access patterns of the objects matching `filter`, to show when they could
+/// move to a cheaper storage class.
+#[derive(Debug, Default)]
+pub struct AnalyticsConfiguration {
+    pub id: AnalyticsId,
+    pub filter: Option<AnalyticsFilter>,
+    pub storage_class_analysis: StorageClassAnalysis,
+}
+
+/// Parse `AnalyticsConfiguration` from XML
+struct AnalyticsConfigurationParser;
+impl AnalyticsConfigurationParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<AnalyticsConfiguration, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = AnalyticsConfiguration::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Id" {
+                obj.id = try!(AnalyticsIdParser::parse_xml("Id", stack));
+                continue;
+            }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(AnalyticsFilterParser::parse_xml("Filter", stack)));
+                continue;
+            }
+            if current_name == "StorageClassAnalysis" {
+                obj.storage_class_analysis = try!(
+                    StorageClassAnalysisParser::parse_xml("StorageClassAnalysis", stack)
+                );
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+#[derive(Debug, Default)]
+pub struct PutBucketAnalyticsConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: AnalyticsId,
+    pub analytics_configuration: AnalyticsConfiguration,
+}
+
+#[derive(Debug, Default)]
+pub struct GetBucketAnalyticsConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: AnalyticsId,
+}
+
+#[derive(Debug, Default)]
+pub struct DeleteBucketAnalyticsConfigurationRequest {
+    pub bucket: BucketName,
+    pub id: AnalyticsId,
+}
+
+#[derive(Debug, Default)]
+pub struct ListBucketAnalyticsConfigurationsRequest {
+    pub bucket: BucketName,
+    /// The `NextContinuationToken` of the previous page, to continue listing from.
+    pub continuation_token: Option<ContinuationToken>,
+}
+
+#[derive(Debug, Default)]
+pub struct ListBucketAnalyticsConfigurationsOutput {
+    pub is_truncated: IsTruncated,
+    pub continuation_token: Option<ContinuationToken>,
+    /// Pass this as the `continuation_token` of the next request to get the next page.
+    pub next_continuation_token: Option<ContinuationToken>,
+    pub analytics_configurations: Vec<AnalyticsConfiguration>,
+}
+
+/// Parse `ListBucketAnalyticsConfigurationsOutput` from XML
+struct ListBucketAnalyticsConfigurationsOutputParser;
+impl ListBucketAnalyticsConfigurationsOutputParser {
+    fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ListBucketAnalyticsConfigurationsOutput, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = ListBucketAnalyticsConfigurationsOutput::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "IsTruncated" {
+                obj.is_truncated = try!(IsTruncatedParser::parse_xml("IsTruncated", stack));
+                continue;
+            }
+            if current_name == "ContinuationToken" {
+                obj.continuation_token = Some(try!(ContinuationTokenParser::parse_xml("ContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "NextContinuationToken" {
+                obj.next_continuation_token = Some(try!(ContinuationTokenParser::parse_xml("NextContinuationToken", stack)));
+                continue;
+            }
+            if current_name == "AnalyticsConfiguration" {
+                obj.analytics_configurations.push(
+                    try!(AnalyticsConfigurationParser::parse_xml("AnalyticsConfiguration", stack))
+                );
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+/// Options for how an `S3Client` addresses buckets, and what it calls around its requests.
+#[derive(Debug, Default)]
+pub struct S3Config {
+    /// Sends requests to a bucket through the nearest edge location, at
+    /// `bucket.s3-accelerate.amazonaws.com`. The bucket must have Transfer Acceleration
+    /// enabled.
+    pub use_accelerate: bool,
+    /// Addresses buckets by the first segment of the path rather than by subdomain.
+    pub force_path_style: bool,
+    /// Called, in order, around every request the client sends.
+    pub interceptors: Vec<Arc<Interceptor + Send + Sync>>,
+}
+
+pub struct S3Client<P> where P: ProvideAwsCredentials {
+    credentials_provider: P,
+    region: Region,
+    hostname: Option<String>,
+    config: S3Config,
+}
+
+impl<P> S3Client<P> where P: ProvideAwsCredentials {
+    pub fn new(credentials_provider: P, region: Region) -> S3Client<P> {
+        S3Client { credentials_provider: credentials_provider, region: region, hostname: None, config: S3Config::default() }
+    }
+
+    /// Creates a client addressing buckets as `config` asks. Transfer Acceleration only works
+    /// with buckets addressed by subdomain, so it can't be combined with `force_path_style`.
+    pub fn with_config(credentials_provider: P, region: Region, config: S3Config) -> Result<S3Client<P>, AwsError> {
+        if config.use_accelerate && config.force_path_style {
+            return Err(AwsError::new("ClientConfigError: Transfer Acceleration can't be used with path-style addressing"));
+        }
+
+        Ok(S3Client { credentials_provider: credentials_provider, region: region, hostname: None, config: config })
+    }
+
+    /// Send requests to `hostname` instead of the default endpoint for the region.
+    ///
+    /// The hostname may be given as a URL like `http://localhost:9000` to choose the scheme,
+    /// otherwise HTTPS is used. Buckets are still addressed by subdomain of the hostname unless
+    /// the client was created with `force_path_style`, which most S3-compatible servers,
+    /// including the mock in `rusoto_test_utils`, expect.
+    pub fn set_hostname(&mut self, hostname: Option<String>) {
+        self.hostname = hostname;
+    }
+
+    /// Signs and sends a request, calling the client's interceptors around it.
+    fn sign_and_execute(&mut self, request: &mut SignedRequest) -> Result<Response, AwsError> {
+        request.set_interceptors(self.config.interceptors.clone());
+
+        Ok(request.sign_and_execute(try!(self.credentials_provider.credentials())))
+    }
+
+    /// Returns metadata about all of the versions of objects in a bucket.
+    pub fn list_object_versions(&mut self, input: &ListObjectVersionsRequest) -> Result<ListObjectVersionsOutput, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/{Bucket}?versions");
+        let mut params = Params::new();
+        params.put("Action", "ListObjectVersions");
+        ListObjectVersionsRequestWriter::write_params(&mut params, "", &input);
+        request.set_params(params);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        stack.next();
+        match status {
+            200 => {
+                Ok(try!(ListObjectVersionsOutputParser::parse_xml("ListObjectVersionsOutput", &mut stack)))
+            }
+            _ => { Err(AwsError::new("error")) }
+        }
+    }
+    /// Replaces a policy on a bucket. If the bucket already has a policy, the one in
+    /// this request completely replaces it.
+    pub fn put_bucket_policy(&mut self, input: &PutBucketPolicyRequest) -> Result<(), AwsError> {
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/{Bucket}?policy");
+        let mut params = Params::new();
+        params.put("Action", "PutBucketPolicy");
+        PutBucketPolicyRequestWriter::write_params(&mut params, "", &input);
+        request.set_params(params);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        stack.next();
+        match status {
+            200 => {
+                Ok(())
+            }
+            _ => { Err(AwsError::new("error")) }
+        }
+    }
+    /// Returns some or all (up to 1000) of the objects in a bucket. You can use the
+    /// request parameters as selection criteria to return a subset of the objects in
+    /// a bucket.
+    pub fn list_objects(&mut self, input: &ListObjectsRequest) -> Result<ListObjectsOutput, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/{Bucket}");
+        let mut params = Params::new();
+        params.put("Action", "ListObjects");
+        ListObjectsRequestWriter::write_params(&mut params, "", &input);
+        request.set_params(params);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        stack.next();
+        match status {
+            200 => {
+                Ok(try!(ListObjectsOutputParser::parse_xml("ListObjectsOutput", &mut stack)))
+            }
+            _ => { Err(AwsError::new("error")) }
+        }
+    }
+    /// Returns some or all (up to 1000) of the objects in a bucket, using version 2 of the
+    /// API, which pages with continuation tokens rather than markers.
+    pub fn list_objects_v2(&mut self, input: &ListObjectsV2Request) -> Result<ListObjectsV2Output, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        ListObjectsV2RequestWriter::write_params(&mut params, input);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                Ok(try!(ListObjectsV2OutputParser::parse_xml("ListBucketResult", &mut stack)))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for ListObjectsV2: {}", status))) }
+        }
+    }
+    /// Set the website configuration for a bucket.
+    pub fn put_bucket_website(&mut self, input: &PutBucketWebsiteRequest) -> Result<(), AwsError> {
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/{Bucket}?website");
+        let mut params = Params::new();
         params.put("Action", "PutBucketWebsite");
         PutBucketWebsiteRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11118,7 +12819,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketNotification");
         PutBucketNotificationRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11140,7 +12841,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketLogging");
         PutBucketLoggingRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11156,22 +12857,28 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
     /// Creates a new replication configuration (or replaces an existing one, if
     /// present).
     pub fn put_bucket_replication(&mut self, input: &PutBucketReplicationRequest) -> Result<(), AwsError> {
-        let mut request = SignedRequest::new("PUT", "s3", self.region, "/{Bucket}?replication");
+        let payload = replication_configuration_xml(&input.replication_configuration);
+        let content_md5 = match input.content_md5 {
+            Some(ref content_md5) => content_md5.to_owned(),
+            None => hash(MD5, &payload).to_base64(STANDARD),
+        };
+
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/");
         let mut params = Params::new();
-        params.put("Action", "PutBucketReplication");
-        PutBucketReplicationRequestWriter::write_params(&mut params, "", &input);
+        params.put("replication", "");
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        request.add_header("Content-MD5", &content_md5);
+        if let Some(ref token) = input.token {
+            request.add_header("x-amz-bucket-object-lock-token", token);
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
-        let mut reader = EventReader::new(result);
-        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-        stack.next(); // xml start tag
-        stack.next();
         match status {
-            200 => {
-                Ok(())
-            }
-            _ => { Err(AwsError::new("error")) }
+            200 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for PutBucketReplication: {}", status))) }
         }
     }
     /// Uploads a part in a multipart upload.
@@ -11182,8 +12889,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
 
         request.set_payload(input.body);
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
         if let Some(ref md5) = input.content_md5 {
             request.add_header("Content-MD5", &md5);
@@ -11196,7 +12902,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("uploadId", &upload_id);
         request.set_params(params);
 
-        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
 
         match status {
@@ -11243,11 +12949,10 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
             request.add_header("Content-MD5", &md5);
         }
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
         request.set_payload(input.body);
 
-        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
 
         match status {
@@ -11273,7 +12978,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "DeleteBucketCors");
         DeleteBucketCorsRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11294,7 +12999,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketVersioning");
         PutBucketVersioningRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11314,7 +13019,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketCors");
         GetBucketCorsRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11335,7 +13040,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketLifecycle");
         PutBucketLifecycleRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11355,7 +13060,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketAcl");
         GetBucketAclRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11376,7 +13081,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketLogging");
         GetBucketLoggingRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11397,7 +13102,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "HeadBucket");
         HeadBucketRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11416,7 +13121,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         let mut params = Params::new();
         params.put("Action", "PutBucketAcl");
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11436,7 +13141,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "DeleteBucketWebsite");
         DeleteBucketWebsiteRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11456,7 +13161,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "DeleteBucketPolicy");
         DeleteBucketPolicyRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11476,7 +13181,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketNotificationConfiguration");
         GetBucketNotificationConfigurationRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11492,26 +13197,63 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
     /// This operation enables you to delete multiple objects from a bucket using a
     /// single HTTP request. You may specify up to 1000 keys.
     pub fn delete_objects(&mut self, input: &DeleteObjectsRequest) -> Result<DeleteObjectsOutput, AwsError> {
-        // let mut uri = String::from("/");
-        // uri = uri +  &input.key.to_string();
-        // let mut request = SignedRequest::new("DELETE", "s3", self.region, &uri);
-        // let mut params = Params::new();
-        //
-        // let hostname = self.hostname(Some(&input.bucket));
-        // request.set_hostname(Some(hostname));
-        //
-        // params.put("Action", "DeleteObjects");
-        // DeleteObjectsRequestWriter::write_params(&mut params, "", &input);
-        // request.set_params(params);
-        // let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
-        // let status = result.status.to_u16();
-        // match status {
-        //  200 => {
-        //      Ok(try!(DeleteObjectsOutputParser::parse_xml("DeleteObjectsOutput", &mut stack)))
-        //  }
-        //  _ => { Err(AwsError::new("error")) }
-        // }
-        Err(AwsError::new("not implemented"))
+        let (hostname, path_prefix) = self.bucket_endpoint(&input.bucket);
+        let credentials = try!(self.credentials_provider.credentials()).clone();
+
+        execute_delete_objects(&hostname, &path_prefix, self.region, &credentials, self.config.interceptors.clone(), input)
+    }
+
+    /// Deletes any number of objects from a bucket, in batches of 1000 keys with up to
+    /// `concurrency` requests in flight at once.
+    ///
+    /// The batches are sent in quiet mode, so the result only lists the keys that couldn't be
+    /// deleted. A batch whose request fails doesn't stop the others; each of its keys is
+    /// listed with the code `RequestFailed` and the error as the message.
+    pub fn delete_objects_in_batches(&mut self, bucket: &str, objects: Vec<ObjectIdentifier>,
+        concurrency: usize) -> Result<Errors, AwsError> {
+        let (hostname, path_prefix) = self.bucket_endpoint(&bucket.to_string());
+        let credentials = try!(self.credentials_provider.credentials()).clone();
+        let region = self.region;
+        let mut requests = delete_objects_batches(bucket, objects).into_iter().map(Arc::new);
+        let mut errors = Vec::new();
+
+        loop {
+            let batches = requests.by_ref().take(cmp::max(concurrency, 1)).collect::<Vec<_>>();
+
+            if batches.is_empty() {
+                break;
+            }
+
+            let handles = batches.iter().map(|request| {
+                let hostname = hostname.clone();
+                let path_prefix = path_prefix.clone();
+                let credentials = credentials.clone();
+                let interceptors = self.config.interceptors.clone();
+                let request = request.clone();
+
+                thread::spawn(move || {
+                    execute_delete_objects(&hostname, &path_prefix, region, &credentials, interceptors, &request)
+                })
+            }).collect::<Vec<_>>();
+
+            for (request, handle) in batches.iter().zip(handles) {
+                let result = handle.join().unwrap_or_else(|_| Err(AwsError::new("DeleteObjects request panicked")));
+
+                match result {
+                    Ok(output) => errors.extend(output.errors),
+                    Err(error) => errors.extend(request.delete.objects.iter().map(|object| {
+                        S3ClientError {
+                            version_id: object.version_id.clone().unwrap_or_default(),
+                            code: "RequestFailed".to_string(),
+                            message: error.description().to_string(),
+                            key: object.key.clone(),
+                        }
+                    })),
+                }
+            }
+        }
+
+        Ok(errors)
     }
     pub fn delete_bucket_replication(&mut self, input: &DeleteBucketReplicationRequest) -> Result<(), AwsError> {
         let mut request = SignedRequest::new("DELETE", "s3", self.region, "/{Bucket}?replication");
@@ -11519,7 +13261,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "DeleteBucketReplication");
         DeleteBucketReplicationRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11539,7 +13281,10 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "CopyObject");
         CopyObjectRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11559,7 +13304,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         let mut params = Params::new();
         params.put("Action", "ListBuckets");
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11586,7 +13331,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketRequestPayment");
         PutBucketRequestPaymentRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11606,7 +13351,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketNotificationConfiguration");
         PutBucketNotificationConfigurationRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11628,7 +13373,10 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "HeadObject");
         HeadObjectRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11648,7 +13396,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "DeleteBucketTagging");
         DeleteBucketTaggingRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11668,7 +13416,10 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetObjectTorrent");
         GetObjectTorrentRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11688,7 +13439,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketLifecycle");
         GetBucketLifecycleRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11707,8 +13458,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         let region = Region::UsEast1;
         let mut create_config : Vec<u8>;
         let mut request = SignedRequest::new("PUT", "s3", region, "");
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
         if needs_create_bucket_config(self.region) {
             create_config = create_bucket_config_xml(self.region);
@@ -11720,7 +13470,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
             Some(ref canned_acl) => request.add_header("x-amz-acl", &canned_acl_in_aws_format(&canned_acl)),
         }
 
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
 
         match status {
@@ -11746,12 +13496,11 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("uploadId", &input.upload_id.to_string());
         request.set_params(params);
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
         request.set_payload(input.multipart_upload);
 
-        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
 
         match status {
@@ -11776,7 +13525,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketWebsite");
         GetBucketWebsiteRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11800,10 +13549,9 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("uploads", "");
         request.set_params(params);
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
 
         let mut reader = EventReader::new(result);
@@ -11822,10 +13570,9 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
     pub fn delete_bucket(&mut self, input: &DeleteBucketRequest, region: Region) -> Result<(), AwsError> {
         let mut request = SignedRequest::new("DELETE", "s3", region, "");
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
-        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         match status {
             204 => {
@@ -11864,7 +13611,9 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         let accept_ranges = try!(S3Client::<P>::get_value_for_header("accept-ranges".to_string(), response));
         let last_modified = try!(S3Client::<P>::get_value_for_header("Last-Modified".to_string(), response));
         let content_range = try!(S3Client::<P>::get_value_for_header("Content-Range".to_string(), response));
-        let request_charged = try!(S3Client::<P>::get_value_for_header("x-amz-request-charged".to_string(), response));
+        let request_charged = RequestCharged::from_header(
+            &try!(S3Client::<P>::get_value_for_header("x-amz-request-charged".to_string(), response))
+        );
         let content_encoding = try!(S3Client::<P>::get_value_for_header("Content-Encoding".to_string(), response));
         let replication_status = try!(S3Client::<P>::get_value_for_header("x-amz-replication-status".to_string(), response));
         let storage_class = try!(S3Client::<P>::get_value_for_header("x-amz-storage-class".to_string(), response));
@@ -11933,14 +13682,16 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         let mut request = SignedRequest::new("GET", "s3", self.region, &uri);
         let mut params = Params::new();
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
         params.put("Action", "GetObject");
         GetObjectRequestWriter::write_params(&mut params, "", &input);
 
         request.set_params(params);
-        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
 
         match status {
@@ -11966,7 +13717,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketPolicy");
         GetBucketPolicyRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -11986,7 +13737,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketVersioning");
         GetBucketVersioningRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12007,10 +13758,9 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("uploads", "");
         request.set_params(params);
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12032,7 +13782,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketRequestPayment");
         GetBucketRequestPaymentRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12052,7 +13802,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketTagging");
         PutBucketTaggingRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12072,7 +13822,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketTagging");
         GetBucketTaggingRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12096,10 +13846,9 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("uploadId", &input.upload_id.to_string());
         request.set_params(params);
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12120,7 +13869,10 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutObjectAcl");
         PutObjectAclRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12140,7 +13892,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketLocation");
         GetBucketLocationRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12160,7 +13912,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "PutBucketCors");
         PutBucketCorsRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12180,7 +13932,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "DeleteBucketLifecycle");
         DeleteBucketLifecycleRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12200,7 +13952,7 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetBucketNotification");
         GetBucketNotificationConfigurationRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12221,10 +13973,9 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("uploadId", &input.upload_id.to_string());
         request.set_params(params);
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
-        let mut result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
 
         match status {
@@ -12251,7 +14002,10 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         params.put("Action", "GetObjectAcl");
         GetObjectAclRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -12291,60 +14045,509 @@ impl<P> S3Client<P> where P: ProvideAwsCredentials {
         let mut request = SignedRequest::new("DELETE", "s3", self.region, &uri);
         let mut params = Params::new();
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
 
         params.put("Action", "DeleteObject");
         DeleteObjectRequestWriter::write_params(&mut params, "", &input);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+
+        match status {
+            204 => {
+                Ok(DeleteObjectOutput::default())
+                // Ok(try!(DeleteObjectOutputParser::parse_xml("DeleteObjectOutput", &mut stack)))
+            }
+            _ => { Err(AwsError::new("delete object error")) }
+        }
+    }
+    /// Restores an archived copy of an object back into Amazon S3
+    pub fn restore_object(&mut self, input: &RestoreObjectRequest) -> Result<RestoreObjectOutput, AwsError> {
+        let mut request = SignedRequest::new("POST", "s3", self.region, "/{Bucket}/{Key+}?restore");
+        let mut params = Params::new();
+        params.put("Action", "RestoreObject");
+        RestoreObjectRequestWriter::write_params(&mut params, "", &input);
+        request.set_params(params);
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer.as_str());
+        }
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        stack.next();
+        match status {
+            200 => {
+                Ok(try!(RestoreObjectOutputParser::parse_xml("RestoreObjectOutput", &mut stack)))
+            }
+            _ => { Err(AwsError::new("error")) }
+        }
+    }
+    pub fn get_bucket_replication(&mut self, input: &GetBucketReplicationRequest) -> Result<GetBucketReplicationOutput, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("replication", "");
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                let replication_configuration = try!(ReplicationConfigurationParser::parse_xml("ReplicationConfiguration", &mut stack));
+                Ok(GetBucketReplicationOutput { replication_configuration: replication_configuration })
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for GetBucketReplication: {}", status))) }
+        }
+    }
+
+    /// Sets the Object Lock configuration of a bucket, whose rule is applied to every new
+    /// object version.
+    pub fn put_object_lock_configuration(&mut self, input: &PutObjectLockConfigurationRequest) -> Result<(), AwsError> {
+        let payload = object_lock_configuration_xml(&input.object_lock_configuration);
+        let content_md5 = hash(MD5, &payload).to_base64(STANDARD);
+
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("object-lock", "");
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        request.add_header("Content-MD5", &content_md5);
+        if let Some(ref token) = input.token {
+            request.add_header("x-amz-bucket-object-lock-token", token);
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            200 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for PutObjectLockConfiguration: {}", status))) }
+        }
+    }
+    /// Returns the Object Lock configuration of a bucket.
+    pub fn get_object_lock_configuration(&mut self, input: &GetObjectLockConfigurationRequest) -> Result<ObjectLockConfiguration, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("object-lock", "");
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                Ok(try!(ObjectLockConfigurationParser::parse_xml("ObjectLockConfiguration", &mut stack)))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for GetObjectLockConfiguration: {}", status))) }
+        }
+    }
+    /// Places a retention period on an object version.
+    pub fn put_object_retention(&mut self, input: &PutObjectRetentionRequest) -> Result<(), AwsError> {
+        let payload = object_lock_retention_xml(&input.retention);
+        let mut request = self.put_object_retention_request(input, &payload);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            200 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for PutObjectRetention: {}", status))) }
+        }
+    }
+    /// Returns the retention period of an object version.
+    pub fn get_object_retention(&mut self, input: &GetObjectRetentionRequest) -> Result<ObjectLockRetention, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, &format!("/{}", input.key));
+        let mut params = Params::new();
+        params.put("retention", "");
+        if let Some(ref version_id) = input.version_id {
+            params.put("versionId", version_id);
+        }
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                Ok(try!(ObjectLockRetentionParser::parse_xml("Retention", &mut stack)))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for GetObjectRetention: {}", status))) }
+        }
+    }
+
+    /// Adds an S3 Intelligent-Tiering configuration to a bucket, or replaces the one with
+    /// the same ID.
+    pub fn put_bucket_intelligent_tiering_configuration(&mut self, input: &PutBucketIntelligentTieringConfigurationRequest) -> Result<(), AwsError> {
+        let payload = intelligent_tiering_configuration_xml(&input.intelligent_tiering_configuration);
+
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("intelligent-tiering", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            200 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for PutBucketIntelligentTieringConfiguration: {}", status))) }
+        }
+    }
+    /// Returns an S3 Intelligent-Tiering configuration of a bucket.
+    pub fn get_bucket_intelligent_tiering_configuration(&mut self, input: &GetBucketIntelligentTieringConfigurationRequest) -> Result<IntelligentTieringConfiguration, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("intelligent-tiering", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                Ok(try!(IntelligentTieringConfigurationParser::parse_xml("IntelligentTieringConfiguration", &mut stack)))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for GetBucketIntelligentTieringConfiguration: {}", status))) }
+        }
+    }
+    /// Returns a page of the S3 Intelligent-Tiering configurations of a bucket.
+    pub fn list_bucket_intelligent_tiering_configurations(&mut self, input: &ListBucketIntelligentTieringConfigurationsRequest) -> Result<ListBucketIntelligentTieringConfigurationsOutput, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("intelligent-tiering", "");
+        if let Some(ref continuation_token) = input.continuation_token {
+            params.put("continuation-token", continuation_token);
+        }
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                Ok(try!(ListBucketIntelligentTieringConfigurationsOutputParser::parse_xml("ListBucketIntelligentTieringConfigurationsResult", &mut stack)))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for ListBucketIntelligentTieringConfigurations: {}", status))) }
+        }
+    }
+    /// Lists every S3 Intelligent-Tiering configuration of a bucket, following the
+    /// continuation tokens of each page.
+    pub fn list_all_bucket_intelligent_tiering_configurations(&mut self, bucket: &str) -> Result<Vec<IntelligentTieringConfiguration>, AwsError> {
+        let mut input = ListBucketIntelligentTieringConfigurationsRequest {
+            bucket: bucket.to_owned(),
+            continuation_token: None,
+        };
+        let mut configurations = Vec::new();
+
+        loop {
+            let output = try!(self.list_bucket_intelligent_tiering_configurations(&input));
+
+            configurations.extend(output.intelligent_tiering_configurations);
+
+            match (output.is_truncated, output.next_continuation_token) {
+                (true, Some(token)) => input.continuation_token = Some(token),
+                _ => return Ok(configurations),
+            }
+        }
+    }
+    /// Deletes an S3 Intelligent-Tiering configuration of a bucket.
+    pub fn delete_bucket_intelligent_tiering_configuration(&mut self, input: &DeleteBucketIntelligentTieringConfigurationRequest) -> Result<(), AwsError> {
+        let mut request = SignedRequest::new("DELETE", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("intelligent-tiering", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            204 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for DeleteBucketIntelligentTieringConfiguration: {}", status))) }
+        }
+    }
+    /// Enables or suspends Transfer Acceleration for a bucket.
+    pub fn put_bucket_accelerate_configuration(&mut self, bucket: &str, status: AccelerateStatus) -> Result<(), AwsError> {
+        let payload = accelerate_configuration_xml(status);
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("accelerate", "");
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &bucket.to_owned());
+        request.set_payload(Some(&payload));
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            200 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for PutBucketAccelerateConfiguration: {}", status))) }
+        }
+    }
+    /// Returns whether Transfer Acceleration is enabled or suspended for a bucket. Buckets that
+    /// have never had it enabled are `Suspended`.
+    pub fn get_bucket_accelerate_configuration(&mut self, bucket: &str) -> Result<AccelerateStatus, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("accelerate", "");
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &bucket.to_owned());
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                let configuration = try!(AccelerateConfigurationParser::parse_xml("AccelerateConfiguration", &mut stack));
+                Ok(configuration.status.unwrap_or(AccelerateStatus::Suspended))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for GetBucketAccelerateConfiguration: {}", status))) }
+        }
+    }
+    /// Adds an inventory configuration to a bucket, or replaces the one with the same ID.
+    pub fn put_bucket_inventory_configuration(&mut self, input: &PutBucketInventoryConfigurationRequest) -> Result<(), AwsError> {
+        let payload = inventory_configuration_xml(&input.inventory_configuration);
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("inventory", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        request.set_payload(Some(&payload));
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            200 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for PutBucketInventoryConfiguration: {}", status))) }
+        }
+    }
+    /// Returns an inventory configuration of a bucket.
+    pub fn get_bucket_inventory_configuration(&mut self, input: &GetBucketInventoryConfigurationRequest) -> Result<InventoryConfiguration, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("inventory", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                Ok(try!(InventoryConfigurationParser::parse_xml("InventoryConfiguration", &mut stack)))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for GetBucketInventoryConfiguration: {}", status))) }
+        }
+    }
+    /// Returns a page of the inventory configurations of a bucket.
+    pub fn list_bucket_inventory_configurations(&mut self, input: &ListBucketInventoryConfigurationsRequest) -> Result<ListBucketInventoryConfigurationsOutput, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("inventory", "");
+        if let Some(ref continuation_token) = input.continuation_token {
+            params.put("continuation-token", continuation_token);
+        }
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        let mut reader = EventReader::new(result);
+        let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+        stack.next(); // xml start tag
+        match status {
+            200 => {
+                Ok(try!(ListBucketInventoryConfigurationsOutputParser::parse_xml("ListInventoryConfigurationsResult", &mut stack)))
+            }
+            _ => { Err(AwsError::new(format!("HTTP response code for ListBucketInventoryConfigurations: {}", status))) }
+        }
+    }
+    /// Lists every inventory configuration of a bucket, following the continuation tokens of
+    /// each page.
+    pub fn list_all_bucket_inventory_configurations(&mut self, bucket: &str) -> Result<Vec<InventoryConfiguration>, AwsError> {
+        let mut input = ListBucketInventoryConfigurationsRequest {
+            bucket: bucket.to_owned(),
+            continuation_token: None,
+        };
+        let mut configurations = Vec::new();
+
+        loop {
+            let output = try!(self.list_bucket_inventory_configurations(&input));
+
+            configurations.extend(output.inventory_configurations);
+
+            match (output.is_truncated, output.next_continuation_token) {
+                (true, Some(token)) => input.continuation_token = Some(token),
+                _ => return Ok(configurations),
+            }
+        }
+    }
+    /// Deletes an inventory configuration of a bucket.
+    pub fn delete_bucket_inventory_configuration(&mut self, input: &DeleteBucketInventoryConfigurationRequest) -> Result<(), AwsError> {
+        let mut request = SignedRequest::new("DELETE", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("inventory", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            204 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for DeleteBucketInventoryConfiguration: {}", status))) }
+        }
+    }
+    /// Adds an analytics configuration to a bucket, or replaces the one with the same ID.
+    pub fn put_bucket_analytics_configuration(&mut self, input: &PutBucketAnalyticsConfigurationRequest) -> Result<(), AwsError> {
+        let payload = analytics_configuration_xml(&input.analytics_configuration);
+        let mut request = SignedRequest::new("PUT", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("analytics", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        request.set_payload(Some(&payload));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
-
         match status {
-            204 => {
-                Ok(DeleteObjectOutput::default())
-                // Ok(try!(DeleteObjectOutputParser::parse_xml("DeleteObjectOutput", &mut stack)))
-            }
-            _ => { Err(AwsError::new("delete object error")) }
+            200 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for PutBucketAnalyticsConfiguration: {}", status))) }
         }
     }
-    /// Restores an archived copy of an object back into Amazon S3
-    pub fn restore_object(&mut self, input: &RestoreObjectRequest) -> Result<RestoreObjectOutput, AwsError> {
-        let mut request = SignedRequest::new("POST", "s3", self.region, "/{Bucket}/{Key+}?restore");
+    /// Returns an analytics configuration of a bucket.
+    pub fn get_bucket_analytics_configuration(&mut self, input: &GetBucketAnalyticsConfigurationRequest) -> Result<AnalyticsConfiguration, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
         let mut params = Params::new();
-        params.put("Action", "RestoreObject");
-        RestoreObjectRequestWriter::write_params(&mut params, "", &input);
+        params.put("analytics", "");
+        params.put("id", &input.id);
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
         stack.next(); // xml start tag
-        stack.next();
         match status {
             200 => {
-                Ok(try!(RestoreObjectOutputParser::parse_xml("RestoreObjectOutput", &mut stack)))
+                Ok(try!(AnalyticsConfigurationParser::parse_xml("AnalyticsConfiguration", &mut stack)))
             }
-            _ => { Err(AwsError::new("error")) }
+            _ => { Err(AwsError::new(format!("HTTP response code for GetBucketAnalyticsConfiguration: {}", status))) }
         }
     }
-    pub fn get_bucket_replication(&mut self, input: &GetBucketReplicationRequest) -> Result<GetBucketReplicationOutput, AwsError> {
-        let mut request = SignedRequest::new("GET", "s3", self.region, "/{Bucket}?replication");
+    /// Returns a page of the analytics configurations of a bucket.
+    pub fn list_bucket_analytics_configurations(&mut self, input: &ListBucketAnalyticsConfigurationsRequest) -> Result<ListBucketAnalyticsConfigurationsOutput, AwsError> {
+        let mut request = SignedRequest::new("GET", "s3", self.region, "/");
         let mut params = Params::new();
-        params.put("Action", "GetBucketReplication");
-        GetBucketReplicationRequestWriter::write_params(&mut params, "", &input);
+        params.put("analytics", "");
+        if let Some(ref continuation_token) = input.continuation_token {
+            params.put("continuation-token", continuation_token);
+        }
         request.set_params(params);
-        let result = request.sign_and_execute(try!(self.credentials_provider.credentials()));
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status.to_u16();
         let mut reader = EventReader::new(result);
         let mut stack = XmlResponseFromAws::new(reader.events().peekable());
         stack.next(); // xml start tag
-        stack.next();
         match status {
             200 => {
-                Ok(try!(GetBucketReplicationOutputParser::parse_xml("GetBucketReplicationOutput", &mut stack)))
+                Ok(try!(ListBucketAnalyticsConfigurationsOutputParser::parse_xml("ListBucketAnalyticsConfigurationResult", &mut stack)))
             }
-            _ => { Err(AwsError::new("error")) }
+            _ => { Err(AwsError::new(format!("HTTP response code for ListBucketAnalyticsConfigurations: {}", status))) }
+        }
+    }
+    /// Lists every analytics configuration of a bucket, following the continuation tokens of
+    /// each page.
+    pub fn list_all_bucket_analytics_configurations(&mut self, bucket: &str) -> Result<Vec<AnalyticsConfiguration>, AwsError> {
+        let mut input = ListBucketAnalyticsConfigurationsRequest {
+            bucket: bucket.to_owned(),
+            continuation_token: None,
+        };
+        let mut configurations = Vec::new();
+
+        loop {
+            let output = try!(self.list_bucket_analytics_configurations(&input));
+
+            configurations.extend(output.analytics_configurations);
+
+            match (output.is_truncated, output.next_continuation_token) {
+                (true, Some(token)) => input.continuation_token = Some(token),
+                _ => return Ok(configurations),
+            }
+        }
+    }
+    /// Deletes an analytics configuration of a bucket.
+    pub fn delete_bucket_analytics_configuration(&mut self, input: &DeleteBucketAnalyticsConfigurationRequest) -> Result<(), AwsError> {
+        let mut request = SignedRequest::new("DELETE", "s3", self.region, "/");
+        let mut params = Params::new();
+        params.put("analytics", "");
+        params.put("id", &input.id);
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status.to_u16();
+        match status {
+            204 => Ok(()),
+            _ => { Err(AwsError::new(format!("HTTP response code for DeleteBucketAnalyticsConfiguration: {}", status))) }
+        }
+    }
+
+    /// Builds the unsigned `PutObjectRetention` request carrying `payload`, which S3 requires
+    /// the MD5 of.
+    fn put_object_retention_request<'a>(&self, input: &PutObjectRetentionRequest, payload: &'a [u8])
+        -> SignedRequest<'a> {
+        let mut request = SignedRequest::new("PUT", "s3", self.region, &format!("/{}", input.key));
+        let mut params = Params::new();
+        params.put("retention", "");
+        if let Some(ref version_id) = input.version_id {
+            params.put("versionId", version_id);
+        }
+        request.set_params(params);
+        self.set_bucket_endpoint(&mut request, &input.bucket);
+        request.add_header("Content-MD5", &hash(MD5, payload).to_base64(STANDARD));
+        if input.bypass_governance_retention {
+            request.add_header("x-amz-bypass-governance-retention", "true");
+        }
+        request.set_payload(Some(payload));
+        request
+    }
+
+    /// Points `request` at `bucket`, either with a subdomain of the default endpoint or with
+    /// the first segment of the path on a custom one.
+    fn set_bucket_endpoint(&self, request: &mut SignedRequest, bucket: &BucketName) {
+        let (hostname, path_prefix) = self.bucket_endpoint(bucket);
+        let path = format!("{}{}", path_prefix, request.path());
+
+        request.set_path(&path);
+        request.set_hostname(Some(hostname));
+    }
+
+    /// Returns the hostname for requests to `bucket` and what should precede their paths.
+    fn bucket_endpoint(&self, bucket: &BucketName) -> (String, String) {
+        match self.hostname {
+            Some(ref hostname) if self.config.force_path_style => (hostname.to_owned(), format!("/{}", bucket)),
+            Some(ref hostname) => {
+                let (scheme, host) = split_endpoint(hostname);
+                (format!("{}://{}.{}", scheme, bucket, host), String::new())
+            }
+            None if self.config.force_path_style => (self.hostname(None), format!("/{}", bucket)),
+            None if self.config.use_accelerate => (format!("{}.s3-accelerate.amazonaws.com", bucket), String::new()),
+            None => (self.hostname(Some(bucket)), String::new()),
         }
     }
 
@@ -12391,11 +14594,39 @@ impl<P> S3Helper<P> where P: ProvideAwsCredentials {
         }
     }
 
+    /// Creates a new S3 helper whose client addresses buckets as `config` asks.
+    pub fn with_config(credentials_provider: P, region: Region, config: S3Config) -> Result<S3Helper<P>, AwsError> {
+        Ok(S3Helper {
+            client: try!(S3Client::with_config(credentials_provider, region, config))
+        })
+    }
+
+    /// Send requests to `hostname` instead of the default endpoint for the region, as
+    /// `S3Client::set_hostname` does.
+    pub fn set_hostname(&mut self, hostname: Option<String>) {
+        self.client.set_hostname(hostname);
+    }
+
     /// Lists buckets
     pub fn list_buckets(&mut self) -> Result<ListBucketsOutput, AwsError> {
         self.client.list_buckets()
     }
 
+    /// Lists the objects in a bucket whose keys start with `prefix`, following continuation
+    /// tokens until every page has been fetched.
+    pub fn list_objects_with_prefix(&mut self, bucket_name: &str, prefix: &str) -> Result<ObjectList, AwsError> {
+        let mut request = ListObjectsV2Request::default();
+        request.bucket = bucket_name.to_string();
+        request.prefix = Some(prefix.to_string());
+
+        read_all_pages(None, |continuation_token| {
+            request.continuation_token = continuation_token;
+            let output = try!(self.client.list_objects_v2(&request));
+
+            Ok((output.contents, output.next_continuation_token))
+        })
+    }
+
     /// Creates bucket in default us-east-1/us-standard region.
     pub fn create_bucket(&mut self, bucket_name: &str, canned_acl: Option<CannedAcl>) -> Result<CreateBucketOutput, AwsError> {
         self.create_bucket_in_region(bucket_name, Region::UsEast1, canned_acl)
@@ -12600,112 +14831,512 @@ impl<P> S3Helper<P> where P: ProvideAwsCredentials {
                 }
             }
         }
-        Ok(parts)
+        Ok(parts)
+    }
+
+    fn upload_a_part(&mut self, buffer: &[u8], part_number: &i32,
+            bucket_name: &str, upload_id: &str, object_name: &str) -> Result<String, AwsError> {
+
+        let mut upload_part_request = UploadPartRequest::default();
+        upload_part_request.body = Some(&buffer);
+
+        let hash = hash(MD5, upload_part_request.body.unwrap()).to_base64(STANDARD);
+        upload_part_request.content_md5 = Some(hash);
+
+        upload_part_request.bucket = bucket_name.to_string();
+        upload_part_request.upload_id = upload_id.to_string();
+        upload_part_request.part_number = *part_number;
+        upload_part_request.key = object_name.to_string();
+
+        match self.client.upload_part(&upload_part_request) {
+            Err(why) => {
+                println!("Error uploading part: {:?}", why);
+                Err(AwsError::new("oops in upload_a_part"))
+            },
+            Ok(response) => {
+                Ok(response.to_string())
+            }
+        }
+    }
+
+    /// Lists multipart uploads not yet completed for specified bucket
+    pub fn list_multipart_uploads_for_bucket(&mut self, bucket_name: &str) -> Result<ListMultipartUploadsOutput, AwsError> {
+        let mut request = ListMultipartUploadsRequest::default();
+        request.bucket = bucket_name.to_string();
+
+        match self.client.list_multipart_uploads(&request) {
+            Err(why) => Err(AwsError::new(format!("Couldn't do list_multipart_uploads: {:?}", why))),
+            Ok(result) => Ok(result),
+        }
+    }
+
+    /// Deletes specified object from specified bucket.
+    pub fn delete_object(&mut self, bucket_name: &str, object_name: &str) ->  Result<DeleteObjectOutput, AwsError> {
+        let mut request = DeleteObjectRequest::default();
+        request.key = object_name.to_string();
+        request.bucket = bucket_name.to_string();
+        self.client.delete_object(&request)
+    }
+
+    /// Abort multipart upload.
+    pub fn abort_multipart_upload(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<AbortMultipartUploadOutput, AwsError> {
+        let mut request = AbortMultipartUploadRequest::default();
+        request.key = object_name.to_string();
+        request.bucket = bucket_name.to_string();
+        request.upload_id = upload_id.to_string();
+        self.client.abort_multipart_upload(&request)
+    }
+
+    /// List parts from a multiupload request.
+    pub fn multipart_upload_list_parts(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<ListPartsOutput, AwsError> {
+        let mut request = ListPartsRequest::default();
+        request.key = object_name.to_string();
+        request.bucket = bucket_name.to_string();
+        request.upload_id = upload_id.to_string();
+        self.client.list_parts(&request)
+    }
+}
+
+/// Helper function to determine if a create config is needed.
+pub fn needs_create_bucket_config(region: Region) -> bool {
+    match region {
+        Region::UsEast1 => false,
+        _ => true,
+    }
+}
+
+// This is a bit hacky to get functionality until we figure out an XML writing util.
+/// Manually writes out bucket configuration (location constraint) in XML.
+pub fn create_bucket_config_xml(region: Region) -> Vec<u8> {
+    match region {
+        Region::UsEast1 => {
+            Vec::new() // shouldn't actually execute this: panic! or unreachable! this?
+        }
+        _ => {
+            let xml = format!("<CreateBucketConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">
+        <LocationConstraint>{}</LocationConstraint>
+        </CreateBucketConfiguration >", region);
+            xml.into_bytes()
+        }
+    }
+}
+
+/// Writes out XML with all the parts in it for S3 to complete.
+pub fn multipart_upload_finish_xml(parts: &[String]) -> Result<Vec<u8>, AwsError> {
+    if parts.len() < 1 {
+        return Err(AwsError::new("Can't finish upload on 0 parts."));
+    }
+    let mut response = String::from("<CompleteMultipartUpload>");
+
+    let mut part_number = 1;
+    for etag in parts {
+        response = response + &format!("<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>", part_number, etag);
+        part_number += 1;
+    }
+
+    response = response + "</CompleteMultipartUpload>";
+
+    Ok(response.into_bytes())
+}
+
+/// The most keys a single `DeleteObjects` request may contain.
+pub const DELETE_OBJECTS_MAX_KEYS: usize = 1000;
+
+/// Splits `objects` into quiet `DeleteObjects` requests of at most 1000 keys each.
+pub fn delete_objects_batches(bucket: &str, objects: Vec<ObjectIdentifier>) -> Vec<DeleteObjectsRequest> {
+    let mut batches = Vec::new();
+    let mut objects = objects.into_iter().peekable();
+
+    while objects.peek().is_some() {
+        let mut request = DeleteObjectsRequest::default();
+        request.bucket = bucket.to_string();
+        request.delete.objects = objects.by_ref().take(DELETE_OBJECTS_MAX_KEYS).collect();
+        request.delete.quiet = Some(true);
+        batches.push(request);
+    }
+
+    batches
+}
+
+/// Builds the XML body of a `DeleteObjects` request.
+pub fn delete_objects_xml(delete: &Delete) -> Vec<u8> {
+    let mut body = String::from("<Delete>");
+
+    if let Some(quiet) = delete.quiet {
+        body = body + &format!("<Quiet>{}</Quiet>", quiet);
+    }
+
+    for object in &delete.objects {
+        body = body + &format!("<Object><Key>{}</Key>", escape_xml(&object.key));
+        if let Some(ref version_id) = object.version_id {
+            body = body + &format!("<VersionId>{}</VersionId>", escape_xml(version_id));
+        }
+        body = body + "</Object>";
+    }
+
+    body = body + "</Delete>";
+
+    body.into_bytes()
+}
+
+/// Builds the XML body of a `PutObjectLockConfiguration` request.
+pub fn object_lock_configuration_xml(configuration: &ObjectLockConfiguration) -> Vec<u8> {
+    let mut body = String::from("<ObjectLockConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+
+    if let Some(ref enabled) = configuration.object_lock_enabled {
+        body = body + &format!("<ObjectLockEnabled>{}</ObjectLockEnabled>", escape_xml(enabled));
+    }
+
+    if let Some(ref rule) = configuration.rule {
+        body = body + "<Rule>";
+        if let Some(ref retention) = rule.default_retention {
+            body = body + "<DefaultRetention>";
+            if let Some(ref mode) = retention.mode {
+                body = body + &format!("<Mode>{}</Mode>", escape_xml(mode));
+            }
+            if let Some(days) = retention.days {
+                body = body + &format!("<Days>{}</Days>", days);
+            }
+            if let Some(years) = retention.years {
+                body = body + &format!("<Years>{}</Years>", years);
+            }
+            body = body + "</DefaultRetention>";
+        }
+        body = body + "</Rule>";
+    }
+
+    body = body + "</ObjectLockConfiguration>";
+
+    body.into_bytes()
+}
+
+/// Builds the XML body of a `PutObjectRetention` request.
+pub fn object_lock_retention_xml(retention: &ObjectLockRetention) -> Vec<u8> {
+    let mut body = String::from("<Retention xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+
+    if let Some(ref mode) = retention.mode {
+        body = body + &format!("<Mode>{}</Mode>", escape_xml(mode));
+    }
+    if let Some(ref retain_until_date) = retention.retain_until_date {
+        body = body + &format!("<RetainUntilDate>{}</RetainUntilDate>", escape_xml(retain_until_date));
+    }
+
+    body = body + "</Retention>";
+
+    body.into_bytes()
+}
+
+/// Builds the XML body of a `PutBucketIntelligentTieringConfiguration` request.
+pub fn intelligent_tiering_configuration_xml(configuration: &IntelligentTieringConfiguration) -> Vec<u8> {
+    let mut body = String::from("<IntelligentTieringConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+
+    body = body + &format!("<Id>{}</Id>", escape_xml(&configuration.id));
+
+    if let Some(ref filter) = configuration.filter {
+        body = body + "<Filter>";
+        if let Some(ref prefix) = filter.prefix {
+            body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
+        }
+        if let Some(ref tag) = filter.tag {
+            body = body + &tag_xml(tag);
+        }
+        if let Some(ref and) = filter.and {
+            body = body + "<And>";
+            if let Some(ref prefix) = and.prefix {
+                body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
+            }
+            for tag in &and.tags {
+                body = body + &tag_xml(tag);
+            }
+            body = body + "</And>";
+        }
+        body = body + "</Filter>";
+    }
+
+    body = body + &format!("<Status>{}</Status>", escape_xml(&configuration.status));
+
+    for tiering in &configuration.tierings {
+        body = body + &format!(
+            "<Tiering><Days>{}</Days><AccessTier>{}</AccessTier></Tiering>",
+            tiering.days,
+            escape_xml(&tiering.access_tier)
+        );
+    }
+
+    body = body + "</IntelligentTieringConfiguration>";
+
+    body.into_bytes()
+}
+
+/// Builds the XML body of a `PutBucketReplication` request.
+pub fn replication_configuration_xml(configuration: &ReplicationConfiguration) -> Vec<u8> {
+    let mut body = String::from("<ReplicationConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+
+    body = body + &format!("<Role>{}</Role>", escape_xml(&configuration.role));
+
+    for rule in &configuration.rules {
+        body = body + "<Rule>";
+        if let Some(ref id) = rule.id {
+            body = body + &format!("<ID>{}</ID>", escape_xml(id));
+        }
+        if let Some(priority) = rule.priority {
+            body = body + &format!("<Priority>{}</Priority>", priority);
+        }
+        match rule.filter {
+            Some(ref filter) => {
+                body = body + "<Filter>";
+                if let Some(ref prefix) = filter.prefix {
+                    body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
+                }
+                if let Some(ref tag) = filter.tag {
+                    body = body + &tag_xml(tag);
+                }
+                if let Some(ref and) = filter.and {
+                    body = body + "<And>";
+                    if let Some(ref prefix) = and.prefix {
+                        body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
+                    }
+                    for tag in &and.tags {
+                        body = body + &tag_xml(tag);
+                    }
+                    body = body + "</And>";
+                }
+                body = body + "</Filter>";
+            }
+            None => body = body + &format!("<Prefix>{}</Prefix>", escape_xml(&rule.prefix)),
+        }
+        body = body + &format!("<Status>{}</Status>", escape_xml(&rule.status));
+        if let Some(ref replication) = rule.existing_object_replication {
+            body = body + &format!(
+                "<ExistingObjectReplication><Status>{}</Status></ExistingObjectReplication>",
+                escape_xml(&replication.status)
+            );
+        }
+        body = body + &destination_xml(&rule.destination);
+        if let Some(ref replication) = rule.delete_marker_replication {
+            body = body + &format!(
+                "<DeleteMarkerReplication><Status>{}</Status></DeleteMarkerReplication>",
+                escape_xml(&replication.status)
+            );
+        }
+        body = body + "</Rule>";
+    }
+
+    body = body + "</ReplicationConfiguration>";
+
+    body.into_bytes()
+}
+
+fn destination_xml(destination: &Destination) -> String {
+    let mut body = format!("<Destination><Bucket>{}</Bucket>", escape_xml(&destination.bucket));
+
+    if let Some(ref account) = destination.account {
+        body = body + &format!("<Account>{}</Account>", escape_xml(account));
+    }
+    if let Some(ref storage_class) = destination.storage_class {
+        body = body + &format!("<StorageClass>{}</StorageClass>", escape_xml(storage_class));
+    }
+    if let Some(ref translation) = destination.access_control_translation {
+        body = body + &format!(
+            "<AccessControlTranslation><Owner>{}</Owner></AccessControlTranslation>",
+            escape_xml(&translation.owner)
+        );
+    }
+    if let Some(ref replication_time) = destination.replication_time {
+        body = body + &format!("<ReplicationTime><Status>{}</Status>", escape_xml(&replication_time.status));
+        body = body + &replication_time_value_xml("Time", &replication_time.time);
+        body = body + "</ReplicationTime>";
+    }
+    if let Some(ref metrics) = destination.metrics {
+        body = body + &format!("<Metrics><Status>{}</Status>", escape_xml(&metrics.status));
+        if let Some(ref event_threshold) = metrics.event_threshold {
+            body = body + &replication_time_value_xml("EventThreshold", event_threshold);
+        }
+        body = body + "</Metrics>";
+    }
+
+    body + "</Destination>"
+}
+
+fn replication_time_value_xml(name: &str, value: &ReplicationTimeValue) -> String {
+    match value.minutes {
+        Some(minutes) => format!("<{0}><Minutes>{1}</Minutes></{0}>", name, minutes),
+        None => format!("<{0}></{0}>", name),
+    }
+}
+
+/// Builds the XML body of a `PutBucketAccelerateConfiguration` request.
+pub fn accelerate_configuration_xml(status: AccelerateStatus) -> Vec<u8> {
+    format!(
+        "<AccelerateConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\"><Status>{}</Status></AccelerateConfiguration>",
+        status.as_str()
+    ).into_bytes()
+}
+
+/// Builds the XML body of a `PutBucketInventoryConfiguration` request.
+pub fn inventory_configuration_xml(configuration: &InventoryConfiguration) -> Vec<u8> {
+    let mut body = String::from("<InventoryConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+
+    let destination = &configuration.destination.s3_bucket_destination;
+    body = body + "<Destination><S3BucketDestination>";
+    if let Some(ref account_id) = destination.account_id {
+        body = body + &format!("<AccountId>{}</AccountId>", escape_xml(account_id));
+    }
+    body = body + &format!("<Bucket>{}</Bucket>", escape_xml(&destination.bucket));
+    body = body + &format!("<Format>{}</Format>", escape_xml(&destination.format));
+    if let Some(ref prefix) = destination.prefix {
+        body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
+    }
+    if let Some(ref encryption) = destination.encryption {
+        body = body + "<Encryption>";
+        if encryption.sse_s3 {
+            body = body + "<SSE-S3></SSE-S3>";
+        }
+        if let Some(ref key_id) = encryption.sse_kms_key_id {
+            body = body + &format!("<SSE-KMS><KeyId>{}</KeyId></SSE-KMS>", escape_xml(key_id));
+        }
+        body = body + "</Encryption>";
+    }
+    body = body + "</S3BucketDestination></Destination>";
+
+    body = body + &format!("<IsEnabled>{}</IsEnabled>", configuration.is_enabled);
+
+    if let Some(ref filter) = configuration.filter {
+        body = body + &format!("<Filter><Prefix>{}</Prefix></Filter>", escape_xml(&filter.prefix));
+    }
+
+    body = body + &format!("<Id>{}</Id>", escape_xml(&configuration.id));
+    body = body + &format!(
+        "<IncludedObjectVersions>{}</IncludedObjectVersions>",
+        escape_xml(&configuration.included_object_versions)
+    );
+
+    if !configuration.optional_fields.is_empty() {
+        body = body + "<OptionalFields>";
+        for field in &configuration.optional_fields {
+            body = body + &format!("<Field>{}</Field>", escape_xml(field));
+        }
+        body = body + "</OptionalFields>";
     }
 
-    fn upload_a_part(&mut self, buffer: &[u8], part_number: &i32,
-            bucket_name: &str, upload_id: &str, object_name: &str) -> Result<String, AwsError> {
+    body = body + &format!(
+        "<Schedule><Frequency>{}</Frequency></Schedule>",
+        escape_xml(&configuration.schedule.frequency)
+    );
 
-        let mut upload_part_request = UploadPartRequest::default();
-        upload_part_request.body = Some(&buffer);
+    body = body + "</InventoryConfiguration>";
 
-        let hash = hash(MD5, upload_part_request.body.unwrap()).to_base64(STANDARD);
-        upload_part_request.content_md5 = Some(hash);
+    body.into_bytes()
+}
 
-        upload_part_request.bucket = bucket_name.to_string();
-        upload_part_request.upload_id = upload_id.to_string();
-        upload_part_request.part_number = *part_number;
-        upload_part_request.key = object_name.to_string();
+/// Builds the XML body of a `PutBucketAnalyticsConfiguration` request.
+pub fn analytics_configuration_xml(configuration: &AnalyticsConfiguration) -> Vec<u8> {
+    let mut body = String::from("<AnalyticsConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
 
-        match self.client.upload_part(&upload_part_request) {
-            Err(why) => {
-                println!("Error uploading part: {:?}", why);
-                Err(AwsError::new("oops in upload_a_part"))
-            },
-            Ok(response) => {
-                Ok(response.to_string())
+    body = body + &format!("<Id>{}</Id>", escape_xml(&configuration.id));
+
+    if let Some(ref filter) = configuration.filter {
+        body = body + "<Filter>";
+        if let Some(ref prefix) = filter.prefix {
+            body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
+        }
+        if let Some(ref tag) = filter.tag {
+            body = body + &tag_xml(tag);
+        }
+        if let Some(ref and) = filter.and {
+            body = body + "<And>";
+            if let Some(ref prefix) = and.prefix {
+                body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
             }
+            for tag in &and.tags {
+                body = body + &tag_xml(tag);
+            }
+            body = body + "</And>";
         }
+        body = body + "</Filter>";
     }
 
-    /// Lists multipart uploads not yet completed for specified bucket
-    pub fn list_multipart_uploads_for_bucket(&mut self, bucket_name: &str) -> Result<ListMultipartUploadsOutput, AwsError> {
-        let mut request = ListMultipartUploadsRequest::default();
-        request.bucket = bucket_name.to_string();
+    body = body + "<StorageClassAnalysis>";
+    if let Some(ref data_export) = configuration.storage_class_analysis.data_export {
+        let destination = &data_export.destination.s3_bucket_destination;
 
-        match self.client.list_multipart_uploads(&request) {
-            Err(why) => Err(AwsError::new(format!("Couldn't do list_multipart_uploads: {:?}", why))),
-            Ok(result) => Ok(result),
+        body = body + "<DataExport>";
+        body = body + &format!(
+            "<OutputSchemaVersion>{}</OutputSchemaVersion>",
+            escape_xml(&data_export.output_schema_version)
+        );
+        body = body + "<Destination><S3BucketDestination>";
+        body = body + &format!("<Format>{}</Format>", escape_xml(&destination.format));
+        if let Some(ref bucket_account_id) = destination.bucket_account_id {
+            body = body + &format!("<BucketAccountId>{}</BucketAccountId>", escape_xml(bucket_account_id));
         }
+        body = body + &format!("<Bucket>{}</Bucket>", escape_xml(&destination.bucket));
+        if let Some(ref prefix) = destination.prefix {
+            body = body + &format!("<Prefix>{}</Prefix>", escape_xml(prefix));
+        }
+        body = body + "</S3BucketDestination></Destination></DataExport>";
     }
+    body = body + "</StorageClassAnalysis>";
 
-    /// Deletes specified object from specified bucket.
-    pub fn delete_object(&mut self, bucket_name: &str, object_name: &str) ->  Result<DeleteObjectOutput, AwsError> {
-        let mut request = DeleteObjectRequest::default();
-        request.key = object_name.to_string();
-        request.bucket = bucket_name.to_string();
-        self.client.delete_object(&request)
-    }
-
-    /// Abort multipart upload.
-    pub fn abort_multipart_upload(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<AbortMultipartUploadOutput, AwsError> {
-        let mut request = AbortMultipartUploadRequest::default();
-        request.key = object_name.to_string();
-        request.bucket = bucket_name.to_string();
-        request.upload_id = upload_id.to_string();
-        self.client.abort_multipart_upload(&request)
-    }
+    body = body + "</AnalyticsConfiguration>";
 
-    /// List parts from a multiupload request.
-    pub fn multipart_upload_list_parts(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<ListPartsOutput, AwsError> {
-        let mut request = ListPartsRequest::default();
-        request.key = object_name.to_string();
-        request.bucket = bucket_name.to_string();
-        request.upload_id = upload_id.to_string();
-        self.client.list_parts(&request)
-    }
+    body.into_bytes()
 }
 
-/// Helper function to determine if a create config is needed.
-pub fn needs_create_bucket_config(region: Region) -> bool {
-    match region {
-        Region::UsEast1 => false,
-        _ => true,
-    }
+fn tag_xml(tag: &Tag) -> String {
+    format!("<Tag><Key>{}</Key><Value>{}</Value></Tag>", escape_xml(&tag.key), escape_xml(&tag.value))
 }
 
-// This is a bit hacky to get functionality until we figure out an XML writing util.
-/// Manually writes out bucket configuration (location constraint) in XML.
-pub fn create_bucket_config_xml(region: Region) -> Vec<u8> {
-    match region {
-        Region::UsEast1 => {
-            Vec::new() // shouldn't actually execute this: panic! or unreachable! this?
-        }
-        _ => {
-            let xml = format!("<CreateBucketConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">
-        <LocationConstraint>{}</LocationConstraint>
-        </CreateBucketConfiguration >", region);
-            xml.into_bytes()
-        }
-    }
+fn escape_xml(value: &str) -> String {
+    value.replace("&", "&amp;")
+        .replace("<", "&lt;")
+        .replace(">", "&gt;")
+        .replace("\"", "&quot;")
+        .replace("'", "&apos;")
 }
 
-/// Writes out XML with all the parts in it for S3 to complete.
-pub fn multipart_upload_finish_xml(parts: &[String]) -> Result<Vec<u8>, AwsError> {
-    if parts.len() < 1 {
-        return Err(AwsError::new("Can't finish upload on 0 parts."));
-    }
-    let mut response = String::from("<CompleteMultipartUpload>");
+/// Sends a `DeleteObjects` request, which S3 requires to carry the MD5 of its body.
+fn execute_delete_objects(hostname: &str, path_prefix: &str, region: Region, credentials: &AwsCredentials,
+    interceptors: Vec<Arc<Interceptor + Send + Sync>>, input: &DeleteObjectsRequest)
+    -> Result<DeleteObjectsOutput, AwsError> {
+    let payload = delete_objects_xml(&input.delete);
+    let content_md5 = hash(MD5, &payload).to_base64(STANDARD);
 
-    let mut part_number = 1;
-    for etag in parts {
-        response = response + &format!("<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>", part_number, etag);
-        part_number += 1;
+    let mut request = SignedRequest::new("POST", "s3", region, &format!("{}/", path_prefix));
+    let mut params = Params::new();
+    params.put("delete", "");
+    request.set_params(params);
+    request.set_hostname(Some(hostname.to_string()));
+    request.set_interceptors(interceptors);
+    request.add_header("Content-MD5", &content_md5);
+    if let Some(ref mfa) = input.mfa {
+        request.add_header("x-amz-mfa", mfa);
+    }
+    if let Some(ref request_payer) = input.request_payer {
+        request.add_header("x-amz-request-payer", request_payer.as_str());
     }
+    request.set_payload(Some(&payload));
 
-    response = response + "</CompleteMultipartUpload>";
+    let mut result = request.sign_and_execute(credentials);
+    let status = result.status.to_u16();
 
-    Ok(response.into_bytes())
+    match status {
+        200 => {
+            let mut reader = EventReader::new(result);
+            let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+            stack.next(); // xml start tag
+
+            Ok(try!(DeleteObjectsOutputParser::parse_xml("DeleteResult", &mut stack)))
+        }
+        _ => {
+            // An unreadable body fails the request rather than panicking its batch's thread.
+            let mut body = String::new();
+            if let Err(error) = result.read_to_string(&mut body) {
+                return Err(AwsError::new(format!("error in delete_objects: unreadable response: {}", error)));
+            }
+            Err(AwsError::new(format!("error in delete_objects: {}", body)))
+        }
+    }
 }
 
 /// Maps canned acl to AWS format.  EG public-read.
@@ -12728,13 +15359,28 @@ mod tests {
 
     use xml::reader::*;
 
+    use chrono::UTC;
+
+    use credential::{AwsCredentials, ProfileProvider};
+    use param::{Params, ServiceParams};
     use region::Region;
+    use signature::SignedRequest;
     use super::*;
+    use super::AccelerateConfigurationParser;
+    use super::AnalyticsConfigurationParser;
     use super::CompleteMultipartUploadOutputParser;
     use super::CreateMultipartUploadOutputParser;
+    use super::DeleteObjectsOutputParser;
+    use super::GetObjectRequestWriter;
+    use super::IntelligentTieringConfigurationParser;
+    use super::ListBucketInventoryConfigurationsOutputParser;
     use super::ListBucketsOutputParser;
+    use super::ListObjectsV2OutputParser;
     use super::ListMultipartUploadsOutputParser;
     use super::ListPartsOutputParser;
+    use super::ObjectLockConfigurationParser;
+    use super::ObjectLockRetentionParser;
+    use super::ReplicationConfigurationParser;
     use xmlutil::*;
 
     #[test]
@@ -12937,4 +15583,485 @@ mod tests {
             panic!("us-east-1 should not have bucket constraint.");
         }
     }
+
+    #[test]
+    fn delete_objects_batches_of_1000_keys() {
+        let objects = (0..2500).map(|i| {
+            let mut object = ObjectIdentifier::default();
+            object.key = format!("logs/{}.gz", i);
+            object
+        }).collect::<Vec<ObjectIdentifier>>();
+
+        let batches = delete_objects_batches("rusoto-logs", objects);
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0].delete.objects.len(), 1000);
+        assert_eq!(batches[1].delete.objects.len(), 1000);
+        assert_eq!(batches[2].delete.objects.len(), 500);
+        assert_eq!(batches[2].delete.objects[499].key, "logs/2499.gz");
+        assert!(batches.iter().all(|batch| batch.bucket == "rusoto-logs" && batch.delete.quiet == Some(true)));
+    }
+
+    #[test]
+    fn delete_objects_xml_is_quiet_and_escaped() {
+        let mut first = ObjectIdentifier::default();
+        first.key = "a&b.txt".to_string();
+        let mut second = ObjectIdentifier::default();
+        second.key = "c.txt".to_string();
+        second.version_id = Some("3HL4kqtJlcpXroDTDmJ".to_string());
+
+        let mut delete = Delete::default();
+        delete.objects = vec![first, second];
+        delete.quiet = Some(true);
+
+        assert_eq!(
+            str::from_utf8(&delete_objects_xml(&delete)).unwrap(),
+            "<Delete><Quiet>true</Quiet><Object><Key>a&amp;b.txt</Key></Object>\
+             <Object><Key>c.txt</Key><VersionId>3HL4kqtJlcpXroDTDmJ</VersionId></Object></Delete>"
+        );
+    }
+
+    #[test]
+    fn delete_objects_result_with_errors() {
+        let file = File::open("tests/sample-data/s3_delete_objects.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = DeleteObjectsOutputParser::parse_xml("DeleteResult", &mut reader).unwrap();
+
+        assert_eq!(result.deleted.len(), 1);
+        assert_eq!(result.deleted[0].key, "reports/2016-05-01.csv");
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(result.errors[0].key, "reports/2016-05-02.csv");
+        assert_eq!(result.errors[0].code, "AccessDenied");
+        assert_eq!(result.errors[0].message, "Access Denied");
+        assert_eq!(result.errors[1].code, "InternalError");
+        assert_eq!(result.errors[1].version_id, "UIORUnfndfiufdisojhr398493jfdkjFJjkndnqUifhnw89493jJFJ");
+    }
+
+    #[test]
+    fn list_objects_v2_result() {
+        let file = File::open("tests/sample-data/s3_list_objects_v2.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = ListObjectsV2OutputParser::parse_xml("ListBucketResult", &mut reader).unwrap();
+
+        assert_eq!(result.name, "rusoto-test-bucket");
+        assert_eq!(result.key_count, 2);
+        assert!(result.is_truncated);
+        assert_eq!(result.contents.len(), 2);
+        assert_eq!(result.contents[0].key, "logs/2016-05-01.log");
+        assert_eq!(result.contents[1].size, 12);
+        assert_eq!(result.contents[1].e_tag, "\"591785b794601e212b260e25925636fd\"");
+        assert_eq!(
+            result.next_continuation_token,
+            Some("1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=".to_owned())
+        );
+    }
+
+    #[test]
+    fn object_lock_configuration_result() {
+        let file = File::open("tests/sample-data/s3_get_object_lock_configuration.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = ObjectLockConfigurationParser::parse_xml("ObjectLockConfiguration", &mut reader).unwrap();
+
+        assert_eq!(result.object_lock_enabled, Some("Enabled".to_owned()));
+        let retention = result.rule.unwrap().default_retention.unwrap();
+        assert_eq!(retention.mode, Some("GOVERNANCE".to_owned()));
+        assert_eq!(retention.days, Some(30));
+        assert_eq!(retention.years, None);
+    }
+
+    #[test]
+    fn object_retention_result() {
+        let file = File::open("tests/sample-data/s3_get_object_retention.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = ObjectLockRetentionParser::parse_xml("Retention", &mut reader).unwrap();
+
+        assert_eq!(result.mode, Some("COMPLIANCE".to_owned()));
+        assert_eq!(result.retain_until_date, Some("2017-01-01T00:00:00.000Z".to_owned()));
+    }
+
+    #[test]
+    fn object_lock_configuration_xml_looks_right() {
+        let configuration = ObjectLockConfiguration {
+            object_lock_enabled: Some("Enabled".to_owned()),
+            rule: Some(ObjectLockRule {
+                default_retention: Some(DefaultRetention {
+                    mode: Some("COMPLIANCE".to_owned()),
+                    days: None,
+                    years: Some(7),
+                }),
+            }),
+        };
+
+        assert_eq!(
+            str::from_utf8(&object_lock_configuration_xml(&configuration)).unwrap(),
+            "<ObjectLockConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+             <ObjectLockEnabled>Enabled</ObjectLockEnabled><Rule><DefaultRetention>\
+             <Mode>COMPLIANCE</Mode><Years>7</Years></DefaultRetention></Rule></ObjectLockConfiguration>"
+        );
+    }
+
+    #[test]
+    fn put_object_retention_bypasses_governance_when_asked() {
+        let client = S3Client::new(
+            ProfileProvider::with_configuration("tests/sample-data/multiple_profile_credentials", "foo"),
+            Region::UsEast1,
+        );
+        let mut input = PutObjectRetentionRequest {
+            bucket: "rusoto-locked".to_owned(),
+            key: "reports/2016.csv".to_owned(),
+            retention: ObjectLockRetention {
+                mode: Some("GOVERNANCE".to_owned()),
+                retain_until_date: Some("2017-01-01T00:00:00.000Z".to_owned()),
+            },
+            version_id: Some("3HL4kqtJlcpXroDTDmJ".to_owned()),
+            bypass_governance_retention: true,
+        };
+        let payload = object_lock_retention_xml(&input.retention);
+
+        {
+            let request = client.put_object_retention_request(&input, &payload);
+            assert_eq!(
+                request.headers().get("x-amz-bypass-governance-retention"),
+                Some(&vec![b"true".to_vec()])
+            );
+            assert!(request.headers().contains_key("content-md5"));
+            assert_eq!(request.path(), "/reports/2016.csv");
+        }
+
+        input.bypass_governance_retention = false;
+        let request = client.put_object_retention_request(&input, &payload);
+        assert!(request.headers().get("x-amz-bypass-governance-retention").is_none());
+    }
+
+    #[test]
+    fn intelligent_tiering_configuration_with_both_archive_tiers() {
+        let file = File::open("tests/sample-data/s3_get_bucket_intelligent_tiering_configuration.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = IntelligentTieringConfigurationParser::parse_xml("IntelligentTieringConfiguration", &mut reader).unwrap();
+
+        assert_eq!(result.id, "archive-logs");
+        assert_eq!(result.status, "Enabled");
+        assert_eq!(result.filter.unwrap().prefix, Some("logs/".to_owned()));
+        assert_eq!(result.tierings.len(), 2);
+        assert_eq!(result.tierings[0].access_tier, "ARCHIVE_ACCESS");
+        assert_eq!(result.tierings[0].days, 90);
+        assert_eq!(result.tierings[1].access_tier, "DEEP_ARCHIVE_ACCESS");
+        assert_eq!(result.tierings[1].days, 180);
+    }
+
+    #[test]
+    fn intelligent_tiering_configuration_xml_with_tag_filter() {
+        let mut tag = Tag::default();
+        tag.key = "class".to_owned();
+        tag.value = "cold".to_owned();
+        let configuration = IntelligentTieringConfiguration {
+            id: "cold".to_owned(),
+            filter: Some(IntelligentTieringFilter { tag: Some(tag), ..IntelligentTieringFilter::default() }),
+            status: "Enabled".to_owned(),
+            tierings: vec![Tiering { days: 180, access_tier: "DEEP_ARCHIVE_ACCESS".to_owned() }],
+        };
+
+        assert_eq!(
+            str::from_utf8(&intelligent_tiering_configuration_xml(&configuration)).unwrap(),
+            "<IntelligentTieringConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+             <Id>cold</Id><Filter><Tag><Key>class</Key><Value>cold</Value></Tag></Filter>\
+             <Status>Enabled</Status><Tiering><Days>180</Days><AccessTier>DEEP_ARCHIVE_ACCESS</AccessTier>\
+             </Tiering></IntelligentTieringConfiguration>"
+        );
+    }
+
+    #[test]
+    fn cross_account_replication_configuration() {
+        let file = File::open("tests/sample-data/s3_get_bucket_replication.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = ReplicationConfigurationParser::parse_xml("ReplicationConfiguration", &mut reader).unwrap();
+
+        assert_eq!(result.role, "arn:aws:iam::123456789012:role/s3-replication");
+        assert_eq!(result.rules.len(), 1);
+
+        let rule = &result.rules[0];
+        assert_eq!(rule.id, Some("backup-to-dr".to_owned()));
+        assert_eq!(rule.priority, Some(1));
+        assert_eq!(rule.status, "Enabled");
+        let and = rule.filter.as_ref().unwrap().and.as_ref().unwrap();
+        assert_eq!(and.prefix, Some("invoices/".to_owned()));
+        assert_eq!(and.tags[0].key, "replicate");
+        assert_eq!(rule.delete_marker_replication.as_ref().unwrap().status, "Disabled");
+        assert_eq!(rule.existing_object_replication.as_ref().unwrap().status, "Enabled");
+
+        let destination = &rule.destination;
+        assert_eq!(destination.bucket, "arn:aws:s3:::rusoto-dr");
+        assert_eq!(destination.account, Some("210987654321".to_owned()));
+        assert_eq!(destination.storage_class, Some("STANDARD_IA".to_owned()));
+        assert_eq!(destination.access_control_translation.as_ref().unwrap().owner, "Destination");
+        assert_eq!(destination.replication_time.as_ref().unwrap().time.minutes, Some(15));
+        assert_eq!(destination.metrics.as_ref().unwrap().event_threshold.as_ref().unwrap().minutes, Some(15));
+    }
+
+    #[test]
+    fn replication_configuration_xml_with_prefix_rule() {
+        let mut rule = ReplicationRule::default();
+        rule.prefix = "logs/".to_owned();
+        rule.status = "Enabled".to_owned();
+        rule.destination.bucket = "arn:aws:s3:::rusoto-dr".to_owned();
+        let configuration = ReplicationConfiguration {
+            rules: vec![rule],
+            role: "arn:aws:iam::123456789012:role/s3-replication".to_owned(),
+        };
+
+        assert_eq!(
+            str::from_utf8(&replication_configuration_xml(&configuration)).unwrap(),
+            "<ReplicationConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+             <Role>arn:aws:iam::123456789012:role/s3-replication</Role><Rule><Prefix>logs/</Prefix>\
+             <Status>Enabled</Status><Destination><Bucket>arn:aws:s3:::rusoto-dr</Bucket></Destination>\
+             </Rule></ReplicationConfiguration>"
+        );
+    }
+
+    #[test]
+    fn request_payer_is_only_sent_as_a_header() {
+        let input = GetObjectRequest {
+            bucket: "rusoto-public-dataset".to_owned(),
+            key: "genomes/chr1.fa".to_owned(),
+            request_payer: Some(RequestPayer::Requester),
+            ..GetObjectRequest::default()
+        };
+
+        let mut params = Params::new();
+        params.put("Action", "GetObject");
+        GetObjectRequestWriter::write_params(&mut params, "", &input);
+
+        assert!(!params.contains_key("x-amz-request-payer"));
+        assert_eq!(RequestPayer::Requester.as_str(), "requester");
+        assert_eq!(RequestCharged::from_header("requester"), Some(RequestCharged::Requester));
+        assert_eq!(RequestCharged::from_header(""), None);
+    }
+
+    #[test]
+    fn parse_accelerate_configuration() {
+        let file = File::open("tests/sample-data/s3_get_bucket_accelerate_configuration.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = AccelerateConfigurationParser::parse_xml("AccelerateConfiguration", &mut reader);
+
+        match result {
+            Err(_) => panic!("Couldn't parse s3_get_bucket_accelerate_configuration.xml"),
+            Ok(configuration) => assert_eq!(configuration.status, Some(AccelerateStatus::Enabled)),
+        }
+    }
+
+    #[test]
+    fn accelerate_configuration_body() {
+        assert_eq!(
+            str::from_utf8(&accelerate_configuration_xml(AccelerateStatus::Suspended)).unwrap(),
+            "<AccelerateConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+             <Status>Suspended</Status></AccelerateConfiguration>"
+        );
+    }
+
+    #[test]
+    fn accelerated_requests_use_accelerate_endpoint() {
+        let config = S3Config { use_accelerate: true, ..S3Config::default() };
+        let client = S3Client::with_config(
+            ProfileProvider::with_configuration("tests/sample-data/multiple_profile_credentials", "foo"),
+            Region::UsWest2,
+            config,
+        ).unwrap();
+        let credentials = AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", None, UTC::now());
+        let mut request = SignedRequest::new("PUT", "s3", Region::UsWest2, "/reports/2016.csv");
+        client.set_bucket_endpoint(&mut request, &"rusoto-fast".to_owned());
+        request.sign(&credentials);
+
+        assert_eq!(
+            request.headers().get("host"),
+            Some(&vec![b"rusoto-fast.s3-accelerate.amazonaws.com".to_vec()])
+        );
+        assert_eq!(request.path(), "/reports/2016.csv");
+    }
+
+    #[test]
+    fn custom_endpoints_address_buckets_by_subdomain_unless_path_style_is_forced() {
+        let provider = || ProfileProvider::with_configuration("tests/sample-data/multiple_profile_credentials", "foo");
+        let bucket = "rusoto-test".to_owned();
+
+        let mut client = S3Client::new(provider(), Region::UsWest2);
+        client.set_hostname(Some("http://minio.example.com:9000".to_owned()));
+        let mut request = SignedRequest::new("GET", "s3", Region::UsWest2, "/report.csv");
+        client.set_bucket_endpoint(&mut request, &bucket);
+
+        assert_eq!(request.scheme(), "http");
+        assert_eq!(request.hostname(), "rusoto-test.minio.example.com:9000");
+        assert_eq!(request.path(), "/report.csv");
+
+        let config = S3Config { force_path_style: true, ..S3Config::default() };
+        let mut client = S3Client::with_config(provider(), Region::UsWest2, config).unwrap();
+        client.set_hostname(Some("http://[::1]:9000".to_owned()));
+        let mut request = SignedRequest::new("GET", "s3", Region::UsWest2, "/report.csv");
+        client.set_bucket_endpoint(&mut request, &bucket);
+
+        assert_eq!(request.scheme(), "http");
+        assert_eq!(request.hostname(), "[::1]:9000");
+        assert_eq!(request.path(), "/rusoto-test/report.csv");
+    }
+
+    #[test]
+    fn accelerate_cannot_be_used_with_path_style() {
+        let config = S3Config { use_accelerate: true, force_path_style: true, ..S3Config::default() };
+        let result = S3Client::with_config(
+            ProfileProvider::with_configuration("tests/sample-data/multiple_profile_credentials", "foo"),
+            Region::UsWest2,
+            config,
+        );
+
+        match result {
+            Err(e) => assert!(format!("{}", e).starts_with("ClientConfigError: ")),
+            Ok(_) => panic!("Transfer Acceleration was allowed with path-style addressing"),
+        }
+    }
+
+    #[test]
+    fn list_inventory_configurations_on_different_schedules() {
+        let file = File::open("tests/sample-data/s3_list_bucket_inventory_configurations.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = ListBucketInventoryConfigurationsOutputParser::parse_xml("ListInventoryConfigurationsResult", &mut reader).unwrap();
+
+        assert_eq!(result.is_truncated, false);
+        assert_eq!(result.inventory_configurations.len(), 2);
+
+        let daily = &result.inventory_configurations[0];
+        assert_eq!(daily.id, "daily-current");
+        assert!(daily.is_enabled);
+        assert_eq!(daily.schedule.frequency, "Daily");
+        assert_eq!(daily.included_object_versions, "Current");
+        assert_eq!(daily.optional_fields, vec!["Size".to_owned(), "StorageClass".to_owned()]);
+        assert_eq!(daily.filter.as_ref().unwrap().prefix, "reports/");
+
+        let destination = &daily.destination.s3_bucket_destination;
+        assert_eq!(destination.bucket, "arn:aws:s3:::rusoto-inventory");
+        assert_eq!(destination.format, "CSV");
+        assert_eq!(destination.account_id, Some("123456789012".to_owned()));
+        assert!(destination.encryption.as_ref().unwrap().sse_s3);
+
+        let weekly = &result.inventory_configurations[1];
+        assert_eq!(weekly.id, "weekly-all");
+        assert!(!weekly.is_enabled);
+        assert_eq!(weekly.schedule.frequency, "Weekly");
+        assert_eq!(weekly.included_object_versions, "All");
+        assert!(weekly.filter.is_none());
+        assert!(weekly.optional_fields.is_empty());
+
+        let encryption = weekly.destination.s3_bucket_destination.encryption.as_ref().unwrap();
+        assert!(!encryption.sse_s3);
+        assert_eq!(encryption.sse_kms_key_id, Some("arn:aws:kms:us-east-1:123456789012:key/inventory".to_owned()));
+    }
+
+    #[test]
+    fn inventory_configuration_xml_with_optional_fields() {
+        let configuration = InventoryConfiguration {
+            destination: InventoryDestination {
+                s3_bucket_destination: InventoryS3BucketDestination {
+                    bucket: "arn:aws:s3:::rusoto-inventory".to_owned(),
+                    format: "Parquet".to_owned(),
+                    ..InventoryS3BucketDestination::default()
+                },
+            },
+            is_enabled: true,
+            id: "daily".to_owned(),
+            included_object_versions: "Current".to_owned(),
+            optional_fields: vec!["ETag".to_owned()],
+            schedule: InventorySchedule { frequency: "Daily".to_owned() },
+            ..InventoryConfiguration::default()
+        };
+
+        assert_eq!(
+            str::from_utf8(&inventory_configuration_xml(&configuration)).unwrap(),
+            "<InventoryConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+             <Destination><S3BucketDestination><Bucket>arn:aws:s3:::rusoto-inventory</Bucket>\
+             <Format>Parquet</Format></S3BucketDestination></Destination><IsEnabled>true</IsEnabled>\
+             <Id>daily</Id><IncludedObjectVersions>Current</IncludedObjectVersions>\
+             <OptionalFields><Field>ETag</Field></OptionalFields>\
+             <Schedule><Frequency>Daily</Frequency></Schedule></InventoryConfiguration>"
+        );
+    }
+
+    #[test]
+    fn analytics_configuration_with_tag_filter_and_export() {
+        let file = File::open("tests/sample-data/s3_get_bucket_analytics_configuration.xml").unwrap();
+        let file = BufReader::new(file);
+        let mut my_parser  = EventReader::new(file);
+        let my_stack = my_parser.events().peekable();
+        let mut reader = XmlResponseFromFile::new(my_stack);
+        reader.next(); // xml start node
+        let result = AnalyticsConfigurationParser::parse_xml("AnalyticsConfiguration", &mut reader).unwrap();
+
+        assert_eq!(result.id, "infrequent-media");
+
+        let filter = result.filter.unwrap();
+        assert!(filter.prefix.is_none());
+        let tag = filter.tag.unwrap();
+        assert_eq!(tag.key, "content");
+        assert_eq!(tag.value, "media");
+
+        let data_export = result.storage_class_analysis.data_export.unwrap();
+        assert_eq!(data_export.output_schema_version, "V_1");
+
+        let destination = data_export.destination.s3_bucket_destination;
+        assert_eq!(destination.format, "CSV");
+        assert_eq!(destination.bucket_account_id, Some("123456789012".to_owned()));
+        assert_eq!(destination.bucket, "arn:aws:s3:::rusoto-analytics");
+        assert_eq!(destination.prefix, Some("media/".to_owned()));
+    }
+
+    #[test]
+    fn analytics_configuration_xml_with_prefix_and_tags() {
+        let mut tag = Tag::default();
+        tag.key = "team".to_owned();
+        tag.value = "video".to_owned();
+        let configuration = AnalyticsConfiguration {
+            id: "video".to_owned(),
+            filter: Some(AnalyticsFilter {
+                and: Some(AnalyticsAndOperator { prefix: Some("uploads/".to_owned()), tags: vec![tag] }),
+                ..AnalyticsFilter::default()
+            }),
+            storage_class_analysis: StorageClassAnalysis::default(),
+        };
+
+        assert_eq!(
+            str::from_utf8(&analytics_configuration_xml(&configuration)).unwrap(),
+            "<AnalyticsConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+             <Id>video</Id><Filter><And><Prefix>uploads/</Prefix><Tag><Key>team</Key><Value>video</Value></Tag>\
+             </And></Filter><StorageClassAnalysis></StorageClassAnalysis></AnalyticsConfiguration>"
+        );
+    }
 }
@@ -0,0 +1,43 @@
+//! Amazon SageMaker
+//!
+//! Manages training jobs, models and endpoints through `api.sagemaker.<region>.amazonaws.com`.
+//! Endpoints are invoked with the separate `sagemaker_runtime` client.
+
+include!(concat!(env!("OUT_DIR"), "/sagemaker.rs"));
+
+impl CreateEndpointInput {
+    pub fn new(endpoint_name: &str, endpoint_config_name: &str) -> CreateEndpointInput {
+        CreateEndpointInput {
+            endpoint_config_name: endpoint_config_name.to_owned(),
+            endpoint_name: endpoint_name.to_owned(),
+            ..CreateEndpointInput::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use region::Region;
+    use signature::SignedRequest;
+
+    use super::CreateEndpointInput;
+
+    #[test]
+    fn management_endpoint() {
+        // Generated requests are signed as "sagemaker" with the "api.sagemaker" endpoint prefix.
+        let mut request = SignedRequest::new("POST", "sagemaker", Region::UsWest2, "/");
+        request.set_endpoint_prefix(Some("api.sagemaker".to_owned()));
+
+        assert_eq!(request.hostname(), "api.sagemaker.us-west-2.amazonaws.com");
+    }
+
+    #[test]
+    fn create_endpoint_input() {
+        let json = serde_json::to_string(&CreateEndpointInput::new("churn", "churn-config-v2")).unwrap();
+
+        assert!(json.contains("\"EndpointConfigName\":\"churn-config-v2\""));
+        assert!(json.contains("\"EndpointName\":\"churn\""));
+    }
+}
@@ -0,0 +1,61 @@
+//! Amazon SageMaker Runtime
+//!
+//! Invokes hosted models through `runtime.sagemaker.<region>.amazonaws.com`, which is separate
+//! from the endpoint the `sagemaker` client manages them through.
+
+include!(concat!(env!("OUT_DIR"), "/sagemaker-runtime.rs"));
+
+impl InvokeEndpointInput {
+    /// Creates a request to send `body` to the endpoint `endpoint_name`. The endpoint's model
+    /// decides which content types it accepts.
+    pub fn new(endpoint_name: &str, body: Vec<u8>, content_type: Option<String>) -> InvokeEndpointInput {
+        InvokeEndpointInput {
+            body: body,
+            content_type: content_type,
+            endpoint_name: endpoint_name.to_owned(),
+            ..InvokeEndpointInput::default()
+        }
+    }
+}
+
+impl<P> SageMakerRuntimeClient<P> where P: ProvideAwsCredentials {
+    /// Invokes the endpoint `endpoint_name` and returns the body of the model's response.
+    pub fn invoke(&mut self, endpoint_name: &str, body: Vec<u8>, content_type: Option<String>)
+        -> AwsResult<Vec<u8>> {
+        let input = InvokeEndpointInput::new(endpoint_name, body, content_type);
+
+        Ok(try!(self.invoke_endpoint(&input)).body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use region::Region;
+    use signature::SignedRequest;
+
+    use super::InvokeEndpointInput;
+
+    fn hostname(endpoint_prefix: &str) -> String {
+        let mut request = SignedRequest::new("POST", "sagemaker", Region::UsWest2, "/");
+        request.set_endpoint_prefix(Some(endpoint_prefix.to_owned()));
+
+        request.hostname()
+    }
+
+    #[test]
+    fn runtime_endpoint_differs_from_management_endpoint() {
+        // Both clients sign requests as "sagemaker", but with different endpoint prefixes.
+        assert_eq!(hostname("runtime.sagemaker"), "runtime.sagemaker.us-west-2.amazonaws.com");
+        assert_eq!(hostname("api.sagemaker"), "api.sagemaker.us-west-2.amazonaws.com");
+    }
+
+    #[test]
+    fn invoke_endpoint_input() {
+        let input = InvokeEndpointInput::new("churn", b"42,0,1,0.5".to_vec(), Some("text/csv".to_owned()));
+
+        assert_eq!(input.endpoint_name, "churn");
+        assert_eq!(input.body, b"42,0,1,0.5".to_vec());
+        assert_eq!(input.content_type, Some("text/csv".to_owned()));
+        assert_eq!(input.accept, None);
+    }
+}
@@ -0,0 +1,137 @@
+//! AWS Secrets Manager
+
+include!(concat!(env!("OUT_DIR"), "/secretsmanager.rs"));
+
+/// The staging label of the version a secret's value is read from by default.
+pub const AWS_CURRENT: &'static str = "AWSCURRENT";
+
+/// The staging label of the version a rotation is in the middle of creating.
+pub const AWS_PENDING: &'static str = "AWSPENDING";
+
+/// The staging label Secrets Manager moves to the last version that was `AWSCURRENT`.
+pub const AWS_PREVIOUS: &'static str = "AWSPREVIOUS";
+
+impl RotateSecretRequest {
+    /// Creates a request to rotate a secret, configuring its rotation function and schedule if
+    /// given. Unless `rotate_immediately` is set, the secret is first rotated in its next
+    /// scheduled window.
+    pub fn new(secret_id: &str, rotation_lambda_arn: Option<String>,
+        rotation_rules: Option<RotationRulesType>, rotate_immediately: bool) -> RotateSecretRequest {
+        RotateSecretRequest {
+            rotate_immediately: Some(rotate_immediately),
+            rotation_lambda_arn: rotation_lambda_arn,
+            rotation_rules: rotation_rules,
+            secret_id: secret_id.to_owned(),
+            ..RotateSecretRequest::default()
+        }
+    }
+}
+
+impl PutSecretValueRequest {
+    /// Creates a request to store a new version of a secret with the given staging labels.
+    /// Without any, the new version becomes `AWSCURRENT`.
+    pub fn new(secret_id: &str, secret_string: &str, version_stages: Vec<String>)
+        -> PutSecretValueRequest {
+        PutSecretValueRequest {
+            secret_id: secret_id.to_owned(),
+            secret_string: Some(secret_string.to_owned()),
+            version_stages: if version_stages.is_empty() { None } else { Some(version_stages) },
+            ..PutSecretValueRequest::default()
+        }
+    }
+}
+
+impl UpdateSecretVersionStageRequest {
+    /// Creates a request to move `version_stage` from one version of a secret to another, or
+    /// to add it to or remove it from a single version when only one is given.
+    pub fn new(secret_id: &str, version_stage: &str, remove_from_version_id: Option<String>,
+        move_to_version_id: Option<String>) -> UpdateSecretVersionStageRequest {
+        UpdateSecretVersionStageRequest {
+            move_to_version_id: move_to_version_id,
+            remove_from_version_id: remove_from_version_id,
+            secret_id: secret_id.to_owned(),
+            version_stage: version_stage.to_owned(),
+        }
+    }
+}
+
+impl<P> SecretsManagerClient<P> where P: ProvideAwsCredentials {
+    /// Stores a new value as the `AWSPENDING` version of a secret, as the first step of a
+    /// rotation.
+    pub fn put_pending_secret_value(&mut self, secret_id: &str, secret_string: &str)
+        -> AwsResult<PutSecretValueResponse> {
+        let stages = vec![AWS_PENDING.to_owned()];
+
+        self.put_secret_value(&PutSecretValueRequest::new(secret_id, secret_string, stages))
+    }
+
+    /// Finishes a rotation by making the pending version `AWSCURRENT`, which makes the current
+    /// version `AWSPREVIOUS`, then removing `AWSPENDING` from the new version.
+    pub fn finish_rotation(&mut self, secret_id: &str, current_version_id: &str,
+        pending_version_id: &str) -> AwsResult<()> {
+        try!(self.update_secret_version_stage(&UpdateSecretVersionStageRequest::new(
+            secret_id,
+            AWS_CURRENT,
+            Some(current_version_id.to_owned()),
+            Some(pending_version_id.to_owned()),
+        )));
+        try!(self.update_secret_version_stage(&UpdateSecretVersionStageRequest::new(
+            secret_id,
+            AWS_PENDING,
+            Some(pending_version_id.to_owned()),
+            None,
+        )));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{AWS_CURRENT, AWS_PENDING, PutSecretValueRequest, RotateSecretRequest};
+    use super::{RotationRulesType, UpdateSecretVersionStageRequest};
+
+    const SECRET_ID: &'static str = "prod/web/database";
+
+    #[test]
+    fn moving_a_stage_is_a_single_request() {
+        let request = UpdateSecretVersionStageRequest::new(
+            SECRET_ID,
+            AWS_CURRENT,
+            Some("EXAMPLE1-90ab-cdef-fedc-ba987EXAMPLE".to_owned()),
+            Some("EXAMPLE2-90ab-cdef-fedc-ba987EXAMPLE".to_owned()),
+        );
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            "{\"MoveToVersionId\":\"EXAMPLE2-90ab-cdef-fedc-ba987EXAMPLE\",\
+             \"RemoveFromVersionId\":\"EXAMPLE1-90ab-cdef-fedc-ba987EXAMPLE\",\
+             \"SecretId\":\"prod/web/database\",\"VersionStage\":\"AWSCURRENT\"}"
+        );
+    }
+
+    #[test]
+    fn pending_secret_value() {
+        let stages = vec![AWS_PENDING.to_owned()];
+        let request = PutSecretValueRequest::new(SECRET_ID, "{\"password\":\"hunter2\"}", stages);
+
+        assert_eq!(request.version_stages, Some(vec!["AWSPENDING".to_owned()]));
+        assert_eq!(PutSecretValueRequest::new(SECRET_ID, "", Vec::new()).version_stages, None);
+    }
+
+    #[test]
+    fn rotate_secret_request() {
+        let request = RotateSecretRequest::new(
+            SECRET_ID,
+            Some("arn:aws:lambda:us-east-1:123456789012:function:rotate-postgres".to_owned()),
+            Some(RotationRulesType { automatically_after_days: Some(30), ..RotationRulesType::default() }),
+            false,
+        );
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"RotateImmediately\":false"));
+        assert!(json.contains("\"RotationRules\":{\"AutomaticallyAfterDays\":30"));
+    }
+}
@@ -0,0 +1,150 @@
+//! AWS Security Hub
+//!
+//! Collects findings from AWS services, partner products and an account's own tools in the
+//! AWS Security Finding Format (ASFF), and checks resources against security standards.
+
+use std::collections::HashMap;
+
+include!(concat!(env!("OUT_DIR"), "/securityhub.rs"));
+
+use pagination::read_all_pages;
+
+/// The version of the AWS Security Finding Format findings are written in.
+pub const SCHEMA_VERSION: &'static str = "2018-10-08";
+
+/// The compliance status of a resource that passed a check.
+pub const PASSED: &'static str = "PASSED";
+
+/// The compliance status of a resource that failed a check.
+pub const FAILED: &'static str = "FAILED";
+
+/// The severity label of findings that need to be fixed first.
+pub const CRITICAL: &'static str = "CRITICAL";
+
+/// The severity label of findings that should be fixed soon.
+pub const HIGH: &'static str = "HIGH";
+
+impl AwsSecurityFinding {
+    /// A finding with the minimum the AWS Security Finding Format requires, from the product
+    /// `product_arn`, such as `arn:aws:securityhub:us-east-1:123456789012:product/123456789012/default`
+    /// for an account's own findings. `created_at` and `updated_at` are ISO 8601 timestamps.
+    pub fn new(id: &str, product_arn: &str, generator_id: &str, aws_account_id: &str, created_at: &str,
+        updated_at: &str, title: &str, description: &str, severity_label: &str, resources: Vec<Resource>)
+        -> AwsSecurityFinding {
+        AwsSecurityFinding {
+            aws_account_id: aws_account_id.to_owned(),
+            created_at: created_at.to_owned(),
+            description: description.to_owned(),
+            generator_id: generator_id.to_owned(),
+            id: id.to_owned(),
+            product_arn: product_arn.to_owned(),
+            resources: resources,
+            schema_version: SCHEMA_VERSION.to_owned(),
+            severity: Some(Severity {
+                label: Some(severity_label.to_owned()),
+                ..Severity::default()
+            }),
+            title: title.to_owned(),
+            updated_at: updated_at.to_owned(),
+            ..AwsSecurityFinding::default()
+        }
+    }
+}
+
+impl<P> SecurityHubClient<P> where P: ProvideAwsCredentials {
+    /// Enables Security Hub for the account, along with the default security standards if
+    /// `enable_default_standards` is set.
+    pub fn enable(&mut self, tags: HashMap<String, String>, enable_default_standards: bool) -> AwsResult<()> {
+        let input = EnableSecurityHubRequest {
+            enable_default_standards: Some(enable_default_standards),
+            tags: if tags.is_empty() { None } else { Some(tags) },
+            ..EnableSecurityHubRequest::default()
+        };
+
+        try!(self.enable_security_hub(&input));
+
+        Ok(())
+    }
+
+    /// Creates or updates up to 100 findings, returning which of them failed to import.
+    pub fn import_findings(&mut self, findings: Vec<AwsSecurityFinding>) -> AwsResult<BatchImportFindingsResponse> {
+        self.batch_import_findings(&BatchImportFindingsRequest { findings: findings })
+    }
+
+    /// Calls `GetFindings` until every page has been read, with up to `max_results` findings on
+    /// each, and returns the findings that match `filters`, in the order of `sort_criteria`.
+    pub fn get_all_findings(&mut self, filters: Option<AwsSecurityFindingFilters>,
+        sort_criteria: Vec<SortCriterion>, max_results: Option<i32>) -> AwsResult<Vec<AwsSecurityFinding>> {
+        let mut input = GetFindingsRequest {
+            filters: filters,
+            max_results: max_results,
+            sort_criteria: if sort_criteria.is_empty() { None } else { Some(sort_criteria) },
+            ..GetFindingsRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.get_findings(&input));
+
+            Ok((output.findings, output.next_token))
+        })
+    }
+
+    /// Creates an insight grouping the findings that match `filters` by
+    /// `group_by_attribute`, such as `ResourceId`, and returns its ARN.
+    pub fn add_insight(&mut self, name: &str, filters: AwsSecurityFindingFilters, group_by_attribute: &str)
+        -> AwsResult<String> {
+        let input = CreateInsightRequest {
+            filters: filters,
+            group_by_attribute: group_by_attribute.to_owned(),
+            name: name.to_owned(),
+        };
+
+        Ok(try!(self.create_insight(&input)).insight_arn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{AwsSecurityFinding, Compliance, FAILED, HIGH, Resource};
+
+    #[test]
+    fn failed_compliance_check_finding() {
+        let mut finding = AwsSecurityFinding::new(
+            "s3-bucket-versioning/build-artifacts",
+            "arn:aws:securityhub:us-east-1:123456789012:product/123456789012/default",
+            "s3-bucket-versioning",
+            "123456789012",
+            "2023-06-12T09:41:27.012Z",
+            "2023-06-12T09:41:27.012Z",
+            "S3 bucket versioning should be enabled",
+            "The bucket build-artifacts doesn't have versioning enabled.",
+            HIGH,
+            vec![Resource {
+                id: "arn:aws:s3:::build-artifacts".to_owned(),
+                aws_type: "AwsS3Bucket".to_owned(),
+                region: Some("us-east-1".to_owned()),
+                ..Resource::default()
+            }],
+        );
+        finding.compliance = Some(Compliance {
+            status: Some(FAILED.to_owned()),
+            related_requirements: Some(vec!["NIST.800-53.r5 CP-10".to_owned()]),
+            ..Compliance::default()
+        });
+
+        let json = serde_json::to_string(&finding).unwrap();
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+        assert_eq!(value.lookup("SchemaVersion").unwrap().as_string(), Some("2018-10-08"));
+        assert_eq!(value.lookup("GeneratorId").unwrap().as_string(), Some("s3-bucket-versioning"));
+        assert_eq!(value.lookup("Title").unwrap().as_string(), Some("S3 bucket versioning should be enabled"));
+        assert_eq!(value.lookup("Severity.Label").unwrap().as_string(), Some("HIGH"));
+        assert_eq!(value.lookup("Compliance.Status").unwrap().as_string(), Some("FAILED"));
+        assert_eq!(value.lookup("Compliance.RelatedRequirements").unwrap().as_array().unwrap().len(), 1);
+        assert_eq!(value.lookup("Resources").unwrap().as_array().unwrap()[0].lookup("Type").unwrap().as_string(),
+            Some("AwsS3Bucket"));
+    }
+}
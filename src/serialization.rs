@@ -54,7 +54,7 @@ impl SerdeBlob for Option<Vec<u8>> {
 
     fn serialize_blob<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: Serializer {
         match *self {
-            Some(ref vec) => serializer.serialize_some(vec),
+            Some(ref vec) => serializer.serialize_some(vec.as_slice().to_base64(STANDARD)),
             None => serializer.serialize_none(),
         }
     }
@@ -0,0 +1,133 @@
+//! Service Quotas
+
+include!(concat!(env!("OUT_DIR"), "/service-quotas.rs"));
+
+use error::expect;
+use pagination::read_all_pages;
+
+/// The state of a quota increase request, parsed from `RequestedServiceQuotaChange::status`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuotaChangeState {
+    Pending,
+    CaseOpened,
+    Approved,
+    Denied,
+    CaseClosed,
+    Unknown,
+}
+
+impl QuotaChangeState {
+    pub fn from_status(status: &str) -> QuotaChangeState {
+        match status {
+            "PENDING" => QuotaChangeState::Pending,
+            "CASE_OPENED" => QuotaChangeState::CaseOpened,
+            "APPROVED" => QuotaChangeState::Approved,
+            "DENIED" => QuotaChangeState::Denied,
+            "CASE_CLOSED" => QuotaChangeState::CaseClosed,
+            _ => QuotaChangeState::Unknown,
+        }
+    }
+
+    /// Whether the request has been approved, denied or closed.
+    pub fn is_finished(&self) -> bool {
+        match *self {
+            QuotaChangeState::Pending | QuotaChangeState::CaseOpened => false,
+            _ => true,
+        }
+    }
+}
+
+impl RequestedServiceQuotaChange {
+    /// The state of the request, which is `Unknown` if the response didn't include one.
+    pub fn change_state(&self) -> QuotaChangeState {
+        self.status.as_ref().map_or(QuotaChangeState::Unknown, |status| QuotaChangeState::from_status(status))
+    }
+}
+
+impl<P> ServiceQuotasClient<P> where P: ProvideAwsCredentials {
+    /// Calls `ListServiceQuotas` until every page has been read, and returns the applied
+    /// quotas of the service `service_code`, such as `ec2`.
+    pub fn list_all_service_quotas(&mut self, service_code: &str) -> AwsResult<Vec<ServiceQuota>> {
+        let mut input = ListServiceQuotasRequest {
+            service_code: service_code.to_owned(),
+            ..ListServiceQuotasRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_service_quotas(&input));
+
+            Ok((output.quotas.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Asks for the quota `quota_code` of `service_code` to be raised to `desired_value`,
+    /// returning the request, whose ID can be passed to `requested_quota_change`.
+    pub fn request_quota_increase(&mut self, service_code: &str, quota_code: &str, desired_value: f64)
+        -> AwsResult<RequestedServiceQuotaChange> {
+        let input = RequestServiceQuotaIncreaseRequest {
+            desired_value: desired_value,
+            quota_code: quota_code.to_owned(),
+            service_code: service_code.to_owned(),
+            ..RequestServiceQuotaIncreaseRequest::default()
+        };
+
+        expect(try!(self.request_service_quota_increase(&input)).requested_quota, "the requested quota")
+    }
+
+    /// Describes a quota increase request.
+    pub fn requested_quota_change(&mut self, request_id: &str) -> AwsResult<RequestedServiceQuotaChange> {
+        let input = GetRequestedServiceQuotaChangeRequest { request_id: request_id.to_owned() };
+
+        expect(try!(self.get_requested_service_quota_change(&input)).requested_quota, "the requested quota")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{ListServiceQuotasResponse, QuotaChangeState, RequestedServiceQuotaChange};
+
+    #[test]
+    fn parse_quota_with_usage_metric() {
+        let mut body = String::new();
+        File::open("tests/sample-data/service_quotas_list_service_quotas.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let quotas = serde_json::from_str::<ListServiceQuotasResponse>(&body).unwrap().quotas.unwrap();
+
+        assert_eq!(quotas.len(), 1);
+
+        let quota = &quotas[0];
+        assert_eq!(quota.quota_name, Some("Running On-Demand Standard (A, C, D, H, I, M, R, T, Z) instances".to_owned()));
+        assert_eq!(quota.quota_code, Some("L-1216C47A".to_owned()));
+        assert_eq!(quota.value, Some(1152.0));
+        assert_eq!(quota.unit, Some("None".to_owned()));
+        assert_eq!(quota.adjustable, Some(true));
+        assert_eq!(quota.global_quota, Some(false));
+
+        let metric = quota.usage_metric.as_ref().unwrap();
+        assert_eq!(metric.metric_namespace, Some("AWS/Usage".to_owned()));
+        assert_eq!(metric.metric_name, Some("ResourceCount".to_owned()));
+        assert_eq!(metric.metric_statistic_recommendation, Some("Maximum".to_owned()));
+
+        let dimensions = metric.metric_dimensions.as_ref().unwrap();
+        assert_eq!(dimensions.len(), 4);
+        assert_eq!(dimensions["Resource"], "vCPU");
+    }
+
+    #[test]
+    fn change_state() {
+        let change = RequestedServiceQuotaChange {
+            status: Some("CASE_OPENED".to_owned()),
+            ..RequestedServiceQuotaChange::default()
+        };
+
+        assert_eq!(change.change_state(), QuotaChangeState::CaseOpened);
+        assert!(!change.change_state().is_finished());
+        assert!(QuotaChangeState::from_status("DENIED").is_finished());
+    }
+}
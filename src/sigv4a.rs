@@ -0,0 +1,314 @@
+//! The ECDSA keys and signatures of SigV4a, the asymmetric variant of Signature 4.
+//!
+//! A SigV4a signing key is a NIST P-256 private key derived from the secret access key, so
+//! one signature is valid in every region of the request's region set. The `openssl` crate
+//! doesn't bind OpenSSL's elliptic curve API, so the few functions needed are declared here.
+//! The curve arithmetic and nonces are OpenSSL's own, which are constant-time.
+
+use std::os::raw::{c_int, c_uint};
+use std::ptr;
+
+use openssl::crypto::hash::Type::SHA256;
+use openssl::crypto::hash::hash;
+use openssl::crypto::hmac::hmac;
+
+/// The name of the algorithm, as given in the `Authorization` header and string to sign.
+pub const ALGORITHM: &'static str = "AWS4-ECDSA-P256-SHA256";
+
+/// The order of the P-256 group less two, the largest candidate a private key is made from.
+const N_MINUS_TWO: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xBC, 0xE6, 0xFA, 0xAD, 0xA7, 0x17, 0x9E, 0x84, 0xF3, 0xB9, 0xCA, 0xC2, 0xFC, 0x63, 0x25, 0x4F,
+];
+
+#[allow(non_camel_case_types)]
+mod ffi {
+    use std::os::raw::{c_int, c_uchar, c_uint};
+
+    pub enum BIGNUM {}
+    pub enum BN_CTX {}
+    pub enum EC_GROUP {}
+    pub enum EC_KEY {}
+    pub enum EC_POINT {}
+
+    pub const NID_X9_62_PRIME256V1: c_int = 415;
+
+    // libcrypto is linked by `openssl-sys`.
+    extern "C" {
+        pub fn BN_bin2bn(s: *const c_uchar, len: c_int, ret: *mut BIGNUM) -> *mut BIGNUM;
+        pub fn BN_bn2bin(a: *const BIGNUM, to: *mut c_uchar) -> c_int;
+        pub fn BN_num_bits(a: *const BIGNUM) -> c_int;
+        pub fn BN_new() -> *mut BIGNUM;
+        pub fn BN_clear_free(a: *mut BIGNUM);
+        pub fn BN_CTX_new() -> *mut BN_CTX;
+        pub fn BN_CTX_free(ctx: *mut BN_CTX);
+
+        pub fn EC_KEY_new_by_curve_name(nid: c_int) -> *mut EC_KEY;
+        pub fn EC_KEY_free(key: *mut EC_KEY);
+        pub fn EC_KEY_get0_group(key: *const EC_KEY) -> *const EC_GROUP;
+        pub fn EC_KEY_get0_public_key(key: *const EC_KEY) -> *const EC_POINT;
+        pub fn EC_KEY_set_private_key(key: *mut EC_KEY, private_key: *const BIGNUM) -> c_int;
+        pub fn EC_KEY_set_public_key(key: *mut EC_KEY, public_key: *const EC_POINT) -> c_int;
+        pub fn EC_KEY_set_public_key_affine_coordinates(key: *mut EC_KEY, x: *mut BIGNUM, y: *mut BIGNUM) -> c_int;
+
+        pub fn EC_POINT_new(group: *const EC_GROUP) -> *mut EC_POINT;
+        pub fn EC_POINT_free(point: *mut EC_POINT);
+        pub fn EC_POINT_mul(group: *const EC_GROUP, r: *mut EC_POINT, n: *const BIGNUM, q: *const EC_POINT,
+            m: *const BIGNUM, ctx: *mut BN_CTX) -> c_int;
+        pub fn EC_POINT_get_affine_coordinates_GFp(group: *const EC_GROUP, point: *const EC_POINT, x: *mut BIGNUM,
+            y: *mut BIGNUM, ctx: *mut BN_CTX) -> c_int;
+
+        pub fn ECDSA_size(key: *const EC_KEY) -> c_int;
+        pub fn ECDSA_sign(type_: c_int, digest: *const c_uchar, digest_len: c_int, signature: *mut c_uchar,
+            signature_len: *mut c_uint, key: *mut EC_KEY) -> c_int;
+        pub fn ECDSA_verify(type_: c_int, digest: *const c_uchar, digest_len: c_int, signature: *const c_uchar,
+            signature_len: c_int, key: *mut EC_KEY) -> c_int;
+    }
+}
+
+// OpenSSL only fails these calls if it can't allocate, or if it's given a point that isn't
+// on the curve, which `verify` checks for.
+
+/// An OpenSSL big number, cleared when it's freed since it may hold a private key.
+struct BigNum(*mut ffi::BIGNUM);
+
+impl BigNum {
+    fn new() -> BigNum {
+        let bn = unsafe { ffi::BN_new() };
+        assert!(!bn.is_null(), "Couldn't allocate a big number");
+        BigNum(bn)
+    }
+
+    fn from_slice(bytes: &[u8]) -> BigNum {
+        let bn = unsafe { ffi::BN_bin2bn(bytes.as_ptr(), bytes.len() as c_int, ptr::null_mut()) };
+        assert!(!bn.is_null(), "Couldn't allocate a big number");
+        BigNum(bn)
+    }
+
+    /// The number as a 32 byte big-endian integer.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 32];
+        let length = unsafe { ((ffi::BN_num_bits(self.0) + 7) / 8) as usize };
+        assert!(length <= 32);
+        unsafe { ffi::BN_bn2bin(self.0, bytes[32 - length..].as_mut_ptr()) };
+        bytes
+    }
+}
+
+impl Drop for BigNum {
+    fn drop(&mut self) {
+        unsafe { ffi::BN_clear_free(self.0) };
+    }
+}
+
+/// A P-256 key, with or without its private key.
+struct EcKey(*mut ffi::EC_KEY);
+
+impl EcKey {
+    fn p256() -> EcKey {
+        let key = unsafe { ffi::EC_KEY_new_by_curve_name(ffi::NID_X9_62_PRIME256V1) };
+        assert!(!key.is_null(), "Couldn't allocate a P-256 key");
+        EcKey(key)
+    }
+}
+
+impl Drop for EcKey {
+    fn drop(&mut self) {
+        unsafe { ffi::EC_KEY_free(self.0) };
+    }
+}
+
+/// A SigV4a private key and its public key.
+pub struct SigningKey {
+    key: EcKey,
+}
+
+impl SigningKey {
+    /// Derives the signing key of a pair of access keys, with the counter mode HMAC-SHA256
+    /// KDF of NIST SP 800-108 the SigV4a specification calls for. Candidates are drawn until
+    /// one is below the order of the curve less one; one is then added, so the key isn't zero.
+    pub fn derive(access_key_id: &str, secret_access_key: &str) -> SigningKey {
+        let input_key = format!("AWS4A{}", secret_access_key);
+        let mut counter = 1u8;
+
+        loop {
+            let mut fixed_input = vec![0, 0, 0, 1];
+            fixed_input.extend(ALGORITHM.as_bytes());
+            fixed_input.push(0);
+            fixed_input.extend(access_key_id.as_bytes());
+            fixed_input.push(counter);
+            fixed_input.extend(&[0, 0, 1, 0]);
+
+            let mut candidate = hmac(SHA256, input_key.as_bytes(), &fixed_input);
+
+            // Both are 32 bytes long, so comparing them byte by byte compares their values.
+            if &candidate[..] <= &N_MINUS_TWO[..] {
+                increment(&mut candidate);
+                let private_key = BigNum::from_slice(&candidate);
+
+                for byte in &mut candidate {
+                    *byte = 0;
+                }
+
+                return SigningKey { key: key_pair(&private_key) };
+            }
+
+            // The chance of a candidate ever being rejected is about 2^-32.
+            counter = counter.checked_add(1).expect("Couldn't derive a SigV4a signing key");
+        }
+    }
+
+    /// The affine coordinates of the public key, as 32 byte big-endian integers.
+    pub fn public_key(&self) -> (Vec<u8>, Vec<u8>) {
+        let x = BigNum::new();
+        let y = BigNum::new();
+
+        unsafe {
+            let ctx = ffi::BN_CTX_new();
+            let result = ffi::EC_POINT_get_affine_coordinates_GFp(
+                ffi::EC_KEY_get0_group(self.key.0),
+                ffi::EC_KEY_get0_public_key(self.key.0),
+                x.0,
+                y.0,
+                ctx,
+            );
+            ffi::BN_CTX_free(ctx);
+            assert_eq!(result, 1, "Couldn't get the coordinates of the public key");
+        }
+
+        (x.to_bytes(), y.to_bytes())
+    }
+
+    /// Signs the SHA-256 digest of `message`, returning the DER encoded signature.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let digest = hash(SHA256, message);
+
+        unsafe {
+            let mut signature = vec![0; ffi::ECDSA_size(self.key.0) as usize];
+            let mut length: c_uint = 0;
+            let result = ffi::ECDSA_sign(
+                0,
+                digest.as_ptr(),
+                digest.len() as c_int,
+                signature.as_mut_ptr(),
+                &mut length,
+                self.key.0,
+            );
+            assert_eq!(result, 1, "Couldn't sign with the SigV4a key");
+
+            signature.truncate(length as usize);
+            signature
+        }
+    }
+}
+
+/// Adds one to a big-endian integer which is known not to overflow.
+fn increment(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut().rev() {
+        let (sum, overflowed) = byte.overflowing_add(1);
+        *byte = sum;
+        if !overflowed {
+            return;
+        }
+    }
+}
+
+/// Builds the key made of `private_key` and the public key it's the discrete logarithm of.
+fn key_pair(private_key: &BigNum) -> EcKey {
+    let key = EcKey::p256();
+
+    unsafe {
+        let group = ffi::EC_KEY_get0_group(key.0);
+        let public_key = ffi::EC_POINT_new(group);
+        let ctx = ffi::BN_CTX_new();
+        let multiplied = ffi::EC_POINT_mul(group, public_key, private_key.0, ptr::null(), ptr::null(), ctx);
+        let set = ffi::EC_KEY_set_private_key(key.0, private_key.0) == 1 &&
+            ffi::EC_KEY_set_public_key(key.0, public_key) == 1;
+        ffi::BN_CTX_free(ctx);
+        ffi::EC_POINT_free(public_key);
+        assert!(multiplied == 1 && set, "Couldn't build the SigV4a key");
+    }
+
+    key
+}
+
+/// Checks a DER encoded signature of the SHA-256 digest of `message` against a public key
+/// given as its affine coordinates.
+pub fn verify(public_key: (&[u8], &[u8]), message: &[u8], signature: &[u8]) -> bool {
+    let key = EcKey::p256();
+    let x = BigNum::from_slice(public_key.0);
+    let y = BigNum::from_slice(public_key.1);
+    let digest = hash(SHA256, message);
+
+    unsafe {
+        // Fails if the point isn't on the curve.
+        if ffi::EC_KEY_set_public_key_affine_coordinates(key.0, x.0, y.0) != 1 {
+            return false;
+        }
+
+        ffi::ECDSA_verify(
+            0,
+            digest.as_ptr(),
+            digest.len() as c_int,
+            signature.as_ptr(),
+            signature.len() as c_int,
+            key.0,
+        ) == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_serialize::hex::FromHex;
+
+    use super::{SigningKey, verify};
+
+    #[test]
+    fn derived_public_key_matches_the_aws_test_suite() {
+        // The public key of the credentials used throughout the SigV4a test suite of the AWS
+        // Common Runtime, from its `public-key.json`.
+        let key = SigningKey::derive("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+
+        assert_eq!(key.public_key(), (
+            "b6618f6a65740a99e650b33b6b4b5bd0d43b176d721a3edfea7e7d2d56d936b1".from_hex().unwrap(),
+            "865ed22a7eadc9c5cb9d2cbaca1b3699139fedc5043dc6661864218330c8e518".from_hex().unwrap(),
+        ));
+    }
+
+    #[test]
+    fn signatures_verify_with_the_derived_public_key() {
+        let key = SigningKey::derive("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+        let (x, y) = key.public_key();
+        let signature = key.sign(b"AWS4-ECDSA-P256-SHA256\n20150830T123600Z\n20150830/service/aws4_request\n");
+
+        assert_eq!(x.len(), 32);
+        assert_eq!(y.len(), 32);
+        assert_eq!(signature[0], 0x30);
+        assert!(verify((&x, &y), b"AWS4-ECDSA-P256-SHA256\n20150830T123600Z\n20150830/service/aws4_request\n", &signature));
+        assert!(!verify((&x, &y), b"a different string to sign", &signature));
+    }
+
+    #[test]
+    fn derived_keys_depend_on_both_access_keys() {
+        let key = SigningKey::derive("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+        let same = SigningKey::derive("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+        let other = SigningKey::derive("AKIDEXAMPLE2", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+
+        assert_eq!(key.public_key(), same.public_key());
+        assert!(key.public_key() != other.public_key());
+
+        let signature = other.sign(b"message");
+        let (x, y) = key.public_key();
+        assert!(!verify((&x, &y), b"message", &signature));
+    }
+
+    #[test]
+    fn points_off_the_curve_are_rejected() {
+        let key = SigningKey::derive("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+        let (x, mut y) = key.public_key();
+        let signature = key.sign(b"message");
+        y[31] ^= 1;
+
+        assert!(!verify((&x, &y), b"message", &signature));
+    }
+}
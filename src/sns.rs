@@ -0,0 +1,372 @@
+//! Amazon Simple Notification Service
+
+include!(concat!(env!("OUT_DIR"), "/sns.rs"));
+
+use std::collections::BTreeMap;
+
+use serde_json::{self, Value};
+
+/// The subscription attributes that can be changed with `SetSubscriptionAttributes`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SubscriptionAttributeName {
+    DeliveryPolicy,
+    FilterPolicy,
+    FilterPolicyScope,
+    RawMessageDelivery,
+    RedrivePolicy,
+}
+
+impl SubscriptionAttributeName {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            SubscriptionAttributeName::DeliveryPolicy => "DeliveryPolicy",
+            SubscriptionAttributeName::FilterPolicy => "FilterPolicy",
+            SubscriptionAttributeName::FilterPolicyScope => "FilterPolicyScope",
+            SubscriptionAttributeName::RawMessageDelivery => "RawMessageDelivery",
+            SubscriptionAttributeName::RedrivePolicy => "RedrivePolicy",
+        }
+    }
+}
+
+/// A comparison used by a `FilterPolicyValue::Numeric` matcher.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumericComparison {
+    Equal,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl NumericComparison {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            NumericComparison::Equal => "=",
+            NumericComparison::LessThan => "<",
+            NumericComparison::LessThanOrEqual => "<=",
+            NumericComparison::GreaterThan => ">",
+            NumericComparison::GreaterThanOrEqual => ">=",
+        }
+    }
+}
+
+/// One of the values a message attribute may match in a subscription filter policy.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterPolicyValue {
+    /// Matches the attribute exactly.
+    String(String),
+    /// Matches numbers satisfying every comparison, such as `>= 0` and `< 100`.
+    Numeric(Vec<(NumericComparison, f64)>),
+    /// Matches any value except these.
+    AnythingBut(Vec<String>),
+    /// Matches messages with, or without, the attribute.
+    Exists(bool),
+    /// Matches values starting with the prefix.
+    Prefix(String),
+}
+
+impl FilterPolicyValue {
+    fn to_json(&self) -> Value {
+        match *self {
+            FilterPolicyValue::String(ref value) => Value::String(value.clone()),
+            FilterPolicyValue::Numeric(ref comparisons) => {
+                let mut bounds = Vec::new();
+
+                for &(comparison, value) in comparisons {
+                    bounds.push(Value::String(comparison.as_str().to_owned()));
+                    bounds.push(Value::F64(value));
+                }
+
+                matcher("numeric", Value::Array(bounds))
+            }
+            FilterPolicyValue::AnythingBut(ref values) => {
+                matcher("anything-but", Value::Array(values.iter().cloned().map(Value::String).collect()))
+            }
+            FilterPolicyValue::Exists(exists) => matcher("exists", Value::Bool(exists)),
+            FilterPolicyValue::Prefix(ref prefix) => matcher("prefix", Value::String(prefix.clone())),
+        }
+    }
+}
+
+fn matcher(name: &str, value: Value) -> Value {
+    let mut object = BTreeMap::new();
+    object.insert(name.to_owned(), value);
+
+    Value::Object(object)
+}
+
+/// Serializes a filter policy, which maps message attribute names to the values they may
+/// match, to the JSON document SNS expects.
+pub fn filter_policy_json(policy: &HashMap<String, Vec<FilterPolicyValue>>) -> String {
+    let object = policy.iter().map(|(attribute, values)| {
+        (attribute.clone(), Value::Array(values.iter().map(FilterPolicyValue::to_json).collect()))
+    }).collect::<BTreeMap<String, Value>>();
+
+    serde_json::to_string(&Value::Object(object)).expect("filter policies are always valid JSON")
+}
+
+impl SetSubscriptionAttributesInput {
+    pub fn new(subscription_arn: &str, attribute_name: SubscriptionAttributeName, attribute_value: String)
+        -> SetSubscriptionAttributesInput {
+        SetSubscriptionAttributesInput {
+            attribute_name: attribute_name.as_str().to_owned(),
+            attribute_value: Some(attribute_value),
+            subscription_arn: subscription_arn.to_owned(),
+        }
+    }
+}
+
+/// Where `publish_message` sends a message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PublishTarget {
+    /// A standard topic, by ARN.
+    Topic(String),
+    /// A FIFO topic, by ARN. Messages to it need a message group ID.
+    FifoTopic(String),
+    /// A mobile platform endpoint, by ARN.
+    Endpoint(String),
+    /// A phone number, in E.164 format, to send an SMS message to.
+    PhoneNumber(String),
+}
+
+impl PublishTarget {
+    /// The target for a topic ARN, which is a FIFO topic when its name ends with `.fifo`.
+    pub fn topic(topic_arn: &str) -> PublishTarget {
+        if topic_arn.ends_with(".fifo") {
+            PublishTarget::FifoTopic(topic_arn.to_owned())
+        } else {
+            PublishTarget::Topic(topic_arn.to_owned())
+        }
+    }
+}
+
+/// A message for `publish_message`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnsMessage {
+    pub message: String,
+    pub subject: Option<String>,
+    /// The group of a message to a FIFO topic, within which messages are delivered in order.
+    pub message_group_id: Option<String>,
+    /// The token a FIFO topic deduplicates messages by. It can be left out for topics with
+    /// content-based deduplication, which use a hash of the message instead.
+    pub message_deduplication_id: Option<String>,
+}
+
+impl SnsMessage {
+    pub fn new(message: &str) -> SnsMessage {
+        SnsMessage {
+            message: message.to_owned(),
+            ..SnsMessage::default()
+        }
+    }
+}
+
+fn validation_error(message: &str) -> AwsError {
+    AwsError::new(format!("ValidationError: {}", message))
+}
+
+impl PublishInput {
+    /// Creates a request to publish `message` to `target`, failing without a request being sent
+    /// if the message is missing a group ID a FIFO topic requires.
+    pub fn new(target: &PublishTarget, message: SnsMessage) -> Result<PublishInput, AwsError> {
+        let mut input = PublishInput {
+            message: message.message,
+            message_deduplication_id: message.message_deduplication_id,
+            message_group_id: message.message_group_id,
+            subject: message.subject,
+            ..PublishInput::default()
+        };
+
+        match *target {
+            PublishTarget::Topic(ref topic_arn) => input.topic_arn = Some(topic_arn.clone()),
+            PublishTarget::FifoTopic(ref topic_arn) => {
+                if input.message_group_id.is_none() {
+                    return Err(validation_error("Messages to FIFO topics require a message group ID"));
+                }
+
+                input.topic_arn = Some(topic_arn.clone());
+            }
+            PublishTarget::Endpoint(ref target_arn) => input.target_arn = Some(target_arn.clone()),
+            PublishTarget::PhoneNumber(ref phone_number) => input.phone_number = Some(phone_number.clone()),
+        }
+
+        Ok(input)
+    }
+}
+
+impl CreateTopicInput {
+    /// Creates a request for a FIFO topic, whose name has to end with `.fifo`. With
+    /// `content_based_deduplication`, messages published without a deduplication ID are
+    /// deduplicated by a hash of their body.
+    pub fn fifo(name: &str, content_based_deduplication: bool) -> Result<CreateTopicInput, AwsError> {
+        if !name.ends_with(".fifo") {
+            return Err(validation_error("The names of FIFO topics must end with .fifo"));
+        }
+
+        let mut attributes = HashMap::new();
+        attributes.insert("FifoTopic".to_owned(), "true".to_owned());
+        attributes.insert("ContentBasedDeduplication".to_owned(), content_based_deduplication.to_string());
+
+        Ok(CreateTopicInput {
+            attributes: Some(attributes),
+            name: name.to_owned(),
+            ..CreateTopicInput::default()
+        })
+    }
+}
+
+impl<P> SnsClient<P> where P: ProvideAwsCredentials {
+    /// Publishes a message and returns its ID.
+    pub fn publish_message(&mut self, target: &PublishTarget, message: SnsMessage)
+        -> Result<Option<String>, AwsError> {
+        let input = try!(PublishInput::new(target, message));
+
+        Ok(try!(self.publish(&input)).message_id)
+    }
+
+    /// Creates a FIFO topic and returns its ARN.
+    pub fn create_fifo_topic(&mut self, name: &str, content_based_deduplication: bool)
+        -> Result<Option<String>, AwsError> {
+        let input = try!(CreateTopicInput::fifo(name, content_based_deduplication));
+
+        Ok(try!(self.create_topic(&input)).topic_arn)
+    }
+
+    /// Sets a single attribute of a subscription.
+    pub fn set_subscription_attribute(&mut self, subscription_arn: &str,
+        attribute_name: SubscriptionAttributeName, attribute_value: String) -> Result<(), AwsError> {
+        let input = SetSubscriptionAttributesInput::new(subscription_arn, attribute_name, attribute_value);
+
+        self.set_subscription_attributes(&input)
+    }
+
+    /// Returns all of a subscription's attributes, keyed by name.
+    pub fn subscription_attributes(&mut self, subscription_arn: &str)
+        -> Result<HashMap<String, String>, AwsError> {
+        let input = GetSubscriptionAttributesInput { subscription_arn: subscription_arn.to_owned() };
+        let response = try!(self.get_subscription_attributes(&input));
+
+        Ok(response.attributes.unwrap_or_else(HashMap::new))
+    }
+
+    /// Replaces a subscription's filter policy, so only messages whose attributes match it are
+    /// delivered.
+    pub fn set_filter_policy(&mut self, subscription_arn: &str,
+        policy: &HashMap<String, Vec<FilterPolicyValue>>) -> Result<(), AwsError> {
+        self.set_subscription_attribute(
+            subscription_arn,
+            SubscriptionAttributeName::FilterPolicy,
+            filter_policy_json(policy),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use credential::ProfileProvider;
+    use param::Params;
+    use region::Region;
+    use super::{CreateTopicInput, FilterPolicyValue, NumericComparison, PublishInput, PublishTarget};
+    use super::{SetSubscriptionAttributesInput, SetSubscriptionAttributesInputSerializer, SnsClient};
+    use super::{SnsMessage, SubscriptionAttributeName, filter_policy_json};
+
+    const FIFO_TOPIC_ARN: &'static str = "arn:aws:sns:us-east-1:123456789012:orders.fifo";
+
+    #[test]
+    fn filter_policy_matchers() {
+        let mut policy = HashMap::new();
+        policy.insert("store".to_owned(), vec![
+            FilterPolicyValue::String("example_corp".to_owned()),
+            FilterPolicyValue::Prefix("example_".to_owned()),
+        ]);
+        policy.insert("event".to_owned(), vec![
+            FilterPolicyValue::AnythingBut(vec!["order_cancelled".to_owned(), "order_failed".to_owned()]),
+        ]);
+        policy.insert("price_usd".to_owned(), vec![
+            FilterPolicyValue::Numeric(vec![
+                (NumericComparison::GreaterThanOrEqual, 99.5),
+                (NumericComparison::LessThan, 250.25),
+            ]),
+        ]);
+        policy.insert("customer_interests".to_owned(), vec![FilterPolicyValue::Exists(true)]);
+
+        assert_eq!(
+            filter_policy_json(&policy),
+            "{\"customer_interests\":[{\"exists\":true}],\
+             \"event\":[{\"anything-but\":[\"order_cancelled\",\"order_failed\"]}],\
+             \"price_usd\":[{\"numeric\":[\">=\",99.5,\"<\",250.25]}],\
+             \"store\":[\"example_corp\",{\"prefix\":\"example_\"}]}"
+        );
+    }
+
+    #[test]
+    fn filter_policy_escapes_strings() {
+        let mut policy = HashMap::new();
+        policy.insert("note".to_owned(), vec![FilterPolicyValue::String("say \"hi\"".to_owned())]);
+
+        assert_eq!(filter_policy_json(&policy), "{\"note\":[\"say \\\"hi\\\"\"]}");
+    }
+
+    #[test]
+    fn set_subscription_attributes_params() {
+        let input = SetSubscriptionAttributesInput::new(
+            "arn:aws:sns:us-east-1:123456789012:orders:5be8f5c4-ad8d-4b4e-a1e1-2f6c8c5e3c11",
+            SubscriptionAttributeName::RawMessageDelivery,
+            "true".to_owned(),
+        );
+        let mut params = Params::new();
+        SetSubscriptionAttributesInputSerializer::serialize(&mut params, "", &input);
+
+        assert_eq!(params.get("AttributeName"), Some(&"RawMessageDelivery".to_owned()));
+        assert_eq!(params.get("AttributeValue"), Some(&"true".to_owned()));
+        assert_eq!(params.get("SubscriptionArn"), Some(&input.subscription_arn));
+    }
+
+    #[test]
+    fn fifo_topic_requires_message_group_id() {
+        let provider = ProfileProvider::with_configuration(
+            "tests/sample-data/multiple_profile_credentials",
+            "foo",
+        );
+        let mut sns = SnsClient::new(provider, Region::UsEast1);
+        // Nothing listens on port 1, so any request would fail with a connection error instead.
+        sns.set_hostname(Some("127.0.0.1:1".to_owned()));
+
+        let error = sns.publish_message(&PublishTarget::topic(FIFO_TOPIC_ARN), SnsMessage::new("shipped"))
+            .unwrap_err();
+        assert_eq!(
+            format!("{}", error),
+            "ValidationError: Messages to FIFO topics require a message group ID"
+        );
+    }
+
+    #[test]
+    fn fifo_publish_input() {
+        let message = SnsMessage {
+            message_group_id: Some("order-1234".to_owned()),
+            message_deduplication_id: Some("shipped-1".to_owned()),
+            ..SnsMessage::new("shipped")
+        };
+        let input = PublishInput::new(&PublishTarget::topic(FIFO_TOPIC_ARN), message).unwrap();
+
+        assert_eq!(input.topic_arn, Some(FIFO_TOPIC_ARN.to_owned()));
+        assert_eq!(input.message_group_id, Some("order-1234".to_owned()));
+        assert_eq!(input.message_deduplication_id, Some("shipped-1".to_owned()));
+
+        let target = PublishTarget::topic("arn:aws:sns:us-east-1:123456789012:orders");
+        assert_eq!(target, PublishTarget::Topic("arn:aws:sns:us-east-1:123456789012:orders".to_owned()));
+        assert!(PublishInput::new(&target, SnsMessage::new("shipped")).is_ok());
+    }
+
+    #[test]
+    fn create_fifo_topic_input() {
+        let input = CreateTopicInput::fifo("orders.fifo", true).unwrap();
+        let attributes = input.attributes.unwrap();
+
+        assert_eq!(attributes.get("FifoTopic"), Some(&"true".to_owned()));
+        assert_eq!(attributes.get("ContentBasedDeduplication"), Some(&"true".to_owned()));
+        assert!(CreateTopicInput::fifo("orders", false).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! AWS Resource Groups Tagging API
+//!
+//! Tags and untags resources of any service by ARN, and finds resources by their tags.
+
+use std::collections::HashMap;
+use std::error::Error;
+
+include!(concat!(env!("OUT_DIR"), "/resourcegroupstaggingapi.rs"));
+
+use error::AwsError;
+use pagination::read_all_pages;
+
+/// The most resources a single `TagResources` or `UntagResources` call can change.
+const MAX_BATCH_RESOURCES: usize = 20;
+
+impl TagFilter {
+    /// A filter matching resources tagged `key` with any of `values`, or with any value at all
+    /// if there are none.
+    pub fn new(key: &str, values: Vec<String>) -> TagFilter {
+        TagFilter {
+            key: Some(key.to_owned()),
+            values: if values.is_empty() { None } else { Some(values) },
+        }
+    }
+}
+
+/// Records every resource of a chunk as failed with the error of its request, which is split
+/// into its code and message when it has both, like `InvalidParameterException: ...`.
+fn fail_chunk(failed_resources: &mut FailedResourcesMap, chunk: &[String], error: &AwsError) {
+    let description = error.description();
+    let (error_code, error_message) = match description.find(": ") {
+        Some(index) => (Some(description[..index].to_owned()), &description[index + 2..]),
+        None => (None, description),
+    };
+
+    for resource_arn in chunk {
+        failed_resources.insert(resource_arn.clone(), FailureInfo {
+            error_code: error_code.clone(),
+            error_message: Some(error_message.to_owned()),
+            ..FailureInfo::default()
+        });
+    }
+}
+
+impl<P> TaggingClient<P> where P: ProvideAwsCredentials {
+    /// Calls `TagResources` for any number of resources, twenty at a time, and returns the
+    /// resources that couldn't be tagged along with why. If a request fails, every resource
+    /// in its chunk is returned with the request's error, and the rest are still tagged.
+    pub fn tag_all_resources(&mut self, resource_arns: Vec<String>, tags: HashMap<String, String>)
+        -> AwsResult<TagResourcesOutput> {
+        let mut failed_resources = FailedResourcesMap::new();
+
+        for chunk in resource_arns.chunks(MAX_BATCH_RESOURCES) {
+            let input = TagResourcesInput {
+                resource_arn_list: chunk.to_vec(),
+                tags: tags.clone(),
+            };
+
+            match self.tag_resources(&input) {
+                Ok(output) => failed_resources.extend(output.failed_resources_map.unwrap_or_else(HashMap::new)),
+                Err(error) => fail_chunk(&mut failed_resources, chunk, &error),
+            }
+        }
+
+        Ok(TagResourcesOutput { failed_resources_map: Some(failed_resources) })
+    }
+
+    /// Calls `UntagResources` for any number of resources, twenty at a time, and returns the
+    /// resources that couldn't be untagged along with why. If a request fails, every resource
+    /// in its chunk is returned with the request's error, and the rest are still untagged.
+    pub fn untag_all_resources(&mut self, resource_arns: Vec<String>, tag_keys: Vec<String>)
+        -> AwsResult<UntagResourcesOutput> {
+        let mut failed_resources = FailedResourcesMap::new();
+
+        for chunk in resource_arns.chunks(MAX_BATCH_RESOURCES) {
+            let input = UntagResourcesInput {
+                resource_arn_list: chunk.to_vec(),
+                tag_keys: tag_keys.clone(),
+            };
+
+            match self.untag_resources(&input) {
+                Ok(output) => failed_resources.extend(output.failed_resources_map.unwrap_or_else(HashMap::new)),
+                Err(error) => fail_chunk(&mut failed_resources, chunk, &error),
+            }
+        }
+
+        Ok(UntagResourcesOutput { failed_resources_map: Some(failed_resources) })
+    }
+
+    /// Calls `GetResources` until every page has been read, and returns the resources, with
+    /// their tags, matching all of `tag_filters` and any of `resource_type_filters`, such as
+    /// `ec2:instance` or `s3`. Without filters, every tagged resource in the region is returned.
+    pub fn get_all_resources(&mut self, tag_filters: Vec<TagFilter>, resource_type_filters: Vec<String>)
+        -> AwsResult<Vec<ResourceTagMapping>> {
+        let mut input = GetResourcesInput {
+            resource_type_filters: if resource_type_filters.is_empty() { None } else { Some(resource_type_filters) },
+            tag_filters: if tag_filters.is_empty() { None } else { Some(tag_filters) },
+            ..GetResourcesInput::default()
+        };
+
+        read_all_pages(None, |pagination_token| {
+            input.pagination_token = pagination_token;
+            let output = try!(self.get_resources(&input));
+
+            // The last page comes with an empty token rather than none at all.
+            let pagination_token = output.pagination_token.and_then(|token| {
+                if token.is_empty() { None } else { Some(token) }
+            });
+
+            Ok((output.resource_tag_mapping_list.unwrap_or_else(Vec::new), pagination_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use error::AwsError;
+    use super::{FailedResourcesMap, TagFilter, TagResourcesOutput, fail_chunk};
+
+    #[test]
+    fn parse_partial_failure() {
+        let mut body = String::new();
+        File::open("tests/sample-data/tagging_tag_resources_partial_failure.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let output = serde_json::from_str::<TagResourcesOutput>(&body).unwrap();
+        let failed = output.failed_resources_map.unwrap();
+
+        assert_eq!(failed.len(), 1);
+
+        let failure = &failed["arn:aws:s3:::not a bucket"];
+        assert_eq!(failure.status_code, Some(400));
+        assert_eq!(failure.error_code, Some("InvalidParameterException".to_owned()));
+    }
+
+    #[test]
+    fn failed_chunks_keep_earlier_failures() {
+        let mut failed = FailedResourcesMap::new();
+        let chunk = vec![
+            "arn:aws:sqs:us-east-1:123456789012:a".to_owned(),
+            "arn:aws:sqs:us-east-1:123456789012:b".to_owned(),
+        ];
+
+        fail_chunk(&mut failed, &chunk[..1], &AwsError::new("ThrottledException: Rate exceeded"));
+        fail_chunk(&mut failed, &chunk[1..], &AwsError::new("Failed to parse error as JSON"));
+
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[&chunk[0]].error_code, Some("ThrottledException".to_owned()));
+        assert_eq!(failed[&chunk[0]].error_message, Some("Rate exceeded".to_owned()));
+        assert_eq!(failed[&chunk[1]].error_code, None);
+        assert_eq!(failed[&chunk[1]].error_message, Some("Failed to parse error as JSON".to_owned()));
+    }
+
+    #[test]
+    fn tag_filter_without_values() {
+        let json = serde_json::to_string(&TagFilter::new("team", Vec::new())).unwrap();
+
+        assert!(json.contains("\"Key\":\"team\""));
+        assert!(json.contains("\"Values\":null"));
+    }
+}
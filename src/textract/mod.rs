@@ -0,0 +1,161 @@
+//! Amazon Textract
+//!
+//! Detects text, forms, tables and answers to queries in documents. A response is a flat
+//! list of blocks which refer to each other by ID: a page contains lines, a line contains
+//! words, and a table contains cells.
+
+include!(concat!(env!("OUT_DIR"), "/textract.rs"));
+
+use error::expect;
+
+/// The feature type which detects tables and their cells.
+pub const TABLES: &'static str = "TABLES";
+
+/// The feature type which detects key-value pairs in forms.
+pub const FORMS: &'static str = "FORMS";
+
+/// The feature type which answers the questions of a `QueriesConfig`.
+pub const QUERIES: &'static str = "QUERIES";
+
+/// The relationship from a block to the blocks it contains.
+pub const CHILD: &'static str = "CHILD";
+
+/// The relationship from a table to its merged cells, each of which has the cells it spans
+/// as children.
+pub const MERGED_CELL: &'static str = "MERGED_CELL";
+
+/// A document for Textract to analyze.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextractDocument {
+    /// A document stored in S3, in the same region as the client.
+    S3Object {
+        bucket: String,
+        name: String,
+        version: Option<String>,
+    },
+    /// The bytes of a JPEG, PNG, PDF or TIFF document, of up to 10 MB.
+    Bytes(Vec<u8>),
+}
+
+impl From<TextractDocument> for Document {
+    fn from(document: TextractDocument) -> Document {
+        match document {
+            TextractDocument::S3Object { bucket, name, version } => {
+                Document {
+                    s3_object: Some(S3Object {
+                        bucket: Some(bucket),
+                        name: Some(name),
+                        version: version,
+                    }),
+                    ..Document::default()
+                }
+            }
+            TextractDocument::Bytes(bytes) => {
+                Document {
+                    bytes: Some(bytes),
+                    ..Document::default()
+                }
+            }
+        }
+    }
+}
+
+impl Block {
+    /// The IDs of the blocks this block is related to by `relationship_type`, such as `CHILD`.
+    pub fn related_ids(&self, relationship_type: &str) -> Vec<&str> {
+        self.relationships.as_ref().map_or(Vec::new(), |relationships| {
+            relationships.iter()
+                .filter(|relationship| {
+                    relationship.aws_type.as_ref().map_or(false, |aws_type| aws_type == relationship_type)
+                })
+                .flat_map(|relationship| relationship.ids.as_ref().map_or(&[][..], |ids| &ids[..]))
+                .map(|id| &id[..])
+                .collect()
+        })
+    }
+}
+
+/// Returns the blocks `block` is related to by `relationship_type`, looking them up among
+/// `blocks` in the order of the relationship.
+pub fn related_blocks<'a>(blocks: &'a [Block], block: &Block, relationship_type: &str) -> Vec<&'a Block> {
+    block.related_ids(relationship_type).into_iter()
+        .filter_map(|id| blocks.iter().find(|block| block.id.as_ref().map_or(false, |block_id| block_id == id)))
+        .collect()
+}
+
+impl<P> TextractClient<P> where P: ProvideAwsCredentials {
+    /// Detects the lines and words of text in a single page `document`.
+    pub fn detect_text(&mut self, document: TextractDocument) -> AwsResult<DetectDocumentTextResponse> {
+        self.detect_document_text(&DetectDocumentTextRequest { document: document.into() })
+    }
+
+    /// Analyzes a single page `document` for the `feature_types` `TABLES`, `FORMS` or
+    /// `QUERIES`, the last of which needs `queries_config`.
+    pub fn analyze(&mut self, document: TextractDocument, feature_types: Vec<String>,
+        queries_config: Option<QueriesConfig>) -> AwsResult<AnalyzeDocumentResponse> {
+        let input = AnalyzeDocumentRequest {
+            document: document.into(),
+            feature_types: feature_types,
+            queries_config: queries_config,
+            ..AnalyzeDocumentRequest::default()
+        };
+
+        self.analyze_document(&input)
+    }
+
+    /// Starts an asynchronous job analyzing a multi-page PDF or TIFF document in S3, and
+    /// returns its ID. The job's results are read with `GetDocumentAnalysis`, after the
+    /// completion is published to `notification_channel` if it's set.
+    pub fn start_analysis(&mut self, document_location: DocumentLocation, feature_types: Vec<String>,
+        output_config: Option<OutputConfig>, notification_channel: Option<NotificationChannel>)
+        -> AwsResult<String> {
+        let input = StartDocumentAnalysisRequest {
+            document_location: document_location,
+            feature_types: feature_types,
+            notification_channel: notification_channel,
+            output_config: output_config,
+            ..StartDocumentAnalysisRequest::default()
+        };
+
+        expect(try!(self.start_document_analysis(&input)).job_id, "the job ID")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{AnalyzeDocumentResponse, CHILD, MERGED_CELL, related_blocks};
+
+    #[test]
+    fn parse_table_with_merged_cells() {
+        let mut body = String::new();
+        File::open("tests/sample-data/textract_analyze_document.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let response = serde_json::from_str::<AnalyzeDocumentResponse>(&body).unwrap();
+        let blocks = response.blocks.unwrap();
+
+        assert_eq!(response.document_metadata.unwrap().pages, Some(2));
+        assert_eq!(blocks.iter().filter(|block| block.block_type == Some("PAGE".to_owned())).count(), 2);
+
+        let table = blocks.iter().find(|block| block.block_type == Some("TABLE".to_owned())).unwrap();
+        assert_eq!(table.page, Some(2));
+        assert_eq!(related_blocks(&blocks, table, CHILD).len(), 4);
+
+        let merged_cells = related_blocks(&blocks, table, MERGED_CELL);
+        assert_eq!(merged_cells.len(), 1);
+        assert_eq!(merged_cells[0].column_span, Some(2));
+
+        let header = related_blocks(&blocks, merged_cells[0], CHILD);
+        assert_eq!(header.len(), 2);
+        assert_eq!(header[0].row_index, Some(1));
+        assert_eq!(header[1].column_index, Some(2));
+
+        let word = related_blocks(&blocks, header[0], CHILD)[0];
+        assert_eq!(word.text, Some("Quarter".to_owned()));
+        assert!(word.geometry.as_ref().unwrap().bounding_box.is_some());
+    }
+}
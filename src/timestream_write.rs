@@ -0,0 +1,167 @@
+//! Amazon Timestream Write
+//!
+//! Timestream only accepts requests on the endpoint returned by `DescribeEndpoints`, so the
+//! client discovers it before the first request and again whenever it's about to expire.
+
+include!(concat!(env!("OUT_DIR"), "/timestream-write.rs"));
+
+impl WriteRecordsRequest {
+    /// Creates a request to write `records` to a table, moving the dimensions and values shared
+    /// by every record into `CommonAttributes` so they're only sent once.
+    pub fn with_common_attributes(database_name: &str, table_name: &str, mut records: Vec<Record>)
+        -> WriteRecordsRequest {
+        WriteRecordsRequest {
+            common_attributes: extract_common_attributes(&mut records),
+            database_name: database_name.to_owned(),
+            records: records,
+            table_name: table_name.to_owned(),
+        }
+    }
+}
+
+/// Removes the attributes shared by every record from `records`, returning them as a record.
+fn extract_common_attributes(records: &mut [Record]) -> Option<Record> {
+    if records.len() < 2 {
+        return None;
+    }
+
+    let mut common = Record::default();
+
+    common.measure_name = take_common(records, |record| &mut record.measure_name);
+    common.measure_value = take_common(records, |record| &mut record.measure_value);
+    common.measure_value_type = take_common(records, |record| &mut record.measure_value_type);
+    common.time = take_common(records, |record| &mut record.time);
+    common.time_unit = take_common(records, |record| &mut record.time_unit);
+    common.version = take_common(records, |record| &mut record.version);
+
+    let shared_dimensions = records[0].dimensions.clone().unwrap_or_else(Vec::new)
+        .into_iter()
+        .filter(|dimension| {
+            records[1..].iter().all(|record| {
+                record.dimensions.as_ref().map_or(false, |dimensions| dimensions.contains(dimension))
+            })
+        })
+        .collect::<Vec<Dimension>>();
+
+    if !shared_dimensions.is_empty() {
+        for record in records.iter_mut() {
+            let remaining = record.dimensions.take().unwrap_or_else(Vec::new)
+                .into_iter()
+                .filter(|dimension| !shared_dimensions.contains(dimension))
+                .collect::<Vec<Dimension>>();
+
+            if !remaining.is_empty() {
+                record.dimensions = Some(remaining);
+            }
+        }
+
+        common.dimensions = Some(shared_dimensions);
+    }
+
+    if common == Record::default() {
+        None
+    } else {
+        Some(common)
+    }
+}
+
+/// Clears `field` on every record and returns its value if all of the records agree on it.
+fn take_common<T, F>(records: &mut [Record], field: F) -> Option<T>
+where T: Clone + PartialEq, F: Fn(&mut Record) -> &mut Option<T> {
+    let value = field(&mut records[0]).clone();
+
+    if value.is_none() || !records.iter_mut().all(|record| *field(record) == value) {
+        return None;
+    }
+
+    for record in records.iter_mut() {
+        *field(record) = None;
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use credential::ProfileProvider;
+    use region::Region;
+
+    use super::{Dimension, Record, TimestreamWriteClient, WriteRecordsRequest};
+
+    fn dimension(name: &str, value: &str) -> Dimension {
+        Dimension {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            ..Dimension::default()
+        }
+    }
+
+    fn cpu_record(host: &str, value: &str, time: &str) -> Record {
+        Record {
+            dimensions: Some(vec![dimension("region", "us-east-1"), dimension("host", host)]),
+            measure_name: Some("cpu_utilization".to_owned()),
+            measure_value: Some(value.to_owned()),
+            measure_value_type: Some("DOUBLE".to_owned()),
+            time: Some(time.to_owned()),
+            time_unit: Some("MILLISECONDS".to_owned()),
+            ..Record::default()
+        }
+    }
+
+    #[test]
+    fn shared_attributes_are_moved_to_common_attributes() {
+        let request = WriteRecordsRequest::with_common_attributes("metrics", "cpu", vec![
+            cpu_record("web-1", "13.5", "1500000000000"),
+            cpu_record("web-2", "72.1", "1500000000000"),
+            cpu_record("web-3", "40.0", "1500000001000"),
+        ]);
+        let common = request.common_attributes.unwrap();
+
+        assert_eq!(common.dimensions, Some(vec![dimension("region", "us-east-1")]));
+        assert_eq!(common.measure_name, Some("cpu_utilization".to_owned()));
+        assert_eq!(common.measure_value_type, Some("DOUBLE".to_owned()));
+        assert_eq!(common.time_unit, Some("MILLISECONDS".to_owned()));
+        assert_eq!(common.measure_value, None);
+        assert_eq!(common.time, None);
+
+        assert_eq!(request.records[0], Record {
+            dimensions: Some(vec![dimension("host", "web-1")]),
+            measure_value: Some("13.5".to_owned()),
+            time: Some("1500000000000".to_owned()),
+            ..Record::default()
+        });
+        assert_eq!(request.records[2].time, Some("1500000001000".to_owned()));
+    }
+
+    #[test]
+    fn nothing_shared_means_no_common_attributes() {
+        let mut memory = cpu_record("web-1", "512", "1500000000000");
+        memory.dimensions = Some(vec![dimension("host", "web-1")]);
+        memory.measure_name = Some("memory_used".to_owned());
+        memory.measure_value_type = Some("BIGINT".to_owned());
+        memory.time_unit = Some("SECONDS".to_owned());
+
+        let request = WriteRecordsRequest::with_common_attributes("metrics", "hosts", vec![
+            cpu_record("web-2", "13.5", "1500000001000"),
+            memory,
+        ]);
+
+        assert_eq!(request.common_attributes, None);
+        assert_eq!(request.records[1].measure_name, Some("memory_used".to_owned()));
+    }
+
+    #[test]
+    fn overridden_hostname_skips_endpoint_discovery() {
+        let provider = ProfileProvider::with_configuration(
+            "tests/sample-data/multiple_profile_credentials",
+            "foo",
+        );
+        let mut client = TimestreamWriteClient::new(provider, Region::UsEast1);
+        client.set_hostname(Some("ingest-cell2.timestream.us-east-1.amazonaws.com".to_owned()));
+
+        assert_eq!(
+            client.discovered_hostname().unwrap(),
+            Some("ingest-cell2.timestream.us-east-1.amazonaws.com".to_owned())
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! AWS Transfer Family
+
+include!(concat!(env!("OUT_DIR"), "/transfer.rs"));
+
+impl CreateUserRequest {
+    /// Creates a request to add a service-managed user to a server, who can sign in with the
+    /// private key matching `ssh_public_key_body` and accesses storage as `role`.
+    pub fn new(server_id: &str, user_name: &str, role: &str, home_directory: Option<String>,
+        ssh_public_key_body: Option<String>) -> CreateUserRequest {
+        CreateUserRequest {
+            home_directory: home_directory,
+            role: role.to_owned(),
+            server_id: server_id.to_owned(),
+            ssh_public_key_body: ssh_public_key_body,
+            user_name: user_name.to_owned(),
+            ..CreateUserRequest::default()
+        }
+    }
+}
+
+impl<P> TransferClient<P> where P: ProvideAwsCredentials {
+    /// Creates a server and returns its ID.
+    pub fn create_server_id(&mut self, input: &CreateServerRequest) -> AwsResult<String> {
+        Ok(try!(self.create_server(input)).server_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{CreateServerRequest, CreateUserRequest, DescribeServerResponse, Tag};
+
+    #[test]
+    fn parse_online_vpc_server() {
+        let mut body = String::new();
+        File::open("tests/sample-data/transfer_describe_server.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let server = serde_json::from_str::<DescribeServerResponse>(&body).unwrap().server;
+
+        assert_eq!(server.server_id, Some("s-01234567890abcdef".to_owned()));
+        assert_eq!(server.state, Some("ONLINE".to_owned()));
+        assert_eq!(server.endpoint_type, Some("VPC".to_owned()));
+        assert_eq!(server.identity_provider_type, Some("SERVICE_MANAGED".to_owned()));
+        assert_eq!(server.protocols, Some(vec!["SFTP".to_owned(), "FTPS".to_owned()]));
+        assert_eq!(server.user_count, Some(3));
+
+        let endpoint = server.endpoint_details.unwrap();
+        assert_eq!(endpoint.vpc_id, Some("vpc-0a1b2c3d".to_owned()));
+        assert_eq!(endpoint.subnet_ids, Some(vec!["subnet-1a2b3c4d".to_owned(), "subnet-5e6f7a8b".to_owned()]));
+        assert_eq!(endpoint.vpc_endpoint_id, Some("vpce-0123456789abcdef0".to_owned()));
+    }
+
+    #[test]
+    fn create_server_request() {
+        let request = CreateServerRequest {
+            endpoint_type: Some("PUBLIC".to_owned()),
+            identity_provider_type: Some("SERVICE_MANAGED".to_owned()),
+            protocols: Some(vec!["SFTP".to_owned()]),
+            tags: Some(vec![Tag { key: "team".to_owned(), value: "data".to_owned() }]),
+            ..CreateServerRequest::default()
+        };
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"Protocols\":[\"SFTP\"]"));
+        assert!(json.contains("\"Tags\":[{\"Key\":\"team\",\"Value\":\"data\"}]"));
+    }
+
+    #[test]
+    fn create_user_request() {
+        let request = CreateUserRequest::new(
+            "s-01234567890abcdef",
+            "alice",
+            "arn:aws:iam::123456789012:role/transfer-s3-access",
+            Some("/uploads/alice".to_owned()),
+            None,
+        );
+
+        assert_eq!(request.home_directory, Some("/uploads/alice".to_owned()));
+        assert_eq!(request.ssh_public_key_body, None);
+        assert_eq!(request.home_directory_type, None);
+    }
+}
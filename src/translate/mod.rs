@@ -0,0 +1,131 @@
+//! Amazon Translate
+
+include!(concat!(env!("OUT_DIR"), "/translate.rs"));
+
+use pagination::read_all_pages;
+
+/// The formality setting for a formal register, which only some target languages support.
+pub const FORMAL: &'static str = "FORMAL";
+
+/// The formality setting for an informal register.
+pub const INFORMAL: &'static str = "INFORMAL";
+
+/// The profanity setting that replaces profane words and phrases with `?$#@$`.
+pub const MASK: &'static str = "MASK";
+
+/// The only merge strategy, which replaces an existing terminology of the same name.
+pub const OVERWRITE: &'static str = "OVERWRITE";
+
+/// The format of terminologies in comma separated values, with a header row of language codes.
+pub const CSV: &'static str = "CSV";
+
+/// The format of terminologies in Translation Memory eXchange.
+pub const TMX: &'static str = "TMX";
+
+impl TranslationSettings {
+    /// Settings with the formality `FORMAL` or `INFORMAL`, and the profanity `MASK` if
+    /// `mask_profanity` is set.
+    pub fn new(formality: Option<String>, mask_profanity: bool) -> TranslationSettings {
+        TranslationSettings {
+            formality: formality,
+            profanity: if mask_profanity { Some(MASK.to_owned()) } else { None },
+            ..TranslationSettings::default()
+        }
+    }
+}
+
+impl TranslateTextRequest {
+    /// Creates a request to translate `text` between two language codes, such as `en` and `de`.
+    /// The source language code `auto` has Amazon Comprehend detect it.
+    pub fn new(text: &str, source_language_code: &str, target_language_code: &str,
+        terminology_names: Vec<String>, settings: Option<TranslationSettings>) -> TranslateTextRequest {
+        TranslateTextRequest {
+            settings: settings,
+            source_language_code: source_language_code.to_owned(),
+            target_language_code: target_language_code.to_owned(),
+            terminology_names: if terminology_names.is_empty() { None } else { Some(terminology_names) },
+            text: text.to_owned(),
+        }
+    }
+}
+
+impl TerminologyData {
+    /// The contents of a terminology file in the format `CSV` or `TMX`.
+    pub fn new(file: Vec<u8>, format: &str) -> TerminologyData {
+        TerminologyData {
+            file: file,
+            format: format.to_owned(),
+            ..TerminologyData::default()
+        }
+    }
+}
+
+impl ImportTerminologyRequest {
+    /// Creates a request to import the terminology `name` with the merge strategy `OVERWRITE`.
+    pub fn new(name: &str, merge_strategy: &str, terminology_data: TerminologyData, description: Option<String>)
+        -> ImportTerminologyRequest {
+        ImportTerminologyRequest {
+            description: description,
+            merge_strategy: merge_strategy.to_owned(),
+            name: name.to_owned(),
+            terminology_data: terminology_data,
+            ..ImportTerminologyRequest::default()
+        }
+    }
+}
+
+impl<P> TranslateClient<P> where P: ProvideAwsCredentials {
+    /// Translates `text` without any terminologies or settings, and returns the translation.
+    pub fn translate(&mut self, text: &str, source_language_code: &str, target_language_code: &str)
+        -> AwsResult<String> {
+        let input = TranslateTextRequest::new(text, source_language_code, target_language_code, Vec::new(), None);
+
+        Ok(try!(self.translate_text(&input)).translated_text)
+    }
+
+    /// Calls `ListTerminologies` until every page has been read, with up to `max_results`
+    /// terminologies on each, and returns all of them.
+    pub fn list_all_terminologies(&mut self, max_results: Option<i32>) -> AwsResult<Vec<TerminologyProperties>> {
+        let mut input = ListTerminologiesRequest {
+            max_results: max_results,
+            ..ListTerminologiesRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.list_terminologies(&input));
+
+            Ok((output.terminology_properties_list.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{CSV, FORMAL, ImportTerminologyRequest, OVERWRITE, TerminologyData};
+    use super::{TranslateTextRequest, TranslationSettings};
+
+    #[test]
+    fn formal_translation_settings() {
+        let settings = TranslationSettings::new(Some(FORMAL.to_owned()), false);
+        let request = TranslateTextRequest::new("How are you?", "en", "de", Vec::new(), Some(settings));
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"Settings\":{"));
+        assert!(json.contains("\"Formality\":\"FORMAL\""));
+        assert!(json.contains("\"Profanity\":null"));
+        assert!(json.contains("\"TerminologyNames\":null"));
+    }
+
+    #[test]
+    fn terminology_file_is_base64_encoded() {
+        let data = TerminologyData::new(b"en,de\nRusoto,Rusoto\n".to_vec(), CSV);
+        let request = ImportTerminologyRequest::new("brands", OVERWRITE, data, None);
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(json.contains("\"File\":\"ZW4sZGUKUnVzb3RvLFJ1c290bwo=\""));
+        assert!(json.contains("\"Format\":\"CSV\""));
+    }
+}
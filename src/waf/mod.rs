@@ -0,0 +1,98 @@
+//! AWS WAF Classic
+//!
+//! Manages the IP sets, rules and web ACLs of WAF Classic, for accounts which haven't moved to
+//! WAFv2. Every request which changes something must carry a change token, which can only be
+//! used once. The API has a single endpoint, `waf.amazonaws.com`, for CloudFront distributions.
+
+include!(concat!(env!("OUT_DIR"), "/waf.rs"));
+
+use error::AwsError;
+use pagination::read_all_pages;
+
+/// The type of IP set descriptors holding an IPv4 address range.
+pub const IPV4: &'static str = "IPV4";
+
+/// The type of IP set descriptors holding an IPv6 address range.
+pub const IPV6: &'static str = "IPV6";
+
+/// The action of updates which add a descriptor.
+pub const INSERT: &'static str = "INSERT";
+
+/// The action of updates which remove a descriptor.
+pub const DELETE: &'static str = "DELETE";
+
+impl IPSetUpdate {
+    /// Adds the address range `value`, in CIDR notation, of the type `IPV4` or `IPV6`.
+    pub fn insert(descriptor_type: &str, value: &str) -> IPSetUpdate {
+        IPSetUpdate::new(INSERT, descriptor_type, value)
+    }
+
+    /// Removes the address range `value`, in CIDR notation, of the type `IPV4` or `IPV6`.
+    pub fn delete(descriptor_type: &str, value: &str) -> IPSetUpdate {
+        IPSetUpdate::new(DELETE, descriptor_type, value)
+    }
+
+    fn new(action: &str, descriptor_type: &str, value: &str) -> IPSetUpdate {
+        IPSetUpdate {
+            action: action.to_owned(),
+            ip_set_descriptor: IPSetDescriptor {
+                aws_type: descriptor_type.to_owned(),
+                value: value.to_owned(),
+            },
+        }
+    }
+}
+
+impl<P> WafClient<P> where P: ProvideAwsCredentials {
+    /// Gets a change token for the next request which changes something.
+    pub fn change_token(&mut self) -> AwsResult<String> {
+        let output = try!(self.get_change_token(&GetChangeTokenRequest::default()));
+
+        output.change_token.ok_or_else(|| AwsError::new("GetChangeToken did not return a change token"))
+    }
+
+    /// Creates an empty IP set, getting a change token first if `change_token` is `None`.
+    pub fn add_ip_set(&mut self, name: &str, change_token: Option<String>) -> AwsResult<CreateIPSetResponse> {
+        let input = CreateIPSetRequest {
+            change_token: try!(self.change_token_or_new(change_token)),
+            name: name.to_owned(),
+        };
+
+        self.create_ip_set(&input)
+    }
+
+    /// Inserts addresses into and deletes them from the IP set `ip_set_id`, getting a change
+    /// token first if `change_token` is `None`. Returns the change token the update was made
+    /// with, whose status shows when the update has propagated.
+    pub fn change_ip_set(&mut self, ip_set_id: &str, change_token: Option<String>, updates: Vec<IPSetUpdate>)
+        -> AwsResult<String> {
+        let input = UpdateIPSetRequest {
+            change_token: try!(self.change_token_or_new(change_token)),
+            ip_set_id: ip_set_id.to_owned(),
+            updates: updates,
+        };
+        let change_token = input.change_token.clone();
+
+        Ok(try!(self.update_ip_set(&input)).change_token.unwrap_or(change_token))
+    }
+
+    /// Calls `ListIPSets` until every page has been read, with up to `limit` IP sets on each,
+    /// and returns all of them.
+    pub fn list_all_ip_sets(&mut self, limit: Option<i32>) -> AwsResult<Vec<IPSetSummary>> {
+        let mut input = ListIPSetsRequest { limit: limit, next_marker: None };
+
+        read_all_pages(None, |next_marker| {
+            input.next_marker = next_marker;
+            let output = try!(self.list_ip_sets(&input));
+
+            Ok((output.ip_sets.unwrap_or_else(Vec::new), output.next_marker))
+        })
+    }
+
+    fn change_token_or_new(&mut self, change_token: Option<String>) -> AwsResult<String> {
+        match change_token {
+            Some(change_token) => Ok(change_token),
+            None => self.change_token(),
+        }
+    }
+}
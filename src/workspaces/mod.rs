@@ -0,0 +1,102 @@
+//! Amazon WorkSpaces
+
+include!(concat!(env!("OUT_DIR"), "/workspaces.rs"));
+
+use pagination::read_all_pages;
+
+/// The state of a WorkSpace which is being created.
+pub const PENDING: &'static str = "PENDING";
+
+/// The state of a WorkSpace which users can log in to.
+pub const AVAILABLE: &'static str = "AVAILABLE";
+
+/// The state of a WorkSpace which is restarting.
+pub const REBOOTING: &'static str = "REBOOTING";
+
+/// The state of a WorkSpace which has been deleted, along with its volumes.
+pub const TERMINATED: &'static str = "TERMINATED";
+
+impl<P> WorkspacesClient<P> where P: ProvideAwsCredentials {
+    /// Calls `DescribeWorkspaces` until every page has been read, and returns all of the
+    /// WorkSpaces, or only those in `workspace_ids` if it isn't empty. They can instead be
+    /// narrowed down to those in the directory `directory_id`, and of the user `user_name`.
+    pub fn describe_all_workspaces(&mut self, workspace_ids: Vec<String>, directory_id: Option<String>,
+        user_name: Option<String>) -> AwsResult<Vec<Workspace>> {
+        let mut input = DescribeWorkspacesRequest {
+            directory_id: directory_id,
+            user_name: user_name,
+            workspace_ids: if workspace_ids.is_empty() { None } else { Some(workspace_ids) },
+            ..DescribeWorkspacesRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.describe_workspaces(&input));
+
+            Ok((output.workspaces.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Creates up to 25 WorkSpaces. Each request either becomes a `PENDING` WorkSpace or is
+    /// listed with the reason it failed, so one bad request doesn't stop the others.
+    pub fn add_workspaces(&mut self, workspaces: Vec<WorkspaceRequest>) -> AwsResult<CreateWorkspacesResult> {
+        self.create_workspaces(&CreateWorkspacesRequest { workspaces: workspaces })
+    }
+
+    /// Terminates up to 25 WorkSpaces, deleting their volumes. The WorkSpaces which couldn't
+    /// be terminated are listed in the `failed_requests` of the result.
+    pub fn remove_workspaces(&mut self, workspace_ids: Vec<String>) -> AwsResult<TerminateWorkspacesResult> {
+        let input = TerminateWorkspacesRequest {
+            terminate_workspace_requests: workspace_ids.into_iter()
+                .map(|workspace_id| TerminateRequest { workspace_id: workspace_id })
+                .collect(),
+        };
+
+        self.terminate_workspaces(&input)
+    }
+
+    /// Restarts up to 25 WorkSpaces, which must be `AVAILABLE`, `IMPAIRED` or `UNHEALTHY`. The
+    /// WorkSpaces which couldn't be restarted are listed in the `failed_requests` of the result.
+    pub fn restart_workspaces(&mut self, workspace_ids: Vec<String>) -> AwsResult<RebootWorkspacesResult> {
+        let input = RebootWorkspacesRequest {
+            reboot_workspace_requests: workspace_ids.into_iter()
+                .map(|workspace_id| RebootRequest { workspace_id: workspace_id })
+                .collect(),
+        };
+
+        self.reboot_workspaces(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::{CreateWorkspacesResult, PENDING};
+
+    #[test]
+    fn parse_partially_failed_creation() {
+        let mut body = String::new();
+        File::open("tests/sample-data/workspaces_create_workspaces.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let result = serde_json::from_str::<CreateWorkspacesResult>(&body).unwrap();
+
+        let pending = result.pending_requests.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].workspace_id, Some("ws-f7hsrphp6".to_owned()));
+        assert_eq!(pending[0].user_name, Some("alice".to_owned()));
+        assert_eq!(pending[0].state, Some(PENDING.to_owned()));
+
+        // The failed request is returned as it was sent, so it can be corrected and retried.
+        let failed = result.failed_requests.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].error_code, Some("ResourceNotFound.User".to_owned()));
+
+        let request = failed[0].workspace_request.as_ref().unwrap();
+        assert_eq!(request.user_name, "mallory");
+        assert_eq!(request.bundle_id, "wsb-clj85qzj1");
+    }
+}
@@ -158,6 +158,26 @@ pub fn end_element<T: Peek + Next>(element_name: &str, stack: &mut T)  -> Result
 	}
 }
 
+/// consume the next element along with everything nested inside it, whatever its name
+pub fn skip_tree<T: Peek + Next>(stack: &mut T) -> Result<(), XmlParseError> {
+	let mut depth = 0;
+
+	loop {
+		match stack.next() {
+			Some(XmlEvent::StartElement { .. }) => depth += 1,
+			Some(XmlEvent::EndElement { .. }) => {
+				depth -= 1;
+
+				if depth == 0 {
+					return Ok(());
+				}
+			}
+			Some(_) => (),
+			None => return Err(XmlParseError::new("Unexpected end of document while skipping an element")),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +270,20 @@ mod tests {
 	    }
 	}
 
+	#[test]
+	fn skip_tree_happy_path() {
+	    let file = File::open("tests/sample-data/list_queues_with_queue.xml").unwrap();
+	    let file = BufReader::new(file);
+	    let mut my_parser  = EventReader::new(file);
+	    let my_stack = my_parser.events().peekable();
+	    let mut reader = XmlResponseFromFile::new(my_stack);
+
+	    // skip two leading fields since we ignore them (xml declaration, return type declaration)
+	    reader.next();
+	    reader.next();
+
+	    skip_tree(&mut reader).unwrap();
+	    assert_eq!(peek_at_name(&mut reader).unwrap(), "ResponseMetadata");
+	}
+
 }
@@ -0,0 +1,100 @@
+//! AWS X-Ray
+
+pub use self::segment::{TraceSegment, new_segment_id, new_trace_id};
+
+mod segment;
+
+include!(concat!(env!("OUT_DIR"), "/xray.rs"));
+
+use pagination::read_all_pages;
+
+/// The most trace IDs a single `BatchGetTraces` call can look up.
+const MAX_BATCH_TRACE_IDS: usize = 5;
+
+impl<P> XRayClient<P> where P: ProvideAwsCredentials {
+    /// Uploads segment documents, such as those built with `TraceSegment`, and returns the
+    /// segments X-Ray didn't accept.
+    pub fn put_segments(&mut self, trace_segment_documents: Vec<String>)
+        -> AwsResult<Vec<UnprocessedTraceSegment>> {
+        let input = PutTraceSegmentsRequest { trace_segment_documents: trace_segment_documents };
+        let output = try!(self.put_trace_segments(&input));
+
+        Ok(output.unprocessed_trace_segments.unwrap_or_else(Vec::new))
+    }
+
+    /// Calls `GetTraceSummaries` until every page has been read, and returns the summaries of
+    /// the traces between `start_time` and `end_time`, in seconds since the epoch, that match
+    /// `filter_expression`. With `sampling`, only a sample of the traces is returned.
+    pub fn get_all_trace_summaries(&mut self, start_time: f64, end_time: f64, sampling: bool,
+        filter_expression: Option<String>) -> AwsResult<Vec<TraceSummary>> {
+        let mut input = GetTraceSummariesRequest {
+            end_time: end_time,
+            filter_expression: filter_expression,
+            sampling: Some(sampling),
+            start_time: start_time,
+            ..GetTraceSummariesRequest::default()
+        };
+
+        read_all_pages(None, |next_token| {
+            input.next_token = next_token;
+            let output = try!(self.get_trace_summaries(&input));
+
+            Ok((output.trace_summaries.unwrap_or_else(Vec::new), output.next_token))
+        })
+    }
+
+    /// Calls `BatchGetTraces` for any number of trace IDs, five at a time and following every
+    /// page, and returns the traces found along with the IDs that weren't.
+    pub fn batch_get_all_traces(&mut self, trace_ids: Vec<String>) -> AwsResult<BatchGetTracesResult> {
+        let mut traces = Vec::new();
+        let mut unprocessed_trace_ids = Vec::new();
+
+        for chunk in trace_ids.chunks(MAX_BATCH_TRACE_IDS) {
+            let mut input = BatchGetTracesRequest {
+                next_token: None,
+                trace_ids: chunk.to_vec(),
+            };
+
+            loop {
+                let output = try!(self.batch_get_traces(&input));
+
+                traces.extend(output.traces.unwrap_or_else(Vec::new));
+                unprocessed_trace_ids.extend(output.unprocessed_trace_ids.unwrap_or_else(Vec::new));
+
+                match output.next_token {
+                    Some(next_token) => input.next_token = Some(next_token),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(BatchGetTracesResult {
+            next_token: None,
+            traces: Some(traces),
+            unprocessed_trace_ids: Some(unprocessed_trace_ids),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json;
+
+    use super::PutTraceSegmentsResult;
+
+    #[test]
+    fn parse_unprocessed_trace_segments() {
+        let mut body = String::new();
+        File::open("tests/sample-data/xray_put_trace_segments_unprocessed.json").unwrap()
+            .read_to_string(&mut body).unwrap();
+        let output = serde_json::from_str::<PutTraceSegmentsResult>(&body).unwrap();
+        let unprocessed = output.unprocessed_trace_segments.unwrap();
+
+        assert_eq!(unprocessed.len(), 1);
+        assert_eq!(unprocessed[0].id, Some("70de5b6f19ff9a0a".to_owned()));
+        assert_eq!(unprocessed[0].error_code, Some("InvalidSegmentDocument".to_owned()));
+    }
+}
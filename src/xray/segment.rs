@@ -0,0 +1,358 @@
+//! Building segment documents for `PutTraceSegments`.
+
+use std::collections::BTreeMap;
+
+use openssl::crypto::rand::rand_bytes;
+use rustc_serialize::hex::ToHex;
+use serde_json::{self, Value};
+
+use error::{AwsError, AwsResult};
+
+/// Returns a new trace ID for a request that started at `start_time`, in seconds since the
+/// epoch: `1-`, the start time as 8 hex digits, then 96 random bits as 24 hex digits.
+pub fn new_trace_id(start_time: f64) -> String {
+    format!("1-{:08x}-{}", start_time as u32, rand_bytes(12).to_hex())
+}
+
+/// Returns a new, random 64-bit segment ID as 16 hex digits.
+pub fn new_segment_id() -> String {
+    rand_bytes(8).to_hex()
+}
+
+/// The HTTP request a segment records, and the response to it once there is one.
+#[derive(Debug)]
+struct Http {
+    method: String,
+    url: String,
+    user_agent: Option<String>,
+    client_ip: Option<String>,
+    status: Option<u16>,
+    content_length: Option<u64>,
+}
+
+/// Builds the JSON document of a segment, or of a subsegment embedded in one.
+///
+/// A segment without an end time is sent as in progress, and has to be sent again with
+/// one when its work is done.
+#[derive(Debug)]
+pub struct TraceSegment {
+    name: String,
+    id: String,
+    trace_id: Option<String>,
+    parent_id: Option<String>,
+    start_time: f64,
+    end_time: Option<f64>,
+    http: Option<Http>,
+    annotations: BTreeMap<String, Value>,
+    metadata: BTreeMap<String, BTreeMap<String, Value>>,
+    subsegments: Vec<TraceSegment>,
+}
+
+impl TraceSegment {
+    /// Starts a segment of the trace `trace_id`, for the work done by the service `name`.
+    pub fn new(name: &str, trace_id: &str, id: &str, start_time: f64) -> TraceSegment {
+        TraceSegment {
+            trace_id: Some(trace_id.to_owned()),
+            ..TraceSegment::new_subsegment(name, id, start_time)
+        }
+    }
+
+    /// Starts a subsegment, to add to its parent with `subsegment`.
+    pub fn new_subsegment(name: &str, id: &str, start_time: f64) -> TraceSegment {
+        TraceSegment {
+            name: name.to_owned(),
+            id: id.to_owned(),
+            trace_id: None,
+            parent_id: None,
+            start_time: start_time,
+            end_time: None,
+            http: None,
+            annotations: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+            subsegments: Vec::new(),
+        }
+    }
+
+    /// Sets the ID of the upstream segment, from the `Parent` of the `X-Amzn-Trace-Id` header.
+    pub fn parent_id(mut self, parent_id: &str) -> Self {
+        self.parent_id = Some(parent_id.to_owned());
+        self
+    }
+
+    /// Sets when the work finished, which marks the segment as no longer in progress.
+    pub fn end_time(mut self, end_time: f64) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    /// Records the HTTP request the segment served.
+    pub fn http_request(mut self, method: &str, url: &str, user_agent: Option<String>,
+        client_ip: Option<String>) -> Self {
+        self.http = Some(Http {
+            method: method.to_owned(),
+            url: url.to_owned(),
+            user_agent: user_agent,
+            client_ip: client_ip,
+            status: None,
+            content_length: None,
+        });
+        self
+    }
+
+    /// Records the response to the segment's HTTP request. A 4xx status marks the segment
+    /// as an error, 429 also as throttled, and a 5xx status as a fault.
+    pub fn http_response(mut self, status: u16, content_length: Option<u64>) -> Self {
+        if let Some(ref mut http) = self.http {
+            http.status = Some(status);
+            http.content_length = content_length;
+        }
+        self
+    }
+
+    /// Adds an annotation, which X-Ray indexes for filter expressions. Values should be strings,
+    /// numbers or booleans.
+    pub fn annotation(mut self, key: &str, value: Value) -> Self {
+        self.annotations.insert(key.to_owned(), value);
+        self
+    }
+
+    /// Adds metadata under `namespace`, which X-Ray stores but doesn't index.
+    pub fn metadata(mut self, namespace: &str, key: &str, value: Value) -> Self {
+        self.metadata.entry(namespace.to_owned())
+            .or_insert_with(BTreeMap::new)
+            .insert(key.to_owned(), value);
+        self
+    }
+
+    /// Embeds a subsegment.
+    pub fn subsegment(mut self, subsegment: TraceSegment) -> Self {
+        self.subsegments.push(subsegment);
+        self
+    }
+
+    /// Returns the segment document, or an error if an ID or name isn't one X-Ray accepts.
+    pub fn build(self) -> AwsResult<String> {
+        if self.trace_id.is_none() {
+            return Err(AwsError::new("A segment document requires a trace ID"));
+        }
+
+        Ok(serde_json::to_string(&try!(self.into_json())).expect("segments are always valid JSON"))
+    }
+
+    fn into_json(self) -> AwsResult<Value> {
+        if self.name.is_empty() || self.name.chars().count() > 200 {
+            return Err(AwsError::new(format!("Invalid segment name: {:?}", self.name)));
+        }
+
+        if !is_segment_id(&self.id) {
+            return Err(AwsError::new(format!("Invalid segment ID: {:?}", self.id)));
+        }
+
+        let mut object = BTreeMap::new();
+
+        if let Some(trace_id) = self.trace_id {
+            if !is_trace_id(&trace_id) {
+                return Err(AwsError::new(format!("Invalid trace ID: {:?}", trace_id)));
+            }
+
+            object.insert("trace_id".to_owned(), Value::String(trace_id));
+        }
+
+        object.insert("name".to_owned(), Value::String(self.name));
+        object.insert("id".to_owned(), Value::String(self.id));
+        object.insert("start_time".to_owned(), Value::F64(self.start_time));
+
+        match self.end_time {
+            Some(end_time) => object.insert("end_time".to_owned(), Value::F64(end_time)),
+            None => object.insert("in_progress".to_owned(), Value::Bool(true)),
+        };
+
+        if let Some(parent_id) = self.parent_id {
+            object.insert("parent_id".to_owned(), Value::String(parent_id));
+        }
+
+        if let Some(http) = self.http {
+            if let Some(status) = http.status {
+                if status >= 400 && status < 500 {
+                    object.insert("error".to_owned(), Value::Bool(true));
+                }
+                if status == 429 {
+                    object.insert("throttle".to_owned(), Value::Bool(true));
+                }
+                if status >= 500 {
+                    object.insert("fault".to_owned(), Value::Bool(true));
+                }
+            }
+
+            object.insert("http".to_owned(), http.into_json());
+        }
+
+        if !self.annotations.is_empty() {
+            object.insert("annotations".to_owned(), Value::Object(self.annotations));
+        }
+
+        if !self.metadata.is_empty() {
+            let metadata = self.metadata.into_iter().map(|(namespace, values)| {
+                (namespace, Value::Object(values))
+            }).collect();
+
+            object.insert("metadata".to_owned(), Value::Object(metadata));
+        }
+
+        if !self.subsegments.is_empty() {
+            let mut subsegments = Vec::new();
+
+            for subsegment in self.subsegments {
+                subsegments.push(try!(subsegment.into_json()));
+            }
+
+            object.insert("subsegments".to_owned(), Value::Array(subsegments));
+        }
+
+        Ok(Value::Object(object))
+    }
+}
+
+impl Http {
+    fn into_json(self) -> Value {
+        let mut request = BTreeMap::new();
+        request.insert("method".to_owned(), Value::String(self.method));
+        request.insert("url".to_owned(), Value::String(self.url));
+
+        if let Some(user_agent) = self.user_agent {
+            request.insert("user_agent".to_owned(), Value::String(user_agent));
+        }
+
+        if let Some(client_ip) = self.client_ip {
+            request.insert("client_ip".to_owned(), Value::String(client_ip));
+        }
+
+        let mut http = BTreeMap::new();
+        http.insert("request".to_owned(), Value::Object(request));
+
+        if let Some(status) = self.status {
+            let mut response = BTreeMap::new();
+            response.insert("status".to_owned(), Value::U64(status as u64));
+
+            if let Some(content_length) = self.content_length {
+                response.insert("content_length".to_owned(), Value::U64(content_length));
+            }
+
+            http.insert("response".to_owned(), Value::Object(response));
+        }
+
+        Value::Object(http)
+    }
+}
+
+fn is_hex(text: &str, len: usize) -> bool {
+    text.len() == len && text.chars().all(|c| c.is_digit(16) && !c.is_uppercase())
+}
+
+/// Whether `id` is 64 bits as 16 lowercase hex digits.
+fn is_segment_id(id: &str) -> bool {
+    is_hex(id, 16)
+}
+
+/// Whether `trace_id` has the version 1 format `new_trace_id` returns.
+fn is_trace_id(trace_id: &str) -> bool {
+    let parts = trace_id.split('-').collect::<Vec<&str>>();
+
+    parts.len() == 3 && parts[0] == "1" && is_hex(parts[1], 8) && is_hex(parts[2], 24)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{self, Value};
+
+    use super::{TraceSegment, is_segment_id, is_trace_id, new_segment_id, new_trace_id};
+
+    const TRACE_ID: &'static str = "1-581cf771-a006649127e371903a2de979";
+
+    #[test]
+    fn segment_with_http_and_subsegment() {
+        let document = TraceSegment::new("checkout", TRACE_ID, "70de5b6f19ff9a0a", 1478293361.271)
+            .end_time(1478293361.449)
+            .http_request("POST", "https://shop.example.com/orders", Some("curl/7.47.0".to_owned()), None)
+            .http_response(503, Some(53))
+            .annotation("customer_tier", Value::String("gold".to_owned()))
+            .metadata("debug", "cart_items", Value::U64(3))
+            .subsegment(
+                TraceSegment::new_subsegment("inventory", "53995c3f42cd8ad8", 1478293361.28)
+                    .end_time(1478293361.39)
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            document,
+            "{\"annotations\":{\"customer_tier\":\"gold\"},\"end_time\":1478293361.449,\"fault\":true,\
+             \"http\":{\"request\":{\"method\":\"POST\",\"url\":\"https://shop.example.com/orders\",\
+             \"user_agent\":\"curl/7.47.0\"},\"response\":{\"content_length\":53,\"status\":503}},\
+             \"id\":\"70de5b6f19ff9a0a\",\"metadata\":{\"debug\":{\"cart_items\":3}},\"name\":\"checkout\",\
+             \"start_time\":1478293361.271,\"subsegments\":[{\"end_time\":1478293361.39,\
+             \"id\":\"53995c3f42cd8ad8\",\"name\":\"inventory\",\"start_time\":1478293361.28}],\
+             \"trace_id\":\"1-581cf771-a006649127e371903a2de979\"}"
+        );
+    }
+
+    #[test]
+    fn in_progress_segment() {
+        let document = TraceSegment::new("checkout", TRACE_ID, "70de5b6f19ff9a0a", 1478293361.271)
+            .parent_id("3da1a9b2d6fa7b5c")
+            .build()
+            .unwrap();
+        let json = serde_json::from_str::<Value>(&document).unwrap();
+
+        assert_eq!(json.find("in_progress"), Some(&Value::Bool(true)));
+        assert_eq!(json.find("end_time"), None);
+        assert_eq!(json.find("parent_id").and_then(Value::as_string), Some("3da1a9b2d6fa7b5c"));
+    }
+
+    #[test]
+    fn client_errors_and_throttling() {
+        let build = |status| {
+            let document = TraceSegment::new("api", TRACE_ID, "70de5b6f19ff9a0a", 1478293361.0)
+                .end_time(1478293362.0)
+                .http_request("GET", "https://api.example.com/", None, None)
+                .http_response(status, None)
+                .build()
+                .unwrap();
+
+            serde_json::from_str::<Value>(&document).unwrap()
+        };
+
+        let throttled = build(429);
+        assert_eq!(throttled.find("error"), Some(&Value::Bool(true)));
+        assert_eq!(throttled.find("throttle"), Some(&Value::Bool(true)));
+        assert_eq!(throttled.find("fault"), None);
+
+        let ok = build(200);
+        assert_eq!(ok.find("error"), None);
+        assert_eq!(ok.lookup("http.response.status"), Some(&Value::U64(200)));
+    }
+
+    #[test]
+    fn invalid_documents() {
+        assert!(TraceSegment::new("checkout", "1-581cf771", "70de5b6f19ff9a0a", 0.0).build().is_err());
+        assert!(TraceSegment::new("checkout", TRACE_ID, "70DE5B6F19FF9A0A", 0.0).build().is_err());
+        assert!(TraceSegment::new("", TRACE_ID, "70de5b6f19ff9a0a", 0.0).build().is_err());
+        assert!(TraceSegment::new_subsegment("inventory", "53995c3f42cd8ad8", 0.0).build().is_err());
+        assert!(
+            TraceSegment::new("checkout", TRACE_ID, "70de5b6f19ff9a0a", 0.0)
+                .subsegment(TraceSegment::new_subsegment("inventory", "bogus", 0.0))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn generated_ids() {
+        let trace_id = new_trace_id(1478293361.271);
+
+        assert!(trace_id.starts_with("1-581cf771-"));
+        assert!(is_trace_id(&trace_id));
+        assert!(is_segment_id(&new_segment_id()));
+        assert!(new_segment_id() != new_segment_id());
+    }
+}
@@ -0,0 +1,165 @@
+//! An in-memory stand-in for the group operations of Amazon EC2 Auto Scaling.
+
+use std::cmp;
+use std::sync::{Arc, Mutex};
+
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+
+use server::{MockRequest, MockResponse, MockServer, escape_xml};
+
+const NAMESPACE: &'static str = "http://autoscaling.amazonaws.com/doc/2011-01-01/";
+
+const REQUEST_ID: &'static str = "0f02a07d-b677-11e2-9eb0-dd50EXAMPLE";
+
+/// The number of groups `DescribeAutoScalingGroups` returns on each page.
+const PAGE_SIZE: usize = 2;
+
+/// A mock Auto Scaling server supporting `DescribeAutoScalingGroups`.
+///
+/// Unlike Auto Scaling, which returns up to 50 groups a page unless `MaxRecords` says
+/// otherwise, groups are listed two to a page, and `MaxRecords` is ignored. Each group has as
+/// many healthy instances in `us-east-1a` as its desired capacity, and only groups named in
+/// `AutoScalingGroupNames` are listed if it's given.
+pub struct MockAutoScalingServer {
+    server: MockServer,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockAutoScalingServer {
+    /// Starts a server on a free port.
+    pub fn new() -> MockAutoScalingServer {
+        let state = Arc::new(Mutex::new(State::default()));
+
+        MockAutoScalingServer {
+            server: MockServer::start(AutoScalingHandler { state: state.clone() }),
+            state: state,
+        }
+    }
+
+    /// The URL to pass to `AutoScalingClient::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// Adds a group with a desired capacity of `desired_capacity`.
+    pub fn add_auto_scaling_group(&self, name: &str, desired_capacity: i32) {
+        self.state.lock().unwrap().groups.push((name.to_owned(), desired_capacity));
+    }
+
+    /// The `NextToken` of each request so far, in the order they arrived.
+    pub fn next_tokens(&self) -> Vec<Option<String>> {
+        self.state.lock().unwrap().next_tokens.clone()
+    }
+}
+
+impl Default for MockAutoScalingServer {
+    fn default() -> MockAutoScalingServer {
+        MockAutoScalingServer::new()
+    }
+}
+
+struct AutoScalingHandler {
+    state: Arc<Mutex<State>>,
+}
+
+impl Handler for AutoScalingHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        self.state.lock().unwrap().dispatch(&request).send(response);
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    next_tokens: Vec<Option<String>>,
+    /// The name and desired capacity of each group.
+    groups: Vec<(String, i32)>,
+}
+
+impl State {
+    fn dispatch(&mut self, request: &MockRequest) -> MockResponse {
+        let action = request.param("Action").unwrap_or("");
+        if action != "DescribeAutoScalingGroups" {
+            return error("InvalidAction", &format!("The action {} is not valid for this endpoint.", action));
+        }
+
+        let next_token = request.param("NextToken");
+        self.next_tokens.push(next_token.map(|next_token| next_token.to_owned()));
+
+        let names = request.params.iter()
+            .filter(|&&(ref key, _)| key.starts_with("AutoScalingGroupNames.member."))
+            .map(|&(_, ref value)| &value[..])
+            .collect::<Vec<&str>>();
+
+        let groups = self.groups.iter()
+            .filter(|&&(ref name, _)| names.is_empty() || names.contains(&&name[..]))
+            .collect::<Vec<&(String, i32)>>();
+
+        // Tokens are simply the index of the first group of the page.
+        let start = match next_token.map(|next_token| next_token.parse::<usize>()) {
+            None => 0,
+            Some(Ok(start)) if start <= groups.len() => start,
+            Some(_) => return error("InvalidNextToken", "The token is not valid."),
+        };
+        let end = cmp::min(start + PAGE_SIZE, groups.len());
+
+        let members = groups[start..end].iter().map(|&&(ref name, desired_capacity)| {
+            let instances = (0..desired_capacity).map(|index| {
+                format!(
+                    "<member><InstanceId>i-{:08x}</InstanceId><AvailabilityZone>us-east-1a</AvailabilityZone>\
+                     <LifecycleState>InService</LifecycleState><HealthStatus>Healthy</HealthStatus>\
+                     <ProtectedFromScaleIn>false</ProtectedFromScaleIn></member>",
+                    index
+                )
+            }).collect::<String>();
+
+            format!(
+                "<member><AutoScalingGroupName>{name}</AutoScalingGroupName><MinSize>1</MinSize>\
+                 <MaxSize>5</MaxSize><DesiredCapacity>{desired_capacity}</DesiredCapacity>\
+                 <DefaultCooldown>300</DefaultCooldown>\
+                 <AvailabilityZones><member>us-east-1a</member></AvailabilityZones>\
+                 <HealthCheckType>EC2</HealthCheckType><CreatedTime>2016-05-06T17:47:15.107Z</CreatedTime>\
+                 <Instances>{instances}</Instances></member>",
+                name = escape_xml(name),
+                desired_capacity = desired_capacity,
+                instances = instances
+            )
+        }).collect::<String>();
+
+        let next_token = if end < groups.len() {
+            format!("<NextToken>{}</NextToken>", end)
+        } else {
+            String::new()
+        };
+
+        MockResponse::xml(StatusCode::Ok, format!(
+            "<?xml version=\"1.0\"?><{action}Response xmlns=\"{namespace}\"><{action}Result>\
+             <AutoScalingGroups>{members}</AutoScalingGroups>{next_token}</{action}Result>\
+             <ResponseMetadata><RequestId>{request_id}</RequestId></ResponseMetadata></{action}Response>",
+            action = action,
+            namespace = NAMESPACE,
+            members = members,
+            next_token = next_token,
+            request_id = REQUEST_ID,
+        ))
+    }
+}
+
+fn error(code: &str, message: &str) -> MockResponse {
+    MockResponse::xml(StatusCode::BadRequest, format!(
+        "<?xml version=\"1.0\"?><ErrorResponse xmlns=\"{}\">\
+         <Error><Type>Sender</Type><Code>{}</Code><Message>{}</Message></Error>\
+         <RequestId>{}</RequestId></ErrorResponse>",
+        NAMESPACE,
+        code,
+        escape_xml(message),
+        REQUEST_ID,
+    ))
+}
@@ -0,0 +1,266 @@
+//! An in-memory stand-in for the channel operations of the Amazon Chime SDK Messaging API.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use hyper::method::Method;
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+use rustc_serialize::json::Json;
+use time::{get_time, strptime};
+
+use server::{MockRequest, MockResponse, MockServer};
+
+/// The number of messages `ListChannelMessages` returns on each page.
+const PAGE_SIZE: usize = 2;
+
+/// A mock Chime SDK Messaging server supporting `CreateChannel`, `SendChannelMessage` and
+/// `ListChannelMessages`.
+///
+/// Like Chime, every request must name the user it's made on behalf of in the
+/// `x-amz-chime-bearer` header, which becomes the sender of the messages it sends. Messages
+/// are listed two to a page, and only those sent within `not-before` and `not-after`, which
+/// must be ISO 8601 timestamps, if given.
+pub struct MockChimeSdkMessagingServer {
+    server: MockServer,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockChimeSdkMessagingServer {
+    /// Starts a server on a free port.
+    pub fn new() -> MockChimeSdkMessagingServer {
+        let state = Arc::new(Mutex::new(State::default()));
+
+        MockChimeSdkMessagingServer {
+            server: MockServer::start(ChimeSdkMessagingHandler { state: state.clone() }),
+            state: state,
+        }
+    }
+
+    /// The URL to pass to `ChimeSdkMessagingClient::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// The operation and `x-amz-chime-bearer` header of each request so far, in the order they
+    /// arrived. Requests without the header are rejected and not included.
+    pub fn bearers(&self) -> Vec<(String, String)> {
+        self.state.lock().unwrap().bearers.clone()
+    }
+}
+
+impl Default for MockChimeSdkMessagingServer {
+    fn default() -> MockChimeSdkMessagingServer {
+        MockChimeSdkMessagingServer::new()
+    }
+}
+
+struct ChimeSdkMessagingHandler {
+    state: Arc<Mutex<State>>,
+}
+
+impl Handler for ChimeSdkMessagingHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        self.state.lock().unwrap().dispatch(&request).send(response);
+    }
+}
+
+#[derive(Debug)]
+struct Message {
+    id: String,
+    content: String,
+    message_type: String,
+    sender: String,
+    /// When the message was sent, in seconds since the epoch.
+    created_timestamp: i64,
+}
+
+impl Message {
+    fn to_json(&self) -> Json {
+        let mut sender = BTreeMap::new();
+        sender.insert("Arn".to_owned(), Json::String(self.sender.clone()));
+
+        let mut message = BTreeMap::new();
+        message.insert("Content".to_owned(), Json::String(self.content.clone()));
+        message.insert("CreatedTimestamp".to_owned(), Json::I64(self.created_timestamp));
+        message.insert("MessageId".to_owned(), Json::String(self.id.clone()));
+        message.insert("Sender".to_owned(), Json::Object(sender));
+        message.insert("Type".to_owned(), Json::String(self.message_type.clone()));
+        Json::Object(message)
+    }
+}
+
+#[derive(Debug)]
+struct Channel {
+    arn: String,
+    messages: Vec<Message>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    bearers: Vec<(String, String)>,
+    channels: Vec<Channel>,
+    next_id: u64,
+}
+
+impl State {
+    fn dispatch(&mut self, request: &MockRequest) -> MockResponse {
+        let operation = match (&request.method, &request.path[..]) {
+            (&Method::Post, "/channels") => "CreateChannel",
+            (&Method::Post, path) if is_messages_path(path) => "SendChannelMessage",
+            (&Method::Get, path) if is_messages_path(path) => "ListChannelMessages",
+            _ => return error(StatusCode::NotFound, "NotFoundException", "Unknown operation"),
+        };
+
+        let bearer = match request.header("x-amz-chime-bearer") {
+            Some(bearer) => bearer.to_owned(),
+            None => {
+                return error(StatusCode::BadRequest, "BadRequestException", "x-amz-chime-bearer is required")
+            }
+        };
+        self.bearers.push((operation.to_owned(), bearer.clone()));
+
+        if operation == "CreateChannel" {
+            return self.create_channel(request);
+        }
+
+        let channel_arn = request.path["/channels/".len()..request.path.len() - "/messages".len()].to_owned();
+        let channel = match self.channels.iter_mut().find(|channel| channel.arn == channel_arn) {
+            Some(channel) => channel,
+            None => return error(StatusCode::NotFound, "NotFoundException", "The channel does not exist"),
+        };
+
+        if operation == "SendChannelMessage" {
+            self.next_id += 1;
+            send_channel_message(channel, request, bearer, self.next_id)
+        } else {
+            list_channel_messages(channel, request)
+        }
+    }
+
+    fn create_channel(&mut self, request: &MockRequest) -> MockResponse {
+        let input = match Json::from_str(&String::from_utf8_lossy(&request.body)) {
+            Ok(input) => input,
+            Err(_) => return error(StatusCode::BadRequest, "BadRequestException", "The request body is not valid JSON"),
+        };
+
+        let app_instance_arn = match input.find("AppInstanceArn").and_then(Json::as_string) {
+            Some(app_instance_arn) => app_instance_arn,
+            None => return error(StatusCode::BadRequest, "BadRequestException", "AppInstanceArn is required"),
+        };
+
+        self.next_id += 1;
+        let arn = format!("{}/channel/{:016x}", app_instance_arn, self.next_id);
+        self.channels.push(Channel {
+            arn: arn.clone(),
+            messages: Vec::new(),
+        });
+
+        json(StatusCode::Created, format!("{{\"ChannelArn\":{}}}", Json::String(arn)))
+    }
+}
+
+fn is_messages_path(path: &str) -> bool {
+    path.starts_with("/channels/") && path.ends_with("/messages") && path.len() > "/channels//messages".len()
+}
+
+fn send_channel_message(channel: &mut Channel, request: &MockRequest, sender: String, id: u64) -> MockResponse {
+    let input = match Json::from_str(&String::from_utf8_lossy(&request.body)) {
+        Ok(input) => input,
+        Err(_) => return error(StatusCode::BadRequest, "BadRequestException", "The request body is not valid JSON"),
+    };
+
+    let field = |name: &str| input.find(name).and_then(Json::as_string).map(|value| value.to_owned());
+
+    let (content, message_type) = match (field("Content"), field("Type"), field("Persistence")) {
+        (Some(content), Some(message_type), Some(_)) => (content, message_type),
+        _ => {
+            return error(StatusCode::BadRequest, "BadRequestException", "Content, Type and Persistence are required")
+        }
+    };
+
+    let message_id = format!("{:032x}", id);
+    channel.messages.push(Message {
+        id: message_id.clone(),
+        content: content,
+        message_type: message_type,
+        sender: sender,
+        created_timestamp: get_time().sec,
+    });
+
+    json(StatusCode::Created, format!(
+        "{{\"ChannelArn\":{},\"MessageId\":{},\"Status\":{{\"Value\":\"SENT\"}}}}",
+        Json::String(channel.arn.clone()),
+        Json::String(message_id),
+    ))
+}
+
+fn list_channel_messages(channel: &Channel, request: &MockRequest) -> MockResponse {
+    let mut bounds = Vec::new();
+
+    for name in &["not-before", "not-after"] {
+        match request.param(name).map(parse_iso8601) {
+            Some(None) => {
+                let message = format!("{} must be an ISO 8601 timestamp", name);
+                return error(StatusCode::BadRequest, "BadRequestException", &message);
+            }
+            bound => bounds.push(bound.and_then(|bound| bound)),
+        }
+    }
+
+    let mut messages = channel.messages.iter().filter(|message| {
+        bounds[0].map_or(true, |not_before| message.created_timestamp >= not_before) &&
+            bounds[1].map_or(true, |not_after| message.created_timestamp <= not_after)
+    }).collect::<Vec<&Message>>();
+
+    // Like Chime, messages are listed newest first unless asked otherwise.
+    if request.param("sort-order") != Some("ASCENDING") {
+        messages.reverse();
+    }
+
+    let start = request.param("next-token").and_then(|token| token.parse().ok()).unwrap_or(0);
+    let start = ::std::cmp::min(start, messages.len());
+    let end = ::std::cmp::min(start + PAGE_SIZE, messages.len());
+
+    let mut output = BTreeMap::new();
+    output.insert("ChannelArn".to_owned(), Json::String(channel.arn.clone()));
+    output.insert(
+        "ChannelMessages".to_owned(),
+        Json::Array(messages[start..end].iter().map(|message| message.to_json()).collect()),
+    );
+
+    if end < messages.len() {
+        output.insert("NextToken".to_owned(), Json::String(end.to_string()));
+    }
+
+    json(StatusCode::Ok, Json::Object(output).to_string())
+}
+
+/// Parses a timestamp like `2021-05-15T12:00:00Z` or `2021-05-15T12:00:00.250Z` into seconds
+/// since the epoch.
+fn parse_iso8601(timestamp: &str) -> Option<i64> {
+    if !timestamp.ends_with('Z') {
+        return None;
+    }
+
+    let timestamp = &timestamp[..timestamp.len() - 1];
+    let whole_seconds = timestamp.find('.').map_or(timestamp, |index| &timestamp[..index]);
+
+    strptime(whole_seconds, "%Y-%m-%dT%H:%M:%S").ok().map(|time| time.to_timespec().sec)
+}
+
+fn error(status: StatusCode, code: &str, message: &str) -> MockResponse {
+    json(status, format!("{{\"__type\":\"{}\",\"message\":\"{}\"}}", code, message))
+        .with_header("x-amzn-ErrorType", code)
+}
+
+fn json(status: StatusCode, body: String) -> MockResponse {
+    MockResponse::new(status, body.into_bytes()).with_header("Content-Type", "application/json")
+}
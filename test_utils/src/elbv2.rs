@@ -0,0 +1,193 @@
+//! An in-memory stand-in for the describe operations of Elastic Load Balancing v2.
+
+use std::cmp;
+use std::sync::{Arc, Mutex};
+
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+
+use server::{MockRequest, MockResponse, MockServer, escape_xml};
+
+const ARN_PREFIX: &'static str = "arn:aws:elasticloadbalancing:us-east-1:123456789012";
+
+const NAMESPACE: &'static str = "http://elasticloadbalancing.amazonaws.com/doc/2015-12-01/";
+
+const REQUEST_ID: &'static str = "6581c0ac-f39f-11e5-bb98-57195a6eb84a";
+
+/// The number of load balancers or target groups returned on each page.
+const PAGE_SIZE: usize = 2;
+
+/// A mock ELBv2 server supporting `DescribeLoadBalancers` and `DescribeTargetGroups`.
+///
+/// Unlike ELBv2, which returns up to 400 items a page, both operations return two items a
+/// page, so that paging can be tested with a handful of items. Target groups may be filtered
+/// by `LoadBalancerArn`; other filters are ignored.
+pub struct MockElbv2Server {
+    server: MockServer,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockElbv2Server {
+    /// Starts a server on a free port.
+    pub fn new() -> MockElbv2Server {
+        let state = Arc::new(Mutex::new(State::default()));
+
+        MockElbv2Server {
+            server: MockServer::start(Elbv2Handler { state: state.clone() }),
+            state: state,
+        }
+    }
+
+    /// The URL to pass to `ElbV2Client::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// Adds an application load balancer and returns its ARN.
+    pub fn add_load_balancer(&self, name: &str) -> String {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+
+        let arn = format!("{}:loadbalancer/app/{}/{:016x}", ARN_PREFIX, name, state.next_id);
+        state.load_balancers.push((arn.clone(), name.to_owned()));
+        arn
+    }
+
+    /// Adds a target group, forwarded to by the load balancer `load_balancer_arn` if given, and
+    /// returns its ARN.
+    pub fn add_target_group(&self, name: &str, load_balancer_arn: Option<&str>) -> String {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+
+        let arn = format!("{}:targetgroup/{}/{:016x}", ARN_PREFIX, name, state.next_id);
+        state.target_groups.push(TargetGroup {
+            arn: arn.clone(),
+            name: name.to_owned(),
+            load_balancer_arn: load_balancer_arn.map(|arn| arn.to_owned()),
+        });
+        arn
+    }
+
+    /// The action and `Marker` of each request so far, in the order they arrived.
+    pub fn requests(&self) -> Vec<(String, Option<String>)> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Default for MockElbv2Server {
+    fn default() -> MockElbv2Server {
+        MockElbv2Server::new()
+    }
+}
+
+struct Elbv2Handler {
+    state: Arc<Mutex<State>>,
+}
+
+impl Handler for Elbv2Handler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        self.state.lock().unwrap().dispatch(&request).send(response);
+    }
+}
+
+#[derive(Debug)]
+struct TargetGroup {
+    arn: String,
+    name: String,
+    load_balancer_arn: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    requests: Vec<(String, Option<String>)>,
+    /// The ARN and name of each load balancer.
+    load_balancers: Vec<(String, String)>,
+    target_groups: Vec<TargetGroup>,
+    next_id: u64,
+}
+
+impl State {
+    fn dispatch(&mut self, request: &MockRequest) -> MockResponse {
+        let action = request.param("Action").unwrap_or("").to_owned();
+        let marker = request.param("Marker").map(|marker| marker.to_owned());
+        self.requests.push((action.clone(), marker));
+
+        let members = match &action[..] {
+            "DescribeLoadBalancers" => {
+                self.load_balancers.iter().map(|&(ref arn, ref name)| {
+                    format!(
+                        "<member><LoadBalancerArn>{}</LoadBalancerArn>\
+                         <LoadBalancerName>{}</LoadBalancerName>\
+                         <State><Code>active</Code></State><Type>application</Type></member>",
+                        escape_xml(arn),
+                        escape_xml(name)
+                    )
+                }).collect::<Vec<String>>()
+            }
+            "DescribeTargetGroups" => {
+                let load_balancer_arn = request.param("LoadBalancerArn");
+
+                self.target_groups.iter().filter(|target_group| {
+                    load_balancer_arn.is_none() ||
+                        target_group.load_balancer_arn.as_ref().map(|arn| &arn[..]) == load_balancer_arn
+                }).map(|target_group| {
+                    format!(
+                        "<member><TargetGroupArn>{}</TargetGroupArn>\
+                         <TargetGroupName>{}</TargetGroupName></member>",
+                        escape_xml(&target_group.arn),
+                        escape_xml(&target_group.name)
+                    )
+                }).collect::<Vec<String>>()
+            }
+            _ => return error("InvalidAction", &format!("The action {} is not valid for this endpoint.", action)),
+        };
+
+        let list = if action == "DescribeLoadBalancers" { "LoadBalancers" } else { "TargetGroups" };
+
+        // Markers are simply the index of the first item of the page.
+        let start = match request.param("Marker").map(|marker| marker.parse::<usize>()) {
+            None => 0,
+            Some(Ok(start)) if start <= members.len() => start,
+            Some(_) => return error("ValidationError", "The marker is not valid."),
+        };
+        let end = cmp::min(start + PAGE_SIZE, members.len());
+
+        let next_marker = if end < members.len() {
+            format!("<NextMarker>{}</NextMarker>", end)
+        } else {
+            String::new()
+        };
+
+        success(&action, &format!("<{0}>{1}</{0}>{2}", list, members[start..end].concat(), next_marker))
+    }
+}
+
+fn success(action: &str, result: &str) -> MockResponse {
+    MockResponse::xml(StatusCode::Ok, format!(
+        "<?xml version=\"1.0\"?><{action}Response xmlns=\"{namespace}\"><{action}Result>{result}</{action}Result>\
+         <ResponseMetadata><RequestId>{request_id}</RequestId></ResponseMetadata></{action}Response>",
+        action = action,
+        namespace = NAMESPACE,
+        result = result,
+        request_id = REQUEST_ID,
+    ))
+}
+
+fn error(code: &str, message: &str) -> MockResponse {
+    MockResponse::xml(StatusCode::BadRequest, format!(
+        "<?xml version=\"1.0\"?><ErrorResponse xmlns=\"{}\">\
+         <Error><Type>Sender</Type><Code>{}</Code><Message>{}</Message></Error>\
+         <RequestId>{}</RequestId></ErrorResponse>",
+        NAMESPACE,
+        code,
+        escape_xml(message),
+        REQUEST_ID,
+    ))
+}
@@ -0,0 +1,125 @@
+//! An in-memory stand-in for the data endpoints of Amazon Kinesis Video Streams.
+
+use std::sync::{Arc, Mutex};
+
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+use rustc_serialize::json::Json;
+
+use server::{MockRequest, MockResponse, MockServer};
+
+/// A mock Kinesis Video server supporting `GetDataEndpoint`, and `PutMedia` on a second server
+/// standing in for the data endpoint it returns.
+///
+/// `PutMedia` is only accepted by the data endpoint, which acknowledges each request as a
+/// single fragment that was received and persisted.
+pub struct MockKinesisVideoServer {
+    server: MockServer,
+    data_server: MockServer,
+    media: Arc<Mutex<Vec<(String, Vec<u8>)>>>,
+}
+
+impl MockKinesisVideoServer {
+    /// Starts both servers on free ports.
+    pub fn new() -> MockKinesisVideoServer {
+        let media = Arc::new(Mutex::new(Vec::new()));
+        let data_server = MockServer::start(DataHandler { media: media.clone() });
+
+        MockKinesisVideoServer {
+            server: MockServer::start(ControlHandler { data_endpoint: data_server.endpoint() }),
+            data_server: data_server,
+            media: media,
+        }
+    }
+
+    /// The URL to pass to `KinesisVideoClient::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// The URL `GetDataEndpoint` returns for every stream and API.
+    pub fn data_endpoint(&self) -> String {
+        self.data_server.endpoint()
+    }
+
+    /// The stream name and body of each `PutMedia` request the data endpoint received.
+    pub fn media(&self) -> Vec<(String, Vec<u8>)> {
+        self.media.lock().unwrap().clone()
+    }
+}
+
+impl Default for MockKinesisVideoServer {
+    fn default() -> MockKinesisVideoServer {
+        MockKinesisVideoServer::new()
+    }
+}
+
+struct ControlHandler {
+    data_endpoint: String,
+}
+
+impl Handler for ControlHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        let mock_response = if request.path == "/getDataEndpoint" {
+            let body = Json::from_str(&String::from_utf8_lossy(&request.body)).ok();
+
+            match body.as_ref().and_then(|body| body.find("APIName")) {
+                Some(_) => json(
+                    StatusCode::Ok,
+                    format!("{{\"DataEndpoint\":{}}}", Json::String(self.data_endpoint.clone())),
+                ),
+                None => error(StatusCode::BadRequest, "InvalidArgumentException", "APIName is required"),
+            }
+        } else {
+            error(StatusCode::NotFound, "ResourceNotFoundException", "Unknown operation")
+        };
+
+        mock_response.send(response);
+    }
+}
+
+struct DataHandler {
+    media: Arc<Mutex<Vec<(String, Vec<u8>)>>>,
+}
+
+impl Handler for DataHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        let mock_response = match (&request.path[..], request.header("x-amzn-stream-name")) {
+            ("/putMedia", Some(stream_name)) => {
+                let mut media = self.media.lock().unwrap();
+                media.push((stream_name.to_owned(), request.body.clone()));
+
+                let fragment_number = format!("{:020}", media.len());
+                json(StatusCode::Ok, format!(
+                    "{{\"EventType\":\"RECEIVED\",\"FragmentTimecode\":0,\"FragmentNumber\":\"{0}\"}}\
+                     {{\"EventType\":\"PERSISTED\",\"FragmentTimecode\":0,\"FragmentNumber\":\"{0}\"}}",
+                    fragment_number
+                ))
+            }
+            ("/putMedia", None) => {
+                error(StatusCode::BadRequest, "InvalidArgumentException", "x-amzn-stream-name is required")
+            }
+            _ => error(StatusCode::NotFound, "ResourceNotFoundException", "Unknown operation"),
+        };
+
+        mock_response.send(response);
+    }
+}
+
+fn error(status: StatusCode, error_type: &str, message: &str) -> MockResponse {
+    json(status, format!("{{\"__type\":\"{}\",\"message\":\"{}\"}}", error_type, message))
+        .with_header("x-amzn-ErrorType", error_type)
+}
+
+fn json(status: StatusCode, body: String) -> MockResponse {
+    MockResponse::new(status, body.into_bytes()).with_header("Content-Type", "application/json")
+}
@@ -0,0 +1,44 @@
+//! In-memory mock AWS servers for testing code that uses Rusoto.
+//!
+//! Each server keeps its state in memory and listens on a port of the loopback interface
+//! picked by the operating system, so any number of tests can run side by side without an
+//! AWS account. Point a client at one with `set_hostname`:
+//!
+//! ```ignore
+//! let server = MockSqsServer::new();
+//! let mut sqs = SqsClient::new(provider, Region::UsEast1);
+//! sqs.set_hostname(Some(server.endpoint()));
+//! ```
+//!
+//! Requests aren't authenticated, so any credentials will do.
+
+extern crate hyper;
+extern crate openssl;
+extern crate rustc_serialize;
+extern crate time;
+extern crate url;
+
+pub use autoscaling::MockAutoScalingServer;
+pub use chime_sdk_messaging::MockChimeSdkMessagingServer;
+pub use ecs_credentials::MockEcsCredentialsServer;
+pub use elbv2::MockElbv2Server;
+pub use kinesis_video::MockKinesisVideoServer;
+pub use mediaconvert::MockMediaConvertServer;
+pub use qldb_session::MockQldbSessionServer;
+pub use s3::MockS3Server;
+pub use sqs::MockSqsServer;
+pub use sso::MockSsoServer;
+pub use waf::MockWafServer;
+
+mod autoscaling;
+mod chime_sdk_messaging;
+mod ecs_credentials;
+mod elbv2;
+mod kinesis_video;
+mod mediaconvert;
+mod qldb_session;
+mod s3;
+mod server;
+mod sqs;
+mod sso;
+mod waf;
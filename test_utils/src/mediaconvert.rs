@@ -0,0 +1,120 @@
+//! An in-memory stand-in for the regional and account-specific endpoints of AWS Elemental
+//! MediaConvert.
+
+use std::sync::{Arc, Mutex};
+
+use hyper::method::Method;
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+use rustc_serialize::json::Json;
+
+use server::{MockRequest, MockResponse, MockServer};
+
+/// A mock MediaConvert server supporting `DescribeEndpoints`, and `ListQueues` on a second
+/// server standing in for the account-specific endpoint it returns.
+///
+/// Like MediaConvert, the regional endpoint only answers `DescribeEndpoints`, and the
+/// account endpoint lists a single `Default` queue.
+pub struct MockMediaConvertServer {
+    server: MockServer,
+    account_server: MockServer,
+    operations: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockMediaConvertServer {
+    /// Starts both servers on free ports.
+    pub fn new() -> MockMediaConvertServer {
+        let operations = Arc::new(Mutex::new(Vec::new()));
+        let account_server = MockServer::start(AccountHandler { operations: operations.clone() });
+
+        MockMediaConvertServer {
+            server: MockServer::start(RegionalHandler {
+                account_endpoint: account_server.endpoint(),
+                operations: operations.clone(),
+            }),
+            account_server: account_server,
+            operations: operations,
+        }
+    }
+
+    /// The URL to pass to `MediaConvertClient::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The URL `DescribeEndpoints` returns.
+    pub fn account_endpoint(&self) -> String {
+        self.account_server.endpoint()
+    }
+
+    /// The operations requested so far, each prefixed with `regional:` or `account:` for the
+    /// endpoint it was sent to, in the order they arrived.
+    pub fn operations(&self) -> Vec<String> {
+        self.operations.lock().unwrap().clone()
+    }
+}
+
+impl Default for MockMediaConvertServer {
+    fn default() -> MockMediaConvertServer {
+        MockMediaConvertServer::new()
+    }
+}
+
+struct RegionalHandler {
+    account_endpoint: String,
+    operations: Arc<Mutex<Vec<String>>>,
+}
+
+impl Handler for RegionalHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        let mock_response = match (&request.method, &request.path[..]) {
+            (&Method::Post, "/2017-08-29/endpoints") => {
+                self.operations.lock().unwrap().push("regional:DescribeEndpoints".to_owned());
+
+                json(StatusCode::Ok, format!(
+                    "{{\"endpoints\":[{{\"url\":{}}}]}}",
+                    Json::String(self.account_endpoint.clone())
+                ))
+            }
+            _ => {
+                self.operations.lock().unwrap().push(format!("regional:{} {}", request.method, request.path));
+
+                error(StatusCode::BadRequest, "BadRequestException", "You must use the customer-specific endpoint")
+            }
+        };
+
+        mock_response.send(response);
+    }
+}
+
+struct AccountHandler {
+    operations: Arc<Mutex<Vec<String>>>,
+}
+
+impl Handler for AccountHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        let mock_response = match (&request.method, &request.path[..]) {
+            (&Method::Get, "/2017-08-29/queues") => {
+                self.operations.lock().unwrap().push("account:ListQueues".to_owned());
+
+                json(StatusCode::Ok, "{\"queues\":[{\"name\":\"Default\",\"status\":\"ACTIVE\"}]}".to_owned())
+            }
+            _ => error(StatusCode::NotFound, "NotFoundException", "Unknown operation"),
+        };
+
+        mock_response.send(response);
+    }
+}
+
+fn error(status: StatusCode, error_type: &str, message: &str) -> MockResponse {
+    json(status, format!("{{\"__type\":\"{}\",\"message\":\"{}\"}}", error_type, message))
+        .with_header("x-amzn-ErrorType", error_type)
+}
+
+fn json(status: StatusCode, body: String) -> MockResponse {
+    MockResponse::new(status, body.into_bytes()).with_header("Content-Type", "application/json")
+}
@@ -0,0 +1,285 @@
+//! An in-memory stand-in for the Amazon QLDB Session API.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+use rustc_serialize::json::Json;
+
+use server::{MockRequest, MockResponse, MockServer};
+
+/// A mock QLDB Session server supporting the `StartSession`, `StartTransaction`,
+/// `ExecuteStatement` and `CommitTransaction` commands of `SendCommand`.
+///
+/// Statements aren't run: each returns an empty page, and is recorded against its ledger when
+/// its transaction commits. Unlike QLDB, any commit digest is accepted and echoed back.
+pub struct MockQldbSessionServer {
+    server: MockServer,
+    ledgers: Arc<Mutex<Ledgers>>,
+}
+
+impl MockQldbSessionServer {
+    /// Starts a server on a free port.
+    pub fn new() -> MockQldbSessionServer {
+        let ledgers = Arc::new(Mutex::new(Ledgers::default()));
+
+        MockQldbSessionServer {
+            server: MockServer::start(QldbSessionHandler { ledgers: ledgers.clone() }),
+            ledgers: ledgers,
+        }
+    }
+
+    /// The URL to pass to `QldbSessionClient::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// The statements of the committed transactions on a ledger, in the order they were
+    /// executed.
+    pub fn committed_statements(&self, ledger_name: &str) -> Vec<String> {
+        self.ledgers.lock().unwrap().committed.get(ledger_name).cloned().unwrap_or_else(Vec::new)
+    }
+}
+
+impl Default for MockQldbSessionServer {
+    fn default() -> MockQldbSessionServer {
+        MockQldbSessionServer::new()
+    }
+}
+
+struct QldbSessionHandler {
+    ledgers: Arc<Mutex<Ledgers>>,
+}
+
+impl Handler for QldbSessionHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        self.ledgers.lock().unwrap().dispatch(&request).send(response);
+    }
+}
+
+#[derive(Debug)]
+struct Transaction {
+    session_token: String,
+    statements: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Ledgers {
+    /// The ledger each session token was issued for.
+    sessions: HashMap<String, String>,
+    transactions: HashMap<String, Transaction>,
+    committed: HashMap<String, Vec<String>>,
+    next_id: u64,
+}
+
+impl Ledgers {
+    fn dispatch(&mut self, request: &MockRequest) -> MockResponse {
+        let body = String::from_utf8_lossy(&request.body);
+        let command = match Json::from_str(&body) {
+            Ok(command) => command,
+            Err(_) => return error("BadRequestException", "The request body is not valid JSON."),
+        };
+
+        if let Some(start_session) = command.find("StartSession") {
+            return match string(start_session, "LedgerName") {
+                Some(ledger_name) => {
+                    let session_token = self.new_id("session");
+                    self.sessions.insert(session_token.clone(), ledger_name);
+
+                    success("StartSession", &[("SessionToken", Json::String(session_token))])
+                }
+                None => missing_parameter("LedgerName"),
+            };
+        }
+
+        let session_token = match string(&command, "SessionToken") {
+            Some(ref session_token) if self.sessions.contains_key(session_token) => session_token.clone(),
+            _ => return error("InvalidSessionException", "The session token is not valid."),
+        };
+
+        if command.find("StartTransaction").is_some() {
+            let transaction_id = self.new_id("transaction");
+            self.transactions.insert(transaction_id.clone(), Transaction {
+                session_token: session_token,
+                statements: Vec::new(),
+            });
+
+            return success("StartTransaction", &[("TransactionId", Json::String(transaction_id))]);
+        }
+
+        if let Some(execute_statement) = command.find("ExecuteStatement") {
+            let statement = match string(execute_statement, "Statement") {
+                Some(statement) => statement,
+                None => return missing_parameter("Statement"),
+            };
+
+            return match self.transaction(execute_statement, &session_token) {
+                Some(transaction) => {
+                    transaction.statements.push(statement);
+
+                    let mut page = BTreeMap::new();
+                    page.insert("Values".to_owned(), Json::Array(Vec::new()));
+
+                    success("ExecuteStatement", &[("FirstPage", Json::Object(page))])
+                }
+                None => no_transaction(),
+            };
+        }
+
+        if let Some(commit_transaction) = command.find("CommitTransaction") {
+            let commit_digest = match commit_transaction.find("CommitDigest") {
+                Some(commit_digest) => commit_digest.clone(),
+                None => return missing_parameter("CommitDigest"),
+            };
+            let transaction_id = match string(commit_transaction, "TransactionId") {
+                Some(transaction_id) => transaction_id,
+                None => return missing_parameter("TransactionId"),
+            };
+
+            if self.transaction(commit_transaction, &session_token).is_none() {
+                return no_transaction();
+            }
+
+            let transaction = self.transactions.remove(&transaction_id).unwrap();
+            let ledger_name = self.sessions[&session_token].clone();
+            self.committed.entry(ledger_name).or_insert_with(Vec::new).extend(transaction.statements);
+
+            return success("CommitTransaction", &[
+                ("TransactionId", Json::String(transaction_id)),
+                ("CommitDigest", commit_digest),
+            ]);
+        }
+
+        error("BadRequestException", "The request does not contain a supported command.")
+    }
+
+    fn new_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+
+        format!("{}-{:08}", prefix, self.next_id)
+    }
+
+    /// The open transaction a command's `TransactionId` names, if it belongs to the session.
+    fn transaction(&mut self, command: &Json, session_token: &str) -> Option<&mut Transaction> {
+        let transaction_id = match string(command, "TransactionId") {
+            Some(transaction_id) => transaction_id,
+            None => return None,
+        };
+
+        match self.transactions.get_mut(&transaction_id) {
+            Some(transaction) => {
+                if transaction.session_token == session_token { Some(transaction) } else { None }
+            }
+            None => None,
+        }
+    }
+}
+
+fn string(object: &Json, key: &str) -> Option<String> {
+    object.find(key).and_then(Json::as_string).map(str::to_owned)
+}
+
+fn success(command: &str, result: &[(&str, Json)]) -> MockResponse {
+    let result = result.iter().map(|&(key, ref value)| (key.to_owned(), value.clone())).collect();
+    let mut body = BTreeMap::new();
+    body.insert(command.to_owned(), Json::Object(result));
+
+    json(StatusCode::Ok, Json::Object(body))
+}
+
+fn error(error_type: &str, message: &str) -> MockResponse {
+    let mut body = BTreeMap::new();
+    body.insert("__type".to_owned(), Json::String(error_type.to_owned()));
+    body.insert("message".to_owned(), Json::String(message.to_owned()));
+
+    json(StatusCode::BadRequest, Json::Object(body))
+}
+
+fn missing_parameter(name: &str) -> MockResponse {
+    error("BadRequestException", &format!("The command must contain the parameter {}.", name))
+}
+
+fn no_transaction() -> MockResponse {
+    error("InvalidSessionException", "The transaction is not open in this session.")
+}
+
+fn json(status: StatusCode, body: Json) -> MockResponse {
+    MockResponse::new(status, body.to_string().into_bytes())
+        .with_header("Content-Type", "application/x-amz-json-1.0")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str;
+
+    use hyper::method::Method;
+    use hyper::status::StatusCode;
+    use rustc_serialize::json::Json;
+
+    use server::MockRequest;
+    use super::Ledgers;
+
+    fn send(ledgers: &mut Ledgers, body: &str) -> (StatusCode, Json) {
+        let response = ledgers.dispatch(&MockRequest {
+            method: Method::Post,
+            path: "/".to_owned(),
+            content_type: Some("application/x-amz-json-1.0".to_owned()),
+            headers: Vec::new(),
+            params: Vec::new(),
+            body: body.as_bytes().to_vec(),
+        });
+
+        (response.status, Json::from_str(str::from_utf8(&response.body).unwrap()).unwrap())
+    }
+
+    #[test]
+    fn statements_are_recorded_on_commit() {
+        let mut ledgers = Ledgers::default();
+
+        let (_, body) = send(&mut ledgers, "{\"StartSession\":{\"LedgerName\":\"vehicles\"}}");
+        let session_token = body.find_path(&["StartSession", "SessionToken"]).unwrap().as_string().unwrap()
+            .to_owned();
+
+        let (_, body) = send(&mut ledgers, &format!(
+            "{{\"SessionToken\":\"{}\",\"StartTransaction\":{{}}}}", session_token
+        ));
+        let transaction_id = body.find_path(&["StartTransaction", "TransactionId"]).unwrap().as_string()
+            .unwrap().to_owned();
+
+        let (status, _) = send(&mut ledgers, &format!(
+            "{{\"SessionToken\":\"{}\",\
+             \"ExecuteStatement\":{{\"TransactionId\":\"{}\",\"Statement\":\"SELECT 1\"}}}}",
+            session_token,
+            transaction_id
+        ));
+        assert_eq!(status, StatusCode::Ok);
+        assert!(ledgers.committed.is_empty());
+
+        let (_, body) = send(&mut ledgers, &format!(
+            "{{\"SessionToken\":\"{}\",\
+             \"CommitTransaction\":{{\"TransactionId\":\"{}\",\"CommitDigest\":\"3q2+7w==\"}}}}",
+            session_token,
+            transaction_id
+        ));
+        let commit_digest = body.find_path(&["CommitTransaction", "CommitDigest"]).unwrap();
+        assert_eq!(commit_digest.as_string(), Some("3q2+7w=="));
+        assert_eq!(ledgers.committed["vehicles"], vec!["SELECT 1".to_owned()]);
+    }
+
+    #[test]
+    fn unknown_session() {
+        let mut ledgers = Ledgers::default();
+
+        let (status, body) = send(&mut ledgers, "{\"SessionToken\":\"bogus\",\"StartTransaction\":{}}");
+        assert_eq!(status, StatusCode::BadRequest);
+        assert_eq!(body.find("__type").unwrap().as_string(), Some("InvalidSessionException"));
+    }
+}
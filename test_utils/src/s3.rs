@@ -0,0 +1,551 @@
+//! An in-memory stand-in for Amazon S3.
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use hyper::method::Method;
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+use time::now_utc;
+
+use server::{MockRequest, MockResponse, MockServer, escape_xml, md5_hex};
+
+/// The most keys a single `ListObjectsV2` call can return, or `DeleteObjects` call delete.
+const MAX_KEYS: usize = 1000;
+
+const REQUEST_ID: &'static str = "4442587FB7D0A2F9";
+
+/// A mock S3 server supporting `PutObject`, `GetObject`, `DeleteObject`, `DeleteObjects` and
+/// `ListObjectsV2`.
+///
+/// Buckets are addressed by path, as clients created with `force_path_style` do, and are
+/// created the first time an object is put in them.
+///
+/// Buckets can be made to answer every request with a redirect to another server, as S3 does
+/// when a request is sent to the wrong region.
+///
+/// Every bucket pays for its own requests, but `GetObject` requests sent with
+/// `x-amz-request-payer: requester` are answered with `x-amz-request-charged: requester`, as
+/// they would be by a requester pays bucket.
+pub struct MockS3Server {
+    server: MockServer,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl MockS3Server {
+    /// Starts a server on a free port.
+    pub fn new() -> MockS3Server {
+        let buckets = Arc::new(Mutex::new(Buckets::default()));
+
+        MockS3Server {
+            server: MockServer::start(S3Handler { buckets: buckets.clone() }),
+            buckets: buckets,
+        }
+    }
+
+    /// The URL to pass to `S3Client::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// Stores an object, as `PutObject` would.
+    pub fn put_object(&self, bucket: &str, key: &str, body: &[u8]) {
+        self.buckets.lock().unwrap().put(bucket, key, body.to_vec(), None);
+    }
+
+    /// The contents of an object, if it exists.
+    pub fn object(&self, bucket: &str, key: &str) -> Option<Vec<u8>> {
+        self.buckets.lock().unwrap().objects(bucket).and_then(|objects| {
+            objects.get(key).map(|object| object.body.clone())
+        })
+    }
+
+    /// The keys of all of the objects in a bucket, in order.
+    pub fn keys(&self, bucket: &str) -> Vec<String> {
+        self.buckets.lock().unwrap().objects(bucket).map_or(Vec::new(), |objects| {
+            objects.keys().cloned().collect()
+        })
+    }
+
+    /// Answers every request for `bucket` from now on with a `307 Temporary Redirect` to the
+    /// server at `endpoint`, such as another server's `endpoint()`.
+    pub fn redirect_bucket(&self, bucket: &str, endpoint: &str) {
+        self.buckets.lock().unwrap().redirects.insert(bucket.to_owned(), endpoint.to_owned());
+    }
+
+    /// The operation of each supported request so far, in the order they arrived.
+    pub fn operations(&self) -> Vec<String> {
+        self.buckets.lock().unwrap().operations.clone()
+    }
+}
+
+impl Default for MockS3Server {
+    fn default() -> MockS3Server {
+        MockS3Server::new()
+    }
+}
+
+struct S3Handler {
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl Handler for S3Handler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        self.buckets.lock().unwrap().dispatch(&request).send(response);
+    }
+}
+
+#[derive(Debug)]
+struct Object {
+    body: Vec<u8>,
+    content_type: Option<String>,
+    e_tag: String,
+    /// When the object was stored, in the format used by the `Last-Modified` header.
+    last_modified: String,
+    /// When the object was stored, in the format used by XML responses.
+    last_modified_iso8601: String,
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    buckets: BTreeMap<String, BTreeMap<String, Object>>,
+    operations: Vec<String>,
+    /// The endpoints buckets redirect to.
+    redirects: BTreeMap<String, String>,
+}
+
+impl Buckets {
+    fn dispatch(&mut self, request: &MockRequest) -> MockResponse {
+        let path = request.path.trim_left_matches('/');
+
+        let (bucket, key) = match path.find('/') {
+            Some(index) => (&path[..index], &path[index + 1..]),
+            None => (path, ""),
+        };
+
+        if bucket.is_empty() {
+            return not_implemented("ListBuckets", "/");
+        }
+
+        let operation = match (&request.method, key.is_empty()) {
+            (&Method::Get, true) if request.param("list-type") == Some("2") => "ListObjectsV2",
+            (&Method::Get, true) => "ListObjects",
+            (&Method::Post, true) if request.param("delete").is_some() => "DeleteObjects",
+            (&Method::Put, false) => "PutObject",
+            (&Method::Get, false) => "GetObject",
+            (&Method::Delete, false) => "DeleteObject",
+            _ => "",
+        };
+
+        if !operation.is_empty() {
+            self.operations.push(operation.to_owned());
+        }
+
+        if let Some(endpoint) = self.redirects.get(bucket) {
+            return MockResponse::xml(StatusCode::TemporaryRedirect, format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Error><Code>TemporaryRedirect</Code>\
+                 <Message>Please re-send this request to the specified temporary endpoint.</Message>\
+                 <Bucket>{}</Bucket><Endpoint>{}</Endpoint><RequestId>{}</RequestId></Error>",
+                escape_xml(bucket),
+                escape_xml(endpoint),
+                REQUEST_ID
+            ));
+        }
+
+        match (&request.method, key.is_empty()) {
+            (&Method::Get, true) => {
+                if request.param("list-type") == Some("2") {
+                    self.list_objects_v2(bucket, request)
+                } else {
+                    not_implemented("ListObjects", bucket)
+                }
+            }
+            (&Method::Post, true) if operation == "DeleteObjects" => self.delete_objects(bucket, request),
+            (&Method::Put, false) => {
+                let e_tag = self.put(bucket, key, request.body.clone(), request.content_type.clone());
+
+                MockResponse::new(StatusCode::Ok, Vec::new()).with_header("ETag", &e_tag)
+            }
+            (&Method::Get, false) => {
+                match self.objects(bucket).and_then(|objects| objects.get(key)) {
+                    Some(object) => {
+                        let response = MockResponse::new(StatusCode::Ok, object.body.clone())
+                            .with_header("ETag", &object.e_tag)
+                            .with_header("Last-Modified", &object.last_modified)
+                            .with_header(
+                                "Content-Type",
+                                object.content_type.as_ref().map_or("binary/octet-stream", |c| &c[..])
+                            );
+
+                        match request.header("x-amz-request-payer") {
+                            Some("requester") => response.with_header("x-amz-request-charged", "requester"),
+                            _ => response,
+                        }
+                    }
+                    None => {
+                        error(StatusCode::NotFound, "NoSuchKey", "The specified key does not exist.", key)
+                    }
+                }
+            }
+            (&Method::Delete, false) => {
+                if let Some(objects) = self.buckets.get_mut(bucket) {
+                    objects.remove(key);
+                }
+
+                MockResponse::new(StatusCode::NoContent, Vec::new())
+            }
+            _ => {
+                error(
+                    StatusCode::MethodNotAllowed,
+                    "MethodNotAllowed",
+                    "The specified method is not allowed against this resource.",
+                    path,
+                )
+            }
+        }
+    }
+
+    fn objects(&self, bucket: &str) -> Option<&BTreeMap<String, Object>> {
+        self.buckets.get(bucket)
+    }
+
+    /// Stores an object and returns its ETag.
+    fn put(&mut self, bucket: &str, key: &str, body: Vec<u8>, content_type: Option<String>) -> String {
+        let now = now_utc();
+        let e_tag = format!("\"{}\"", md5_hex(&body));
+
+        let object = Object {
+            body: body,
+            content_type: content_type,
+            e_tag: e_tag.clone(),
+            last_modified: format!("{}", now.rfc822()),
+            last_modified_iso8601: now.strftime("%Y-%m-%dT%H:%M:%S.000Z").unwrap().to_string(),
+        };
+
+        self.buckets.entry(bucket.to_owned()).or_insert_with(BTreeMap::new).insert(key.to_owned(), object);
+
+        e_tag
+    }
+
+    fn delete_objects(&mut self, bucket: &str, request: &MockRequest) -> MockResponse {
+        let body = String::from_utf8_lossy(&request.body).into_owned();
+        let keys = elements(&body, "Key");
+
+        if keys.len() > MAX_KEYS {
+            return error(
+                StatusCode::BadRequest,
+                "MalformedXML",
+                "The XML you provided was not well-formed or did not validate against our published schema",
+                bucket,
+            );
+        }
+
+        let objects = match self.buckets.get_mut(bucket) {
+            Some(objects) => objects,
+            None => {
+                return error(StatusCode::NotFound, "NoSuchBucket", "The specified bucket does not exist.", bucket);
+            }
+        };
+
+        let quiet = elements(&body, "Quiet").first().map_or(false, |quiet| quiet == "true");
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <DeleteResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">"
+        );
+
+        for key in &keys {
+            objects.remove(key);
+
+            if !quiet {
+                xml.push_str(&format!("<Deleted><Key>{}</Key></Deleted>", escape_xml(key)));
+            }
+        }
+
+        xml.push_str("</DeleteResult>");
+
+        MockResponse::xml(StatusCode::Ok, xml)
+    }
+
+    fn list_objects_v2(&self, bucket: &str, request: &MockRequest) -> MockResponse {
+        let objects = match self.objects(bucket) {
+            Some(objects) => objects,
+            None => {
+                return error(StatusCode::NotFound, "NoSuchBucket", "The specified bucket does not exist.", bucket);
+            }
+        };
+
+        let prefix = request.param("prefix").unwrap_or("");
+        let delimiter = request.param("delimiter").unwrap_or("");
+        let max_keys = request.param("max-keys")
+            .and_then(|max_keys| max_keys.parse::<usize>().ok())
+            .map_or(MAX_KEYS, |max_keys| cmp::min(max_keys, MAX_KEYS));
+
+        // Continuation tokens are simply the last key or common prefix of the previous page.
+        let start_after = request.param("continuation-token")
+            .or(request.param("start-after"))
+            .unwrap_or("");
+
+        let mut entries = Vec::new();
+
+        for (key, object) in objects.iter().filter(|&(key, _)| key.starts_with(prefix)) {
+            let common_prefix = if delimiter.is_empty() {
+                None
+            } else {
+                key[prefix.len()..].find(delimiter).map(|index| {
+                    key[..prefix.len() + index + delimiter.len()].to_owned()
+                })
+            };
+
+            let entry = match common_prefix {
+                Some(common_prefix) => Entry::CommonPrefix(common_prefix),
+                None => Entry::Object(key, object),
+            };
+
+            if entry.name() > start_after && entries.last() != Some(&entry) {
+                entries.push(entry);
+            }
+        }
+
+        let is_truncated = entries.len() > max_keys;
+        entries.truncate(max_keys);
+
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <ListBucketResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\"><Name>{}</Name>",
+            escape_xml(bucket)
+        );
+
+        // Empty elements are left out, as Rusoto can't parse them.
+        if !prefix.is_empty() {
+            xml.push_str(&format!("<Prefix>{}</Prefix>", escape_xml(prefix)));
+        }
+
+        if !delimiter.is_empty() {
+            xml.push_str(&format!("<Delimiter>{}</Delimiter>", escape_xml(delimiter)));
+        }
+
+        xml.push_str(&format!(
+            "<KeyCount>{}</KeyCount><MaxKeys>{}</MaxKeys><IsTruncated>{}</IsTruncated>",
+            entries.len(),
+            max_keys,
+            is_truncated
+        ));
+
+        for entry in &entries {
+            xml.push_str(&entry.to_xml());
+        }
+
+        if let Some(token) = request.param("continuation-token") {
+            xml.push_str(&format!("<ContinuationToken>{}</ContinuationToken>", escape_xml(token)));
+        }
+
+        if is_truncated {
+            if let Some(entry) = entries.last() {
+                xml.push_str(&format!(
+                    "<NextContinuationToken>{}</NextContinuationToken>",
+                    escape_xml(entry.name())
+                ));
+            }
+        }
+
+        xml.push_str("</ListBucketResult>");
+
+        MockResponse::xml(StatusCode::Ok, xml)
+    }
+}
+
+/// An object or a common prefix in a `ListObjectsV2` response.
+#[derive(Debug)]
+enum Entry<'a> {
+    Object(&'a str, &'a Object),
+    CommonPrefix(String),
+}
+
+impl<'a> Entry<'a> {
+    fn name(&self) -> &str {
+        match *self {
+            Entry::Object(key, _) => key,
+            Entry::CommonPrefix(ref prefix) => prefix,
+        }
+    }
+
+    fn to_xml(&self) -> String {
+        match *self {
+            Entry::Object(key, object) => {
+                format!(
+                    "<Contents><Key>{}</Key><LastModified>{}</LastModified><ETag>{}</ETag>\
+                     <Size>{}</Size><StorageClass>STANDARD</StorageClass></Contents>",
+                    escape_xml(key),
+                    object.last_modified_iso8601,
+                    escape_xml(&object.e_tag),
+                    object.body.len()
+                )
+            }
+            Entry::CommonPrefix(ref prefix) => {
+                format!("<CommonPrefixes><Prefix>{}</Prefix></CommonPrefixes>", escape_xml(prefix))
+            }
+        }
+    }
+}
+
+impl<'a> PartialEq for Entry<'a> {
+    fn eq(&self, other: &Entry<'a>) -> bool {
+        self.name() == other.name()
+    }
+}
+
+/// The unescaped text of every `name` element in a request body, which needn't be parsed more
+/// thoroughly than that.
+fn elements(xml: &str, name: &str) -> Vec<String> {
+    let start_tag = format!("<{}>", name);
+    let end_tag = format!("</{}>", name);
+
+    xml.split(&start_tag[..]).skip(1).filter_map(|rest| {
+        rest.find(&end_tag[..]).map(|end| {
+            rest[..end]
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&")
+        })
+    }).collect()
+}
+
+fn not_implemented(operation: &str, resource: &str) -> MockResponse {
+    let message = format!("{} is not supported by the mock server.", operation);
+
+    error(StatusCode::NotImplemented, "NotImplemented", &message, resource)
+}
+
+fn error(status: StatusCode, code: &str, message: &str, resource: &str) -> MockResponse {
+    MockResponse::xml(status, format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Error><Code>{}</Code><Message>{}</Message>\
+         <Resource>{}</Resource><RequestId>{}</RequestId></Error>",
+        code,
+        escape_xml(message),
+        escape_xml(resource),
+        REQUEST_ID
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str;
+
+    use hyper::method::Method;
+    use hyper::status::StatusCode;
+
+    use server::MockRequest;
+    use super::Buckets;
+
+    fn request(method: Method, path: &str, params: &[(&str, &str)], body: &[u8]) -> MockRequest {
+        MockRequest {
+            method: method,
+            path: path.to_owned(),
+            content_type: None,
+            headers: Vec::new(),
+            params: params.iter().map(|&(key, value)| (key.to_owned(), value.to_owned())).collect(),
+            body: body.to_vec(),
+        }
+    }
+
+    fn list(buckets: &mut Buckets, params: &[(&str, &str)]) -> String {
+        let mut params = params.to_vec();
+        params.push(("list-type", "2"));
+
+        let response = buckets.dispatch(&request(Method::Get, "/bucket", &params, b""));
+        assert_eq!(response.status, StatusCode::Ok);
+
+        String::from_utf8(response.body).unwrap()
+    }
+
+    #[test]
+    fn put_get_and_delete() {
+        let mut buckets = Buckets::default();
+
+        let response = buckets.dispatch(&request(Method::Put, "/bucket/a/b.txt", &[], b"hello\n"));
+        assert_eq!(response.status, StatusCode::Ok);
+        assert_eq!(response.headers[0], ("ETag", "\"b1946ac92492d2347c6235b4d2611184\"".to_owned()));
+
+        let response = buckets.dispatch(&request(Method::Get, "/bucket/a/b.txt", &[], b""));
+        assert_eq!(response.status, StatusCode::Ok);
+        assert_eq!(response.body, b"hello\n".to_vec());
+
+        let response = buckets.dispatch(&request(Method::Delete, "/bucket/a/b.txt", &[], b""));
+        assert_eq!(response.status, StatusCode::NoContent);
+
+        let response = buckets.dispatch(&request(Method::Get, "/bucket/a/b.txt", &[], b""));
+        assert_eq!(response.status, StatusCode::NotFound);
+        assert!(str::from_utf8(&response.body).unwrap().contains("<Code>NoSuchKey</Code>"));
+    }
+
+    #[test]
+    fn delete_objects_removes_each_key() {
+        let mut buckets = Buckets::default();
+
+        for key in &["a & b", "c", "d"] {
+            buckets.put("bucket", key, Vec::new(), None);
+        }
+
+        let body = b"<Delete><Quiet>true</Quiet><Object><Key>a &amp; b</Key></Object>\
+                     <Object><Key>c</Key></Object></Delete>";
+        let response = buckets.dispatch(&request(Method::Post, "/bucket", &[("delete", "")], body));
+        assert_eq!(response.status, StatusCode::Ok);
+        assert!(!str::from_utf8(&response.body).unwrap().contains("<Deleted>"));
+
+        assert_eq!(buckets.objects("bucket").unwrap().keys().collect::<Vec<_>>(), vec!["d"]);
+        assert_eq!(buckets.operations, vec!["DeleteObjects".to_owned()]);
+    }
+
+    #[test]
+    fn list_pages_with_continuation_tokens() {
+        let mut buckets = Buckets::default();
+
+        for key in &["logs/1", "logs/2", "logs/3", "other"] {
+            buckets.put("bucket", key, Vec::new(), None);
+        }
+
+        let first = list(&mut buckets, &[("prefix", "logs/"), ("max-keys", "2")]);
+        assert!(first.contains("<KeyCount>2</KeyCount>"));
+        assert!(first.contains("<IsTruncated>true</IsTruncated>"));
+        assert!(first.contains("<Key>logs/2</Key>"));
+        assert!(first.contains("<NextContinuationToken>logs/2</NextContinuationToken>"));
+
+        let second = list(&mut buckets, &[
+            ("prefix", "logs/"),
+            ("max-keys", "2"),
+            ("continuation-token", "logs/2"),
+        ]);
+        assert!(second.contains("<KeyCount>1</KeyCount>"));
+        assert!(second.contains("<Key>logs/3</Key>"));
+        assert!(second.contains("<IsTruncated>false</IsTruncated>"));
+        assert!(!second.contains("NextContinuationToken"));
+    }
+
+    #[test]
+    fn list_groups_keys_by_delimiter() {
+        let mut buckets = Buckets::default();
+
+        for key in &["2016/05/01.log", "2016/05/02.log", "2016/06/01.log", "README"] {
+            buckets.put("bucket", key, Vec::new(), None);
+        }
+
+        let result = list(&mut buckets, &[("delimiter", "/")]);
+        assert!(result.contains("<KeyCount>2</KeyCount>"));
+        assert!(result.contains("<CommonPrefixes><Prefix>2016/</Prefix></CommonPrefixes>"));
+        assert!(result.contains("<Key>README</Key>"));
+
+        let result = list(&mut buckets, &[("prefix", "2016/"), ("delimiter", "/")]);
+        assert!(result.contains("<Prefix>2016/05/</Prefix>"));
+        assert!(result.contains("<Prefix>2016/06/</Prefix>"));
+    }
+}
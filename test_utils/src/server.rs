@@ -0,0 +1,181 @@
+//! Running the mock servers, and the parts of the AWS wire protocols they share.
+
+use std::io::Read;
+
+use hyper::header::ContentType;
+use hyper::method::Method;
+use hyper::server::{Handler, Listening, Request, Response, Server};
+use hyper::status::StatusCode;
+use hyper::uri::RequestUri;
+use openssl::crypto::hash::Type::MD5;
+use openssl::crypto::hash::hash;
+use rustc_serialize::hex::ToHex;
+use url::form_urlencoded;
+use url::percent_encoding::lossy_utf8_percent_decode;
+
+/// A server handling requests on the loopback interface until it's dropped.
+pub struct MockServer {
+    listening: Listening,
+}
+
+impl MockServer {
+    /// Binds to a free port and starts handling requests in the background.
+    pub fn start<H>(handler: H) -> MockServer where H: Handler + 'static {
+        let server = Server::http("127.0.0.1:0").expect("Failed to bind mock server");
+
+        MockServer {
+            listening: server.handle(handler).expect("Failed to start mock server"),
+        }
+    }
+
+    /// The `host:port` the server is listening on.
+    pub fn hostname(&self) -> String {
+        format!("127.0.0.1:{}", self.listening.socket.port())
+    }
+
+    /// The plain HTTP URL of the server.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}", self.hostname())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        let _ = self.listening.close();
+    }
+}
+
+/// The parts of a request the mock servers look at.
+#[derive(Debug)]
+pub struct MockRequest {
+    pub method: Method,
+    /// The percent-decoded path, without the query string.
+    pub path: String,
+    pub content_type: Option<String>,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    /// The query string parameters, followed by those of a form-encoded body.
+    pub params: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    pub fn read(mut request: Request) -> MockRequest {
+        let mut body = Vec::new();
+        let _ = request.read_to_end(&mut body);
+
+        let uri = match request.uri {
+            RequestUri::AbsolutePath(ref path) => path.clone(),
+            _ => "/".to_owned(),
+        };
+
+        let (path, query) = match uri.find('?') {
+            Some(index) => (&uri[..index], &uri[index + 1..]),
+            None => (&uri[..], ""),
+        };
+
+        let mut params = form_urlencoded::parse(query.as_bytes());
+        let content_type = request.headers.get::<ContentType>().map(|content_type| {
+            format!("{}", content_type.0)
+        });
+
+        let is_form = content_type.as_ref().map_or(false, |content_type| {
+            content_type.starts_with("application/x-www-form-urlencoded")
+        });
+
+        if is_form {
+            params.extend(form_urlencoded::parse(&body));
+        }
+
+        let headers = request.headers.iter().map(|header| {
+            (header.name().to_lowercase(), header.value_string())
+        }).collect();
+
+        MockRequest {
+            method: request.method.clone(),
+            path: lossy_utf8_percent_decode(path.as_bytes()),
+            content_type: content_type,
+            headers: headers,
+            params: params,
+            body: body,
+        }
+    }
+
+    /// Returns the value of the header `name`, which must be lowercase.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|&&(ref key, _)| key == name).map(|&(_, ref value)| &value[..])
+    }
+
+    /// Returns the first value of the parameter `name`.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|&&(ref key, _)| key == name).map(|&(_, ref value)| &value[..])
+    }
+}
+
+/// What a mock server sends back.
+#[derive(Debug)]
+pub struct MockResponse {
+    pub status: StatusCode,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn new(status: StatusCode, body: Vec<u8>) -> MockResponse {
+        MockResponse {
+            status: status,
+            headers: Vec::new(),
+            body: body,
+        }
+    }
+
+    /// A response with an XML body.
+    pub fn xml(status: StatusCode, body: String) -> MockResponse {
+        MockResponse::new(status, body.into_bytes()).with_header("Content-Type", "text/xml")
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: &str) -> MockResponse {
+        self.headers.push((name, value.to_owned()));
+        self
+    }
+
+    pub fn send(self, mut response: Response) {
+        *response.status_mut() = self.status;
+
+        for (name, value) in self.headers {
+            response.headers_mut().set_raw(name, vec![value.into_bytes()]);
+        }
+
+        let _ = response.send(&self.body);
+    }
+}
+
+/// Escapes the characters that can't appear as-is in XML text.
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// The MD5 digest of `bytes` as lowercase hex, as used in ETags and SQS message digests.
+pub fn md5_hex(bytes: &[u8]) -> String {
+    hash(MD5, bytes).to_hex()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_xml, md5_hex};
+
+    #[test]
+    fn escapes_xml() {
+        assert_eq!(escape_xml("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;");
+    }
+
+    #[test]
+    fn md5_digest() {
+        assert_eq!(md5_hex(b"hello\n"), "b1946ac92492d2347c6235b4d2611184");
+    }
+}
@@ -0,0 +1,321 @@
+//! An in-memory stand-in for Amazon SQS.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+
+use server::{MockRequest, MockResponse, MockServer, escape_xml, md5_hex};
+
+/// The account ID used in queue URLs.
+const ACCOUNT_ID: &'static str = "123456789012";
+
+const NAMESPACE: &'static str = "http://queue.amazonaws.com/doc/2012-11-05/";
+
+const REQUEST_ID: &'static str = "b6633655-283d-45b4-aee4-4e84e0ae6afa";
+
+/// The most messages a single `ReceiveMessage` call can return.
+const MAX_RECEIVED_MESSAGES: usize = 10;
+
+/// A mock SQS server supporting `SendMessage`, `ReceiveMessage` and `DeleteMessage`.
+///
+/// Queues are created the first time they're used. A received message stays in its queue,
+/// hidden from further `ReceiveMessage` calls, until it's deleted; unlike SQS, its visibility
+/// timeout never expires.
+pub struct MockSqsServer {
+    server: MockServer,
+    queues: Arc<Mutex<Queues>>,
+}
+
+impl MockSqsServer {
+    /// Starts a server on a free port.
+    pub fn new() -> MockSqsServer {
+        let queues = Arc::new(Mutex::new(Queues::default()));
+
+        MockSqsServer {
+            server: MockServer::start(SqsHandler { queues: queues.clone() }),
+            queues: queues,
+        }
+    }
+
+    /// The URL to pass to `SqsClient::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// The URL of the queue `queue_name` on this server.
+    pub fn queue_url(&self, queue_name: &str) -> String {
+        format!("http://{}/{}/{}", self.hostname(), ACCOUNT_ID, queue_name)
+    }
+
+    /// Adds a message to the queue `queue_name`, as `SendMessage` would.
+    pub fn send_message(&self, queue_name: &str, body: &str) {
+        self.queues.lock().unwrap().send(queue_name, body);
+    }
+
+    /// The bodies of the messages in the queue `queue_name` that haven't been deleted, in the
+    /// order they were sent.
+    pub fn messages(&self, queue_name: &str) -> Vec<String> {
+        self.queues.lock().unwrap().queues.get(queue_name).map_or(Vec::new(), |messages| {
+            messages.iter().map(|message| message.body.clone()).collect()
+        })
+    }
+}
+
+impl Default for MockSqsServer {
+    fn default() -> MockSqsServer {
+        MockSqsServer::new()
+    }
+}
+
+struct SqsHandler {
+    queues: Arc<Mutex<Queues>>,
+}
+
+impl Handler for SqsHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        self.queues.lock().unwrap().dispatch(&request).send(response);
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Message {
+    id: String,
+    body: String,
+    receipt_handle: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Queues {
+    queues: HashMap<String, Vec<Message>>,
+    next_id: u64,
+}
+
+impl Queues {
+    fn dispatch(&mut self, request: &MockRequest) -> MockResponse {
+        let action = request.param("Action").unwrap_or("");
+
+        let queue_name = match request.param("QueueUrl") {
+            Some(queue_url) => queue_url.rsplit('/').next().unwrap_or("").to_owned(),
+            None => return missing_parameter("QueueUrl"),
+        };
+
+        match action {
+            "SendMessage" => {
+                match request.param("MessageBody") {
+                    Some(body) => {
+                        let id = self.send(&queue_name, body);
+                        let result = format!(
+                            "<MD5OfMessageBody>{}</MD5OfMessageBody><MessageId>{}</MessageId>",
+                            md5_hex(body.as_bytes()),
+                            id
+                        );
+
+                        success(action, Some(&result))
+                    }
+                    None => missing_parameter("MessageBody"),
+                }
+            }
+            "ReceiveMessage" => {
+                let limit = request.param("MaxNumberOfMessages")
+                    .and_then(|limit| limit.parse::<usize>().ok())
+                    .map_or(1, |limit| cmp::min(limit, MAX_RECEIVED_MESSAGES));
+
+                let result = self.receive(&queue_name, limit).iter().map(|message| {
+                    format!(
+                        "<Message><MessageId>{}</MessageId><ReceiptHandle>{}</ReceiptHandle>\
+                         <MD5OfBody>{}</MD5OfBody><Body>{}</Body></Message>",
+                        message.id,
+                        escape_xml(message.receipt_handle.as_ref().unwrap()),
+                        md5_hex(message.body.as_bytes()),
+                        escape_xml(&message.body)
+                    )
+                }).collect::<String>();
+
+                success(action, Some(&result))
+            }
+            "DeleteMessage" => {
+                let receipt_handle = match request.param("ReceiptHandle") {
+                    Some(receipt_handle) => receipt_handle,
+                    None => return missing_parameter("ReceiptHandle"),
+                };
+
+                if self.delete(&queue_name, receipt_handle) {
+                    success(action, None)
+                } else {
+                    error("ReceiptHandleIsInvalid", "The input receipt handle is invalid.")
+                }
+            }
+            _ => {
+                error("InvalidAction", &format!("The action {} is not valid for this endpoint.", action))
+            }
+        }
+    }
+
+    fn send(&mut self, queue_name: &str, body: &str) -> String {
+        self.next_id += 1;
+        let id = format!("00000000-0000-4000-8000-{:012}", self.next_id);
+
+        self.queues.entry(queue_name.to_owned()).or_insert_with(Vec::new).push(Message {
+            id: id.clone(),
+            body: body.to_owned(),
+            receipt_handle: None,
+        });
+
+        id
+    }
+
+    /// Hides up to `limit` visible messages, giving each a new receipt handle, and returns them.
+    fn receive(&mut self, queue_name: &str, limit: usize) -> Vec<Message> {
+        let next_id = &mut self.next_id;
+        let messages = self.queues.entry(queue_name.to_owned()).or_insert_with(Vec::new);
+        let mut received = Vec::new();
+
+        let visible = messages.iter_mut().filter(|message| message.receipt_handle.is_none());
+
+        for message in visible.take(limit) {
+            *next_id += 1;
+            message.receipt_handle = Some(format!("{}#{}", message.id, next_id));
+            received.push(message.clone());
+        }
+
+        received
+    }
+
+    fn delete(&mut self, queue_name: &str, receipt_handle: &str) -> bool {
+        let messages = match self.queues.get_mut(queue_name) {
+            Some(messages) => messages,
+            None => return false,
+        };
+
+        let count = messages.len();
+        messages.retain(|message| message.receipt_handle.as_ref().map_or(true, |h| h != receipt_handle));
+
+        messages.len() < count
+    }
+}
+
+fn success(action: &str, result: Option<&str>) -> MockResponse {
+    let result = match result {
+        Some(result) => format!("<{0}Result>{1}</{0}Result>", action, result),
+        None => String::new(),
+    };
+
+    MockResponse::xml(StatusCode::Ok, format!(
+        "<?xml version=\"1.0\"?><{action}Response xmlns=\"{namespace}\">{result}\
+         <ResponseMetadata><RequestId>{request_id}</RequestId></ResponseMetadata></{action}Response>",
+        action = action,
+        namespace = NAMESPACE,
+        result = result,
+        request_id = REQUEST_ID,
+    ))
+}
+
+fn error(code: &str, message: &str) -> MockResponse {
+    MockResponse::xml(StatusCode::BadRequest, format!(
+        "<?xml version=\"1.0\"?><ErrorResponse xmlns=\"{}\">\
+         <Error><Type>Sender</Type><Code>{}</Code><Message>{}</Message><Detail/></Error>\
+         <RequestId>{}</RequestId></ErrorResponse>",
+        NAMESPACE,
+        code,
+        escape_xml(message),
+        REQUEST_ID,
+    ))
+}
+
+fn missing_parameter(name: &str) -> MockResponse {
+    error("MissingParameter", &format!("The request must contain the parameter {}.", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str;
+
+    use hyper::method::Method;
+    use hyper::status::StatusCode;
+
+    use server::MockRequest;
+    use super::Queues;
+
+    fn request(params: &[(&str, &str)]) -> MockRequest {
+        MockRequest {
+            method: Method::Post,
+            path: "/".to_owned(),
+            content_type: Some("application/x-www-form-urlencoded".to_owned()),
+            headers: Vec::new(),
+            params: params.iter().map(|&(key, value)| (key.to_owned(), value.to_owned())).collect(),
+            body: Vec::new(),
+        }
+    }
+
+    const QUEUE_URL: &'static str = "http://127.0.0.1:1234/123456789012/jobs";
+
+    #[test]
+    fn send_receive_and_delete() {
+        let mut queues = Queues::default();
+
+        let response = queues.dispatch(&request(&[
+            ("Action", "SendMessage"),
+            ("QueueUrl", QUEUE_URL),
+            ("MessageBody", "<hello>"),
+        ]));
+        assert_eq!(response.status, StatusCode::Ok);
+        assert!(str::from_utf8(&response.body).unwrap().contains(
+            "<SendMessageResult><MD5OfMessageBody>"
+        ));
+
+        let response = queues.dispatch(&request(&[("Action", "ReceiveMessage"), ("QueueUrl", QUEUE_URL)]));
+        let body = str::from_utf8(&response.body).unwrap().to_owned();
+        assert!(body.contains("<Body>&lt;hello&gt;</Body>"));
+        assert!(body.contains("<ReceiptHandle>00000000-0000-4000-8000-000000000001#2</ReceiptHandle>"));
+
+        let response = queues.dispatch(&request(&[
+            ("Action", "DeleteMessage"),
+            ("QueueUrl", QUEUE_URL),
+            ("ReceiptHandle", "00000000-0000-4000-8000-000000000001#2"),
+        ]));
+        assert_eq!(response.status, StatusCode::Ok);
+        assert!(queues.queues["jobs"].is_empty());
+    }
+
+    #[test]
+    fn received_messages_are_hidden_until_deleted() {
+        let mut queues = Queues::default();
+        queues.send("jobs", "first");
+        queues.send("jobs", "second");
+        queues.send("jobs", "third");
+
+        assert_eq!(queues.receive("jobs", 2).len(), 2);
+
+        let remaining = queues.receive("jobs", 10);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].body, "third");
+        assert!(queues.receive("jobs", 10).is_empty());
+        assert_eq!(queues.queues["jobs"].len(), 3);
+    }
+
+    #[test]
+    fn errors() {
+        let mut queues = Queues::default();
+
+        let response = queues.dispatch(&request(&[("Action", "SendMessage")]));
+        assert_eq!(response.status, StatusCode::BadRequest);
+        assert!(str::from_utf8(&response.body).unwrap().contains("<Code>MissingParameter</Code>"));
+
+        let response = queues.dispatch(&request(&[
+            ("Action", "DeleteMessage"),
+            ("QueueUrl", QUEUE_URL),
+            ("ReceiptHandle", "bogus"),
+        ]));
+        assert!(str::from_utf8(&response.body).unwrap().contains("<Code>ReceiptHandleIsInvalid</Code>"));
+    }
+}
@@ -0,0 +1,282 @@
+//! An in-memory stand-in for the IP set operations of the AWS WAF Classic API.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use hyper::server::{Handler, Request, Response};
+use hyper::status::StatusCode;
+use rustc_serialize::json::Json;
+
+use server::{MockRequest, MockResponse, MockServer};
+
+/// A mock WAF Classic server supporting `GetChangeToken`, `CreateIPSet`, `UpdateIPSet` and
+/// `ListIPSets`.
+///
+/// Like WAF, each mutating request must carry a change token from `GetChangeToken` which
+/// hasn't been used yet. Changes take effect immediately, so there's no `GetChangeTokenStatus`.
+pub struct MockWafServer {
+    server: MockServer,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockWafServer {
+    /// Starts a server on a free port.
+    pub fn new() -> MockWafServer {
+        let state = Arc::new(Mutex::new(State::default()));
+
+        MockWafServer {
+            server: MockServer::start(WafHandler { state: state.clone() }),
+            state: state,
+        }
+    }
+
+    /// The URL to pass to `WafClient::set_hostname`.
+    pub fn endpoint(&self) -> String {
+        self.server.endpoint()
+    }
+
+    /// The `host:port` requests to the server are sent to.
+    pub fn hostname(&self) -> String {
+        self.server.hostname()
+    }
+
+    /// The operations requested so far, such as `GetChangeToken`, in the order they arrived.
+    pub fn operations(&self) -> Vec<String> {
+        self.state.lock().unwrap().operations.clone()
+    }
+
+    /// The `Type` and `Value` of each descriptor in an IP set, in the order they were inserted.
+    pub fn ip_set_descriptors(&self, ip_set_id: &str) -> Vec<(String, String)> {
+        self.state.lock().unwrap().ip_sets.iter()
+            .find(|ip_set| ip_set.id == ip_set_id)
+            .map_or_else(Vec::new, |ip_set| ip_set.descriptors.clone())
+    }
+}
+
+impl Default for MockWafServer {
+    fn default() -> MockWafServer {
+        MockWafServer::new()
+    }
+}
+
+struct WafHandler {
+    state: Arc<Mutex<State>>,
+}
+
+impl Handler for WafHandler {
+    fn handle(&self, request: Request, response: Response) {
+        let request = MockRequest::read(request);
+
+        self.state.lock().unwrap().dispatch(&request).send(response);
+    }
+}
+
+#[derive(Debug)]
+struct IpSet {
+    id: String,
+    name: String,
+    descriptors: Vec<(String, String)>,
+}
+
+impl IpSet {
+    fn to_json(&self, with_descriptors: bool) -> Json {
+        let mut ip_set = BTreeMap::new();
+        ip_set.insert("IPSetId".to_owned(), Json::String(self.id.clone()));
+        ip_set.insert("Name".to_owned(), Json::String(self.name.clone()));
+
+        if with_descriptors {
+            let descriptors = self.descriptors.iter().map(|&(ref descriptor_type, ref value)| {
+                let mut descriptor = BTreeMap::new();
+                descriptor.insert("Type".to_owned(), Json::String(descriptor_type.clone()));
+                descriptor.insert("Value".to_owned(), Json::String(value.clone()));
+                Json::Object(descriptor)
+            }).collect();
+
+            ip_set.insert("IPSetDescriptors".to_owned(), Json::Array(descriptors));
+        }
+
+        Json::Object(ip_set)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    operations: Vec<String>,
+    /// The change tokens which have been issued but not used.
+    change_tokens: HashSet<String>,
+    ip_sets: Vec<IpSet>,
+    next_id: u64,
+}
+
+impl State {
+    fn dispatch(&mut self, request: &MockRequest) -> MockResponse {
+        let operation = match request.header("x-amz-target").and_then(|target| target.split('.').nth(1)) {
+            Some(operation) => operation.to_owned(),
+            None => return error("WAFInvalidOperationException", "The request has no X-Amz-Target."),
+        };
+        self.operations.push(operation.clone());
+
+        let body = String::from_utf8_lossy(&request.body);
+        let input = match Json::from_str(&body) {
+            Ok(input) => input,
+            Err(_) => return error("WAFInvalidParameterException", "The request body is not valid JSON."),
+        };
+
+        if operation == "GetChangeToken" {
+            let change_token = self.new_id("change-token");
+            self.change_tokens.insert(change_token.clone());
+
+            return success(vec![("ChangeToken", Json::String(change_token))]);
+        }
+
+        if operation == "ListIPSets" {
+            let ip_sets = self.ip_sets.iter().map(|ip_set| ip_set.to_json(false)).collect();
+
+            return success(vec![("IPSets", Json::Array(ip_sets))]);
+        }
+
+        let change_token = match string(&input, "ChangeToken") {
+            Some(change_token) => change_token,
+            None => return missing_parameter("ChangeToken"),
+        };
+
+        match &operation[..] {
+            "CreateIPSet" => {
+                let name = match string(&input, "Name") {
+                    Some(name) => name,
+                    None => return missing_parameter("Name"),
+                };
+
+                if !self.change_tokens.remove(&change_token) {
+                    return stale_change_token();
+                }
+
+                let ip_set = IpSet { id: self.new_id("ip-set"), name: name, descriptors: Vec::new() };
+                let response = success(vec![
+                    ("ChangeToken", Json::String(change_token)),
+                    ("IPSet", ip_set.to_json(true)),
+                ]);
+                self.ip_sets.push(ip_set);
+
+                response
+            }
+            "UpdateIPSet" => {
+                let ip_set_id = match string(&input, "IPSetId") {
+                    Some(ip_set_id) => ip_set_id,
+                    None => return missing_parameter("IPSetId"),
+                };
+                let updates = input.find("Updates").and_then(Json::as_array).cloned().unwrap_or_else(Vec::new);
+
+                if !self.ip_sets.iter().any(|ip_set| ip_set.id == ip_set_id) {
+                    return error("WAFNonexistentItemException", "The IP set does not exist.");
+                }
+
+                if !self.change_tokens.remove(&change_token) {
+                    return stale_change_token();
+                }
+
+                let ip_set = self.ip_sets.iter_mut().find(|ip_set| ip_set.id == ip_set_id).unwrap();
+
+                for update in updates {
+                    let descriptor = match update.find("IPSetDescriptor") {
+                        Some(descriptor) => descriptor,
+                        None => continue,
+                    };
+                    let descriptor = (
+                        string(descriptor, "Type").unwrap_or_else(String::new),
+                        string(descriptor, "Value").unwrap_or_else(String::new),
+                    );
+
+                    match string(&update, "Action").as_ref().map(|action| &action[..]) {
+                        Some("INSERT") => ip_set.descriptors.push(descriptor),
+                        Some("DELETE") => ip_set.descriptors.retain(|existing| *existing != descriptor),
+                        _ => {}
+                    }
+                }
+
+                success(vec![("ChangeToken", Json::String(change_token))])
+            }
+            _ => error("WAFInvalidOperationException", "The operation is not supported."),
+        }
+    }
+
+    fn new_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+
+        format!("{}-{:08}", prefix, self.next_id)
+    }
+}
+
+fn string(object: &Json, key: &str) -> Option<String> {
+    object.find(key).and_then(Json::as_string).map(str::to_owned)
+}
+
+fn success(fields: Vec<(&str, Json)>) -> MockResponse {
+    let body = fields.into_iter().map(|(key, value)| (key.to_owned(), value)).collect();
+
+    json(StatusCode::Ok, Json::Object(body))
+}
+
+fn error(error_type: &str, message: &str) -> MockResponse {
+    let mut body = BTreeMap::new();
+    body.insert("__type".to_owned(), Json::String(error_type.to_owned()));
+    body.insert("message".to_owned(), Json::String(message.to_owned()));
+
+    json(StatusCode::BadRequest, Json::Object(body))
+}
+
+fn missing_parameter(name: &str) -> MockResponse {
+    error("WAFInvalidParameterException", &format!("The request must contain the parameter {}.", name))
+}
+
+fn stale_change_token() -> MockResponse {
+    error("WAFStaleDataException", "The change token has already been used or was never issued.")
+}
+
+fn json(status: StatusCode, body: Json) -> MockResponse {
+    MockResponse::new(status, body.to_string().into_bytes())
+        .with_header("Content-Type", "application/x-amz-json-1.1")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str;
+
+    use hyper::method::Method;
+    use hyper::status::StatusCode;
+    use rustc_serialize::json::Json;
+
+    use server::MockRequest;
+    use super::State;
+
+    fn send(state: &mut State, operation: &str, body: &str) -> (StatusCode, Json) {
+        let response = state.dispatch(&MockRequest {
+            method: Method::Post,
+            path: "/".to_owned(),
+            content_type: Some("application/x-amz-json-1.1".to_owned()),
+            headers: vec![("x-amz-target".to_owned(), format!("AWSWAF_20150824.{}", operation))],
+            params: Vec::new(),
+            body: body.as_bytes().to_vec(),
+        });
+
+        (response.status, Json::from_str(str::from_utf8(&response.body).unwrap()).unwrap())
+    }
+
+    #[test]
+    fn change_tokens_can_only_be_used_once() {
+        let mut state = State::default();
+
+        let (_, body) = send(&mut state, "GetChangeToken", "{}");
+        let change_token = body.find("ChangeToken").unwrap().as_string().unwrap().to_owned();
+        let create = format!("{{\"Name\":\"blocked\",\"ChangeToken\":\"{}\"}}", change_token);
+
+        let (status, body) = send(&mut state, "CreateIPSet", &create);
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(body.find_path(&["IPSet", "Name"]).unwrap().as_string(), Some("blocked"));
+
+        let (status, body) = send(&mut state, "CreateIPSet", &create);
+        assert_eq!(status, StatusCode::BadRequest);
+        assert_eq!(body.find("__type").unwrap().as_string(), Some("WAFStaleDataException"));
+        assert_eq!(state.ip_sets.len(), 1);
+    }
+}
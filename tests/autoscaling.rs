@@ -0,0 +1,53 @@
+#![cfg(feature = "autoscaling")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::autoscaling::AutoScalingClient;
+use rusoto_test_utils::MockAutoScalingServer;
+
+fn autoscaling_client(server: &MockAutoScalingServer) -> AutoScalingClient<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut autoscaling = AutoScalingClient::new(provider, Region::UsEast1);
+    autoscaling.set_hostname(Some(server.endpoint()));
+    autoscaling
+}
+
+#[test]
+fn describe_all_auto_scaling_groups_reads_every_page() {
+    let server = MockAutoScalingServer::new();
+    let mut autoscaling = autoscaling_client(&server);
+
+    for (index, name) in ["web", "api", "workers", "batch", "cron"].iter().enumerate() {
+        server.add_auto_scaling_group(name, index as i32 + 1);
+    }
+
+    let groups = autoscaling.describe_all_auto_scaling_groups(None).unwrap();
+    let names = groups.iter().map(|group| &group.auto_scaling_group_name[..]).collect::<Vec<&str>>();
+    assert_eq!(names, vec!["web", "api", "workers", "batch", "cron"]);
+    assert_eq!(groups[4].desired_capacity, 5);
+    assert_eq!(groups[4].instances.as_ref().unwrap().len(), 5);
+
+    assert_eq!(server.next_tokens(), vec![None, Some("2".to_owned()), Some("4".to_owned())]);
+}
+
+#[test]
+fn describe_all_auto_scaling_groups_by_name() {
+    let server = MockAutoScalingServer::new();
+    let mut autoscaling = autoscaling_client(&server);
+
+    for name in &["web", "api", "workers"] {
+        server.add_auto_scaling_group(name, 2);
+    }
+
+    let names = vec!["workers".to_owned(), "web".to_owned()];
+    let groups = autoscaling.describe_all_auto_scaling_groups(Some(names)).unwrap();
+    let names = groups.iter().map(|group| &group.auto_scaling_group_name[..]).collect::<Vec<&str>>();
+    assert_eq!(names, vec!["web", "workers"]);
+    assert_eq!(server.next_tokens(), vec![None]);
+}
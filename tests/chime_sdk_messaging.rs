@@ -0,0 +1,101 @@
+#![cfg(feature = "chime_sdk_messaging")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+extern crate time;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::chime_sdk_messaging::{
+    ASCENDING,
+    ChimeSdkMessagingClient,
+    DESCENDING,
+    PERSISTENT,
+    PRIVATE,
+    RESTRICTED,
+    STANDARD,
+};
+use rusoto_test_utils::MockChimeSdkMessagingServer;
+
+const APP_INSTANCE_ARN: &'static str = "arn:aws:chime:us-east-1:123456789012:app-instance/0b3a8f1e";
+const ALICE: &'static str = "arn:aws:chime:us-east-1:123456789012:app-instance/0b3a8f1e/user/alice";
+const BOB: &'static str = "arn:aws:chime:us-east-1:123456789012:app-instance/0b3a8f1e/user/bob";
+
+fn chime_client(server: &MockChimeSdkMessagingServer) -> ChimeSdkMessagingClient<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut chime = ChimeSdkMessagingClient::new(provider, Region::UsEast1);
+    chime.set_hostname(Some(server.endpoint()));
+    chime
+}
+
+#[test]
+fn requests_carry_the_chime_bearer() {
+    let server = MockChimeSdkMessagingServer::new();
+    let mut chime = chime_client(&server);
+
+    let channel_arn = chime.add_channel(APP_INSTANCE_ARN, "support", RESTRICTED, PRIVATE, None, ALICE).unwrap();
+    assert!(channel_arn.starts_with(&format!("{}/channel/", APP_INSTANCE_ARN)));
+
+    let sent = chime.post_channel_message(&channel_arn, "Hello", STANDARD, PERSISTENT, None, BOB, None).unwrap();
+    assert_eq!(sent.channel_arn, Some(channel_arn.clone()));
+    assert!(sent.message_id.is_some());
+
+    chime.list_all_channel_messages(&channel_arn, DESCENDING, None, None, ALICE).unwrap();
+
+    assert_eq!(server.bearers(), vec![
+        ("CreateChannel".to_owned(), ALICE.to_owned()),
+        ("SendChannelMessage".to_owned(), BOB.to_owned()),
+        ("ListChannelMessages".to_owned(), ALICE.to_owned()),
+    ]);
+}
+
+#[test]
+fn messages_are_listed_across_pages() {
+    let server = MockChimeSdkMessagingServer::new();
+    let mut chime = chime_client(&server);
+
+    let channel_arn = chime.add_channel(APP_INSTANCE_ARN, "support", RESTRICTED, PRIVATE, None, ALICE).unwrap();
+
+    for content in &["one", "two", "three"] {
+        chime.post_channel_message(&channel_arn, content, STANDARD, PERSISTENT, None, BOB, None).unwrap();
+    }
+
+    let messages = chime.list_all_channel_messages(&channel_arn, ASCENDING, None, None, ALICE).unwrap();
+    let contents = messages.iter().map(|message| message.content.clone().unwrap()).collect::<Vec<String>>();
+    assert_eq!(contents, vec!["one".to_owned(), "two".to_owned(), "three".to_owned()]);
+
+    // The sender of each message is the bearer it was sent on behalf of.
+    let sender = messages[0].sender.as_ref().unwrap();
+    assert_eq!(sender.arn, Some(BOB.to_owned()));
+    assert_eq!(messages[0].aws_type, Some(STANDARD.to_owned()));
+
+    // Two pages of two messages, after the channel was created and the messages were sent.
+    assert_eq!(server.bearers().len(), 1 + 3 + 2);
+}
+
+#[test]
+fn messages_are_listed_between_timestamps() {
+    let server = MockChimeSdkMessagingServer::new();
+    let mut chime = chime_client(&server);
+
+    let channel_arn = chime.add_channel(APP_INSTANCE_ARN, "support", RESTRICTED, PRIVATE, None, ALICE).unwrap();
+    chime.post_channel_message(&channel_arn, "Hello", STANDARD, PERSISTENT, None, BOB, None).unwrap();
+
+    // The mock rejects timestamps which aren't ISO 8601.
+    let now = time::get_time().sec as f64;
+    let messages = chime.list_all_channel_messages(
+        &channel_arn,
+        ASCENDING,
+        Some(now - 3600.0),
+        Some(now + 3600.5),
+        ALICE,
+    ).unwrap();
+    assert_eq!(messages.len(), 1);
+
+    let messages = chime.list_all_channel_messages(&channel_arn, ASCENDING, Some(now + 3600.0), None, ALICE)
+        .unwrap();
+    assert!(messages.is_empty());
+}
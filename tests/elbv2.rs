@@ -0,0 +1,66 @@
+#![cfg(feature = "elbv2")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::elbv2::{ElbV2Client, TargetGroup};
+use rusoto_test_utils::MockElbv2Server;
+
+fn elbv2_client(server: &MockElbv2Server) -> ElbV2Client<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut elbv2 = ElbV2Client::new(provider, Region::UsEast1);
+    elbv2.set_hostname(Some(server.endpoint()));
+    elbv2
+}
+
+#[test]
+fn describe_all_load_balancers_reads_every_page() {
+    let server = MockElbv2Server::new();
+    let mut elbv2 = elbv2_client(&server);
+
+    let arns = ["web", "api", "internal"].iter()
+        .map(|name| server.add_load_balancer(name))
+        .collect::<Vec<String>>();
+
+    let load_balancers = elbv2.describe_all_load_balancers().unwrap();
+    let described = load_balancers.iter()
+        .map(|load_balancer| load_balancer.load_balancer_arn.clone().unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(described, arns);
+    assert_eq!(load_balancers[2].load_balancer_name, Some("internal".to_owned()));
+
+    assert_eq!(server.requests(), vec![
+        ("DescribeLoadBalancers".to_owned(), None),
+        ("DescribeLoadBalancers".to_owned(), Some("2".to_owned())),
+    ]);
+}
+
+#[test]
+fn describe_all_target_groups_of_a_load_balancer() {
+    let server = MockElbv2Server::new();
+    let mut elbv2 = elbv2_client(&server);
+
+    let web = server.add_load_balancer("web");
+    let api = server.add_load_balancer("api");
+    for name in &["web-blue", "web-green", "web-canary"] {
+        server.add_target_group(name, Some(&web));
+    }
+    server.add_target_group("api", Some(&api));
+    server.add_target_group("unused", None);
+
+    let names = |target_groups: Vec<TargetGroup>| {
+        target_groups.into_iter()
+            .map(|target_group| target_group.target_group_name.unwrap())
+            .collect::<Vec<String>>()
+    };
+
+    let target_groups = elbv2.describe_all_target_groups(Some(web)).unwrap();
+    assert_eq!(names(target_groups), vec!["web-blue", "web-green", "web-canary"]);
+    assert_eq!(elbv2.describe_all_target_groups(None).unwrap().len(), 5);
+    assert_eq!(server.requests().len(), 5);
+}
@@ -0,0 +1,77 @@
+extern crate chrono;
+#[macro_use] extern crate log;
+extern crate rusoto;
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, UTC};
+use log::{LogLevel, LogLevelFilter, LogMetadata, LogRecord};
+use rusoto::{AwsCredentials, AwsError, ExpiryStatus, ExpiryWatchProvider, ProvideAwsCredentials};
+
+/// Keeps the warnings logged by Rusoto, so they can be checked.
+struct CapturingLogger {
+    warnings: Arc<Mutex<Vec<String>>>,
+}
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &LogMetadata) -> bool {
+        metadata.level() <= LogLevel::Warn && metadata.target().starts_with("rusoto")
+    }
+
+    fn log(&self, record: &LogRecord) {
+        if self.enabled(record.metadata()) {
+            self.warnings.lock().unwrap().push(format!("{}", record.args()));
+        }
+    }
+}
+
+struct StaticProvider {
+    credentials: AwsCredentials,
+}
+
+impl ProvideAwsCredentials for StaticProvider {
+    fn credentials(&mut self) -> Result<&AwsCredentials, AwsError> {
+        Ok(&self.credentials)
+    }
+}
+
+fn expiring_in(duration: Duration) -> StaticProvider {
+    StaticProvider {
+        credentials: AwsCredentials::new("foo", "bar", None, UTC::now() + duration),
+    }
+}
+
+// The logger is global, so every check that depends on it lives in this one test.
+#[test]
+fn expiry_watch_provider_logs_a_warning() {
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let logger = CapturingLogger { warnings: warnings.clone() };
+    log::set_logger(|max_log_level| {
+        max_log_level.set(LogLevelFilter::Warn);
+        Box::new(logger)
+    }).unwrap();
+
+    // Logged by a test, to show that only Rusoto's warnings are kept.
+    warn!("not from rusoto");
+
+    let mut provider = ExpiryWatchProvider::new(expiring_in(Duration::minutes(3)));
+    assert_eq!(provider.poll(), ExpiryStatus::Valid);
+    assert!(warnings.lock().unwrap().is_empty());
+
+    assert!(provider.credentials().is_ok());
+    assert_eq!(warnings.lock().unwrap().len(), 1);
+
+    assert_eq!(provider.poll(), ExpiryStatus::ExpiringSoon);
+    let logged = warnings.lock().unwrap().clone();
+    assert_eq!(logged.len(), 2);
+    assert!(logged[1].starts_with("AWS credentials expire soon, at "), "{:?}", logged);
+
+    let mut expired = ExpiryWatchProvider::new(expiring_in(Duration::minutes(-1)));
+    assert!(expired.credentials().is_err());
+    assert!(warnings.lock().unwrap()[2].starts_with("AWS credentials expired at "));
+
+    let mut distant = ExpiryWatchProvider::new(expiring_in(Duration::hours(1)));
+    assert!(distant.credentials().is_ok());
+    assert_eq!(distant.poll(), ExpiryStatus::Valid);
+    assert_eq!(warnings.lock().unwrap().len(), 3);
+}
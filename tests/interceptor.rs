@@ -0,0 +1,110 @@
+#![cfg(feature = "sqs")]
+
+extern crate hyper;
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyper::client::Response;
+use rusoto::{Interceptor, ProfileProvider, Region, SignedRequest};
+use rusoto::sqs::{SendMessageRequest, SqsClient};
+use rusoto_test_utils::MockSqsServer;
+
+/// Records the operations and durations of the requests it's called around.
+struct RecordingInterceptor {
+    requests: Mutex<Vec<String>>,
+    durations: Mutex<Vec<Duration>>,
+}
+
+impl RecordingInterceptor {
+    fn new() -> Arc<RecordingInterceptor> {
+        Arc::new(RecordingInterceptor {
+            requests: Mutex::new(Vec::new()),
+            durations: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+impl Interceptor for RecordingInterceptor {
+    fn before_request(&self, request: &mut SignedRequest) {
+        self.requests.lock().unwrap().push(request.params().get("Action").cloned().unwrap_or_else(String::new));
+    }
+
+    fn after_response(&self, _request: &SignedRequest, _response: &Response, duration: Duration) {
+        self.durations.lock().unwrap().push(duration);
+    }
+}
+
+fn provider() -> ProfileProvider {
+    ProfileProvider::with_configuration("tests/sample-data/multiple_profile_credentials", "foo")
+}
+
+#[test]
+fn interceptors_are_called_around_each_request() {
+    let server = MockSqsServer::new();
+    let interceptor = RecordingInterceptor::new();
+
+    let mut sqs = SqsClient::new(provider(), Region::UsEast1);
+    sqs.set_hostname(Some(server.endpoint()));
+    sqs.set_interceptors(vec![interceptor.clone()]);
+
+    let input = SendMessageRequest {
+        queue_url: server.queue_url("jobs"),
+        message_body: "resize".to_owned(),
+        ..Default::default()
+    };
+    sqs.send_message(&input).unwrap();
+
+    assert_eq!(*interceptor.requests.lock().unwrap(), vec!["SendMessage".to_owned()]);
+    {
+        let durations = interceptor.durations.lock().unwrap();
+        assert_eq!(durations.len(), 1);
+        assert!(durations[0] > Duration::new(0, 0));
+    }
+
+    // Another client's requests aren't seen by the interceptor.
+    let mut other = SqsClient::new(provider(), Region::UsEast1);
+    other.set_hostname(Some(server.endpoint()));
+    other.send_message(&input).unwrap();
+
+    // Nor are this client's, once its interceptors are replaced.
+    sqs.set_interceptors(Vec::new());
+    sqs.send_message(&input).unwrap();
+
+    assert_eq!(interceptor.requests.lock().unwrap().len(), 1);
+    assert_eq!(interceptor.durations.lock().unwrap().len(), 1);
+}
+
+#[cfg(feature = "s3")]
+#[test]
+fn redirected_requests_are_intercepted_once() {
+    use rusoto::s3::{DeleteObjectRequest, S3Client, S3Config};
+    use rusoto_test_utils::MockS3Server;
+
+    let wrong_region = MockS3Server::new();
+    let right_region = MockS3Server::new();
+    wrong_region.redirect_bucket("rusoto-test", &right_region.endpoint());
+    right_region.put_object("rusoto-test", "report.csv", b"");
+
+    let interceptor = RecordingInterceptor::new();
+
+    let config = S3Config {
+        force_path_style: true,
+        interceptors: vec![interceptor.clone()],
+        ..S3Config::default()
+    };
+    let mut s3 = S3Client::with_config(provider(), Region::UsEast1, config).unwrap();
+    s3.set_hostname(Some(wrong_region.endpoint()));
+
+    let mut input = DeleteObjectRequest::default();
+    input.bucket = "rusoto-test".to_owned();
+    input.key = "report.csv".to_owned();
+    s3.delete_object(&input).unwrap();
+
+    // `before_request` is called once for the request, and `after_response` for both responses.
+    assert_eq!(interceptor.requests.lock().unwrap().len(), 1);
+    assert_eq!(interceptor.durations.lock().unwrap().len(), 2);
+    assert_eq!(right_region.keys("rusoto-test"), Vec::<String>::new());
+}
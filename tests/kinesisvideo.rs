@@ -0,0 +1,39 @@
+#![cfg(feature = "kinesisvideo")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::kinesisvideo::{KinesisVideoClient, PUT_MEDIA, RELATIVE};
+use rusoto_test_utils::MockKinesisVideoServer;
+
+fn kinesis_video_client(server: &MockKinesisVideoServer) -> KinesisVideoClient<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut kinesis_video = KinesisVideoClient::new(provider, Region::UsWest2);
+    kinesis_video.set_hostname(Some(server.endpoint()));
+    kinesis_video
+}
+
+#[test]
+fn put_media_is_sent_to_the_data_endpoint() {
+    let server = MockKinesisVideoServer::new();
+    let mut kinesis_video = kinesis_video_client(&server);
+
+    assert_eq!(
+        kinesis_video.data_endpoint(Some("front-door".to_owned()), None, PUT_MEDIA).unwrap(),
+        server.data_endpoint()
+    );
+
+    let acks = kinesis_video.put_media("front-door", b"\x1a\x45\xdf\xa3 fragment", RELATIVE, 1514764800.0)
+        .unwrap();
+    assert_eq!(acks.len(), 2);
+    assert_eq!(acks[0].event_type, "RECEIVED");
+    assert_eq!(acks[1].event_type, "PERSISTED");
+    assert_eq!(acks[1].fragment_number, acks[0].fragment_number);
+
+    assert_eq!(server.media(), vec![("front-door".to_owned(), b"\x1a\x45\xdf\xa3 fragment".to_vec())]);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "mediaconvert")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::mediaconvert::{ListQueuesRequest, MediaConvertClient};
+use rusoto_test_utils::MockMediaConvertServer;
+
+#[test]
+fn requests_are_sent_to_the_discovered_endpoint() {
+    let server = MockMediaConvertServer::new();
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut mediaconvert = MediaConvertClient::new(provider, Region::UsWest2);
+    mediaconvert.set_hostname(Some(server.endpoint()));
+    mediaconvert.discover_endpoint().unwrap();
+
+    for _ in 0..2 {
+        let queues = mediaconvert.list_queues(&ListQueuesRequest::default()).unwrap().queues.unwrap();
+        assert_eq!(queues[0].name, "Default");
+    }
+
+    // The endpoint is only looked up once, and the regional endpoint isn't asked for queues.
+    assert_eq!(server.operations(), vec![
+        "regional:DescribeEndpoints".to_owned(),
+        "account:ListQueues".to_owned(),
+        "account:ListQueues".to_owned(),
+    ]);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "qldb_session")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::qldb_session::QldbSessionClient;
+use rusoto_test_utils::MockQldbSessionServer;
+
+fn qldb_session_client(server: &MockQldbSessionServer) -> QldbSessionClient<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut qldb_session = QldbSessionClient::new(provider, Region::UsEast1);
+    qldb_session.set_hostname(Some(server.endpoint()));
+    qldb_session
+}
+
+#[test]
+fn start_session_execute_and_commit() {
+    let server = MockQldbSessionServer::new();
+    let mut qldb_session = qldb_session_client(&server);
+
+    let session_token = qldb_session.start_session("vehicle-registration").unwrap();
+    let transaction_id = qldb_session.start_transaction(&session_token).unwrap();
+
+    let page = qldb_session.execute_statement(
+        &session_token,
+        &transaction_id,
+        "INSERT INTO Vehicle << {'VIN': '1N4AL11D75C109151'} >>",
+        Vec::new(),
+    ).unwrap();
+    assert_eq!(page.values, Some(vec![]));
+    assert!(server.committed_statements("vehicle-registration").is_empty());
+
+    let committed = qldb_session.commit_transaction(&session_token, &transaction_id, vec![0xde, 0xad, 0xbe, 0xef])
+        .unwrap();
+    assert_eq!(committed.transaction_id, Some(transaction_id));
+    assert_eq!(committed.commit_digest, Some(vec![0xde, 0xad, 0xbe, 0xef]));
+    assert_eq!(
+        server.committed_statements("vehicle-registration"),
+        vec!["INSERT INTO Vehicle << {'VIN': '1N4AL11D75C109151'} >>".to_owned()]
+    );
+}
+
+#[test]
+fn transactions_belong_to_their_session() {
+    let server = MockQldbSessionServer::new();
+    let mut qldb_session = qldb_session_client(&server);
+
+    let first = qldb_session.start_session("vehicle-registration").unwrap();
+    let second = qldb_session.start_session("vehicle-registration").unwrap();
+    let transaction_id = qldb_session.start_transaction(&first).unwrap();
+
+    let error = qldb_session.execute_statement(&second, &transaction_id, "SELECT * FROM Vehicle", Vec::new())
+        .unwrap_err();
+    assert!(format!("{}", error).starts_with("InvalidSessionException"));
+}
@@ -1,34 +1,137 @@
 #![cfg(feature = "s3")]
 
-extern crate env_logger;
-#[macro_use]
-extern crate log;
-extern crate time;
-
-#[macro_use]
 extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::s3::{
+    GetObjectRequest,
+    ObjectIdentifier,
+    RequestCharged,
+    RequestPayer,
+    S3Client,
+    S3Config,
+    S3Helper,
+};
+use rusoto_test_utils::MockS3Server;
 
-use rusoto::{AwsError, ChainProvider, Region};
-use rusoto::s3::S3Helper;
+fn s3_helper(server: &MockS3Server) -> S3Helper<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let config = S3Config { force_path_style: true, ..S3Config::default() };
+    let mut s3 = S3Helper::with_config(provider, Region::UsWest2, config).unwrap();
+    s3.set_hostname(Some(server.endpoint()));
+    s3
+}
 
 #[test]
-fn all_s3_tests() {
-    let _ = env_logger::init();
-    info!("s3 integration tests starting up.");
-    let mut s3 = S3Helper::new(ChainProvider::new().unwrap(), Region::UsWest2);
-
-    match s3_list_buckets_tests(&mut s3) {
-        Ok(_) => { info!("Everything worked for S3 list buckets."); },
-        Err(err) => { info!("Got error in s3 list buckets: {}", err); }
+fn put_get_and_delete_object() {
+    let server = MockS3Server::new();
+    let mut s3 = s3_helper(&server);
+
+    let report = b"month,total\n05,42\n";
+
+    s3.put_object("rusoto-test", "reports/2016-05.csv", report).unwrap();
+    assert_eq!(server.object("rusoto-test", "reports/2016-05.csv"), Some(report.to_vec()));
+
+    let object = s3.get_object("rusoto-test", "reports/2016-05.csv").unwrap();
+    assert_eq!(object.body, report.to_vec());
+    assert_eq!(object.content_length, 18);
+    assert_eq!(object.e_tag, "\"a86894b1dc4676d0e9a8536974c43b34\"");
+
+    s3.delete_object("rusoto-test", "reports/2016-05.csv").unwrap();
+    assert_eq!(server.object("rusoto-test", "reports/2016-05.csv"), None);
+    assert!(s3.get_object("rusoto-test", "reports/2016-05.csv").is_err());
+}
+
+#[test]
+fn list_objects_follows_continuation_tokens() {
+    let server = MockS3Server::new();
+    let mut s3 = s3_helper(&server);
+
+    for day in 0..1200 {
+        server.put_object("rusoto-test", &format!("logs/{:04}.log", day), b"");
     }
+    server.put_object("rusoto-test", "other.txt", b"");
+
+    let objects = s3.list_objects_with_prefix("rusoto-test", "logs/").unwrap();
+
+    assert_eq!(objects.len(), 1200);
+    assert_eq!(objects[0].key, "logs/0000.log");
+    assert_eq!(objects[1199].key, "logs/1199.log");
+}
+
+#[test]
+fn get_object_from_requester_pays_bucket() {
+    let server = MockS3Server::new();
+    server.put_object("rusoto-public-dataset", "genomes/chr1.fa", b">chr1\n");
+
+    let mut s3 = s3_client(&server);
+
+    let mut input = GetObjectRequest {
+        bucket: "rusoto-public-dataset".to_owned(),
+        key: "genomes/chr1.fa".to_owned(),
+        ..GetObjectRequest::default()
+    };
+
+    let object = s3.get_object(&input).unwrap();
+    assert_eq!(object.request_charged, None);
+
+    input.request_payer = Some(RequestPayer::Requester);
+    let object = s3.get_object(&input).unwrap();
+    assert_eq!(object.body, b">chr1\n".to_vec());
+    assert_eq!(object.request_charged, Some(RequestCharged::Requester));
+}
+
+fn s3_client(server: &MockS3Server) -> S3Client<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let config = S3Config { force_path_style: true, ..S3Config::default() };
+    let mut s3 = S3Client::with_config(provider, Region::UsWest2, config).unwrap();
+    s3.set_hostname(Some(server.endpoint()));
+    s3
+}
+
+fn delete_objects(keys: &[String]) -> Vec<ObjectIdentifier> {
+    keys.iter().map(|key| ObjectIdentifier { key: key.clone(), version_id: None }).collect()
 }
 
-fn s3_list_buckets_tests(s3: &mut S3Helper<ChainProvider>) -> Result<(), AwsError> {
-    let response = try!(s3.list_buckets());
-    info!("Got list of buckets: {:?}", response);
-    for q in response.buckets {
-        info!("Existing bucket: {:?}", q.name);
+#[test]
+fn delete_objects_in_batches_of_1000_keys() {
+    let server = MockS3Server::new();
+    let mut s3 = s3_client(&server);
+
+    let keys = (0..2500).map(|i| format!("logs/{:04}.gz", i)).collect::<Vec<String>>();
+    for key in &keys {
+        server.put_object("rusoto-logs", key, b"");
     }
+    server.put_object("rusoto-logs", "README", b"");
+
+    let errors = s3.delete_objects_in_batches("rusoto-logs", delete_objects(&keys), 2).unwrap();
+
+    assert!(errors.is_empty());
+    assert_eq!(server.operations(), vec!["DeleteObjects".to_owned(); 3]);
+    assert_eq!(server.keys("rusoto-logs"), vec!["README".to_owned()]);
+}
+
+#[test]
+fn delete_objects_in_batches_carries_on_after_a_failed_batch() {
+    let server = MockS3Server::new();
+    let mut s3 = s3_client(&server);
+
+    let keys = (0..1500).map(|i| format!("logs/{:04}.gz", i)).collect::<Vec<String>>();
+    let errors = s3.delete_objects_in_batches("rusoto-missing", delete_objects(&keys), 1).unwrap();
 
-    Ok(())
+    // Every batch is attempted, and each key of a failed batch is reported.
+    assert_eq!(server.operations(), vec!["DeleteObjects".to_owned(); 2]);
+    assert_eq!(errors.len(), 1500);
+    assert_eq!(errors[1499].key, "logs/1499.gz");
+    assert_eq!(errors[1499].code, "RequestFailed");
+    assert!(errors[1499].message.contains("NoSuchBucket"));
 }
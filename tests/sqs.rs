@@ -0,0 +1,70 @@
+#![cfg(feature = "sqs")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::sqs::{DeleteMessageRequest, ReceiveMessageRequest, SendMessageRequest, SqsClient};
+use rusoto_test_utils::MockSqsServer;
+
+fn sqs_client(server: &MockSqsServer) -> SqsClient<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut sqs = SqsClient::new(provider, Region::UsEast1);
+    sqs.set_hostname(Some(server.endpoint()));
+    sqs
+}
+
+#[test]
+fn send_receive_and_delete_messages() {
+    let server = MockSqsServer::new();
+    let mut sqs = sqs_client(&server);
+    let queue_url = server.queue_url("jobs");
+
+    let sent = sqs.send_message(&SendMessageRequest {
+        queue_url: queue_url.clone(),
+        message_body: "{\"job\": \"resize\", \"id\": 7}".to_owned(),
+        ..Default::default()
+    }).unwrap();
+    assert!(sent.message_id.is_some());
+    assert_eq!(server.messages("jobs"), vec!["{\"job\": \"resize\", \"id\": 7}".to_owned()]);
+
+    server.send_message("jobs", "<job>thumbnail</job>");
+
+    let received = sqs.receive_message(&ReceiveMessageRequest {
+        queue_url: queue_url.clone(),
+        max_number_of_messages: Some(10),
+        ..Default::default()
+    }).unwrap();
+    let messages = received.messages.unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[1].body, Some("<job>thumbnail</job>".to_owned()));
+
+    for message in &messages {
+        sqs.delete_message(&DeleteMessageRequest {
+            queue_url: queue_url.clone(),
+            receipt_handle: message.receipt_handle.clone().unwrap(),
+        }).unwrap();
+    }
+
+    assert!(server.messages("jobs").is_empty());
+}
+
+#[test]
+fn received_messages_are_not_received_again() {
+    let server = MockSqsServer::new();
+    let mut sqs = sqs_client(&server);
+
+    server.send_message("jobs", "only once");
+
+    let request = ReceiveMessageRequest {
+        queue_url: server.queue_url("jobs"),
+        ..Default::default()
+    };
+
+    assert_eq!(sqs.receive_message(&request).unwrap().messages.map(|messages| messages.len()), Some(1));
+    assert_eq!(sqs.receive_message(&request).unwrap().messages.map_or(0, |messages| messages.len()), 0);
+}
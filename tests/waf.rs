@@ -0,0 +1,60 @@
+#![cfg(feature = "waf")]
+
+extern crate rusoto;
+extern crate rusoto_test_utils;
+
+use rusoto::{ProfileProvider, Region};
+use rusoto::waf::{IPSetUpdate, IPV4, IPV6, WafClient};
+use rusoto_test_utils::MockWafServer;
+
+fn waf_client(server: &MockWafServer) -> WafClient<ProfileProvider> {
+    let provider = ProfileProvider::with_configuration(
+        "tests/sample-data/multiple_profile_credentials",
+        "foo",
+    );
+
+    let mut waf = WafClient::new(provider, Region::UsEast1);
+    waf.set_hostname(Some(server.endpoint()));
+    waf
+}
+
+#[test]
+fn mutations_fetch_a_change_token_first() {
+    let server = MockWafServer::new();
+    let mut waf = waf_client(&server);
+
+    let created = waf.add_ip_set("blocked", None).unwrap();
+    assert_eq!(server.operations(), vec!["GetChangeToken".to_owned(), "CreateIPSet".to_owned()]);
+
+    let ip_set = created.ip_set.unwrap();
+    assert_eq!(ip_set.name, Some("blocked".to_owned()));
+
+    let change_token = waf.change_ip_set(&ip_set.ip_set_id, None, vec![
+        IPSetUpdate::insert(IPV4, "192.0.2.44/32"),
+        IPSetUpdate::insert(IPV6, "2001:db8::/32"),
+    ]).unwrap();
+    assert!(created.change_token.unwrap() != change_token);
+    assert_eq!(&server.operations()[2..], &["GetChangeToken".to_owned(), "UpdateIPSet".to_owned()]);
+    assert_eq!(server.ip_set_descriptors(&ip_set.ip_set_id), vec![
+        ("IPV4".to_owned(), "192.0.2.44/32".to_owned()),
+        ("IPV6".to_owned(), "2001:db8::/32".to_owned()),
+    ]);
+
+    let ip_sets = waf.list_all_ip_sets(Some(10)).unwrap();
+    assert_eq!(ip_sets.len(), 1);
+    assert_eq!(ip_sets[0].ip_set_id, ip_set.ip_set_id);
+}
+
+#[test]
+fn a_given_change_token_is_used_as_is() {
+    let server = MockWafServer::new();
+    let mut waf = waf_client(&server);
+
+    let change_token = waf.change_token().unwrap();
+    let created = waf.add_ip_set("allowed", Some(change_token.clone())).unwrap();
+    assert_eq!(created.change_token, Some(change_token.clone()));
+    assert_eq!(server.operations(), vec!["GetChangeToken".to_owned(), "CreateIPSet".to_owned()]);
+
+    let error = waf.add_ip_set("allowed-again", Some(change_token)).unwrap_err();
+    assert!(format!("{}", error).starts_with("WAFStaleDataException"));
+}